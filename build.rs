@@ -3,6 +3,31 @@ use std::fs;
 use std::path::Path;
 
 fn main() {
+    // VERGEN_* переменные для метаданных сборки; без git значения заменяются заглушками
+    if let Err(e) = vergen::EmitBuilder::builder()
+        .all_build()
+        .all_cargo()
+        .all_git()
+        .all_rustc()
+        .all_sysinfo()
+        .emit()
+    {
+        println!("cargo:warning=vergen: {}", e);
+    }
+
+    // Параметры цели сборки, которых нет среди переменных vergen
+    for (name, var) in [
+        ("POOLAI_TARGET_TRIPLE", "TARGET"),
+        ("POOLAI_BUILD_PROFILE", "PROFILE"),
+        ("POOLAI_TARGET_OS", "CARGO_CFG_TARGET_OS"),
+        ("POOLAI_TARGET_ARCH", "CARGO_CFG_TARGET_ARCH"),
+        ("POOLAI_TARGET_POINTER_WIDTH", "CARGO_CFG_TARGET_POINTER_WIDTH"),
+        ("POOLAI_TARGET_ENDIAN", "CARGO_CFG_TARGET_ENDIAN"),
+    ] {
+        let value = env::var(var).unwrap_or_else(|_| "unknown".to_string());
+        println!("cargo:rustc-env={}={}", name, value);
+    }
+
     // Генерируем информацию о версии
     let version = env::var("CARGO_PKG_VERSION").unwrap_or_else(|_| "Beta_bolvanka_v1".to_string());
     let build_date = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string();
@@ -14,12 +14,42 @@ use crate::core::state::AppState;
 use crate::pool::pool::PoolManager;
 use crate::monitoring::metrics::SystemMetrics;
 use crate::network::api::ApiServer;
+use crate::admin::ip_allowlist::IpAllowlist;
+use crate::admin::admin_token::AdminTokenHash;
+use crate::admin::maintenance::{MaintenanceScheduler, NewMaintenanceWindow, SystemClock};
+use crate::admin::restart::{restart_components, restart_plan, system_components, RestartError};
+use crate::monitoring::events::EventBus;
+
+/// Файл с запланированными окнами обслуживания
+pub const MAINTENANCE_WINDOWS_PATH: &str = "data/maintenance_windows.json";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdminConfig {
-    pub admin_token: String,
-    pub allowed_ips: Vec<String>,
+    /// SHA-256 токена администратора в hex
+    pub admin_token_hash: AdminTokenHash,
+    /// Разбирается и проверяется при загрузке конфигурации
+    pub allowed_ips: IpAllowlist,
     pub rate_limit: u32,
+    /// Доверять `X-Forwarded-For`: включается только за своим прокси
+    #[serde(default)]
+    pub trust_proxy: bool,
+}
+
+impl AdminConfig {
+    /// Создаёт конфигурацию из токена в открытом виде; сохраняется только хэш
+    pub fn with_token(token: &str, allowed_ips: IpAllowlist, rate_limit: u32) -> Self {
+        Self {
+            admin_token_hash: AdminTokenHash::from_plaintext(token),
+            allowed_ips,
+            rate_limit,
+            trust_proxy: false,
+        }
+    }
+
+    /// Проверяет токен за постоянное время
+    pub fn verify_token(&self, candidate: &str) -> bool {
+        self.admin_token_hash.verify(candidate)
+    }
 }
 
 pub struct AdminPanel {
@@ -29,6 +59,8 @@ pub struct AdminPanel {
     api_server: Arc<ApiServer>,
     config: AdminConfig,
     sessions: Arc<RwLock<HashMap<String, DateTime<Utc>>>>,
+    maintenance: Arc<MaintenanceScheduler>,
+    events: Arc<EventBus>,
 }
 
 impl AdminPanel {
@@ -39,6 +71,12 @@ impl AdminPanel {
         api_server: Arc<ApiServer>,
         config: AdminConfig,
     ) -> Self {
+        let maintenance = Arc::new(MaintenanceScheduler::new(
+            state.clone(),
+            Arc::new(SystemClock),
+            Some(std::path::PathBuf::from(MAINTENANCE_WINDOWS_PATH)),
+        ));
+
         Self {
             state,
             pool_manager,
@@ -46,9 +84,22 @@ impl AdminPanel {
             api_server,
             config,
             sessions: Arc::new(RwLock::new(HashMap::new())),
+            maintenance,
+            events: Arc::new(EventBus::default()),
         }
     }
 
+    /// События остановки и запуска компонентов при перезапуске уходят в общую шину
+    pub fn with_event_bus(mut self, events: Arc<EventBus>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Планировщик окон обслуживания
+    pub fn maintenance_scheduler(&self) -> Arc<MaintenanceScheduler> {
+        self.maintenance.clone()
+    }
+
     pub async fn start_server(&self, address: &str) -> std::io::Result<()> {
         let state = self.state.clone();
         let pool_manager = self.pool_manager.clone();
@@ -56,20 +107,30 @@ impl AdminPanel {
         let api_server = self.api_server.clone();
         let config = self.config.clone();
         let sessions = self.sessions.clone();
+        let maintenance = self.maintenance.clone();
+        let events = self.events.clone();
+
+        maintenance.start(std::time::Duration::from_secs(30));
 
         actix_web::HttpServer::new(move || {
             actix_web::App::new()
+                .wrap(actix_web::middleware::from_fn(crate::admin::ip_filter::admin_ip_filter))
                 .app_data(web::Data::new(state.clone()))
                 .app_data(web::Data::new(pool_manager.clone()))
                 .app_data(web::Data::new(metrics.clone()))
                 .app_data(web::Data::new(api_server.clone()))
                 .app_data(web::Data::new(config.clone()))
                 .app_data(web::Data::new(sessions.clone()))
+                .app_data(web::Data::new(maintenance.clone()))
+                .app_data(web::Data::new(events.clone()))
                 .service(get_system_stats)
                 .service(get_pool_status)
+                .service(get_restart_plan)
                 .service(restart_system)
                 .service(enable_maintenance)
                 .service(disable_maintenance)
+                .service(list_maintenance_windows)
+                .service(schedule_maintenance_window)
                 .service(get_logs)
                 .service(login)
                 .service(logout)
@@ -91,7 +152,7 @@ async fn login(
     config: web::Data<AdminConfig>,
     sessions: web::Data<Arc<RwLock<HashMap<String, DateTime<Utc>>>>>,
 ) -> impl Responder {
-    if req.token != config.admin_token {
+    if !config.verify_token(&req.token) {
         return HttpResponse::Unauthorized().json(serde_json::json!({
             "error": "Invalid token"
         }));
@@ -155,19 +216,37 @@ async fn get_pool_status(
     HttpResponse::Ok().json(status)
 }
 
+/// Параметры перезапуска
+#[derive(Debug, Default, Deserialize)]
+pub struct RestartParams {
+    /// Без подтверждения перезапуск не выполняется
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+#[get("/system/restart/plan")]
+async fn get_restart_plan(
+    pool_manager: web::Data<Arc<PoolManager>>,
+    api_server: web::Data<Arc<ApiServer>>,
+) -> impl Responder {
+    let components = system_components(pool_manager.get_ref().clone(), api_server.get_ref().clone());
+    HttpResponse::Ok().json(restart_plan(&components).await)
+}
+
 #[post("/system/restart")]
 async fn restart_system(
+    params: web::Query<RestartParams>,
     pool_manager: web::Data<Arc<PoolManager>>,
     api_server: web::Data<Arc<ApiServer>>,
+    events: web::Data<Arc<EventBus>>,
 ) -> impl Responder {
-    match restart_system_internal(pool_manager.as_ref(), api_server.as_ref()).await {
-        Ok(_) => HttpResponse::Ok().json(serde_json::json!({
-            "status": "system restarted"
-        })),
-        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
-            "error": e.to_string()
-        })),
-    }
+    restart_system_internal(
+        pool_manager.get_ref().clone(),
+        api_server.get_ref().clone(),
+        events.get_ref().clone(),
+        params.confirm,
+    )
+    .await
 }
 
 #[post("/maintenance/enable")]
@@ -190,6 +269,26 @@ async fn disable_maintenance(
     }))
 }
 
+#[get("/maintenance/windows")]
+pub async fn list_maintenance_windows(
+    scheduler: web::Data<Arc<MaintenanceScheduler>>,
+) -> impl Responder {
+    HttpResponse::Ok().json(scheduler.windows())
+}
+
+#[post("/maintenance/windows")]
+pub async fn schedule_maintenance_window(
+    req: web::Json<NewMaintenanceWindow>,
+    scheduler: web::Data<Arc<MaintenanceScheduler>>,
+) -> impl Responder {
+    match scheduler.schedule(req.into_inner()).await {
+        Ok(window) => HttpResponse::Created().json(window),
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({
+            "error": e
+        })),
+    }
+}
+
 #[get("/logs")]
 async fn get_logs() -> impl Responder {
     let logs = vec![
@@ -203,25 +302,77 @@ async fn get_logs() -> impl Responder {
     HttpResponse::Ok().json(logs)
 }
 
-async fn restart_system_internal(
-    pool_manager: &PoolManager,
-    api_server: &ApiServer,
-) -> Result<(), Box<dyn std::error::Error>> {
-    log::info!("Admin: Restarting system");
-    
-    // Остановка компонентов
-    pool_manager.stop().await?;
-    api_server.stop().await?;
-    
-    // Запуск компонентов
-    pool_manager.start().await?;
-    api_server.start().await?;
-    
-    log::info!("Admin: System restarted successfully");
-    Ok(())
+/// Перезапуск с подтверждением: без `confirm` отвечает 409 с планом перезапуска
+pub async fn restart_system_internal(
+    pool_manager: Arc<PoolManager>,
+    api_server: Arc<ApiServer>,
+    events: Arc<EventBus>,
+    confirm: bool,
+) -> HttpResponse {
+    let components = system_components(pool_manager, api_server);
+    match restart_components(&components, &events, confirm).await {
+        Ok(plan) => HttpResponse::Ok().json(serde_json::json!({
+            "status": "system restarted",
+            "plan": plan
+        })),
+        Err(RestartError::NotConfirmed(plan)) => HttpResponse::Conflict().json(serde_json::json!({
+            "error": "Restart requires confirm=true",
+            "plan": plan
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// Пауза перед завершением процесса, чтобы ответ успел уйти клиенту
+pub const SHUTDOWN_EXIT_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Токен администратора из заголовка `Authorization: Bearer <token>`
+pub(crate) fn bearer_token(req: &actix_web::HttpRequest) -> Option<&str> {
+    req.headers()
+        .get(actix_web::http::header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+}
+
+/// Останавливает модули в обратном порядке, отвечает результатами
+/// по каждому модулю и после ответа завершает процесс
+pub async fn shutdown_system(
+    req: actix_web::HttpRequest,
+    config: web::Data<AdminConfig>,
+    hook: web::Data<Arc<dyn crate::ShutdownHook>>,
+) -> HttpResponse {
+    if !bearer_token(&req).map_or(false, |token| config.verify_token(token)) {
+        return HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Invalid token"
+        }));
+    }
+
+    warn!("Admin: System shutdown requested");
+    let results = crate::shutdown_system_with(hook.get_ref().as_ref()).await;
+    let exit_code = crate::shutdown_exit_code(&results);
+
+    let hook = hook.get_ref().clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(SHUTDOWN_EXIT_DELAY).await;
+        hook.exit(exit_code);
+    });
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "status": "shutting down",
+        "modules": results
+    }))
 }
 
 // API функции для main.rs
+pub async fn get_raid_status(
+    raid_manager: web::Data<Arc<crate::raid::burstraid::BurstRaidManager>>,
+) -> impl Responder {
+    HttpResponse::Ok().json(raid_manager.status_report())
+}
+
 pub async fn get_pool_stats() -> impl Responder {
     serde_json::json!({
         "total_workers": 0,
@@ -277,11 +428,7 @@ mod tests {
 
     #[actix_rt::test]
     async fn test_login() {
-        let config = AdminConfig {
-            admin_token: "test_token".to_string(),
-            allowed_ips: vec![],
-            rate_limit: 100,
-        };
+        let config = AdminConfig::with_token("test_token", IpAllowlist::default(), 100);
         
         let app = test::init_service(
             actix_web::App::new()
@@ -299,4 +446,73 @@ mod tests {
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_success());
     }
+
+    /// Записывает остановленные модули вместо настоящей остановки
+    #[derive(Default)]
+    struct RecordingShutdown {
+        stopped: parking_lot::Mutex<Vec<&'static str>>,
+        exit_code: parking_lot::Mutex<Option<i32>>,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::ShutdownHook for RecordingShutdown {
+        async fn stop_module(&self, module: &'static str) -> Result<(), String> {
+            self.stopped.lock().push(module);
+            if module == "raid" {
+                return Err("raid busy".to_string());
+            }
+            Ok(())
+        }
+
+        fn exit(&self, code: i32) {
+            *self.exit_code.lock() = Some(code);
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_shutdown_stops_modules_in_reverse_order() {
+        let config = AdminConfig::with_token("test_token", IpAllowlist::default(), 100);
+        let recorder = Arc::new(RecordingShutdown::default());
+        let hook: Arc<dyn crate::ShutdownHook> = recorder.clone();
+        let app = test::init_service(
+            actix_web::App::new()
+                .app_data(web::Data::new(config))
+                .app_data(web::Data::new(hook))
+                .route("/system/shutdown", web::post().to(shutdown_system)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/system/shutdown")
+            .insert_header(("Authorization", "Bearer wrong"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::UNAUTHORIZED);
+        assert!(recorder.stopped.lock().is_empty());
+
+        let req = test::TestRequest::post()
+            .uri("/system/shutdown")
+            .insert_header(("Authorization", "Bearer test_token"))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+
+        assert_eq!(
+            *recorder.stopped.lock(),
+            vec![
+                "workers", "admin", "ui", "raid", "tgbot", "vm", "platform",
+                "network", "runtime", "monitoring", "pool", "libs", "core",
+            ]
+        );
+        let modules = body["modules"].as_array().unwrap();
+        assert_eq!(modules.len(), 13);
+        assert_eq!(modules[0]["module"], "workers");
+        assert_eq!(modules[3]["status"], "failed");
+        assert_eq!(modules[3]["message"], "raid busy");
+        assert_eq!(modules[12]["module"], "core");
+        assert_eq!(modules[12]["status"], "stopped");
+
+        // Модуль raid не остановился - процесс завершается с ошибкой
+        tokio::time::sleep(SHUTDOWN_EXIT_DELAY * 2).await;
+        assert_eq!(*recorder.exit_code.lock(), Some(1));
+    }
 } 
\ No newline at end of file
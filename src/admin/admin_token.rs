@@ -0,0 +1,110 @@
+//! Admin Token - Хэш токена администратора
+//!
+//! В конфигурации хранится только SHA-256 токена в hex. Предъявленный токен
+//! хэшируется и сравнивается с сохранённым за постоянное время, поэтому
+//! длительность проверки не зависит от того, в каком байте расхождение.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::convert::TryFrom;
+use subtle::{Choice, ConstantTimeEq};
+
+/// SHA-256 токена администратора
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AdminTokenHash([u8; 32]);
+
+impl AdminTokenHash {
+    /// Хэширует токен в открытом виде
+    pub fn from_plaintext(token: &str) -> Self {
+        Self(Sha256::digest(token.as_bytes()).into())
+    }
+
+    /// Разбирает hex-представление хэша
+    pub fn from_hex(hash: &str) -> Result<Self, String> {
+        let bytes = hex::decode(hash.trim())
+            .map_err(|e| format!("Invalid admin token hash: {}", e))?;
+        let digest: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| "Invalid admin token hash: expected 32 bytes of SHA-256".to_string())?;
+        Ok(Self(digest))
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    /// Проверяет токен сравнением хэшей за постоянное время
+    pub fn verify(&self, candidate: &str) -> bool {
+        self.matches(candidate).into()
+    }
+
+    /// Результат сравнения хэшей в виде `subtle::Choice`, без ветвления по данным
+    fn matches(&self, candidate: &str) -> Choice {
+        let candidate: [u8; 32] = Sha256::digest(candidate.as_bytes()).into();
+        self.0.ct_eq(&candidate)
+    }
+}
+
+impl std::fmt::Debug for AdminTokenHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AdminTokenHash(..)")
+    }
+}
+
+impl TryFrom<String> for AdminTokenHash {
+    type Error = String;
+
+    fn try_from(hash: String) -> Result<Self, Self::Error> {
+        Self::from_hex(&hash)
+    }
+}
+
+impl From<AdminTokenHash> for String {
+    fn from(hash: AdminTokenHash) -> Self {
+        hash.to_hex()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correct_and_wrong_token() {
+        let hash = AdminTokenHash::from_plaintext("s3cret-token");
+
+        assert!(hash.verify("s3cret-token"));
+        assert!(!hash.verify("s3cret-tokeN"));
+        assert!(!hash.verify(""));
+        assert!(!hash.verify("s3cret-token "));
+    }
+
+    #[test]
+    fn test_hex_roundtrip() {
+        let hash = AdminTokenHash::from_plaintext("token");
+        let json = serde_json::to_string(&hash).unwrap();
+        assert_eq!(json, format!("\"{}\"", hash.to_hex()));
+        assert_eq!(serde_json::from_str::<AdminTokenHash>(&json).unwrap(), hash);
+
+        assert!(AdminTokenHash::from_hex("token").is_err());
+        assert!(AdminTokenHash::from_hex("abcd").is_err());
+    }
+
+    #[test]
+    fn test_verification_uses_constant_time_compare() {
+        let token = "a".repeat(64);
+        let hash = AdminTokenHash::from_plaintext(&token);
+
+        // Тип результата фиксирует, что сравнение идёт через `subtle`,
+        // а не через `==` с ранним выходом
+        let exact: Choice = hash.matches(&token);
+        assert_eq!(exact.unwrap_u8(), 1);
+
+        for candidate in [format!("b{}", &token[1..]), format!("{}b", &token[..63])] {
+            let mismatch: Choice = hash.matches(&candidate);
+            assert_eq!(mismatch.unwrap_u8(), 0);
+            assert!(!hash.verify(&candidate));
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! IP Allowlist - Разобранный список разрешённых адресов администратора
+//!
+//! Записи `allowed_ips` разбираются при загрузке конфигурации: точные адреса
+//! (`127.0.0.1`, `::1`) и подсети CIDR (`10.0.0.0/8`). Ошибка в записи
+//! обнаруживается сразу, а не при первой проверке запроса.
+
+use ipnet::IpNet;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::net::IpAddr;
+
+/// Список разрешённых адресов и подсетей
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<String>", into = "Vec<String>")]
+pub struct IpAllowlist {
+    entries: Vec<String>,
+    networks: Vec<IpNet>,
+}
+
+impl IpAllowlist {
+    /// Разбирает записи; ошибка указывает на первую некорректную запись
+    pub fn parse<S: AsRef<str>>(entries: &[S]) -> Result<Self, String> {
+        let mut networks = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let entry = entry.as_ref().trim();
+            let network = if entry.contains('/') {
+                entry.parse::<IpNet>().map_err(|e| {
+                    format!("Invalid allowed_ips entry '{}': {}", entry, e)
+                })?
+            } else {
+                entry
+                    .parse::<IpAddr>()
+                    .map(IpNet::from)
+                    .map_err(|e| format!("Invalid allowed_ips entry '{}': {}", entry, e))?
+            };
+            networks.push(network);
+        }
+
+        Ok(Self {
+            entries: entries.iter().map(|e| e.as_ref().trim().to_string()).collect(),
+            networks,
+        })
+    }
+
+    /// Проверяет, входит ли адрес в список
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        self.networks.iter().any(|network| network.contains(ip))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.networks.is_empty()
+    }
+
+    pub fn networks(&self) -> &[IpNet] {
+        &self.networks
+    }
+}
+
+impl TryFrom<Vec<String>> for IpAllowlist {
+    type Error = String;
+
+    fn try_from(entries: Vec<String>) -> Result<Self, Self::Error> {
+        Self::parse(&entries)
+    }
+}
+
+impl From<IpAllowlist> for Vec<String> {
+    fn from(allowlist: IpAllowlist) -> Self {
+        allowlist.entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_and_cidr_entries() {
+        let allowlist = IpAllowlist::parse(&["127.0.0.1", "::1", "10.0.0.0/8"]).unwrap();
+
+        assert!(allowlist.contains(&"127.0.0.1".parse().unwrap()));
+        assert!(allowlist.contains(&"::1".parse().unwrap()));
+        assert!(allowlist.contains(&"10.20.30.40".parse().unwrap()));
+        assert!(!allowlist.contains(&"127.0.0.2".parse().unwrap()));
+        assert!(!allowlist.contains(&"192.168.1.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_malformed_entry_rejected_at_load() {
+        let err = IpAllowlist::parse(&["127.0.0.1", "127.0.0.300"]).unwrap_err();
+        assert!(err.contains("'127.0.0.300'"), "{}", err);
+
+        let err = IpAllowlist::parse(&["10.0.0.0/33"]).unwrap_err();
+        assert!(err.contains("'10.0.0.0/33'"), "{}", err);
+
+        let json = r#"{"admin_token_hash":"9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08","allowed_ips":["127.0.0.300"],"rate_limit":1}"#;
+        let err = serde_json::from_str::<crate::admin::admin_panel::AdminConfig>(json).unwrap_err();
+        assert!(err.to_string().contains("'127.0.0.300'"));
+    }
+
+    #[test]
+    fn test_serializes_back_to_strings() {
+        let allowlist = IpAllowlist::parse(&["127.0.0.1", "10.0.0.0/8"]).unwrap();
+        let json = serde_json::to_string(&allowlist).unwrap();
+        assert_eq!(json, r#"["127.0.0.1","10.0.0.0/8"]"#);
+    }
+}
@@ -0,0 +1,187 @@
+//! IP Filter - Ограничение административных запросов по адресу клиента
+//!
+//! Запрос пропускается, если адрес клиента входит в `AdminConfig.allowed_ips`.
+//! Пустой список разрешает всё. `X-Forwarded-For` учитывается только при
+//! `trust_proxy`: иначе клиент мог бы подставить любой адрес сам. Доверенный
+//! прокси дописывает адрес клиента в конец заголовка, поэтому берётся
+//! последний адрес: всё левее него мог прислать сам клиент.
+
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::{web, HttpResponse};
+use axum::extract::{ConnectInfo, State};
+use axum::http::StatusCode;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use crate::admin::admin_panel::AdminConfig;
+use crate::admin::ip_allowlist::IpAllowlist;
+
+pub const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+
+/// Адрес клиента: за доверенным прокси - последний адрес из `X-Forwarded-For`,
+/// иначе адрес соединения
+pub fn client_ip(peer: Option<IpAddr>, forwarded_for: Option<&str>, trust_proxy: bool) -> Option<IpAddr> {
+    let forwarded = forwarded_for
+        .filter(|_| trust_proxy)
+        .and_then(|value| value.rsplit(',').next())
+        .and_then(|last| last.trim().parse().ok());
+    forwarded.or(peer)
+}
+
+/// Пустой список разрешает любой адрес; неизвестный адрес запрещён
+pub fn is_allowed(allowlist: &IpAllowlist, ip: Option<IpAddr>) -> bool {
+    allowlist.is_empty() || ip.map_or(false, |ip| allowlist.contains(&ip))
+}
+
+fn admin_request_allowed(
+    config: &AdminConfig,
+    peer: Option<IpAddr>,
+    forwarded_for: Option<&str>,
+) -> bool {
+    let ip = client_ip(peer, forwarded_for, config.trust_proxy);
+    let allowed = is_allowed(&config.allowed_ips, ip);
+    if !allowed {
+        log::warn!("Admin: Rejected request from {:?}", ip);
+    }
+    allowed
+}
+
+fn forbidden_body() -> serde_json::Value {
+    serde_json::json!({ "error": "IP address not allowed" })
+}
+
+/// Actix middleware: `web::scope("/admin").wrap(from_fn(admin_ip_filter))`.
+/// Конфигурация берётся из `web::Data<AdminConfig>`; без неё запрос отклоняется
+pub async fn admin_ip_filter<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, actix_web::Error> {
+    let allowed = match req.app_data::<web::Data<AdminConfig>>() {
+        Some(config) => admin_request_allowed(
+            config,
+            req.peer_addr().map(|addr| addr.ip()),
+            req.headers()
+                .get(FORWARDED_FOR_HEADER)
+                .and_then(|value| value.to_str().ok()),
+        ),
+        None => {
+            log::error!("Admin: AdminConfig is not registered, rejecting request");
+            false
+        }
+    };
+
+    if allowed {
+        Ok(next.call(req).await?.map_into_left_body())
+    } else {
+        Ok(req
+            .into_response(HttpResponse::Forbidden().json(forbidden_body()))
+            .map_into_right_body())
+    }
+}
+
+/// Axum middleware:
+/// `.layer(axum::middleware::from_fn_with_state(config, admin_ip_filter_middleware))`
+pub async fn admin_ip_filter_middleware(
+    State(config): State<Arc<AdminConfig>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    let forwarded_for = request
+        .headers()
+        .get(FORWARDED_FOR_HEADER)
+        .and_then(|value| value.to_str().ok());
+    if admin_request_allowed(&config, Some(addr.ip()), forwarded_for) {
+        next.run(request).await
+    } else {
+        (StatusCode::FORBIDDEN, axum::Json(forbidden_body())).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{middleware::from_fn, test, App};
+
+    fn config(allowed_ips: &[&str], trust_proxy: bool) -> AdminConfig {
+        let mut config = AdminConfig::with_token("token", IpAllowlist::parse(allowed_ips).unwrap(), 100);
+        config.trust_proxy = trust_proxy;
+        config
+    }
+
+    async fn actix_status(config: AdminConfig, peer: &str, forwarded_for: Option<&str>) -> u16 {
+        let app = test::init_service(
+            App::new().app_data(web::Data::new(config)).service(
+                web::scope("/admin")
+                    .wrap(from_fn(admin_ip_filter))
+                    .route("/ping", web::get().to(|| async { HttpResponse::Ok().finish() })),
+            ),
+        )
+        .await;
+
+        let mut req = test::TestRequest::get()
+            .uri("/admin/ping")
+            .peer_addr(format!("{}:5000", peer).parse().unwrap());
+        if let Some(forwarded_for) = forwarded_for {
+            req = req.insert_header((FORWARDED_FOR_HEADER, forwarded_for));
+        }
+        test::call_service(&app, req.to_request()).await.status().as_u16()
+    }
+
+    #[actix_rt::test]
+    async fn test_allowed_ip_passes() {
+        assert_eq!(actix_status(config(&["127.0.0.1", "::1"], false), "127.0.0.1", None).await, 200);
+        assert_eq!(actix_status(config(&[], false), "203.0.113.7", None).await, 200);
+    }
+
+    #[actix_rt::test]
+    async fn test_denied_ip_gets_403() {
+        assert_eq!(actix_status(config(&["127.0.0.1"], false), "203.0.113.7", None).await, 403);
+    }
+
+    #[actix_rt::test]
+    async fn test_cidr_range_matches() {
+        let allowed = config(&["10.0.0.0/8"], false);
+        assert_eq!(actix_status(allowed.clone(), "10.42.0.9", None).await, 200);
+        assert_eq!(actix_status(allowed, "11.0.0.1", None).await, 403);
+    }
+
+    #[actix_rt::test]
+    async fn test_forwarded_for_only_with_trusted_proxy() {
+        let forwarded = Some("172.16.0.1, 10.1.2.3");
+        assert_eq!(actix_status(config(&["10.0.0.0/8"], false), "192.168.0.1", forwarded).await, 403);
+        assert_eq!(actix_status(config(&["10.0.0.0/8"], true), "192.168.0.1", forwarded).await, 200);
+    }
+
+    #[actix_rt::test]
+    async fn test_spoofed_leftmost_forwarded_for_rejected() {
+        // Клиент прислал разрешённый адрес сам, прокси дописал настоящий
+        let forwarded = Some("10.1.2.3, 203.0.113.7");
+        assert_eq!(actix_status(config(&["10.0.0.0/8"], true), "192.168.0.1", forwarded).await, 403);
+    }
+
+    #[tokio::test]
+    async fn test_axum_middleware_filters_by_ip() {
+        use tower::ServiceExt;
+
+        let app = axum::Router::new()
+            .route("/admin/ping", axum::routing::get(|| async { "pong" }))
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::new(config(&["10.0.0.0/8"], false)),
+                admin_ip_filter_middleware,
+            ));
+
+        for (ip, expected) in [([10, 9, 8, 7], StatusCode::OK), ([192, 168, 0, 1], StatusCode::FORBIDDEN)] {
+            let mut request = axum::http::Request::builder()
+                .uri("/admin/ping")
+                .body(axum::body::Body::empty())
+                .unwrap();
+            request.extensions_mut().insert(ConnectInfo(SocketAddr::from((ip, 4000))));
+            assert_eq!(app.clone().oneshot(request).await.unwrap().status(), expected);
+        }
+    }
+}
@@ -0,0 +1,393 @@
+//! Maintenance - Плановые окна обслуживания
+//!
+//! Оператор заранее задаёт окно (начало, конец, необязательный список пулов).
+//! Планировщик сам включает режим обслуживания в начале окна и выключает
+//! в конце. Запланированные окна сохраняются на диск и переживают перезапуск.
+
+use crate::core::state::AppState;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Источник текущего времени
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Системные часы
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Ошибка операций, изменяющих состояние, во время обслуживания
+pub const MAINTENANCE_ERROR: &str = "System under maintenance";
+
+/// Флаг режима обслуживания всей системы. Клоны разделяют одно состояние,
+/// поэтому исполнители (например, распределение задач) видят переключение сразу.
+#[derive(Debug, Clone, Default)]
+pub struct MaintenanceGate(Arc<std::sync::atomic::AtomicBool>);
+
+impl MaintenanceGate {
+    pub fn set(&self, enabled: bool) {
+        self.0.store(enabled, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.0.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// Ошибка `MAINTENANCE_ERROR`, если система на обслуживании
+    pub fn check(&self) -> Result<(), String> {
+        if self.is_enabled() {
+            Err(MAINTENANCE_ERROR.to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Объект, режимом обслуживания которого управляет планировщик
+#[async_trait]
+pub trait MaintenanceTarget: Send + Sync {
+    async fn set_maintenance_mode(&self, enabled: bool);
+    async fn set_pool_maintenance(&self, pool: &str, enabled: bool);
+}
+
+#[async_trait]
+impl MaintenanceTarget for AppState {
+    async fn set_maintenance_mode(&self, enabled: bool) {
+        AppState::set_maintenance_mode(self, enabled).await
+    }
+
+    async fn set_pool_maintenance(&self, pool: &str, enabled: bool) {
+        AppState::set_pool_maintenance(self, pool, enabled).await
+    }
+}
+
+/// Запрос на планирование окна
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewMaintenanceWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// Затронутые пулы; `None` - обслуживание всей системы
+    #[serde(default)]
+    pub pools: Option<Vec<String>>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Запланированное окно обслуживания
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceWindow {
+    pub id: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    #[serde(default)]
+    pub pools: Option<Vec<String>>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl MaintenanceWindow {
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.start <= now && now < self.end
+    }
+}
+
+/// Что планировщик включил сам, чтобы выключать только это
+#[derive(Default)]
+struct AppliedMaintenance {
+    global: bool,
+    pools: HashSet<String>,
+}
+
+/// Планировщик окон обслуживания
+pub struct MaintenanceScheduler {
+    target: Arc<dyn MaintenanceTarget>,
+    clock: Arc<dyn Clock>,
+    storage_path: Option<PathBuf>,
+    windows: Mutex<Vec<MaintenanceWindow>>,
+    applied: tokio::sync::Mutex<AppliedMaintenance>,
+    /// Цикл `run` уже запущен через `start`
+    started: std::sync::atomic::AtomicBool,
+}
+
+impl MaintenanceScheduler {
+    /// Создаёт планировщик и загружает сохранённые окна
+    pub fn new(
+        target: Arc<dyn MaintenanceTarget>,
+        clock: Arc<dyn Clock>,
+        storage_path: Option<PathBuf>,
+    ) -> Self {
+        let windows = storage_path
+            .as_ref()
+            .map(|path| Self::load(path))
+            .unwrap_or_default();
+
+        Self {
+            target,
+            clock,
+            storage_path,
+            windows: Mutex::new(windows),
+            applied: tokio::sync::Mutex::new(AppliedMaintenance::default()),
+            started: std::sync::atomic::AtomicBool::new(false),
+        }
+    }
+
+    fn load(path: &PathBuf) -> Vec<MaintenanceWindow> {
+        let data = match std::fs::read_to_string(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+            Err(e) => {
+                log::warn!("Failed to read maintenance windows from {}: {}", path.display(), e);
+                return Vec::new();
+            }
+        };
+        match serde_json::from_str(&data) {
+            Ok(windows) => windows,
+            Err(e) => {
+                log::warn!("Ignoring corrupt maintenance windows file {}: {}", path.display(), e);
+                Vec::new()
+            }
+        }
+    }
+
+    fn persist(&self, windows: &[MaintenanceWindow]) -> Result<(), String> {
+        let path = match &self.storage_path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let data = serde_json::to_string_pretty(windows).map_err(|e| e.to_string())?;
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, data).map_err(|e| e.to_string())?;
+        std::fs::rename(&tmp, path).map_err(|e| e.to_string())
+    }
+
+    /// Планирует окно обслуживания
+    pub async fn schedule(&self, request: NewMaintenanceWindow) -> Result<MaintenanceWindow, String> {
+        if request.end <= request.start {
+            return Err("Maintenance window end must be after start".to_string());
+        }
+        if request.end <= self.clock.now() {
+            return Err("Maintenance window is already over".to_string());
+        }
+        if let Some(pools) = &request.pools {
+            if pools.is_empty() {
+                return Err("Pool list must not be empty; omit it to cover all pools".to_string());
+            }
+        }
+
+        let window = MaintenanceWindow {
+            id: uuid::Uuid::new_v4().to_string(),
+            start: request.start,
+            end: request.end,
+            pools: request.pools,
+            reason: request.reason,
+        };
+
+        {
+            let mut windows = self.windows.lock();
+            windows.push(window.clone());
+            windows.sort_by_key(|w| w.start);
+            self.persist(&windows)?;
+        }
+        log::info!("Scheduled maintenance window {} ({} - {})", window.id, window.start, window.end);
+
+        self.tick().await;
+        Ok(window)
+    }
+
+    /// Запланированные окна
+    pub fn windows(&self) -> Vec<MaintenanceWindow> {
+        self.windows.lock().clone()
+    }
+
+    /// Применяет окна на текущий момент: включает начавшиеся и выключает
+    /// завершившиеся. Прошедшие окна удаляются.
+    pub async fn tick(&self) {
+        let now = self.clock.now();
+
+        let (global, pools) = {
+            let mut windows = self.windows.lock();
+            let before = windows.len();
+            windows.retain(|w| w.end > now);
+            if windows.len() != before {
+                if let Err(e) = self.persist(&windows) {
+                    log::error!("Failed to persist maintenance windows: {}", e);
+                }
+            }
+
+            let active: Vec<&MaintenanceWindow> =
+                windows.iter().filter(|w| w.is_active_at(now)).collect();
+            let global = active.iter().any(|w| w.pools.is_none());
+            let pools: HashSet<String> = active
+                .iter()
+                .filter_map(|w| w.pools.as_ref())
+                .flatten()
+                .cloned()
+                .collect();
+            (global, pools)
+        };
+
+        let mut applied = self.applied.lock().await;
+        if global != applied.global {
+            self.target.set_maintenance_mode(global).await;
+            applied.global = global;
+        }
+        for pool in pools.difference(&applied.pools) {
+            self.target.set_pool_maintenance(pool, true).await;
+        }
+        for pool in applied.pools.difference(&pools) {
+            self.target.set_pool_maintenance(pool, false).await;
+        }
+        applied.pools = pools;
+    }
+
+    /// Запускает `run` в фоне, если он ещё не запущен. Возвращает `true`,
+    /// если цикл запущен этим вызовом.
+    pub fn start(self: &Arc<Self>, check_interval: Duration) -> bool {
+        if self.started.swap(true, std::sync::atomic::Ordering::SeqCst) {
+            return false;
+        }
+        tokio::spawn(self.clone().run(check_interval));
+        true
+    }
+
+    /// Периодически применяет окна
+    pub async fn run(self: Arc<Self>, check_interval: Duration) {
+        let mut interval = tokio::time::interval(check_interval);
+        loop {
+            interval.tick().await;
+            self.tick().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::ManualClock;
+    use std::collections::HashSet;
+
+    #[derive(Default)]
+    struct MockTarget {
+        global: Mutex<bool>,
+        pools: Mutex<HashSet<String>>,
+    }
+
+    #[async_trait]
+    impl MaintenanceTarget for MockTarget {
+        async fn set_maintenance_mode(&self, enabled: bool) {
+            *self.global.lock() = enabled;
+        }
+
+        async fn set_pool_maintenance(&self, pool: &str, enabled: bool) {
+            if enabled {
+                self.pools.lock().insert(pool.to_string());
+            } else {
+                self.pools.lock().remove(pool);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_window_activates_at_start_and_deactivates_at_end() {
+        let t0 = Utc::now();
+        let clock = Arc::new(ManualClock::new(t0));
+        let target = Arc::new(MockTarget::default());
+        let scheduler = MaintenanceScheduler::new(target.clone(), clock.clone(), None);
+
+        scheduler
+            .schedule(NewMaintenanceWindow {
+                start: t0 + chrono::Duration::minutes(10),
+                end: t0 + chrono::Duration::minutes(20),
+                pools: None,
+                reason: Some("upgrade".to_string()),
+            })
+            .await
+            .unwrap();
+        assert!(!*target.global.lock());
+
+        clock.set(t0 + chrono::Duration::minutes(10));
+        scheduler.tick().await;
+        assert!(*target.global.lock());
+
+        clock.set(t0 + chrono::Duration::minutes(20));
+        scheduler.tick().await;
+        assert!(!*target.global.lock());
+        assert!(scheduler.windows().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_loop_started_once() {
+        let clock = Arc::new(ManualClock::new(Utc::now()));
+        let scheduler = Arc::new(MaintenanceScheduler::new(Arc::new(MockTarget::default()), clock, None));
+
+        assert!(scheduler.start(Duration::from_secs(30)));
+        assert!(!scheduler.start(Duration::from_secs(30)));
+    }
+
+    #[tokio::test]
+    async fn test_pool_window_persisted_across_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("maintenance").join("windows.json");
+        let t0 = Utc::now();
+        let clock = Arc::new(ManualClock::new(t0));
+
+        let scheduler = MaintenanceScheduler::new(
+            Arc::new(MockTarget::default()),
+            clock.clone(),
+            Some(path.clone()),
+        );
+        scheduler
+            .schedule(NewMaintenanceWindow {
+                start: t0 + chrono::Duration::minutes(5),
+                end: t0 + chrono::Duration::minutes(15),
+                pools: Some(vec!["gpu-pool".to_string()]),
+                reason: None,
+            })
+            .await
+            .unwrap();
+        drop(scheduler);
+
+        let target = Arc::new(MockTarget::default());
+        let restored = MaintenanceScheduler::new(target.clone(), clock.clone(), Some(path));
+        assert_eq!(restored.windows().len(), 1);
+
+        clock.set(t0 + chrono::Duration::minutes(5));
+        restored.tick().await;
+        assert!(target.pools.lock().contains("gpu-pool"));
+        assert!(!*target.global.lock());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_window_rejected() {
+        let t0 = Utc::now();
+        let scheduler = MaintenanceScheduler::new(
+            Arc::new(MockTarget::default()),
+            Arc::new(ManualClock::new(t0)),
+            None,
+        );
+        let result = scheduler
+            .schedule(NewMaintenanceWindow {
+                start: t0 + chrono::Duration::minutes(10),
+                end: t0,
+                pools: None,
+                reason: None,
+            })
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -9,11 +9,19 @@
 pub mod admin_panel;
 pub mod system_manager;
 pub mod config_manager;
+pub mod self_test;
+pub mod ip_allowlist;
+pub mod ip_filter;
+pub mod admin_token;
+pub mod maintenance;
+pub mod restart;
 
 use crate::core::state::AppState;
 use crate::pool::pool::PoolManager;
 use crate::monitoring::metrics::SystemMetrics;
 use crate::network::api::ApiServer;
+use crate::monitoring::events::EventBus;
+use restart::{restart_components, restart_plan, system_components, RestartPlan};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -24,6 +32,7 @@ pub struct AdminPanel {
     pool_manager: Arc<PoolManager>,
     metrics: Arc<RwLock<SystemMetrics>>,
     api_server: Arc<ApiServer>,
+    events: Arc<EventBus>,
 }
 
 impl AdminPanel {
@@ -39,9 +48,16 @@ impl AdminPanel {
             pool_manager,
             metrics,
             api_server,
+            events: Arc::new(EventBus::default()),
         }
     }
 
+    /// Публикует остановку и запуск компонентов в общую шину
+    pub fn with_event_bus(mut self, events: Arc<EventBus>) -> Self {
+        self.events = events;
+        self
+    }
+
     /// Получает статистику системы
     pub async fn get_system_stats(&self) -> SystemStats {
         let metrics = self.metrics.read().await;
@@ -68,20 +84,16 @@ impl AdminPanel {
         }
     }
 
-    /// Перезапускает систему
-    pub async fn restart_system(&self) -> Result<(), Box<dyn std::error::Error>> {
-        log::info!("Admin: Restarting system");
-        
-        // Остановка компонентов
-        self.pool_manager.stop().await?;
-        self.api_server.stop().await?;
-        
-        // Запуск компонентов
-        self.pool_manager.start().await?;
-        self.api_server.start().await?;
-        
-        log::info!("Admin: System restarted successfully");
-        Ok(())
+    /// Компоненты, которые затронет перезапуск, и оценка дренажа; ничего не меняет
+    pub async fn restart_plan(&self) -> RestartPlan {
+        restart_plan(&system_components(self.pool_manager.clone(), self.api_server.clone())).await
+    }
+
+    /// Перезапускает систему. Без `confirm` ничего не делает и возвращает ошибку;
+    /// план стоит сначала посмотреть через `restart_plan`.
+    pub async fn restart_system(&self, confirm: bool) -> Result<RestartPlan, Box<dyn std::error::Error>> {
+        let components = system_components(self.pool_manager.clone(), self.api_server.clone());
+        Ok(restart_components(&components, &self.events, confirm).await?)
     }
 
     /// Включает режим обслуживания
@@ -161,4 +173,6 @@ pub async fn health_check() -> Result<(), Box<dyn std::error::Error>> {
 
 pub use admin_panel::*;
 pub use system_manager::*;
-pub use config_manager::*; 
\ No newline at end of file
+pub use config_manager::*;
+pub use self_test::*;
+pub use restart::*; 
\ No newline at end of file
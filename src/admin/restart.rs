@@ -0,0 +1,310 @@
+//! Restart - Перезапуск компонентов системы
+//!
+//! Перед перезапуском оператор получает план: какие компоненты будут
+//! остановлены и запущены заново и сколько займёт дренаж текущей работы.
+//! Сам перезапуск выполняется только с явным подтверждением.
+
+use crate::monitoring::events::{EventBus, EventKind};
+use crate::network::api::ApiServer;
+use crate::pool::pool::PoolManager;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Оценка времени, за которое активный воркер завершает текущую задачу
+pub const POOL_WORKER_DRAIN_ESTIMATE: Duration = Duration::from_millis(500);
+
+/// Компонент, который можно остановить и запустить заново
+#[async_trait]
+pub trait Restartable: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Сколько займёт завершение текущей работы при остановке
+    async fn estimated_drain(&self) -> Duration;
+
+    async fn stop(&self) -> Result<(), String>;
+
+    async fn start(&self) -> Result<(), String>;
+}
+
+#[async_trait]
+impl Restartable for PoolManager {
+    fn name(&self) -> &'static str {
+        "pool"
+    }
+
+    async fn estimated_drain(&self) -> Duration {
+        POOL_WORKER_DRAIN_ESTIMATE * self.active_worker_count().await
+    }
+
+    async fn stop(&self) -> Result<(), String> {
+        PoolManager::stop(self).await.map_err(|e| e.to_string())
+    }
+
+    async fn start(&self) -> Result<(), String> {
+        PoolManager::start(self).await.map_err(|e| e.to_string())
+    }
+}
+
+#[async_trait]
+impl Restartable for ApiServer {
+    fn name(&self) -> &'static str {
+        "api_server"
+    }
+
+    async fn estimated_drain(&self) -> Duration {
+        self.shutdown_timeout()
+    }
+
+    async fn stop(&self) -> Result<(), String> {
+        ApiServer::stop(self).await.map_err(|e| e.to_string())
+    }
+
+    async fn start(&self) -> Result<(), String> {
+        ApiServer::start(self).await.map_err(|e| e.to_string())
+    }
+}
+
+/// Компонент в плане перезапуска
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RestartComponent {
+    pub name: String,
+    pub estimated_drain: Duration,
+}
+
+/// Что будет перезапущено: компоненты в порядке остановки и запуска
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RestartPlan {
+    pub components: Vec<RestartComponent>,
+    /// Компоненты останавливаются по очереди, поэтому дренаж суммируется
+    pub estimated_drain: Duration,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RestartError {
+    #[error("Restart not confirmed")]
+    NotConfirmed(RestartPlan),
+    #[error("Failed to {action} {component}: {message}{}", left_stopped_note(.left_stopped))]
+    Component {
+        component: String,
+        action: &'static str,
+        message: String,
+        /// Компоненты, которые после ошибки остались остановленными
+        left_stopped: Vec<String>,
+    },
+}
+
+fn left_stopped_note(left_stopped: &[String]) -> String {
+    if left_stopped.is_empty() {
+        String::new()
+    } else {
+        format!(" (left stopped: {})", left_stopped.join(", "))
+    }
+}
+
+/// Запускает компоненты по порядку, продолжая после ошибок.
+/// Возвращает имена тех, что запустить не удалось.
+async fn start_all(components: &[Arc<dyn Restartable>], events: &EventBus) -> Vec<String> {
+    let mut failed = Vec::new();
+    for component in components {
+        match component.start().await {
+            Ok(()) => events.publish(EventKind::ComponentStarted, serde_json::json!({ "component": component.name() })),
+            Err(e) => {
+                log::error!("Admin: Failed to start {}: {}", component.name(), e);
+                failed.push(component.name().to_string());
+            }
+        }
+    }
+    failed
+}
+
+/// Строит план, ничего не останавливая
+pub async fn restart_plan(components: &[Arc<dyn Restartable>]) -> RestartPlan {
+    let mut plan = RestartPlan { components: Vec::with_capacity(components.len()), estimated_drain: Duration::ZERO };
+    for component in components {
+        let estimated_drain = component.estimated_drain().await;
+        plan.estimated_drain += estimated_drain;
+        plan.components.push(RestartComponent {
+            name: component.name().to_string(),
+            estimated_drain,
+        });
+    }
+    plan
+}
+
+/// Останавливает и запускает компоненты. Без `confirm` возвращает
+/// `NotConfirmed` с планом и ничего не трогает. Каждая остановка и запуск
+/// публикуются в шину событий. Если компонент не остановился, уже
+/// остановленные запускаются обратно; не запустившиеся перечислены в ошибке.
+pub async fn restart_components(
+    components: &[Arc<dyn Restartable>],
+    events: &EventBus,
+    confirm: bool,
+) -> Result<RestartPlan, RestartError> {
+    let plan = restart_plan(components).await;
+    if !confirm {
+        return Err(RestartError::NotConfirmed(plan));
+    }
+
+    log::info!("Admin: Restarting system: {:?}", plan);
+
+    for (index, component) in components.iter().enumerate() {
+        if let Err(message) = component.stop().await {
+            let left_stopped = start_all(&components[..index], events).await;
+            return Err(RestartError::Component {
+                component: component.name().to_string(),
+                action: "stop",
+                message,
+                left_stopped,
+            });
+        }
+        events.publish(EventKind::ComponentStopped, serde_json::json!({ "component": component.name() }));
+    }
+
+    for (index, component) in components.iter().enumerate() {
+        if let Err(message) = component.start().await {
+            return Err(RestartError::Component {
+                component: component.name().to_string(),
+                action: "start",
+                message,
+                left_stopped: components[index..].iter().map(|c| c.name().to_string()).collect(),
+            });
+        }
+        events.publish(EventKind::ComponentStarted, serde_json::json!({ "component": component.name() }));
+    }
+
+    log::info!("Admin: System restarted successfully");
+    Ok(plan)
+}
+
+/// Пул и API сервер - компоненты, которые перезапускает администратор
+pub fn system_components(pool_manager: Arc<PoolManager>, api_server: Arc<ApiServer>) -> Vec<Arc<dyn Restartable>> {
+    vec![pool_manager, api_server]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    /// Компонент, записывающий вызовы в общий журнал
+    struct FakeComponent {
+        name: &'static str,
+        drain: Duration,
+        calls: Arc<Mutex<Vec<String>>>,
+        fail_stop: bool,
+    }
+
+    #[async_trait]
+    impl Restartable for FakeComponent {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        async fn estimated_drain(&self) -> Duration {
+            self.drain
+        }
+
+        async fn stop(&self) -> Result<(), String> {
+            self.calls.lock().push(format!("stop {}", self.name));
+            if self.fail_stop {
+                return Err("busy".to_string());
+            }
+            Ok(())
+        }
+
+        async fn start(&self) -> Result<(), String> {
+            self.calls.lock().push(format!("start {}", self.name));
+            Ok(())
+        }
+    }
+
+    fn component(name: &'static str, drain: Duration, calls: &Arc<Mutex<Vec<String>>>) -> FakeComponent {
+        FakeComponent { name, drain, calls: calls.clone(), fail_stop: false }
+    }
+
+    fn components(calls: &Arc<Mutex<Vec<String>>>) -> Vec<Arc<dyn Restartable>> {
+        vec![
+            Arc::new(component("pool", Duration::from_secs(2), calls)),
+            Arc::new(component("api_server", Duration::from_secs(30), calls)),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_plan_lists_components_and_total_drain() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let plan = restart_plan(&components(&calls)).await;
+
+        assert_eq!(
+            plan.components,
+            vec![
+                RestartComponent { name: "pool".to_string(), estimated_drain: Duration::from_secs(2) },
+                RestartComponent { name: "api_server".to_string(), estimated_drain: Duration::from_secs(30) },
+            ]
+        );
+        assert_eq!(plan.estimated_drain, Duration::from_secs(32));
+        assert!(calls.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_restart_requires_confirmation() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let components = components(&calls);
+        let events = EventBus::default();
+
+        let result = restart_components(&components, &events, false).await;
+
+        assert!(matches!(result, Err(RestartError::NotConfirmed(plan)) if plan.components.len() == 2));
+        assert!(calls.lock().is_empty());
+        assert!(events.recent(None, 10).is_empty());
+
+        let plan = restart_components(&components, &events, true).await.unwrap();
+
+        assert_eq!(plan.components.len(), 2);
+        assert_eq!(
+            *calls.lock(),
+            vec!["stop pool", "stop api_server", "start pool", "start api_server"]
+        );
+        let published: Vec<(EventKind, String)> = events
+            .recent(None, 10)
+            .into_iter()
+            .map(|event| (event.kind, event.data["component"].as_str().unwrap().to_string()))
+            .collect();
+        assert_eq!(
+            published,
+            vec![
+                (EventKind::ComponentStopped, "pool".to_string()),
+                (EventKind::ComponentStopped, "api_server".to_string()),
+                (EventKind::ComponentStarted, "pool".to_string()),
+                (EventKind::ComponentStarted, "api_server".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_stop_restarts_already_stopped_components() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let components: Vec<Arc<dyn Restartable>> = vec![
+            Arc::new(component("pool", Duration::ZERO, &calls)),
+            Arc::new(FakeComponent { fail_stop: true, ..component("api_server", Duration::ZERO, &calls) }),
+            Arc::new(component("bot", Duration::ZERO, &calls)),
+        ];
+        let events = EventBus::default();
+
+        let err = restart_components(&components, &events, true).await.unwrap_err();
+
+        match err {
+            RestartError::Component { component, action, left_stopped, .. } => {
+                assert_eq!(component, "api_server");
+                assert_eq!(action, "stop");
+                assert!(left_stopped.is_empty());
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert_eq!(*calls.lock(), vec!["stop pool", "stop api_server", "start pool"]);
+        let kinds: Vec<EventKind> = events.recent(None, 10).into_iter().map(|event| event.kind).collect();
+        assert_eq!(kinds, vec![EventKind::ComponentStopped, EventKind::ComponentStarted]);
+    }
+}
@@ -0,0 +1,326 @@
+//! Self Test - Сквозная проверка работоспособности развертывания
+//!
+//! Выполняет лёгкий сценарий на in-memory реализациях:
+//! пул → воркер → задача → прогрев модели → запрос → награда → очистка
+
+use crate::pool::{PoolManager, PoolConfig};
+use crate::pool::reward_system::{RewardSystem, RewardConfig};
+use crate::workers::{WorkerManager, Worker, WorkerStatus, Task, TaskPriority, TaskRequirements};
+use crate::runtime::instance::{
+    InstanceManager, InstanceManagerConfig, DummyModel, default_model_config,
+};
+use crate::core::model_interface::ModelRequest;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+
+const SELF_TEST_POOL: &str = "self-test-pool";
+const SELF_TEST_WORKER: &str = "self-test-worker";
+const SELF_TEST_MODEL: &str = "self-test-model";
+const SELF_TEST_REWARD: &str = "self-test-reward";
+
+/// Результат шага самопроверки
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfTestStep {
+    pub name: String,
+    pub passed: bool,
+    pub message: String,
+    pub duration_ms: u64,
+}
+
+/// Отчёт самопроверки
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfTestReport {
+    pub passed: bool,
+    pub steps: Vec<SelfTestStep>,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+struct SelfTestRecorder {
+    steps: Vec<SelfTestStep>,
+    failed: bool,
+}
+
+impl SelfTestRecorder {
+    fn new() -> Self {
+        Self { steps: Vec::new(), failed: false }
+    }
+
+    /// Шаги после первой ошибки пропускаются (кроме очистки)
+    fn should_run(&mut self, name: &str) -> bool {
+        if self.failed {
+            self.steps.push(SelfTestStep {
+                name: name.to_string(),
+                passed: false,
+                message: "skipped after previous failure".to_string(),
+                duration_ms: 0,
+            });
+            return false;
+        }
+        true
+    }
+
+    fn record(&mut self, name: &str, started: Instant, result: Result<String, String>) {
+        let passed = result.is_ok();
+        if !passed {
+            log::error!("Self-test step '{}' failed", name);
+            self.failed = true;
+        }
+
+        self.steps.push(SelfTestStep {
+            name: name.to_string(),
+            passed,
+            message: result.unwrap_or_else(|e| e),
+            duration_ms: started.elapsed().as_millis() as u64,
+        });
+    }
+}
+
+/// Запускает сквозную самопроверку системы
+pub async fn run_self_test() -> SelfTestReport {
+    log::info!("Running self-test");
+
+    let pool_manager = PoolManager::new();
+    let worker_manager = WorkerManager::new();
+    let instance_manager = InstanceManager::new(InstanceManagerConfig {
+        initial_models: vec![],
+        ..InstanceManagerConfig::default()
+    });
+    let reward_system = RewardSystem::new();
+
+    let mut recorder = SelfTestRecorder::new();
+    let mut instance_id = None;
+
+    if recorder.should_run("create_pool") {
+        let started = Instant::now();
+        let result = pool_manager
+            .create_pool(self_test_pool_config())
+            .await
+            .map(|_| format!("pool '{}' created", SELF_TEST_POOL))
+            .map_err(|e| e.to_string());
+        recorder.record("create_pool", started, result);
+    }
+
+    if recorder.should_run("add_worker") {
+        let started = Instant::now();
+        let result = worker_manager
+            .add_worker(self_test_worker())
+            .await
+            .map(|_| format!("worker '{}' added", SELF_TEST_WORKER))
+            .map_err(|e| e.to_string());
+        recorder.record("add_worker", started, result);
+    }
+
+    if recorder.should_run("submit_task") {
+        let started = Instant::now();
+        let result = match worker_manager.distribute_task(self_test_task()).await {
+            Ok(worker_id) if worker_id == SELF_TEST_WORKER => {
+                Ok(format!("task assigned to '{}'", worker_id))
+            }
+            Ok(worker_id) => Err(format!("task assigned to unexpected worker '{}'", worker_id)),
+            Err(e) => Err(e.to_string()),
+        };
+        recorder.record("submit_task", started, result);
+    }
+
+    if recorder.should_run("warm_model") {
+        let started = Instant::now();
+        let result = instance_manager
+            .create_instance(
+                SELF_TEST_MODEL.to_string(),
+                Arc::new(DummyModel::new()),
+                default_model_config(SELF_TEST_MODEL),
+            )
+            .await
+            .map(|id| {
+                let message = format!("instance '{}' ready", id);
+                instance_id = Some(id);
+                message
+            })
+            .map_err(|e| e.to_string());
+        recorder.record("warm_model", started, result);
+    }
+
+    if recorder.should_run("run_request") {
+        let started = Instant::now();
+        let id = instance_id.clone().unwrap_or_default();
+        let result = instance_manager
+            .process_request(&id, self_test_request())
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|response| {
+                if response.text.is_empty() {
+                    Err("model returned an empty response".to_string())
+                } else {
+                    Ok(format!("{} tokens generated", response.tokens_used))
+                }
+            });
+        recorder.record("run_request", started, result);
+    }
+
+    if recorder.should_run("verify_reward") {
+        let started = Instant::now();
+        let result = verify_reward(&reward_system).await;
+        recorder.record("verify_reward", started, result);
+    }
+
+    // Очистка выполняется всегда
+    let started = Instant::now();
+    let mut cleanup_errors = Vec::new();
+    if let Some(id) = instance_id.as_deref() {
+        if let Err(e) = instance_manager.remove_instance(id).await {
+            cleanup_errors.push(e.to_string());
+        }
+    }
+    if let Err(e) = worker_manager.remove_worker(SELF_TEST_WORKER).await {
+        cleanup_errors.push(e.to_string());
+    }
+    if pool_manager.get_pool(SELF_TEST_POOL).await.is_some() {
+        if let Err(e) = pool_manager.delete_pool(SELF_TEST_POOL).await {
+            cleanup_errors.push(e.to_string());
+        }
+    }
+    let _ = reward_system.remove_reward(SELF_TEST_REWARD).await;
+    let cleanup = if cleanup_errors.is_empty() {
+        Ok("self-test resources removed".to_string())
+    } else {
+        Err(cleanup_errors.join("; "))
+    };
+    recorder.record("cleanup", started, cleanup);
+
+    let report = SelfTestReport {
+        passed: !recorder.failed,
+        steps: recorder.steps,
+        timestamp: chrono::Utc::now(),
+    };
+
+    log::info!("Self-test finished: {}", if report.passed { "passed" } else { "failed" });
+    report
+}
+
+async fn verify_reward(reward_system: &RewardSystem) -> Result<String, String> {
+    reward_system
+        .add_reward(RewardConfig {
+            id: SELF_TEST_REWARD.to_string(),
+            name: "Self-test".to_string(),
+            description: "Self-test reward".to_string(),
+            reward_amount: 1,
+            min_contributions: 1,
+            max_contributions: 1,
+            cooldown_period: 0,
+            active: true,
+        })
+        .await?;
+
+    reward_system
+        .add_contribution_for_event("self-test-event", SELF_TEST_WORKER, SELF_TEST_REWARD, 1)
+        .await?;
+
+    let reward = reward_system.get_reward(SELF_TEST_REWARD).await?;
+    if reward.stats.total_contributions == 1 {
+        Ok("reward accrued".to_string())
+    } else {
+        Err(format!(
+            "expected 1 contribution, found {}",
+            reward.stats.total_contributions
+        ))
+    }
+}
+
+fn self_test_pool_config() -> PoolConfig {
+    PoolConfig {
+        name: SELF_TEST_POOL.to_string(),
+        description: "Self-test pool".to_string(),
+        max_workers: 1,
+        max_memory_gb: 1,
+        max_cpu_cores: 1,
+        auto_scale: false,
+        min_workers: 0,
+        max_workers_per_vm: 1,
+        vm_template: "none".to_string(),
+        network_mode: "none".to_string(),
+        security_groups: vec![],
+        tags: vec!["self-test".to_string()],
+        payout_threshold: 0.1,
+        algorithm: String::new(),
+        allowed_gpu_models: vec![],
+        fee_percentage: 0.0,
+    }
+}
+
+fn self_test_worker() -> Worker {
+    Worker {
+        id: SELF_TEST_WORKER.to_string(),
+        name: "Self-test worker".to_string(),
+        status: WorkerStatus::Active,
+        hashrate: 1.0,
+        cpu_usage: 0.0,
+        memory_usage: 0.0,
+        gpu_usage: 0.0,
+        uptime: std::time::Duration::from_secs(0),
+        last_seen: chrono::Utc::now(),
+        capabilities: vec!["self-test".to_string()],
+        calibrated_hashrate: None,
+        endpoint: None,
+    }
+}
+
+fn self_test_task() -> Task {
+    Task {
+        id: "self-test-task".to_string(),
+        name: "Self-test task".to_string(),
+        priority: TaskPriority::Low,
+        requirements: TaskRequirements {
+            min_cpu: 1.0,
+            min_memory: 1.0,
+            min_gpu: 0.0,
+            capabilities: vec!["self-test".to_string()],
+        },
+        data: serde_json::Value::Null,
+    }
+}
+
+fn self_test_request() -> ModelRequest {
+    ModelRequest {
+        prompt: "self-test".to_string(),
+        max_tokens: Some(8),
+        temperature: None,
+        top_p: None,
+        frequency_penalty: None,
+        presence_penalty: None,
+        stop_sequences: None,
+        stream: None,
+        user_id: None,
+        session_id: None,
+        metadata: None,
+        required_features: vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_self_test_passes_on_in_memory_components() {
+        let report = run_self_test().await;
+
+        let failed: Vec<_> = report.steps.iter().filter(|s| !s.passed).collect();
+        assert!(failed.is_empty(), "failed steps: {:?}", failed);
+        assert!(report.passed);
+
+        let names: Vec<_> = report.steps.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "create_pool",
+                "add_worker",
+                "submit_task",
+                "warm_model",
+                "run_request",
+                "verify_reward",
+                "cleanup",
+            ]
+        );
+    }
+}
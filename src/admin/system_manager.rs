@@ -94,10 +94,10 @@ impl SystemManager {
     pub async fn get_system_info(&self) -> SystemInfo {
         SystemInfo {
             version: env!("CARGO_PKG_VERSION").to_string(),
-            build_date: env!("VERGEN_BUILD_TIMESTAMP").to_string(),
-            rust_version: env!("VERGEN_RUSTC_SEMVER").to_string(),
-            target_arch: env!("VERGEN_CARGO_TARGET_ARCH").to_string(),
-            target_os: env!("VERGEN_CARGO_TARGET_OS").to_string(),
+            build_date: crate::version::build_timestamp().to_string(),
+            rust_version: crate::version::RUST_VERSION.to_string(),
+            target_arch: crate::version::ARCH.to_string(),
+            target_os: crate::version::OS.to_string(),
         }
     }
 
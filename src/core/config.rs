@@ -13,9 +13,17 @@ use chrono::{DateTime, Utc};
 use std::time::Duration;
 use std::fs::{File, OpenOptions};
 use std::io::{Read, Write};
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
 use crate::core::error::CursorError;
-use crate::monitoring::logger::LoggerSystem;
-use crate::monitoring::alert::AlertSystem;
+use crate::monitoring::logger::{LogFileConfig, LogFormat, LoggerSystem};
+use crate::monitoring::webhook::WebhookConfig;
+use crate::workers::worker_monitor::MetricSamplerConfig;
+use crate::workers::calibration::CalibrationConfig;
+use crate::workers::supervisor::SupervisorConfig;
+use crate::vm::idle::IdleShutdownConfig;
+use crate::core::model_interface::PerformanceConfig;
+use crate::monitoring::alert::{AlertRuleConfig, AlertSystem};
 
 #[derive(Error, Debug)]
 pub enum ConfigError {
@@ -25,9 +33,11 @@ pub enum ConfigError {
     TomlError(#[from] toml::de::Error),
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
+    #[error("Config watch error: {0}")]
+    WatchError(String),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerConfig {
     pub http_port: u16,
     pub https_port: u16,
@@ -42,9 +52,23 @@ pub struct ServerConfig {
     pub max_connections: usize,
     pub keep_alive: u64,
     pub client_timeout: u64,
+    /// Запускать только HTTP, если TLS не удалось инициализировать (для разработки)
+    #[serde(default)]
+    pub allow_http_only: bool,
+    /// Лимит запросов клиента в минуту; меняется без перезапуска
+    #[serde(default = "default_rate_limit")]
+    pub rate_limit: u32,
+    /// CA для проверки клиентских сертификатов (mTLS). Если задан,
+    /// соединения без действительного клиентского сертификата отклоняются
+    #[serde(default)]
+    pub client_ca_path: Option<PathBuf>,
+}
+
+fn default_rate_limit() -> u32 {
+    1000
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RaidConfig {
     pub raid_level: u8,
     pub min_disks: u8,
@@ -54,7 +78,7 @@ pub struct RaidConfig {
     pub rebuild_priority: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BridgeConfig {
     pub source_chain: String,
     pub target_chain: String,
@@ -66,13 +90,47 @@ pub struct BridgeConfig {
     pub retry_delay: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
     pub server: ServerConfig,
     pub raid: RaidConfig,
     pub bridge: BridgeConfig,
     pub solana_rpc_url: String,
     pub log_level: String,
+    /// Формат логов: `text` (по умолчанию) или `json`
+    #[serde(default)]
+    pub log_format: LogFormat,
+    /// Файл логов с ротацией; без него логи пишутся в stderr
+    #[serde(default)]
+    pub log_file: Option<LogFileConfig>,
+    /// Исходящие уведомления о событиях; без настройки не отправляются
+    #[serde(default)]
+    pub webhook: Option<WebhookConfig>,
+    /// Периодический сбор метрик воркеров для истории
+    #[serde(default)]
+    pub metric_sampler: MetricSamplerConfig,
+    /// Замер хешрейта воркеров при подключении
+    #[serde(default)]
+    pub calibration: CalibrationConfig,
+    /// Автоматический перезапуск воркеров в статусе `Error`
+    #[serde(default)]
+    pub supervisor: SupervisorConfig,
+    /// Автоостановка простаивающих VM
+    #[serde(default)]
+    pub vm_idle: IdleShutdownConfig,
+    /// JSON-файл с пулами; без него пулы живут только в памяти
+    #[serde(default)]
+    pub pool_storage_path: Option<PathBuf>,
+    /// JSON-файл с балансами наград воркеров: читается при запуске,
+    /// записывается при остановке
+    #[serde(default)]
+    pub reward_balances_path: Option<PathBuf>,
+    /// Кэширование ответов моделей; без секции кэш выключен
+    #[serde(default)]
+    pub model_performance: Option<PerformanceConfig>,
+    /// Пороговые правила алертов, например `cpu_usage > 90 for 5m`
+    #[serde(default)]
+    pub alert_rules: Vec<AlertRuleConfig>,
     pub environment: String,
 }
 
@@ -93,10 +151,14 @@ impl Default for AppConfig {
                 enable_http2: true,
                 enable_ocsp_stapling: true,
                 cert_chain_path: None,
-                bind_address: IpAddr::from_str("0.0.0.0").unwrap(),
+                // Наружу сервер открывается только явной настройкой
+                bind_address: IpAddr::from_str("127.0.0.1").unwrap(),
                 max_connections: 10000,
                 keep_alive: 75,
                 client_timeout: 30,
+                allow_http_only: false,
+                rate_limit: default_rate_limit(),
+                client_ca_path: None,
             },
             raid: RaidConfig {
                 raid_level: 1,
@@ -118,196 +180,326 @@ impl Default for AppConfig {
             },
             solana_rpc_url: "https://api.mainnet-beta.solana.com".to_string(),
             log_level: "info".to_string(),
+            log_format: LogFormat::default(),
+            log_file: None,
+            webhook: None,
+            metric_sampler: MetricSamplerConfig::default(),
+            calibration: CalibrationConfig::default(),
+            supervisor: SupervisorConfig::default(),
+            vm_idle: IdleShutdownConfig::default(),
+            pool_storage_path: None,
+            reward_balances_path: None,
+            model_performance: None,
+            alert_rules: Vec::new(),
             environment: "development".to_string(),
         }
     }
 }
 
 impl AppConfig {
-    pub fn load() -> Result<Self, ConfigError> {
-        let config_path = std::env::var("CONFIG_PATH").unwrap_or_else(|_| "config.toml".to_string());
-        
-        let metadata = std::fs::metadata(&config_path)?;
-        if metadata.permissions().mode() & 0o077 != 0 {
-            return Err(ConfigError::InvalidConfig(
-                "Configuration file has unsafe permissions".to_string()
-            ));
-        }
+    /// Путь к файлу конфигурации (`CONFIG_PATH` или `config.toml`)
+    pub fn path() -> PathBuf {
+        PathBuf::from(std::env::var("CONFIG_PATH").unwrap_or_else(|_| "config.toml".to_string()))
+    }
 
-        if std::path::Path::new(&config_path).exists() {
-            let contents = std::fs::read_to_string(&config_path)?;
-            
-            if let Some(signature) = std::env::var("CONFIG_SIGNATURE").ok() {
-                if !Self::verify_config_signature(&contents, &signature)? {
-                    return Err(ConfigError::InvalidConfig(
-                        "Configuration signature verification failed".to_string()
-                    ));
-                }
-            }
+    pub fn load() -> Result<Self, ConfigError> {
+        let config_path = Self::path();
 
-            let mut config: AppConfig = toml::from_str(&contents)?;
-            config.validate()?;
-            Ok(config)
+        if config_path.exists() {
+            Self::load_from(&config_path)
         } else {
             let config = AppConfig::default();
             let contents = toml::to_string_pretty(&config)?;
             
             let mut file = std::fs::File::create(&config_path)?;
-            file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+            restrict_permissions(&file)?;
             file.write_all(contents.as_bytes())?;
             
             Ok(config)
         }
     }
 
+    /// Читает и проверяет конфигурацию из файла
+    pub fn load_from(config_path: &Path) -> Result<Self, ConfigError> {
+        check_permissions(&std::fs::metadata(config_path)?, config_path)?;
+
+        let contents = std::fs::read_to_string(config_path)?;
+
+        if let Some(signature) = std::env::var("CONFIG_SIGNATURE").ok() {
+            if !Self::verify_config_signature(&contents, &signature)? {
+                return Err(ConfigError::InvalidConfig(
+                    "Configuration signature verification failed".to_string()
+                ));
+            }
+        }
+
+        let config: AppConfig = toml::from_str(&contents)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Следит за файлом конфигурации и применяет изменения без перезапуска.
+    /// Меняются только уровень логирования, лимит запросов и параметры
+    /// комиссии моста; порты и адрес привязки требуют перезапуска.
+    /// Некорректная конфигурация логируется и игнорируется.
+    pub fn watch(
+        path: impl AsRef<Path>,
+        config: Arc<tokio::sync::RwLock<AppConfig>>,
+    ) -> Result<ConfigWatcher, ConfigError> {
+        use notify::Watcher;
+
+        let path = path.as_ref().to_path_buf();
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_os_string())
+            .ok_or_else(|| ConfigError::WatchError(format!("{} is not a file", path.display())))?;
+        // Следим за каталогом: редакторы часто заменяют файл целиком
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            match event {
+                Ok(event) if event.kind.is_modify() || event.kind.is_create() => {
+                    if event.paths.iter().any(|p| p.file_name() == Some(file_name.as_os_str())) {
+                        let _ = tx.send(());
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Config watch error: {}", e),
+            }
+        })
+        .map_err(|e| ConfigError::WatchError(e.to_string()))?;
+        watcher
+            .watch(&dir, notify::RecursiveMode::NonRecursive)
+            .map_err(|e| ConfigError::WatchError(e.to_string()))?;
+
+        let (reloads, _) = tokio::sync::broadcast::channel(CONFIG_RELOAD_CAPACITY);
+        let reload_path = path.clone();
+        let reload_tx = reloads.clone();
+        let task = tokio::spawn(async move {
+            while rx.recv().await.is_some() {
+                // Схлопываем пачку событий от одной записи
+                while rx.try_recv().is_ok() {}
+
+                let reload = match AppConfig::load_from(&reload_path) {
+                    Ok(new_config) => {
+                        let mut current = config.write().await;
+                        let changed = current.apply_hot_reload(new_config);
+                        if changed.is_empty() {
+                            continue;
+                        }
+                        info!("Reloaded configuration: {}", changed.join(", "));
+                        ConfigReload::Applied(changed)
+                    }
+                    Err(e) => {
+                        warn!(
+                            "Ignoring invalid configuration in {}: {}",
+                            reload_path.display(),
+                            e
+                        );
+                        ConfigReload::Rejected(e.to_string())
+                    }
+                };
+                // Подписчиков может не быть
+                let _ = reload_tx.send(reload);
+            }
+        });
+
+        info!("Watching configuration file {}", path.display());
+        Ok(ConfigWatcher { _watcher: watcher, task, reloads })
+    }
+
+    /// Переносит из `new` параметры, допускающие горячую замену.
+    /// Возвращает список изменённых параметров.
+    pub fn apply_hot_reload(&mut self, new: AppConfig) -> Vec<String> {
+        let mut changed = Vec::new();
+
+        if new.server.http_port != self.server.http_port
+            || new.server.https_port != self.server.https_port
+            || new.server.bind_address != self.server.bind_address
+        {
+            warn!("Bind address and ports cannot be changed without a restart; keeping current values");
+        }
+
+        if new.log_level != self.log_level {
+            match new.log_level.parse::<log::LevelFilter>() {
+                Ok(level) => {
+                    log::set_max_level(level);
+                    self.log_level = new.log_level;
+                    changed.push("log_level".to_string());
+                }
+                Err(_) => warn!("Ignoring invalid log_level '{}'", new.log_level),
+            }
+        }
+
+        if new.server.rate_limit != self.server.rate_limit {
+            self.server.rate_limit = new.server.rate_limit;
+            changed.push("server.rate_limit".to_string());
+        }
+
+        if new.bridge.fee_percentage != self.bridge.fee_percentage
+            || new.bridge.min_amount != self.bridge.min_amount
+            || new.bridge.max_amount != self.bridge.max_amount
+        {
+            self.bridge.fee_percentage = new.bridge.fee_percentage;
+            self.bridge.min_amount = new.bridge.min_amount;
+            self.bridge.max_amount = new.bridge.max_amount;
+            changed.push("bridge fees".to_string());
+        }
+
+        changed
+    }
+
+    /// Возвращает первую найденную ошибку конфигурации.
     fn validate(&self) -> Result<(), ConfigError> {
+        match self.validate_all() {
+            Ok(()) => Ok(()),
+            Err(mut errors) => Err(errors.remove(0)),
+        }
+    }
+
+    /// Проверяет всю конфигурацию (сервер, RAID, bridge и проверки
+    /// безопасности) и возвращает все найденные ошибки разом.
+    pub fn validate_all(&self) -> Result<(), Vec<ConfigError>> {
+        let mut issues: Vec<String> = Vec::new();
+
         // Validate server configuration
         if self.server.http_port == self.server.https_port {
-            return Err(ConfigError::InvalidConfig("HTTP and HTTPS ports must be different".to_string()));
+            issues.push("HTTP and HTTPS ports must be different".to_string());
         }
 
         if self.server.http_port == 0 || self.server.https_port == 0 {
-            return Err(ConfigError::InvalidConfig("Port numbers must be greater than 0".to_string()));
+            issues.push("Port numbers must be greater than 0".to_string());
         }
 
-        if !self.server.cert_path.exists() {
-            return Err(ConfigError::InvalidConfig("Certificate file not found".to_string()));
-        }
+        let tls_files_missing = !self.server.cert_path.exists() || !self.server.key_path.exists();
+        if tls_files_missing && self.server.allow_http_only {
+            warn!("TLS certificate or key not found; HTTPS will be disabled (allow_http_only)");
+        } else {
+            if !self.server.cert_path.exists() {
+                issues.push("Certificate file not found".to_string());
+            }
 
-        if !self.server.key_path.exists() {
-            return Err(ConfigError::InvalidConfig("Key file not found".to_string()));
+            if !self.server.key_path.exists() {
+                issues.push("Key file not found".to_string());
+            }
         }
 
         if let Some(chain_path) = &self.server.cert_chain_path {
             if !chain_path.exists() {
-                return Err(ConfigError::InvalidConfig("Certificate chain file not found".to_string()));
+                issues.push("Certificate chain file not found".to_string());
             }
         }
 
-        // Проверка безопасности серверной конфигурации
-        if !self.is_safe_server_config()? {
-            return Err(ConfigError::InvalidConfig("Unsafe server configuration".to_string()));
-        }
-
-        // Validate RAID configuration
-        if self.raid.raid_level > 1 {
-            return Err(ConfigError::InvalidConfig("Only RAID levels 0 and 1 are supported".to_string()));
+        if let Some(ca_path) = &self.server.client_ca_path {
+            if !ca_path.exists() {
+                issues.push("Client CA file not found".to_string());
+            }
         }
 
-        if self.raid.min_disks < 2 {
-            return Err(ConfigError::InvalidConfig("Minimum 2 disks required for RAID".to_string()));
-        }
+        // Проверка безопасности серверной конфигурации
+        issues.extend(self.server_safety_issues());
 
+        // Validate RAID configuration
         if self.raid.stripe_size == 0 {
-            return Err(ConfigError::InvalidConfig("Stripe size must be greater than 0".to_string()));
+            issues.push("Stripe size must be greater than 0".to_string());
         }
 
         // Проверка безопасности RAID конфигурации
-        if !self.is_safe_raid_config()? {
-            return Err(ConfigError::InvalidConfig("Unsafe RAID configuration".to_string()));
-        }
+        issues.extend(self.raid_safety_issues());
 
         // Validate bridge configuration
-        if self.bridge.fee_percentage <= 0.0 || self.bridge.fee_percentage >= 1.0 {
-            return Err(ConfigError::InvalidConfig("Fee percentage must be between 0 and 1".to_string()));
-        }
+        issues.extend(self.bridge_safety_issues());
 
-        if self.bridge.min_amount >= self.bridge.max_amount {
-            return Err(ConfigError::InvalidConfig("Minimum amount must be less than maximum amount".to_string()));
-        }
+        // Одно и то же нарушение может найти несколько проверок
+        let mut seen = std::collections::HashSet::new();
+        issues.retain(|issue| seen.insert(issue.clone()));
 
-        // Проверка безопасности bridge конфигурации
-        if !self.is_safe_bridge_config()? {
-            return Err(ConfigError::InvalidConfig("Unsafe bridge configuration".to_string()));
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues.into_iter().map(ConfigError::InvalidConfig).collect())
         }
-
-        Ok(())
     }
 
     // Вспомогательные методы для проверки безопасности
-    fn is_safe_server_config(&self) -> Result<bool, ConfigError> {
+    fn server_safety_issues(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+
         // Проверка TLS версии
         if self.server.tls_version != "1.3" {
-            return Err(ConfigError::InvalidConfig(
-                "Only TLS 1.3 is supported for security reasons".to_string()
-            ));
+            issues.push("Only TLS 1.3 is supported for security reasons".to_string());
         }
 
         // Проверка cipher suites
         let unsafe_ciphers = ["RC4", "DES", "3DES", "MD5"];
         for cipher in &self.server.cipher_suites {
             if unsafe_ciphers.iter().any(|&c| cipher.contains(c)) {
-                return Err(ConfigError::InvalidConfig(
-                    format!("Unsafe cipher suite detected: {}", cipher)
-                ));
+                issues.push(format!("Unsafe cipher suite detected: {}", cipher));
             }
         }
 
         // Проверка максимального количества соединений
         if self.server.max_connections > 100000 {
-            return Err(ConfigError::InvalidConfig(
-                "Maximum connections limit too high".to_string()
-            ));
+            issues.push("Maximum connections limit too high".to_string());
         }
 
         // Проверка таймаутов
         if self.server.keep_alive > 300 || self.server.client_timeout > 60 {
-            return Err(ConfigError::InvalidConfig(
-                "Timeout values too high".to_string()
-            ));
+            issues.push("Timeout values too high".to_string());
         }
 
-        Ok(true)
+        issues
     }
 
-    fn is_safe_raid_config(&self) -> Result<bool, ConfigError> {
+    fn raid_safety_issues(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+
         // Проверка RAID уровня
-        if self.raid.raid_level > 1 {
-            return Err(ConfigError::InvalidConfig(
-                "Only RAID levels 0 and 1 are supported".to_string()
-            ));
+        if !matches!(self.raid.raid_level, 0 | 1 | 5) {
+            issues.push("Only RAID levels 0, 1 and 5 are supported".to_string());
         }
 
         // Проверка минимального количества дисков
         if self.raid.min_disks < 2 {
-            return Err(ConfigError::InvalidConfig(
-                "Minimum 2 disks required for RAID".to_string()
-            ));
+            issues.push("Minimum 2 disks required for RAID".to_string());
+        }
+
+        // RAID 5: минимум два диска данных и один диск чётности
+        if self.raid.raid_level == 5 && self.raid.min_disks < 3 {
+            issues.push("Minimum 3 disks required for RAID 5".to_string());
         }
 
         // Проверка размера страйпа
-        if self.raid.stripe_size == 0 || self.raid.stripe_size > 1024 * 1024 * 1024 {
-            return Err(ConfigError::InvalidConfig(
-                "Invalid stripe size".to_string()
-            ));
+        if self.raid.stripe_size > 1024 * 1024 * 1024 {
+            issues.push("Invalid stripe size".to_string());
         }
 
-        Ok(true)
+        issues
     }
 
-    fn is_safe_bridge_config(&self) -> Result<bool, ConfigError> {
+    fn bridge_safety_issues(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+
         // Проверка комиссии
         if self.bridge.fee_percentage <= 0.0 || self.bridge.fee_percentage >= 1.0 {
-            return Err(ConfigError::InvalidConfig(
-                "Fee percentage must be between 0 and 1".to_string()
-            ));
+            issues.push("Fee percentage must be between 0 and 1".to_string());
         }
 
         // Проверка минимальной и максимальной суммы
         if self.bridge.min_amount >= self.bridge.max_amount {
-            return Err(ConfigError::InvalidConfig(
-                "Minimum amount must be less than maximum amount".to_string()
-            ));
+            issues.push("Minimum amount must be less than maximum amount".to_string());
         }
 
         // Проверка количества подтверждений
         if self.bridge.confirmation_blocks < 1 || self.bridge.confirmation_blocks > 100 {
-            return Err(ConfigError::InvalidConfig(
-                "Invalid confirmation blocks count".to_string()
-            ));
+            issues.push("Invalid confirmation blocks count".to_string());
         }
 
-        Ok(true)
+        issues
     }
 
     fn verify_config_signature(&self, contents: &str, signature: &str) -> Result<bool, ConfigError> {
@@ -316,6 +508,73 @@ impl AppConfig {
     }
 }
 
+/// Наблюдение за файлом конфигурации; прекращается при удалении
+pub struct ConfigWatcher {
+    _watcher: notify::RecommendedWatcher,
+    task: tokio::task::JoinHandle<()>,
+    reloads: tokio::sync::broadcast::Sender<ConfigReload>,
+}
+
+impl ConfigWatcher {
+    /// Уведомления о перезагрузках. После `Applied` новые значения уже лежат
+    /// в общей конфигурации, переданной в `AppConfig::watch`.
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<ConfigReload> {
+        self.reloads.subscribe()
+    }
+}
+
+/// Сколько непрочитанных уведомлений о перезагрузке хранит канал
+const CONFIG_RELOAD_CAPACITY: usize = 16;
+
+/// Итог перезагрузки файла конфигурации
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigReload {
+    /// Применены изменения перечисленных параметров
+    Applied(Vec<String>),
+    /// Файл некорректен; действует прежняя конфигурация
+    Rejected(String),
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Файл конфигурации доступен только владельцу
+#[cfg(unix)]
+fn restrict_permissions(file: &File) -> Result<(), ConfigError> {
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    Ok(())
+}
+
+/// На Windows права задаются ACL каталога, файл наследует их
+#[cfg(windows)]
+fn restrict_permissions(_file: &File) -> Result<(), ConfigError> {
+    Ok(())
+}
+
+/// Группа и остальные пользователи не должны иметь доступа к конфигурации
+#[cfg(unix)]
+fn check_permissions(metadata: &std::fs::Metadata, _path: &Path) -> Result<(), ConfigError> {
+    if metadata.permissions().mode() & 0o077 != 0 {
+        return Err(ConfigError::InvalidConfig(
+            "Configuration file has unsafe permissions".to_string()
+        ));
+    }
+    Ok(())
+}
+
+/// ACL на Windows не проверяются: только предупреждение
+#[cfg(windows)]
+fn check_permissions(_metadata: &std::fs::Metadata, path: &Path) -> Result<(), ConfigError> {
+    log::warn!(
+        "Skipping permission check for {}: ACL verification is not supported on Windows",
+        path.display()
+    );
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -342,11 +601,118 @@ mod tests {
         config.raid.raid_level = 2;
         assert!(config.validate().is_err());
 
+        // RAID 5 requires at least three disks
+        config = AppConfig::default();
+        config.raid.raid_level = 5;
+        config.raid.min_disks = 2;
+        assert!(!config.raid_safety_issues().is_empty());
+        config.raid.min_disks = 3;
+        assert!(config.raid_safety_issues().is_empty());
+
         // Test invalid bridge configuration
         config = AppConfig::default();
         config.bridge.fee_percentage = 1.5;
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_validate_all_collects_every_error() {
+        let mut config = AppConfig::default();
+        config.server.allow_http_only = true;
+        assert!(config.validate_all().is_ok());
+
+        config.server.tls_version = "1.2".to_string();
+        config.raid.raid_level = 3;
+        config.bridge.fee_percentage = 1.5;
+
+        let errors = config.validate_all().unwrap_err();
+        let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(messages.len(), 3, "{:?}", messages);
+        assert!(messages.iter().any(|m| m.contains("TLS 1.3")));
+        assert!(messages.iter().any(|m| m.contains("RAID levels")));
+        assert!(messages.iter().any(|m| m.contains("Fee percentage")));
+
+        // validate() по-прежнему возвращает первую ошибку
+        let first = config.validate().unwrap_err().to_string();
+        assert_eq!(first, messages[0]);
+    }
+
+    fn write_config(path: &Path, config: &AppConfig) {
+        std::fs::write(path, toml::to_string_pretty(config).unwrap()).unwrap();
+        #[cfg(unix)]
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600)).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_load_rejects_group_readable_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let mut config = AppConfig::default();
+        config.server.allow_http_only = true;
+        write_config(&path, &config);
+        assert!(AppConfig::load_from(&path).is_ok());
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let err = AppConfig::load_from(&path).unwrap_err();
+        assert!(err.to_string().contains("unsafe permissions"));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_load_skips_permission_check_on_windows() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let mut config = AppConfig::default();
+        config.server.allow_http_only = true;
+        write_config(&path, &config);
+        assert!(AppConfig::load_from(&path).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_watch_applies_log_level_and_keeps_ports() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        let mut initial = AppConfig::default();
+        initial.server.allow_http_only = true;
+        write_config(&path, &initial);
+
+        let shared = Arc::new(tokio::sync::RwLock::new(AppConfig::load_from(&path).unwrap()));
+        let watcher = AppConfig::watch(&path, shared.clone()).unwrap();
+        let mut reloads = watcher.subscribe();
+        let next_reload = |reloads: &mut tokio::sync::broadcast::Receiver<ConfigReload>| {
+            let reload = tokio::time::timeout(Duration::from_secs(5), reloads.recv());
+            async move { reload.await.expect("config was not reloaded").unwrap() }
+        };
+
+        // Некорректная конфигурация отклоняется, прежняя остаётся в силе
+        let mut invalid = initial.clone();
+        invalid.bridge.fee_percentage = 5.0;
+        invalid.log_level = "trace".to_string();
+        write_config(&path, &invalid);
+        assert!(matches!(next_reload(&mut reloads).await, ConfigReload::Rejected(_)));
+        assert_eq!(shared.read().await.log_level, "info");
+        assert_eq!(shared.read().await.bridge.fee_percentage, initial.bridge.fee_percentage);
+
+        let mut updated = initial.clone();
+        updated.log_level = "debug".to_string();
+        updated.server.http_port = 9090;
+        updated.server.rate_limit = 10;
+        write_config(&path, &updated);
+
+        // Файл может быть прочитан наполовину записанным; ждём применения
+        let changed = loop {
+            if let ConfigReload::Applied(changed) = next_reload(&mut reloads).await {
+                break changed;
+            }
+        };
+        assert!(changed.contains(&"log_level".to_string()), "{:?}", changed);
+        assert!(changed.contains(&"server.rate_limit".to_string()), "{:?}", changed);
+        assert_eq!(shared.read().await.log_level, "debug");
+        assert_eq!(shared.read().await.server.rate_limit, 10);
+        assert_eq!(shared.read().await.server.http_port, 8080);
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -433,18 +799,22 @@ impl ConfigSystem {
                 .map_err(|e| format!("Failed to create config directory: {}", e))?;
         }
 
+        let contents = serde_json::to_string_pretty(&*config)
+            .map_err(|e| format!("Failed to serialize config: {}", e))?;
+
+        // Пишем во временный файл и переименовываем, чтобы не оставить файл наполовину записанным
+        let tmp_path = path.with_extension("tmp");
         let mut file = OpenOptions::new()
             .create(true)
             .write(true)
             .truncate(true)
-            .open(path)
+            .open(&tmp_path)
             .map_err(|e| format!("Failed to open config file: {}", e))?;
 
-        let contents = serde_json::to_string_pretty(&*config)
-            .map_err(|e| format!("Failed to serialize config: {}", e))?;
-
         file.write_all(contents.as_bytes())
             .map_err(|e| format!("Failed to write config file: {}", e))?;
+        std::fs::rename(&tmp_path, path)
+            .map_err(|e| format!("Failed to replace config file: {}", e))?;
 
         info!("Saved configuration to: {}", self.file_path);
         Ok(())
@@ -608,4 +978,4 @@ impl ConfigSystem {
         info!("Updated section: {}", id);
         Ok(())
     }
-} 
\ No newline at end of file
+}
\ No newline at end of file
@@ -58,6 +58,9 @@ pub enum AppError {
     #[error("Router error: {0}")]
     Router(String),
 
+    #[error("Model error: {0}")]
+    Model(String),
+
     #[error("Reward error: {0}")]
     Reward(String),
 
@@ -111,6 +114,7 @@ impl AppError {
             AppError::VM(msg) => format!("VM error: {}", msg),
             AppError::Bridge(msg) => format!("Bridge error: {}", msg),
             AppError::Router(msg) => format!("Router error: {}", msg),
+            AppError::Model(msg) => format!("Model error: {}", msg),
             AppError::Reward(msg) => format!("Reward error: {}", msg),
             AppError::Pool(msg) => format!("Pool error: {}", msg),
             AppError::Telegram(msg) => format!("Telegram error: {}", msg),
@@ -124,6 +128,53 @@ impl AppError {
 
 pub type AppResult<T> = Result<T, AppError>;
 
+/// Ошибки `CursorCore`. Варианты без прямого аналога сохраняют исходный
+/// текст ошибки вместе с префиксом.
+impl From<crate::core::lib::CursorError> for AppError {
+    fn from(error: crate::core::lib::CursorError) -> Self {
+        use crate::core::lib::CursorError;
+
+        match error {
+            CursorError::BridgeError(msg) => AppError::Bridge(msg),
+            CursorError::ModelError(msg) => AppError::Model(msg),
+            CursorError::RpcError(_) => AppError::Network(error.to_string()),
+            CursorError::TokenError(_)
+            | CursorError::SolanaError(_)
+            | CursorError::TransactionError(_) => AppError::Bridge(error.to_string()),
+        }
+    }
+}
+
+impl From<AppError> for crate::core::lib::CursorError {
+    fn from(error: AppError) -> Self {
+        use crate::core::lib::CursorError;
+
+        match error {
+            AppError::Bridge(msg) => CursorError::BridgeError(msg),
+            AppError::Model(msg) | AppError::Router(msg) => CursorError::ModelError(msg),
+            AppError::Network(msg) => CursorError::RpcError(msg),
+            AppError::Timeout(_) => CursorError::RpcError(error.to_string()),
+            other => CursorError::ModelError(other.to_string()),
+        }
+    }
+}
+
+/// Ошибки `RaidSystem`
+impl From<crate::raid::lib::Error> for AppError {
+    fn from(error: crate::raid::lib::Error) -> Self {
+        use crate::raid::lib::Error as RaidError;
+
+        match error {
+            RaidError::Io(e) => AppError::Io(e),
+            RaidError::Config(e) => AppError::Config(e.to_string()),
+            RaidError::Worker(e) => AppError::Worker(e.to_string()),
+            RaidError::Network(e) => AppError::Network(e.to_string()),
+            RaidError::Vm(e) => AppError::VM(e.to_string()),
+            other => AppError::Unknown(other.to_string()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorConfig {
     pub id: String,
@@ -450,4 +501,65 @@ impl ErrorSystem {
         // Реализация попытки восстановления
         Ok(())
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::lib::CursorError;
+
+    #[test]
+    fn test_cursor_error_into_app_error_keeps_message() {
+        let cases = vec![
+            (CursorError::BridgeError("no route".into()), "Bridge error: no route"),
+            (CursorError::ModelError("overloaded".into()), "Model error: overloaded"),
+            (CursorError::RpcError("503".into()), "Network error: RPC error: 503"),
+            (CursorError::TokenError("bad mint".into()), "Bridge error: Token error: bad mint"),
+            (CursorError::SolanaError("no keypair".into()), "Bridge error: Solana error: no keypair"),
+            (
+                CursorError::TransactionError("expired".into()),
+                "Bridge error: Transaction error: expired",
+            ),
+        ];
+
+        for (error, expected) in cases {
+            assert_eq!(AppError::from(error).to_string(), expected);
+        }
+        assert!(matches!(
+            AppError::from(CursorError::ModelError("overloaded".into())),
+            AppError::Model(_)
+        ));
+    }
+
+    #[test]
+    fn test_app_error_into_cursor_error_keeps_message() {
+        let cases = vec![
+            (AppError::Bridge("no route".into()), "Bridge error: no route"),
+            (AppError::Model("overloaded".into()), "Model error: overloaded"),
+            (AppError::Router("no instance".into()), "Model error: no instance"),
+            (AppError::Network("reset".into()), "RPC error: reset"),
+            (AppError::Timeout("30s".into()), "RPC error: Timeout error: 30s"),
+            (AppError::NotFound("model x".into()), "Model error: Resource not found: model x"),
+        ];
+
+        for (error, expected) in cases {
+            assert_eq!(CursorError::from(error).to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_raid_error_into_app_error_keeps_message() {
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "disk0 missing");
+        let error = AppError::from(crate::raid::lib::Error::Io(io));
+        assert!(matches!(error, AppError::Io(_)));
+        assert!(error.to_string().contains("disk0 missing"));
+
+        fn convert(result: Result<(), crate::raid::lib::Error>) -> AppResult<()> {
+            result?;
+            Ok(())
+        }
+        let io = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "raid path");
+        let error = convert(Err(io.into())).unwrap_err();
+        assert_eq!(error.to_string(), "IO error: raid path");
+    }
+}
@@ -0,0 +1,261 @@
+//! Idempotency - Ключи идемпотентности денежных операций
+//!
+//! Ключ привязан к отпечатку запроса (отправитель, получатель, сумма), поэтому
+//! повтор с тем же ключом и другими параметрами отклоняется. Ключ освобождается
+//! только тогда, когда операция точно не отправлена; если исход неизвестен
+//! (например, истёк таймаут подтверждения), повтор с этим ключом запрещён.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Сколько хранится результат операции по ключу идемпотентности
+pub const IDEMPOTENCY_KEY_TTL: Duration = Duration::from_secs(24 * 3600);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdempotencyState {
+    /// Операция по ключу выполняется
+    Pending,
+    /// Результат выполненной операции (подпись, идентификатор транзакции)
+    Done(String),
+    /// Операция могла быть выполнена, но подтверждения нет
+    Unknown(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum IdempotencyError {
+    #[error("Operation with idempotency key {0} is already in progress")]
+    InProgress(String),
+    #[error("Idempotency key {0} was already used with different parameters")]
+    Mismatch(String),
+    #[error("Outcome of operation with idempotency key {key} is unknown: {message}")]
+    Unknown { key: String, message: String },
+}
+
+/// Результат резервирования ключа
+#[derive(Debug)]
+pub enum IdempotencyBegin {
+    /// Ключ новый: операцию нужно выполнить и завершить через guard
+    Fresh(IdempotencyGuard),
+    /// Операция уже выполнена, повторять её не нужно
+    Done(String),
+}
+
+#[derive(Debug)]
+struct Entry {
+    fingerprint: String,
+    state: IdempotencyState,
+}
+
+#[derive(Debug)]
+struct Entries {
+    entries: HashMap<String, Entry>,
+    order: VecDeque<(String, Instant)>,
+    ttl: Duration,
+}
+
+impl Entries {
+    fn evict(&mut self, now: Instant) {
+        while let Some((key, seen_at)) = self.order.front() {
+            if now.duration_since(*seen_at) < self.ttl {
+                break;
+            }
+            self.entries.remove(key);
+            self.order.pop_front();
+        }
+    }
+
+    fn release(&mut self, key: &str) {
+        self.entries.remove(key);
+        self.order.retain(|(k, _)| k != key);
+    }
+}
+
+/// Ключи идемпотентности с ограниченным временем жизни
+#[derive(Debug, Clone)]
+pub struct IdempotencyStore {
+    inner: Arc<parking_lot::Mutex<Entries>>,
+}
+
+impl IdempotencyStore {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            inner: Arc::new(parking_lot::Mutex::new(Entries {
+                entries: HashMap::new(),
+                order: VecDeque::new(),
+                ttl,
+            })),
+        }
+    }
+
+    /// Резервирует ключ для запроса с отпечатком `fingerprint`
+    pub fn begin(&self, key: &str, fingerprint: &str) -> Result<IdempotencyBegin, IdempotencyError> {
+        self.begin_at(key, fingerprint, Instant::now())
+    }
+
+    pub fn begin_at(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        now: Instant,
+    ) -> Result<IdempotencyBegin, IdempotencyError> {
+        let mut inner = self.inner.lock();
+        inner.evict(now);
+
+        if let Some(entry) = inner.entries.get(key) {
+            if entry.fingerprint != fingerprint {
+                return Err(IdempotencyError::Mismatch(key.to_string()));
+            }
+            return match &entry.state {
+                IdempotencyState::Pending => Err(IdempotencyError::InProgress(key.to_string())),
+                IdempotencyState::Done(result) => Ok(IdempotencyBegin::Done(result.clone())),
+                IdempotencyState::Unknown(message) => Err(IdempotencyError::Unknown {
+                    key: key.to_string(),
+                    message: message.clone(),
+                }),
+            };
+        }
+
+        inner.entries.insert(
+            key.to_string(),
+            Entry { fingerprint: fingerprint.to_string(), state: IdempotencyState::Pending },
+        );
+        inner.order.push_back((key.to_string(), now));
+        Ok(IdempotencyBegin::Fresh(IdempotencyGuard {
+            inner: self.inner.clone(),
+            key: key.to_string(),
+            finished: false,
+        }))
+    }
+
+    pub fn state(&self, key: &str) -> Option<IdempotencyState> {
+        self.inner.lock().entries.get(key).map(|entry| entry.state.clone())
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for IdempotencyStore {
+    fn default() -> Self {
+        Self::new(IDEMPOTENCY_KEY_TTL)
+    }
+}
+
+/// Зарезервированный ключ. Если guard удалён без `complete`/`mark_unknown`
+/// (ошибка до отправки или отмена future), ключ освобождается для повтора.
+#[derive(Debug)]
+pub struct IdempotencyGuard {
+    inner: Arc<parking_lot::Mutex<Entries>>,
+    key: String,
+    finished: bool,
+}
+
+impl IdempotencyGuard {
+    pub fn complete(mut self, result: String) {
+        self.set(IdempotencyState::Done(result));
+    }
+
+    /// Операция могла выполниться: ключ остаётся занятым до истечения TTL
+    pub fn mark_unknown(mut self, message: String) {
+        self.set(IdempotencyState::Unknown(message));
+    }
+
+    fn set(&mut self, state: IdempotencyState) {
+        if let Some(entry) = self.inner.lock().entries.get_mut(&self.key) {
+            entry.state = state;
+        }
+        self.finished = true;
+    }
+}
+
+impl Drop for IdempotencyGuard {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        let mut inner = self.inner.lock();
+        if matches!(inner.entries.get(&self.key), Some(entry) if entry.state == IdempotencyState::Pending) {
+            inner.release(&self.key);
+        }
+    }
+}
+
+/// Отпечаток параметров запроса для привязки к ключу
+pub fn request_fingerprint(parts: &[&str]) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fresh(begin: Result<IdempotencyBegin, IdempotencyError>) -> IdempotencyGuard {
+        match begin {
+            Ok(IdempotencyBegin::Fresh(guard)) => guard,
+            other => panic!("expected fresh key, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_keys_expire_and_bind_to_request() {
+        let store = IdempotencyStore::new(Duration::from_secs(60));
+        let start = Instant::now();
+
+        let guard = fresh(store.begin_at("k", "a", start));
+        assert_eq!(store.begin_at("k", "a", start).unwrap_err(), IdempotencyError::InProgress("k".to_string()));
+        guard.complete("sig".to_string());
+
+        assert!(matches!(
+            store.begin_at("k", "a", start + Duration::from_secs(30)),
+            Ok(IdempotencyBegin::Done(sig)) if sig == "sig"
+        ));
+        assert_eq!(store.begin_at("k", "b", start).unwrap_err(), IdempotencyError::Mismatch("k".to_string()));
+
+        // По истечении TTL ключ забывается
+        let guard = fresh(store.begin_at("k", "b", start + Duration::from_secs(61)));
+        assert_eq!(store.len(), 1);
+        drop(guard);
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_outcome_blocks_retry() {
+        let store = IdempotencyStore::default();
+
+        fresh(store.begin("k", "a")).mark_unknown("confirmation timed out".to_string());
+
+        assert!(matches!(store.begin("k", "a"), Err(IdempotencyError::Unknown { .. })));
+        assert_eq!(store.state("k"), Some(IdempotencyState::Unknown("confirmation timed out".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_operation_releases_key() {
+        let store = IdempotencyStore::default();
+
+        let operation = {
+            let store = store.clone();
+            async move {
+                let _guard = fresh(store.begin("k", "a"));
+                std::future::pending::<()>().await;
+            }
+        };
+        let _ = tokio::time::timeout(Duration::from_millis(10), operation).await;
+
+        assert!(store.state("k").is_none());
+        fresh(store.begin("k", "a"));
+    }
+}
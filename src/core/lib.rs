@@ -51,11 +51,15 @@ use solana_sdk::pubkey::Pubkey;
 use thiserror::Error;
 use std::str::FromStr;
 use solana_sdk::{
-    signature::{Keypair, Signature},
+    hash::Hash,
+    signature::{Keypair, Signature, Signer},
     system_instruction,
     transaction::Transaction,
 };
-use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use crate::core::idempotency::{request_fingerprint, IdempotencyBegin, IdempotencyGuard, IdempotencyStore};
+use crate::core::model_interface::{InferenceDefaults, ModelMetrics, PerformanceConfig};
+use crate::runtime::cache::{CacheLimits, CacheSystem};
 
 mod admin_panel;
 mod admin_ui;
@@ -79,31 +83,301 @@ pub enum CursorError {
     TransactionError(String),
 }
 
+/// Результат симуляции транзакции без отправки в сеть
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationReport {
+    pub compute_units: Option<u64>,
+    pub logs: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// Ошибка вызова Solana RPC
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum RpcFailure {
+    /// Узел недоступен: сеть, таймаут, ошибка транспорта. Учитывается breaker'ом
+    #[error("{0}")]
+    Unavailable(String),
+    /// Узел ответил, но отклонил запрос; на доступность RPC не влияет
+    #[error("{0}")]
+    Rejected(String),
+}
+
+impl From<solana_client::client_error::ClientError> for RpcFailure {
+    fn from(e: solana_client::client_error::ClientError) -> Self {
+        use solana_client::client_error::ClientErrorKind;
+
+        match e.kind() {
+            ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) => RpcFailure::Unavailable(e.to_string()),
+            _ => RpcFailure::Rejected(e.to_string()),
+        }
+    }
+}
+
+/// Операции Solana RPC, которые использует `CursorCore`
+pub trait SolanaRpc: Send + Sync {
+    fn get_latest_blockhash(&self) -> Result<Hash, RpcFailure>;
+    fn send_and_confirm_transaction(&self, transaction: &Transaction) -> Result<Signature, RpcFailure>;
+    fn simulate_transaction(&self, transaction: &Transaction) -> Result<SimulationReport, RpcFailure>;
+}
+
+impl SolanaRpc for RpcClient {
+    fn get_latest_blockhash(&self) -> Result<Hash, RpcFailure> {
+        Ok(RpcClient::get_latest_blockhash(self)?)
+    }
+
+    fn send_and_confirm_transaction(&self, transaction: &Transaction) -> Result<Signature, RpcFailure> {
+        Ok(RpcClient::send_and_confirm_transaction(self, transaction)?)
+    }
+
+    fn simulate_transaction(&self, transaction: &Transaction) -> Result<SimulationReport, RpcFailure> {
+        let result = RpcClient::simulate_transaction(self, transaction)?
+            .value;
+        Ok(SimulationReport {
+            compute_units: result.units_consumed,
+            logs: result.logs.unwrap_or_default(),
+            error: result.err.map(|e| e.to_string()),
+        })
+    }
+}
+
+/// Порог последовательных ошибок RPC, после которого breaker размыкается
+pub const DEFAULT_RPC_FAILURE_THRESHOLD: u32 = 5;
+/// Время в разомкнутом состоянии до пробного запроса
+pub const DEFAULT_RPC_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Состояние circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    /// Запросы проходят
+    Closed,
+    /// Запросы отклоняются без обращения к RPC
+    Open,
+    /// Пропускается один пробный запрос
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct BreakerInner {
+    state: BreakerState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    probe_in_flight: bool,
+}
+
+/// Circuit breaker для вызовов RPC. Состояние общее для всех клонов.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    inner: Arc<parking_lot::Mutex<BreakerInner>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            inner: Arc::new(parking_lot::Mutex::new(BreakerInner {
+                state: BreakerState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                probe_in_flight: false,
+            })),
+        }
+    }
+
+    pub fn state(&self) -> BreakerState {
+        self.state_at(Instant::now())
+    }
+
+    /// Разомкнутый breaker по истечении паузы считается полуоткрытым
+    pub fn state_at(&self, now: Instant) -> BreakerState {
+        let inner = self.inner.lock();
+        match (inner.state, inner.opened_at) {
+            (BreakerState::Open, Some(opened_at)) if now.duration_since(opened_at) >= self.cooldown => {
+                BreakerState::HalfOpen
+            }
+            (state, _) => state,
+        }
+    }
+
+    pub fn call<T>(&self, f: impl FnOnce() -> Result<T, RpcFailure>) -> Result<T, String> {
+        self.call_at(Instant::now(), f)
+    }
+
+    /// Выполняет вызов через breaker; пока он разомкнут, `f` не вызывается.
+    /// Сбоем считается только `RpcFailure::Unavailable`: отклонённый запрос
+    /// означает, что узел отвечает. Паника в `f` засчитывается как сбой.
+    pub fn call_at<T>(&self, now: Instant, f: impl FnOnce() -> Result<T, RpcFailure>) -> Result<T, String> {
+        self.acquire(now)?;
+        let guard = CallGuard { breaker: self, now };
+        let result = f();
+        std::mem::forget(guard);
+        self.record(now, !matches!(result, Err(RpcFailure::Unavailable(_))));
+        result.map_err(|e| e.to_string())
+    }
+
+    fn acquire(&self, now: Instant) -> Result<(), String> {
+        let mut inner = self.inner.lock();
+        if inner.state == BreakerState::Open {
+            let cooled_down = inner
+                .opened_at
+                .map_or(true, |opened_at| now.duration_since(opened_at) >= self.cooldown);
+            if !cooled_down {
+                return Err("RPC circuit breaker is open".to_string());
+            }
+            inner.state = BreakerState::HalfOpen;
+        }
+        if inner.state == BreakerState::HalfOpen {
+            if inner.probe_in_flight {
+                return Err("RPC circuit breaker is half-open, probe in progress".to_string());
+            }
+            inner.probe_in_flight = true;
+        }
+        Ok(())
+    }
+
+    fn record(&self, now: Instant, success: bool) {
+        let mut inner = self.inner.lock();
+        let was_probe = std::mem::take(&mut inner.probe_in_flight);
+
+        if success {
+            if inner.state != BreakerState::Closed {
+                info!("RPC circuit breaker closed");
+            }
+            inner.state = BreakerState::Closed;
+            inner.consecutive_failures = 0;
+            inner.opened_at = None;
+            return;
+        }
+
+        inner.consecutive_failures += 1;
+        if was_probe || inner.consecutive_failures >= self.failure_threshold {
+            if inner.state != BreakerState::Open {
+                error!(
+                    "RPC circuit breaker opened after {} consecutive failures",
+                    inner.consecutive_failures
+                );
+            }
+            inner.state = BreakerState::Open;
+            inner.opened_at = Some(now);
+        }
+    }
+}
+
+/// Засчитывает сбой, если вызов не завершился (паника), чтобы флаг пробы
+/// не остался установленным навсегда
+struct CallGuard<'a> {
+    breaker: &'a CircuitBreaker,
+    now: Instant,
+}
+
+impl Drop for CallGuard<'_> {
+    fn drop(&mut self) {
+        self.breaker.record(self.now, false);
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(DEFAULT_RPC_FAILURE_THRESHOLD, DEFAULT_RPC_COOLDOWN)
+    }
+}
+
+/// Ключ кэша ответов: хэш модели, запроса и параметров генерации
+fn response_cache_key(model_id: &str, prompt: &str, params: &InferenceDefaults) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(model_id.as_bytes());
+    hasher.update([0]);
+    hasher.update(prompt.as_bytes());
+    hasher.update([0]);
+    hasher.update(params.temperature.to_bits().to_le_bytes());
+    hasher.update(params.max_tokens.to_le_bytes());
+    hasher.update(params.top_p.to_bits().to_le_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Счётчики ответов моделей для `model_metrics`
+#[derive(Debug)]
+struct ResponseStats {
+    started: Instant,
+    requests: u64,
+    errors: u64,
+    total_time: Duration,
+}
+
+impl Default for ResponseStats {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            requests: 0,
+            errors: 0,
+            total_time: Duration::ZERO,
+        }
+    }
+}
+
 pub struct CursorCore {
     bridge_manager: Arc<bridges::BridgeManager>,
     lm_router: Arc<lmrouter::LMRouter>,
     load_balancer: Arc<loadbalancer::LoadBalancer>,
     solana_manager: Arc<soladdr::SolanaAddressManager>,
     token_manager: Arc<tgtoken::TokenManager>,
-    rpc_client: Arc<RpcClient>,
+    rpc_client: Arc<dyn SolanaRpc>,
+    rpc_breaker: CircuitBreaker,
+    /// Кэш ответов моделей; `None`, если кэширование выключено
+    response_cache: Option<Arc<CacheSystem>>,
+    response_stats: parking_lot::Mutex<ResponseStats>,
+    processed_transfers: IdempotencyStore,
     keypair: Keypair,
     recent_blockhash: Signature,
 }
 
 impl CursorCore {
     pub fn new(rpc_url: &str) -> Self {
+        Self::with_rpc(Arc::new(RpcClient::new(rpc_url.to_string())))
+    }
+
+    pub fn with_rpc(rpc_client: Arc<dyn SolanaRpc>) -> Self {
         Self {
             bridge_manager: Arc::new(bridges::BridgeManager::new()),
             lm_router: Arc::new(lmrouter::LMRouter::new()),
             load_balancer: Arc::new(loadbalancer::LoadBalancer::new(3, 1000, 60)),
             solana_manager: Arc::new(soladdr::SolanaAddressManager::new()),
             token_manager: Arc::new(tgtoken::TokenManager::new()),
-            rpc_client: Arc::new(RpcClient::new(rpc_url.to_string())),
+            rpc_client,
+            rpc_breaker: CircuitBreaker::default(),
+            response_cache: None,
+            response_stats: parking_lot::Mutex::new(ResponseStats::default()),
+            processed_transfers: IdempotencyStore::default(),
             keypair: Keypair::new(),
             recent_blockhash: Signature::default(),
         }
     }
 
+    pub fn with_rpc_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.rpc_breaker = breaker;
+        self
+    }
+
+    /// Кэш ответов моделей по `enable_caching` и `cache_size`
+    pub fn with_response_cache(mut self, performance: &PerformanceConfig) -> Self {
+        self.response_cache = performance.enable_caching.then(|| {
+            Arc::new(CacheSystem::with_limits(CacheLimits {
+                max_entries: performance.cache_size.max(1) as usize,
+                ..CacheLimits::default()
+            }))
+        });
+        self
+    }
+
+    /// Состояние circuit breaker для RPC
+    pub fn rpc_health(&self) -> BreakerState {
+        self.rpc_breaker.state()
+    }
+
     pub async fn initialize_bridge(
         &self,
         source_network: &str,
@@ -126,6 +400,19 @@ impl CursorCore {
         Ok(bridge_id)
     }
 
+    /// Применяет новые комиссию и лимиты сумм к инициализированному мосту
+    pub fn update_bridge_fees(
+        &self,
+        bridge_id: &str,
+        fee_percentage: f64,
+        min_amount: f64,
+        max_amount: f64,
+    ) -> Result<(), CursorError> {
+        self.bridge_manager
+            .update_fees(bridge_id, fee_percentage, min_amount, max_amount)
+            .map_err(|e| CursorError::BridgeError(e.to_string()))
+    }
+
     pub async fn register_language_model(
         &self,
         model_id: String,
@@ -159,28 +446,217 @@ impl CursorCore {
         &self,
         prompt: &str,
         requirements: &lmrouter::ModelRequirements,
+        params: &InferenceDefaults,
     ) -> Result<String, CursorError> {
-        let (model_id, _) = self.load_balancer.get_available_model(requirements)
-            .await
-            .map_err(|e| CursorError::ModelError(e.to_string()))?;
+        let started = Instant::now();
+        let (model_id, _) = match self.load_balancer.get_available_model(requirements).await {
+            Ok(model) => model,
+            Err(e) => {
+                self.record_response(started.elapsed(), false);
+                return Err(CursorError::ModelError(e.to_string()));
+            }
+        };
+
+        let response = self
+            .cached_response(&model_id, prompt, params, || async {
+                // Здесь будет реализация вызова модели
+                let response = format!("Response from model {}: {}", model_id, prompt);
+
+                self.load_balancer.update_model_stats(&model_id, true, 0.1)
+                    .await
+                    .map_err(|e| CursorError::ModelError(e.to_string()))?;
+                Ok(response)
+            })
+            .await?;
 
-        // Здесь будет реализация вызова модели
-        let response = format!("Response from model {}: {}", model_id, prompt);
-        
-        self.load_balancer.update_model_stats(&model_id, true, 0.1)
-            .await
-            .map_err(|e| CursorError::ModelError(e.to_string()))?;
-            
         Ok(response)
     }
 
+    /// Возвращает ответ из кэша или вычисляет и сохраняет его
+    async fn cached_response<F, Fut>(
+        &self,
+        model_id: &str,
+        prompt: &str,
+        params: &InferenceDefaults,
+        compute: F,
+    ) -> Result<String, CursorError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<String, CursorError>>,
+    {
+        let started = Instant::now();
+        let result = match &self.response_cache {
+            None => compute().await,
+            Some(cache) => {
+                let key = response_cache_key(model_id, prompt, params);
+                match cache.get(&key).await {
+                    Some(response) => Ok(response),
+                    None => match compute().await {
+                        Ok(response) => {
+                            cache.insert(&key, &response).await;
+                            Ok(response)
+                        }
+                        Err(e) => Err(e),
+                    },
+                }
+            }
+        };
+        self.record_response(started.elapsed(), result.is_ok());
+        result
+    }
+
+    fn record_response(&self, elapsed: Duration, success: bool) {
+        let mut stats = self.response_stats.lock();
+        stats.requests += 1;
+        stats.total_time += elapsed;
+        if !success {
+            stats.errors += 1;
+        }
+    }
+
+    /// Метрики ответов моделей: запросы, время ответа, ошибки и доля
+    /// попаданий в кэш. Показатели без источника (токены, загрузка) равны нулю
+    pub async fn model_metrics(&self) -> ModelMetrics {
+        let hit_rate = match &self.response_cache {
+            Some(cache) => {
+                let stats = cache.stats().await;
+                let total = stats.hits + stats.misses;
+                if total == 0 { 0.0 } else { stats.hits as f64 / total as f64 }
+            }
+            None => 0.0,
+        };
+        let (requests, errors, total_time, uptime) = {
+            let stats = self.response_stats.lock();
+            (stats.requests, stats.errors, stats.total_time, stats.started.elapsed())
+        };
+        let per_request = |value: f64| if requests == 0 { 0.0 } else { value / requests as f64 };
+
+        ModelMetrics {
+            requests_processed: requests,
+            requests_per_second: requests as f64 / uptime.as_secs_f64().max(1.0),
+            average_response_time: per_request(total_time.as_secs_f64() * 1000.0),
+            tokens_generated: 0,
+            tokens_per_second: 0.0,
+            memory_usage: 0,
+            gpu_usage: 0.0,
+            cpu_usage: 0.0,
+            error_rate: per_request(errors as f64),
+            cache_hit_rate: hit_rate,
+            active_sessions: 0,
+            queue_length: 0,
+            last_updated: chrono::Utc::now().timestamp() as u64,
+        }
+    }
+
+    /// Переводит токены. Повтор с тем же `idempotency_key` возвращает подпись
+    /// уже выполненного перевода, не отправляя транзакцию повторно. Ключ
+    /// привязан к параметрам перевода; если транзакция ушла в сеть, но
+    /// подтверждения нет, повтор с этим ключом отклоняется до истечения TTL.
     pub async fn transfer_tokens(
         &self,
         from_label: &str,
         to_address: &str,
         amount: u64,
         token_label: &str,
+        idempotency_key: Option<&str>,
+    ) -> Result<String, CursorError> {
+        let Some(key) = idempotency_key else {
+            return self.execute_token_transfer(from_label, to_address, amount, token_label, None).await;
+        };
+
+        let amount_str = amount.to_string();
+        let fingerprint = request_fingerprint(&[from_label, to_address, &amount_str, token_label]);
+        match self.processed_transfers.begin(key, &fingerprint) {
+            Ok(IdempotencyBegin::Done(signature)) => {
+                info!("Transfer with idempotency key {} already completed: {}", key, signature);
+                Ok(signature)
+            }
+            Ok(IdempotencyBegin::Fresh(guard)) => {
+                self.execute_token_transfer(from_label, to_address, amount, token_label, Some(guard)).await
+            }
+            Err(e) => Err(CursorError::TransactionError(e.to_string())),
+        }
+    }
+
+    /// Ошибка до отправки освобождает ключ (guard удаляется без отметки),
+    /// ошибка отправки или подтверждения оставляет исход неизвестным
+    async fn execute_token_transfer(
+        &self,
+        from_label: &str,
+        to_address: &str,
+        amount: u64,
+        token_label: &str,
+        guard: Option<IdempotencyGuard>,
     ) -> Result<String, CursorError> {
+        let transaction = self
+            .build_token_transfer(from_label, to_address, amount, token_label)
+            .await?;
+
+        let mut sent = false;
+        let result = self.rpc_breaker.call(|| {
+            sent = true;
+            self.rpc_client.send_and_confirm_transaction(&transaction)
+        });
+        let signature = match result {
+            Ok(signature) => signature.to_string(),
+            Err(e) => {
+                if let Some(guard) = guard.filter(|_| sent) {
+                    guard.mark_unknown(e.clone());
+                }
+                return Err(CursorError::RpcError(format!("Transaction failed: {}", e)));
+            }
+        };
+
+        if let Some(guard) = guard {
+            guard.complete(signature.clone());
+        }
+        info!("Token transfer completed: {}", signature);
+        Ok(signature)
+    }
+
+    /// Собирает и подписывает перевод токенов, но только симулирует его
+    pub async fn simulate_transfer_tokens(
+        &self,
+        from_label: &str,
+        to_address: &str,
+        amount: u64,
+        token_label: &str,
+    ) -> Result<SimulationReport, CursorError> {
+        let transaction = self
+            .build_token_transfer(from_label, to_address, amount, token_label)
+            .await?;
+        self.simulate(&transaction)
+    }
+
+    pub async fn transfer_sol(
+        &self,
+        from: &Keypair,
+        to: &Pubkey,
+        amount: f64,
+    ) -> Result<Signature, CursorError> {
+        let transaction = self.build_sol_transfer(from, to, amount)?;
+        self.rpc_breaker.call(|| self.rpc_client.send_and_confirm_transaction(&transaction))
+            .map_err(CursorError::TransactionError)
+    }
+
+    /// Собирает и подписывает перевод SOL, но только симулирует его
+    pub async fn simulate_transfer_sol(
+        &self,
+        from: &Keypair,
+        to: &Pubkey,
+        amount: f64,
+    ) -> Result<SimulationReport, CursorError> {
+        let transaction = self.build_sol_transfer(from, to, amount)?;
+        self.simulate(&transaction)
+    }
+
+    async fn build_token_transfer(
+        &self,
+        from_label: &str,
+        to_address: &str,
+        amount: u64,
+        token_label: &str,
+    ) -> Result<Transaction, CursorError> {
         let to_pubkey = Pubkey::from_str(to_address)
             .map_err(|e| CursorError::SolanaError(format!("Invalid destination address: {}", e)))?;
 
@@ -201,35 +677,46 @@ impl CursorCore {
             &[transfer_instruction],
             Some(&from_pubkey),
         );
+        transaction.message.recent_blockhash = self.rpc_breaker.call(|| self.rpc_client.get_latest_blockhash())
+            .map_err(CursorError::RpcError)?;
 
         self.solana_manager.sign_transaction(from_label, &mut transaction)
-            .map_err(|e| CursorError::SolanaError(e.to_string()))?;
-
-        let signature = self.rpc_client.send_and_confirm_transaction(&transaction)
             .await
-            .map_err(|e| CursorError::RpcError(format!("Transaction failed: {}", e)))?;
+            .map_err(|e| CursorError::SolanaError(e.to_string()))?;
 
-        info!("Token transfer completed: {}", signature);
-        Ok(signature.to_string())
+        Ok(transaction)
     }
 
-    pub async fn transfer_sol(
+    fn build_sol_transfer(
         &self,
         from: &Keypair,
         to: &Pubkey,
         amount: f64,
-    ) -> Result<Signature, CursorError> {
+    ) -> Result<Transaction, CursorError> {
         let lamports = (amount * 1_000_000_000.0) as u64;
-        let recent_blockhash = self.rpc_client.get_latest_blockhash()
-            .map_err(|e| CursorError::RpcError(e.to_string()))?;
-        let transaction = Transaction::new_signed_with_payer(
+        let recent_blockhash = self.rpc_breaker.call(|| self.rpc_client.get_latest_blockhash())
+            .map_err(CursorError::RpcError)?;
+        Ok(Transaction::new_signed_with_payer(
             &[system_instruction::transfer(&from.pubkey(), to, lamports)],
             Some(&from.pubkey()),
             &[from],
             recent_blockhash,
+        ))
+    }
+
+    /// Проверяет подписи локально и симулирует транзакцию без отправки
+    fn simulate(&self, transaction: &Transaction) -> Result<SimulationReport, CursorError> {
+        transaction.verify()
+            .map_err(|e| CursorError::SolanaError(format!("Signature verification failed: {}", e)))?;
+
+        let report = self.rpc_breaker.call(|| self.rpc_client.simulate_transaction(transaction))
+            .map_err(|e| CursorError::RpcError(format!("Simulation failed: {}", e)))?;
+
+        info!(
+            "Simulated transaction: {:?} compute units, error: {:?}",
+            report.compute_units, report.error
         );
-        self.rpc_client.send_and_confirm_transaction(&transaction)
-            .map_err(|e| CursorError::TransactionError(e.to_string()))
+        Ok(report)
     }
 
     pub async fn start_admin_panel(&self, address: &str, admin_token: String) -> std::io::Result<()> {
@@ -255,6 +742,340 @@ impl CursorCore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use parking_lot::Mutex;
+
+    /// RPC без сети: запоминает отправленные и симулированные транзакции
+    #[derive(Default)]
+    struct MockRpc {
+        sent: Mutex<Vec<Signature>>,
+        simulated: Mutex<Vec<Signature>>,
+        /// Транзакция уходит в сеть, но подтверждение не приходит
+        confirm_timeout: std::sync::atomic::AtomicBool,
+    }
+
+    impl SolanaRpc for MockRpc {
+        fn get_latest_blockhash(&self) -> Result<Hash, RpcFailure> {
+            Ok(Hash::new_unique())
+        }
+
+        fn send_and_confirm_transaction(&self, transaction: &Transaction) -> Result<Signature, RpcFailure> {
+            self.sent.lock().push(transaction.signatures[0]);
+            if self.confirm_timeout.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err(RpcFailure::Unavailable("confirmation timed out".to_string()));
+            }
+            Ok(transaction.signatures[0])
+        }
+
+        fn simulate_transaction(&self, transaction: &Transaction) -> Result<SimulationReport, RpcFailure> {
+            self.simulated.lock().push(transaction.signatures[0]);
+            Ok(SimulationReport {
+                compute_units: Some(150),
+                logs: vec!["Program 11111111111111111111111111111111 success".to_string()],
+                error: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_does_not_broadcast() {
+        let rpc = Arc::new(MockRpc::default());
+        let core = CursorCore::with_rpc(rpc.clone());
+        let from = Keypair::new();
+        let to = Pubkey::new_unique();
+
+        let report = core.simulate_transfer_sol(&from, &to, 0.5).await.unwrap();
+
+        assert_eq!(report.compute_units, Some(150));
+        assert_eq!(report.logs.len(), 1);
+        assert!(rpc.sent.lock().is_empty());
+        let simulated = rpc.simulated.lock();
+        assert_eq!(simulated.len(), 1);
+        assert_ne!(simulated[0], Signature::default());
+
+        drop(simulated);
+        core.transfer_sol(&from, &to, 0.5).await.unwrap();
+        assert_eq!(rpc.sent.lock().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_rejects_unsigned_transaction() {
+        let rpc = Arc::new(MockRpc::default());
+        let core = CursorCore::with_rpc(rpc.clone());
+        let payer = Pubkey::new_unique();
+        let transaction = Transaction::new_with_payer(
+            &[system_instruction::transfer(&payer, &Pubkey::new_unique(), 1)],
+            Some(&payer),
+        );
+
+        assert!(matches!(core.simulate(&transaction), Err(CursorError::SolanaError(_))));
+        assert!(rpc.simulated.lock().is_empty());
+    }
+
+    #[test]
+    fn test_breaker_opens_and_half_opens_after_cooldown() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(10));
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            assert!(breaker.call_at(start, || Err::<(), _>(RpcFailure::Unavailable("timeout".to_string()))).is_err());
+        }
+        assert_eq!(breaker.state_at(start), BreakerState::Open);
+
+        // Пока разомкнут, вызов не выполняется
+        let mut called = false;
+        let result = breaker.call_at(start + Duration::from_secs(5), || {
+            called = true;
+            Ok(())
+        });
+        assert!(result.unwrap_err().contains("open"));
+        assert!(!called);
+
+        // Неудачная проба снова размыкает breaker
+        let probe_at = start + Duration::from_secs(10);
+        assert_eq!(breaker.state_at(probe_at), BreakerState::HalfOpen);
+        assert!(breaker.call_at(probe_at, || Err::<(), _>(RpcFailure::Unavailable("still down".to_string()))).is_err());
+        assert_eq!(breaker.state_at(probe_at), BreakerState::Open);
+
+        // Удачная проба замыкает
+        let recovered_at = probe_at + Duration::from_secs(10);
+        assert_eq!(breaker.call_at(recovered_at, || Ok(42)), Ok(42));
+        assert_eq!(breaker.state_at(recovered_at), BreakerState::Closed);
+    }
+
+    #[test]
+    fn test_rejected_requests_do_not_open_breaker() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(10));
+        let now = Instant::now();
+
+        for _ in 0..5 {
+            let result = breaker.call_at(now, || Err::<(), _>(RpcFailure::Rejected("insufficient funds".to_string())));
+            assert_eq!(result.unwrap_err(), "insufficient funds");
+        }
+        assert_eq!(breaker.state_at(now), BreakerState::Closed);
+    }
+
+    #[test]
+    fn test_panicking_probe_does_not_block_later_probes() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(10));
+        let start = Instant::now();
+        assert!(breaker.call_at(start, || Err::<(), _>(RpcFailure::Unavailable("down".to_string()))).is_err());
+
+        let probe_at = start + Duration::from_secs(10);
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _ = breaker.call_at(probe_at, || -> Result<(), RpcFailure> { panic!("probe panicked") });
+        }));
+        assert!(panicked.is_err());
+        assert_eq!(breaker.state_at(probe_at), BreakerState::Open);
+
+        let next_probe = probe_at + Duration::from_secs(10);
+        assert_eq!(breaker.call_at(next_probe, || Ok(1)), Ok(1));
+        assert_eq!(breaker.state_at(next_probe), BreakerState::Closed);
+    }
+
+    /// RPC, который отвечает ошибкой, пока установлен флаг `down`
+    #[derive(Default)]
+    struct FlakyRpc {
+        down: std::sync::atomic::AtomicBool,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl SolanaRpc for FlakyRpc {
+        fn get_latest_blockhash(&self) -> Result<Hash, RpcFailure> {
+            use std::sync::atomic::Ordering;
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.down.load(Ordering::SeqCst) {
+                Err(RpcFailure::Unavailable("connection refused".to_string()))
+            } else {
+                Ok(Hash::new_unique())
+            }
+        }
+
+        fn send_and_confirm_transaction(&self, transaction: &Transaction) -> Result<Signature, RpcFailure> {
+            Ok(transaction.signatures[0])
+        }
+
+        fn simulate_transaction(&self, _transaction: &Transaction) -> Result<SimulationReport, RpcFailure> {
+            Err(RpcFailure::Rejected("not supported".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rpc_breaker_short_circuits_and_recovers() {
+        use std::sync::atomic::Ordering;
+
+        let rpc = Arc::new(FlakyRpc::default());
+        rpc.down.store(true, Ordering::SeqCst);
+        let core = CursorCore::with_rpc(rpc.clone())
+            .with_rpc_breaker(CircuitBreaker::new(2, Duration::from_millis(50)));
+        let from = Keypair::new();
+        let to = Pubkey::new_unique();
+
+        for _ in 0..2 {
+            assert!(core.transfer_sol(&from, &to, 0.1).await.is_err());
+        }
+        assert_eq!(core.rpc_health(), BreakerState::Open);
+
+        let err = core.transfer_sol(&from, &to, 0.1).await.unwrap_err();
+        assert!(err.to_string().contains("circuit breaker is open"));
+        assert_eq!(rpc.calls.load(Ordering::SeqCst), 2);
+
+        rpc.down.store(false, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(core.rpc_health(), BreakerState::HalfOpen);
+        assert!(core.transfer_sol(&from, &to, 0.1).await.is_ok());
+        assert_eq!(core.rpc_health(), BreakerState::Closed);
+    }
+
+    fn caching_config(enable_caching: bool) -> PerformanceConfig {
+        PerformanceConfig {
+            batch_size: 1,
+            max_concurrent_requests: 1,
+            timeout_seconds: 30,
+            retry_attempts: 0,
+            enable_caching,
+            cache_size: 16,
+            batch_window_ms: 0,
+        }
+    }
+
+    async fn respond(core: &CursorCore, params: &InferenceDefaults, calls: &std::sync::atomic::AtomicUsize) -> String {
+        core.cached_response("model", "hello", params, || async {
+            let n = calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(format!("response {}", n))
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_repeated_request_served_from_cache() {
+        let core = CursorCore::with_rpc(Arc::new(MockRpc::default()))
+            .with_response_cache(&caching_config(true));
+        let params = InferenceDefaults { temperature: 0.7, max_tokens: 128, top_p: 1.0 };
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+
+        assert_eq!(respond(&core, &params, &calls).await, "response 0");
+        assert_eq!(respond(&core, &params, &calls).await, "response 0");
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        let metrics = core.model_metrics().await;
+        assert_eq!(metrics.requests_processed, 2);
+        assert_eq!(metrics.cache_hit_rate, 0.5);
+
+        // Другая температура — другой ключ
+        let hotter = InferenceDefaults { temperature: 1.2, ..params.clone() };
+        assert_eq!(respond(&core, &hotter, &calls).await, "response 1");
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        // Другой top_p — тоже другой ключ
+        let narrower = InferenceDefaults { top_p: 0.5, ..params.clone() };
+        assert_eq!(respond(&core, &narrower, &calls).await, "response 2");
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_model_metrics_count_failed_responses() {
+        let core = CursorCore::with_rpc(Arc::new(MockRpc::default()));
+        let params = InferenceDefaults { temperature: 0.7, max_tokens: 128, top_p: 1.0 };
+
+        let result = core
+            .cached_response("model", "hello", &params, || async {
+                Err(CursorError::ModelError("model unavailable".to_string()))
+            })
+            .await;
+        assert!(result.is_err());
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+        respond(&core, &params, &calls).await;
+
+        let metrics = core.model_metrics().await;
+        assert_eq!(metrics.requests_processed, 2);
+        assert_eq!(metrics.error_rate, 0.5);
+        assert!(metrics.requests_per_second > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_response_cache_respects_enable_caching() {
+        let core = CursorCore::with_rpc(Arc::new(MockRpc::default()))
+            .with_response_cache(&caching_config(false));
+        let params = InferenceDefaults { temperature: 0.7, max_tokens: 128, top_p: 1.0 };
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+
+        respond(&core, &params, &calls).await;
+        respond(&core, &params, &calls).await;
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(core.model_metrics().await.cache_hit_rate, 0.0);
+    }
+
+    async fn core_with_token(rpc: Arc<MockRpc>) -> CursorCore {
+        let core = CursorCore::with_rpc(rpc);
+        core.create_solana_wallet("alice".to_string()).await.unwrap();
+        core.register_token(
+            "test_token".to_string(),
+            "11111111111111111111111111111111",
+            9,
+            "Test Token".to_string(),
+            "TEST".to_string(),
+        ).await.unwrap();
+        core
+    }
+
+    #[tokio::test]
+    async fn test_repeated_idempotency_key_transfers_once() {
+        let rpc = Arc::new(MockRpc::default());
+        let core = core_with_token(rpc.clone()).await;
+        let to = Pubkey::new_unique().to_string();
+
+        let first = core.transfer_tokens("alice", &to, 10, "test_token", Some("req-1")).await.unwrap();
+        let retry = core.transfer_tokens("alice", &to, 10, "test_token", Some("req-1")).await.unwrap();
+        assert_eq!(first, retry);
+        assert_eq!(rpc.sent.lock().len(), 1);
+
+        // Новый ключ выполняет перевод
+        core.transfer_tokens("alice", &to, 10, "test_token", Some("req-2")).await.unwrap();
+        assert_eq!(rpc.sent.lock().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_bound_to_transfer_parameters() {
+        let rpc = Arc::new(MockRpc::default());
+        let core = core_with_token(rpc.clone()).await;
+        let to = Pubkey::new_unique().to_string();
+
+        core.transfer_tokens("alice", &to, 10, "test_token", Some("req-1")).await.unwrap();
+        let other = core.transfer_tokens("alice", &to, 20, "test_token", Some("req-1")).await;
+        assert!(matches!(other, Err(CursorError::TransactionError(_))));
+        assert_eq!(rpc.sent.lock().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unconfirmed_transfer_is_not_resent() {
+        let rpc = Arc::new(MockRpc::default());
+        let core = core_with_token(rpc.clone()).await;
+        let to = Pubkey::new_unique().to_string();
+
+        rpc.confirm_timeout.store(true, std::sync::atomic::Ordering::SeqCst);
+        assert!(core.transfer_tokens("alice", &to, 10, "test_token", Some("req-1")).await.is_err());
+
+        // Исход неизвестен: повтор с тем же ключом не отправляет транзакцию снова
+        rpc.confirm_timeout.store(false, std::sync::atomic::Ordering::SeqCst);
+        let retry = core.transfer_tokens("alice", &to, 10, "test_token", Some("req-1")).await;
+        assert!(matches!(retry, Err(CursorError::TransactionError(_))));
+        assert_eq!(rpc.sent.lock().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_failed_before_sending_releases_key() {
+        let rpc = Arc::new(MockRpc::default());
+        let core = core_with_token(rpc.clone()).await;
+        let to = Pubkey::new_unique().to_string();
+
+        // Неизвестный кошелёк: транзакция не собрана и не отправлена
+        assert!(core.transfer_tokens("bob", &to, 10, "test_token", Some("req-1")).await.is_err());
+        assert!(core.processed_transfers.is_empty());
+
+        core.transfer_tokens("alice", &to, 10, "test_token", Some("req-2")).await.unwrap();
+        assert_eq!(rpc.sent.lock().len(), 1);
+    }
 
     #[tokio::test]
     async fn test_core_initialization() {
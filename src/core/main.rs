@@ -38,7 +38,14 @@ use std::env;
 
 // Импорты из новых модулей
 use crate::core::state::AppState;
-use crate::core::config::AppConfig;
+use crate::core::config::{AppConfig, ConfigReload};
+use crate::network::api::{actix_rate_limit_filter, RateLimiter};
+use crate::monitoring::events::EventBus;
+use crate::workers::WorkerManager;
+use crate::workers::calibration::RemoteHashProbe;
+use crate::workers::supervisor::{RemoteRestarter, WorkerSupervisor};
+use crate::vm::{create_vm_manager, idle::IdleMonitor};
+use crate::monitoring::webhook::WebhookNotifier;
 use crate::network::tls::TlsManager;
 use crate::platform::model::ModelSystem;
 use crate::network::network::NetworkSystem;
@@ -47,7 +54,8 @@ use crate::runtime::cache::CacheSystem;
 use crate::runtime::queue::QueueSystem;
 use crate::runtime::scheduler::SchedulerSystem;
 use crate::monitoring::monitor::MonitorSystem;
-use crate::monitoring::logger::LoggerSystem;
+use crate::monitoring::logger::{LogFileConfig, LogFormat, LoggerSystem};
+use crate::admin::maintenance::MaintenanceGate;
 use crate::monitoring::metrics::MetricsSystem;
 use crate::monitoring::alert::AlertSystem;
 use crate::core::error::ErrorSystem;
@@ -347,11 +355,40 @@ impl System {
     }
 }
 
-fn init_logging() {
-    Builder::new()
-        .filter_level(LevelFilter::Info)
-        .format_timestamp_millis()
-        .init();
+fn init_logging(logger: &LoggerSystem, format: LogFormat, level: &str, file: Option<&LogFileConfig>) {
+    match file {
+        Some(file) => logger.init_with_file(format, level, file),
+        None => logger.init(format, level),
+    }
+}
+
+/// Инициализирует TLS. При ошибке и `allow_http_only` возвращает `None`
+/// (сервер работает только по HTTP), иначе ошибку.
+fn init_tls(server: &crate::core::config::ServerConfig) -> Result<Option<TlsManager>, String> {
+    let manager = TlsManager::new(
+        &server.cert_path,
+        &server.key_path,
+        server.cert_chain_path.as_deref(),
+        server.enable_http2,
+        server.enable_ocsp_stapling,
+    )
+    .and_then(|manager| match &server.client_ca_path {
+        Some(ca_path) => manager.with_client_auth(ca_path),
+        None => Ok(manager),
+    });
+
+    match manager {
+        Ok(manager) => Ok(Some(manager)),
+        Err(e) if server.allow_http_only => {
+            log::warn!("==========================================================");
+            log::warn!("TLS initialization failed: {}", e);
+            log::warn!("allow_http_only is set: starting HTTP-only, HTTPS DISABLED");
+            log::warn!("Do not use this mode in production");
+            log::warn!("==========================================================");
+            Ok(None)
+        }
+        Err(e) => Err(format!("Failed to initialize TLS manager: {}", e)),
+    }
 }
 
 async fn shutdown_signal() {
@@ -382,11 +419,17 @@ async fn shutdown_signal() {
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    init_logging();
+    // Формат логов задаётся в конфигурации, поэтому она читается до логгера
+    let config = AppConfig::load();
+    let logger = LoggerSystem::new();
+    match &config {
+        Ok(config) => init_logging(&logger, config.log_format, &config.log_level, config.log_file.as_ref()),
+        Err(_) => init_logging(&logger, LogFormat::default(), "info", None),
+    }
     info!("Starting Cursor Core...");
 
     // Load configuration
-    let config = match AppConfig::load() {
+    let config = match config {
         Ok(config) => config,
         Err(e) => {
             error!("Failed to load configuration: {}", e);
@@ -394,17 +437,21 @@ async fn main() -> std::io::Result<()> {
         }
     };
 
+    // Горячая перезагрузка изменяемых параметров конфигурации
+    let shared_config = Arc::new(tokio::sync::RwLock::new(config.clone()));
+    let config_watcher = match AppConfig::watch(AppConfig::path(), shared_config.clone()) {
+        Ok(watcher) => Some(watcher),
+        Err(e) => {
+            error!("Configuration hot reload disabled: {}", e);
+            None
+        }
+    };
+
     // Initialize TLS manager
-    let tls_manager = match TlsManager::new(
-        &config.server.cert_path,
-        &config.server.key_path,
-        config.server.cert_chain_path.as_deref(),
-        config.server.enable_http2,
-        config.server.enable_ocsp_stapling,
-    ) {
+    let tls_manager = match init_tls(&config.server) {
         Ok(manager) => manager,
         Err(e) => {
-            error!("Failed to initialize TLS manager: {}", e);
+            error!("{}", e);
             process::exit(1);
         }
     };
@@ -431,9 +478,17 @@ async fn main() -> std::io::Result<()> {
     // Initialize Vobe dancer
     let vobe_dancer = Arc::new(RwLock::new(VobeDancer::new()));
 
+    // Shared event bus; selected events are also sent to the webhook, if configured
+    let events = Arc::new(EventBus::default());
+    if let Some(webhook) = config.webhook.clone() {
+        info!("Sending event notifications to {}", webhook.url);
+        Arc::new(WebhookNotifier::new(webhook)).spawn(&events);
+    }
+
     // Initialize RAID manager
     let raid_manager = match BurstRaidManager::new(config.raid) {
         Ok(manager) => {
+            let manager = manager.with_event_bus(events.clone());
             vibe_manager.write().update_component_status("RAID", "Ready", Mood::Dancing);
             manager
         },
@@ -452,7 +507,10 @@ async fn main() -> std::io::Result<()> {
     });
 
     let core = match CursorCore::new(&config.solana_rpc_url) {
-        Ok(core) => core,
+        Ok(core) => match &config.model_performance {
+            Some(performance) => core.with_response_cache(performance),
+            None => core,
+        },
         Err(e) => {
             error!("Failed to initialize CursorCore: {}", e);
             process::exit(1);
@@ -460,31 +518,113 @@ async fn main() -> std::io::Result<()> {
     };
 
     // Initialize bridge
-    match core.initialize_bridge(
+    let bridge_id = match core.initialize_bridge(
         &config.bridge.source_chain,
         &config.bridge.target_chain,
-        config.bridge.min_amount,
         config.bridge.fee_percentage,
+        config.bridge.min_amount,
         config.bridge.max_amount,
     ).await {
-        Ok(bridge_id) => info!("Bridge initialized with ID: {}", bridge_id),
+        Ok(bridge_id) => {
+            info!("Bridge initialized with ID: {}", bridge_id);
+            bridge_id
+        }
         Err(e) => {
             error!("Failed to initialize bridge: {}", e);
             process::exit(1);
         }
+    };
+    let core = Arc::new(core);
+
+    // Лимит запросов клиента; меняется при перезагрузке конфигурации
+    let rate_limiter = Arc::new(RateLimiter::new(config.server.rate_limit, 60));
+
+    // Перезагруженные параметры применяются к лимитеру и мосту
+    if let Some(watcher) = &config_watcher {
+        let mut reloads = watcher.subscribe();
+        let shared_config = shared_config.clone();
+        let rate_limiter = rate_limiter.clone();
+        let core = core.clone();
+        tokio::spawn(async move {
+            loop {
+                match reloads.recv().await {
+                    Ok(ConfigReload::Applied(_)) => {
+                        let config = shared_config.read().await;
+                        rate_limiter.set_limit(config.server.rate_limit);
+                        if let Err(e) = core.update_bridge_fees(
+                            &bridge_id,
+                            config.bridge.fee_percentage,
+                            config.bridge.min_amount,
+                            config.bridge.max_amount,
+                        ) {
+                            error!("Failed to apply reloaded bridge fees: {}", e);
+                        }
+                    }
+                    Ok(ConfigReload::Rejected(_)) => {}
+                    // Пропущенные уведомления не важны: читается текущая конфигурация
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {
+                        let config = shared_config.read().await;
+                        rate_limiter.set_limit(config.server.rate_limit);
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
     }
 
+    // Награды воркеров пулов идут за вычетом комиссии пула
+    let pool_manager = Arc::new(
+        PoolManager::with_storage(config.pool_storage_path.clone()).with_event_bus(events.clone()),
+    );
+    // Добавление и удаление воркеров уходит в шину и дальше в webhook
+    let worker_manager = Arc::new(
+        WorkerManager::new()
+            .with_pool_manager(pool_manager.clone())
+            .with_event_bus(events.clone())
+            .with_metric_sampler(config.metric_sampler.clone())
+            .with_calibration(config.calibration.clone(), Arc::new(RemoteHashProbe::new())),
+    );
+    worker_manager.spawn_metric_sampler();
+    let supervisor = WorkerSupervisor::new(
+        worker_manager.clone(),
+        Arc::new(RemoteRestarter::new()),
+        Arc::new(AlertSystem::new()),
+        config.supervisor.clone(),
+    )
+    .await;
+    tokio::spawn(Arc::new(supervisor).run());
+
+    // Простаивающие VM останавливаются; `tracked()` продлевает активность при обращениях
+    let idle_monitor = IdleMonitor::new(
+        Arc::from(create_vm_manager()),
+        Arc::new(AlertSystem::new()),
+        config.vm_idle.clone(),
+    )
+    .await;
+    tokio::spawn(Arc::new(idle_monitor).run());
+
+    let reward_system = Arc::new(RewardSystem::with_base_rate(1.0).with_pool_manager(pool_manager.clone()));
+    if let Some(path) = &config.reward_balances_path {
+        if let Err(e) = reward_system.load_balances(path) {
+            error!("Failed to load reward balances from {}: {}", path.display(), e);
+        }
+    }
+
+    // Обработчики `pool::config` берут менеджер пулов напрямую
+    let pool_data = web::Data::from(pool_manager.clone());
+
     // Create application state
     let app_state = web::Data::new(AppState {
-        core: Arc::new(core),
+        core,
         raid_manager: raid_manager_clone,
         vobe_dancer: vobe_dancer.clone(),
         vibe_manager: vibe_manager.clone(),
-        reward_system: Arc::new(RewardSystem::new(1.0)),
+        reward_system: reward_system.clone(),
         lib_manager: Arc::new(LibraryManager::new(
             std::env::current_dir()?.join("libs")
         )),
-        pool_manager: Arc::new(PoolManager::new()),
+        worker_manager,
+        pool_manager,
     });
 
     let admin_panel = Arc::new(AdminPanel::new(app_state.clone()));
@@ -503,10 +643,14 @@ async fn main() -> std::io::Result<()> {
     // Start HTTP and HTTPS servers
     let http_server = HttpServer::new(move || {
         App::new()
+            .wrap(middleware::from_fn(actix_rate_limit_filter))
             .wrap(Logger::default())
             .wrap(cors.clone())
+            .app_data(web::Data::new(rate_limiter.clone()))
             .app_data(app_state.clone())
+            .app_data(pool_data.clone())
             .app_data(web::Data::new(admin_panel.clone()))
+            .configure(crate::pool::config)
             .service(web::resource("/health").to(|| async { "OK" }))
             .service(web::resource("/dance").to(|state: web::Data<AppState>| async move {
                 if let Ok(mut dancer) = state.vobe_dancer.try_write() {
@@ -554,6 +698,8 @@ async fn main() -> std::io::Result<()> {
                     .route("/{name}", web::delete().to(delete_pool))
                     .route("/{name}/scale", web::post().to(scale_pool))
             )
+            .route("/api/models/respond", web::post().to(get_model_response))
+            .route("/api/models/metrics", web::get().to(get_model_metrics))
             .route("/api/libs/libtorch/check", web::get().to(check_libtorch))
             .route("/api/libs/libtorch/download", web::post().to(download_libtorch))
             .route("/api/libs/libtorch/verify", web::get().to(verify_libtorch))
@@ -563,22 +709,34 @@ async fn main() -> std::io::Result<()> {
     })
     .bind(format!("0.0.0.0:{}", config.server.http_port))?;
 
-    let https_server = HttpServer::new(move || {
-        App::new()
-            .wrap(Logger::default())
-            .wrap(cors.clone())
-            .app_data(app_state.clone())
-            .app_data(web::Data::new(admin_panel.clone()))
-            .service(web::resource("/health").to(|| async { "OK" }))
-    })
-    .bind_rustls(format!("0.0.0.0:{}", config.server.https_port), tls_manager.get_config())?;
+    let https_server = match &tls_manager {
+        Some(tls_manager) => Some(
+            HttpServer::new(move || {
+                App::new()
+                    .wrap(Logger::default())
+                    .wrap(cors.clone())
+                    .app_data(app_state.clone())
+                    .app_data(web::Data::new(admin_panel.clone()))
+                    .service(web::resource("/health").to(|| async { "OK" }))
+            })
+            .bind_rustls(format!("0.0.0.0:{}", config.server.https_port), tls_manager.get_config())?,
+        ),
+        None => None,
+    };
 
     info!("Starting HTTP server on port {}", config.server.http_port);
-    info!("Starting HTTPS server on port {}", config.server.https_port);
+    if https_server.is_some() {
+        info!("Starting HTTPS server on port {}", config.server.https_port);
+    }
 
     // Run both servers
     let http_future = http_server.run();
-    let https_future = https_server.run();
+    let https_future = async move {
+        match https_server {
+            Some(server) => server.run().await,
+            None => std::future::pending().await,
+        }
+    };
 
     // Wait for shutdown
     tokio::select! {
@@ -589,16 +747,88 @@ async fn main() -> std::io::Result<()> {
         }
     }
 
+    if let Some(path) = &config.reward_balances_path {
+        if let Err(e) = reward_system.save_balances(path) {
+            error!("Failed to save reward balances to {}: {}", path.display(), e);
+        }
+    }
+
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::admin::maintenance::MAINTENANCE_ERROR;
+
+    fn server_config_with_missing_cert(allow_http_only: bool) -> crate::core::config::ServerConfig {
+        let mut server = AppConfig::default().server;
+        server.cert_path = "missing/cert.pem".into();
+        server.key_path = "missing/key.pem".into();
+        server.allow_http_only = allow_http_only;
+        server
+    }
+
+    #[test]
+    fn test_missing_cert_falls_back_to_http_when_allowed() {
+        let tls = init_tls(&server_config_with_missing_cert(true)).unwrap();
+        assert!(tls.is_none());
+    }
+
+    #[test]
+    fn test_missing_cert_fails_fast_by_default() {
+        assert!(init_tls(&server_config_with_missing_cert(false)).is_err());
+    }
+
+    #[actix_web::test]
+    async fn test_maintenance_rejects_mining_but_serves_stats() {
+        let maintenance = MaintenanceGate::default();
+        assert!(validate_mining_submission(&maintenance, 10.0).is_ok());
+
+        maintenance.set(true);
+        assert_eq!(
+            validate_mining_submission(&maintenance, 10.0).unwrap_err(),
+            MAINTENANCE_ERROR
+        );
+
+        // Оба маршрута видят один и тот же флаг: приём результатов отклоняется,
+        // статистика пула доступна только на чтение и во время обслуживания
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(web::Data::new(maintenance.clone()))
+                .route(
+                    "/mine",
+                    web::post().to(|gate: web::Data<MaintenanceGate>| async move {
+                        match validate_mining_submission(&gate, 10.0) {
+                            Ok(()) => HttpResponse::Ok().finish(),
+                            Err(e) => HttpResponse::ServiceUnavailable().body(e),
+                        }
+                    }),
+                )
+                .route("/stats", web::get().to(get_pool_stats)),
+        )
+        .await;
+
+        let request = actix_web::test::TestRequest::post().uri("/mine").to_request();
+        let response = actix_web::test::call_service(&app, request).await;
+        assert_eq!(response.status(), actix_web::http::StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(actix_web::test::read_body(response).await, MAINTENANCE_ERROR);
+
+        let request = actix_web::test::TestRequest::get().uri("/stats").to_request();
+        let response = actix_web::test::call_service(&app, request).await;
+        assert!(response.status().is_success());
+        assert!(maintenance.is_enabled());
+
+        // После выхода из обслуживания приём результатов восстанавливается
+        maintenance.set(false);
+        let request = actix_web::test::TestRequest::post().uri("/mine").to_request();
+        let response = actix_web::test::call_service(&app, request).await;
+        assert!(response.status().is_success());
+    }
 
     #[tokio::test]
     async fn test_main_flow() {
-        init_logging();
+        init_logging(&LoggerSystem::new(), LogFormat::Text, "info", None);
         let core = CursorCore::new("https://api.mainnet-beta.solana.com").unwrap();
 
         // Test bridge initialization
@@ -620,15 +850,23 @@ mod tests {
     }
 }
 
+/// Проверки до обработки результата майнинга: во время обслуживания
+/// результаты не принимаются, значение производительности должно быть положительным
+fn validate_mining_submission(maintenance: &MaintenanceGate, performance: f64) -> Result<(), String> {
+    maintenance.check()?;
+
+    if performance <= 0.0 {
+        return Err("Invalid performance value".to_string());
+    }
+    Ok(())
+}
+
 async fn process_mining_result(
     app_state: &Arc<AppState>,
     worker_id: &str,
     performance: f64,
 ) -> Result<(), String> {
-    // Добавить валидацию входных данных
-    if performance <= 0.0 {
-        return Err("Invalid performance value".to_string());
-    }
+    validate_mining_submission(&app_state.maintenance_mode, performance)?;
 
     // Добавить таймаут для обработки
     let process_result = tokio::time::timeout(
@@ -690,6 +928,27 @@ async fn get_worker_rewards(
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct ModelResponseRequest {
+    prompt: String,
+    requirements: crate::platform::lmrouter::ModelRequirements,
+    params: crate::core::model_interface::InferenceDefaults,
+}
+
+async fn get_model_response(
+    data: web::Data<AppState>,
+    request: web::Json<ModelResponseRequest>,
+) -> impl Responder {
+    match data.core.get_model_response(&request.prompt, &request.requirements, &request.params).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => HttpResponse::InternalServerError().json(e.to_string()),
+    }
+}
+
+async fn get_model_metrics(data: web::Data<AppState>) -> impl Responder {
+    HttpResponse::Ok().json(data.core.model_metrics().await)
+}
+
 async fn check_libtorch(data: web::Data<AppState>) -> impl Responder {
     match data.lib_manager.check_libtorch().await {
         Ok(status) => HttpResponse::Ok().json(status),
@@ -5,6 +5,7 @@ pub mod config;
 pub mod error;
 pub mod utils;
 pub mod model_interface;
+pub mod idempotency;
 
 pub use main::*;
 pub use lib::*;
@@ -13,6 +14,7 @@ pub use config::*;
 pub use error::*;
 pub use utils::*;
 pub use model_interface::*;
+pub use idempotency::*;
 
 use std::error::Error;
 
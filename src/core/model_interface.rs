@@ -10,6 +10,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use async_trait::async_trait;
+use futures::stream::{BoxStream, StreamExt};
 use tokio::sync::RwLock;
 use std::sync::Arc;
 
@@ -21,6 +22,26 @@ use crate::platform::gpu::GpuInfo;
 pub trait ModelInterface: Send + Sync {
     /// Обработка запроса к модели
     async fn process_request(&self, request: ModelRequest) -> Result<ModelResponse, AppError>;
+
+    /// Обработка пакета запросов; ответы возвращаются в порядке запросов.
+    /// По умолчанию запросы пакета обрабатываются параллельно по одному.
+    async fn process_batch(&self, requests: Vec<ModelRequest>) -> Vec<Result<ModelResponse, AppError>> {
+        futures::future::join_all(requests.into_iter().map(|request| self.process_request(request))).await
+    }
+
+    /// Потоковая обработка запроса. По умолчанию весь ответ отдаётся одним фрагментом.
+    async fn process_request_stream(
+        &self,
+        request: ModelRequest,
+    ) -> Result<BoxStream<'_, Result<TokenChunk, AppError>>, AppError> {
+        let response = self.process_request(request).await?;
+        let chunk = TokenChunk {
+            index: 0,
+            text: response.text,
+            finish_reason: response.finish_reason,
+        };
+        Ok(futures::stream::once(async move { Ok(chunk) }).boxed())
+    }
     
     /// Получение информации о модели
     async fn get_model_info(&self) -> Result<ModelInfo, AppError>;
@@ -55,6 +76,31 @@ pub struct ModelRequest {
     pub user_id: Option<String>,
     pub session_id: Option<String>,
     pub metadata: Option<HashMap<String, String>>,
+    /// Возможности модели, необходимые для обработки запроса
+    #[serde(default)]
+    pub required_features: Vec<ModelFeature>,
+}
+
+impl ModelRequest {
+    /// Проверяет, что модель поддерживает все требуемые возможности
+    pub fn check_feature_support(&self, info: &ModelInfo) -> Result<(), AppError> {
+        let missing: Vec<String> = self
+            .required_features
+            .iter()
+            .filter(|feature| !info.supported_features.contains(feature))
+            .map(|feature| format!("{:?}", feature))
+            .collect();
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::InvalidInput(format!(
+                "Model '{}' does not support required feature(s): {}",
+                info.name,
+                missing.join(", ")
+            )))
+        }
+    }
 }
 
 /// Ответ модели
@@ -69,6 +115,16 @@ pub struct ModelResponse {
     pub metadata: Option<HashMap<String, String>>,
 }
 
+/// Фрагмент потокового ответа модели
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenChunk {
+    /// Порядковый номер фрагмента
+    pub index: u32,
+    pub text: String,
+    /// Причина завершения; задаётся только в последнем фрагменте
+    pub finish_reason: Option<String>,
+}
+
 /// Информация о модели
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelInfo {
@@ -96,7 +152,7 @@ pub enum ModelType {
 }
 
 /// Возможности модели
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ModelFeature {
     TextGeneration,
     TextCompletion,
@@ -108,6 +164,7 @@ pub enum ModelFeature {
     CodeGeneration,
     ImageGeneration,
     ImageClassification,
+    Embeddings,
     Custom(String),
 }
 
@@ -147,6 +204,74 @@ pub struct ModelConfig {
     pub optimization: OptimizationConfig,
 }
 
+impl ModelConfig {
+    /// Проверяет согласованность значений конфигурации
+    pub fn validate(&self) -> Result<(), AppError> {
+        if !(self.device.memory_fraction > 0.0 && self.device.memory_fraction <= 1.0) {
+            return Err(AppError::InvalidInput(format!(
+                "device.memory_fraction must be within (0, 1], got {}",
+                self.device.memory_fraction
+            )));
+        }
+        if self.performance.batch_size == 0 {
+            return Err(AppError::InvalidInput("performance.batch_size must be greater than 0".to_string()));
+        }
+        if self.performance.max_concurrent_requests == 0 {
+            return Err(AppError::InvalidInput(
+                "performance.max_concurrent_requests must be greater than 0".to_string(),
+            ));
+        }
+        if self.performance.timeout_seconds == 0 {
+            return Err(AppError::InvalidInput("performance.timeout_seconds must be greater than 0".to_string()));
+        }
+        if !(0.0..=1.0).contains(&self.memory.garbage_collection_threshold) {
+            return Err(AppError::InvalidInput(format!(
+                "memory.garbage_collection_threshold must be within 0..=1, got {}",
+                self.memory.garbage_collection_threshold
+            )));
+        }
+        if self.inference.enable_beam_search && self.inference.beam_width == 0 {
+            return Err(AppError::InvalidInput(
+                "inference.beam_width must be greater than 0 with beam search enabled".to_string(),
+            ));
+        }
+        self.inference.defaults().validate()
+    }
+
+    /// Применяет JSON merge patch (RFC 7386) и проверяет результат;
+    /// исходная конфигурация не меняется
+    pub fn merge_patch(&self, patch: &serde_json::Value) -> Result<ModelConfig, AppError> {
+        let mut value = serde_json::to_value(self)?;
+        json_merge_patch(&mut value, patch);
+
+        let config: ModelConfig = serde_json::from_value(value)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid model config patch: {}", e)))?;
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// RFC 7386: объекты сливаются рекурсивно, `null` удаляет поле,
+/// любое другое значение заменяет целевое целиком
+pub fn json_merge_patch(target: &mut serde_json::Value, patch: &serde_json::Value) {
+    let serde_json::Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+
+    if !target.is_object() {
+        *target = serde_json::Value::Object(serde_json::Map::new());
+    }
+    let target = target.as_object_mut().expect("target is an object");
+    for (key, value) in patch {
+        if value.is_null() {
+            target.remove(key);
+        } else {
+            json_merge_patch(target.entry(key.clone()).or_insert(serde_json::Value::Null), value);
+        }
+    }
+}
+
 /// Конфигурация устройства
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeviceConfig {
@@ -156,8 +281,42 @@ pub struct DeviceConfig {
     pub allow_growth: bool,
 }
 
+/// Доля памяти, отдаваемая модели при откате на CPU: оперативная память
+/// делится с остальной системой, поэтому берём меньше, чем на GPU
+pub const CPU_MEMORY_FRACTION: f32 = 0.5;
+
+impl DeviceConfig {
+    /// Выбирает фактическое устройство: без GPU конфигурация для GPU
+    /// (или `Auto`) откатывается на CPU вместо ошибки при загрузке
+    pub fn resolve(&self, gpu_available: bool) -> DeviceConfig {
+        match self.device_type {
+            DeviceType::GPU | DeviceType::Auto if !gpu_available => {
+                if matches!(self.device_type, DeviceType::GPU) {
+                    log::warn!(
+                        "GPU requested but not available, falling back to CPU (memory fraction {} -> {})",
+                        self.memory_fraction,
+                        self.memory_fraction.min(CPU_MEMORY_FRACTION)
+                    );
+                }
+                DeviceConfig {
+                    device_type: DeviceType::CPU,
+                    device_id: None,
+                    memory_fraction: self.memory_fraction.min(CPU_MEMORY_FRACTION),
+                    allow_growth: self.allow_growth,
+                }
+            }
+            DeviceType::Auto => DeviceConfig {
+                device_type: DeviceType::GPU,
+                device_id: self.device_id.or(Some(0)),
+                ..self.clone()
+            },
+            _ => self.clone(),
+        }
+    }
+}
+
 /// Тип устройства
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DeviceType {
     CPU,
     GPU,
@@ -174,6 +333,13 @@ pub struct PerformanceConfig {
     pub retry_attempts: u32,
     pub enable_caching: bool,
     pub cache_size: u64,
+    /// Окно сбора одновременных запросов в пакет, мс; 0 отключает пакетирование
+    #[serde(default = "default_batch_window_ms")]
+    pub batch_window_ms: u64,
+}
+
+fn default_batch_window_ms() -> u64 {
+    0
 }
 
 /// Конфигурация памяти
@@ -196,6 +362,52 @@ pub struct InferenceConfig {
     pub beam_width: u32,
 }
 
+/// Параметры генерации по умолчанию, настраиваемые во время работы
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InferenceDefaults {
+    pub temperature: f32,
+    pub max_tokens: u32,
+    pub top_p: f32,
+}
+
+impl InferenceDefaults {
+    /// Проверяет допустимые диапазоны значений
+    pub fn validate(&self) -> Result<(), AppError> {
+        if !(0.0..=2.0).contains(&self.temperature) {
+            return Err(AppError::InvalidInput(format!(
+                "temperature must be within 0..=2, got {}",
+                self.temperature
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.top_p) {
+            return Err(AppError::InvalidInput(format!(
+                "top_p must be within 0..=1, got {}",
+                self.top_p
+            )));
+        }
+        if self.max_tokens == 0 {
+            return Err(AppError::InvalidInput("max_tokens must be greater than 0".to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl InferenceConfig {
+    pub fn defaults(&self) -> InferenceDefaults {
+        InferenceDefaults {
+            temperature: self.default_temperature,
+            max_tokens: self.default_max_tokens,
+            top_p: self.default_top_p,
+        }
+    }
+
+    pub fn apply_defaults(&mut self, defaults: &InferenceDefaults) {
+        self.default_temperature = defaults.temperature;
+        self.default_max_tokens = defaults.max_tokens;
+        self.default_top_p = defaults.top_p;
+    }
+}
+
 /// Конфигурация оптимизации
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OptimizationConfig {
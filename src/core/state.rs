@@ -1,6 +1,7 @@
 use std::sync::Arc;
 use parking_lot::{RwLock, Mutex};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
 use solana_sdk::pubkey::Pubkey;
 use crate::model::MiningModel;
 use crate::core::CursorCore;
@@ -16,6 +17,7 @@ use chrono::{DateTime, Utc};
 use crate::pool::{PoolConfig, PoolStats};
 use crate::core::error::CursorError;
 use crate::core::config::AppConfig;
+use crate::admin::maintenance::MaintenanceGate;
 use crate::monitoring::metrics::MetricsSystem;
 use crate::monitoring::logger::LoggerSystem;
 use crate::monitoring::alert::AlertSystem;
@@ -75,6 +77,8 @@ pub struct AppState {
     pub worker_manager: Arc<WorkerManager>,
     pub pool_manager: Arc<RwLock<PoolManager>>,
     pub burst_raid: Arc<RwLock<BurstRaidManager>>,
+    pub maintenance_mode: MaintenanceGate,
+    pub maintenance_pools: RwLock<HashSet<String>>,
 }
 
 impl AppState {
@@ -86,6 +90,7 @@ impl AppState {
         model: MiningModel,
         burst_raid: BurstRaidManager,
     ) -> Self {
+        let maintenance_mode = MaintenanceGate::default();
         Self {
             workers: RwLock::new(HashMap::new()),
             raid_status: Mutex::new(HashMap::new()),
@@ -96,9 +101,11 @@ impl AppState {
             vibe_manager: Arc::new(RwLock::new(VibeManager::new())),
             reward_system: Arc::new(RwLock::new(reward_system)),
             lib_manager: Arc::new(RwLock::new(lib_manager)),
-            worker_manager: Arc::new(worker_manager),
+            worker_manager: Arc::new(worker_manager.with_maintenance_gate(maintenance_mode.clone())),
             pool_manager: Arc::new(RwLock::new(pool_manager)),
             burst_raid: Arc::new(RwLock::new(burst_raid)),
+            maintenance_mode,
+            maintenance_pools: RwLock::new(HashSet::new()),
         }
     }
 
@@ -106,6 +113,32 @@ impl AppState {
         self.workers.write().insert(worker.id.clone(), worker);
     }
 
+    /// Включает или выключает режим обслуживания всей системы
+    pub async fn set_maintenance_mode(&self, enabled: bool) {
+        self.maintenance_mode.set(enabled);
+        log::info!("Maintenance mode {}", if enabled { "enabled" } else { "disabled" });
+    }
+
+    pub async fn is_maintenance_mode(&self) -> bool {
+        self.maintenance_mode.is_enabled()
+    }
+
+    /// Включает или выключает режим обслуживания отдельного пула
+    pub async fn set_pool_maintenance(&self, pool: &str, enabled: bool) {
+        let mut pools = self.maintenance_pools.write();
+        if enabled {
+            pools.insert(pool.to_string());
+        } else {
+            pools.remove(pool);
+        }
+        log::info!("Pool {} maintenance {}", pool, if enabled { "enabled" } else { "disabled" });
+    }
+
+    /// Пул на обслуживании, если обслуживается вся система или сам пул
+    pub async fn is_pool_in_maintenance(&self, pool: &str) -> bool {
+        self.is_maintenance_mode().await || self.maintenance_pools.read().contains(pool)
+    }
+
     pub fn update_raid_status(&self, node_id: Pubkey, status: NodeStatus) {
         let mut raid_status = self.raid_status.lock();
         if let Some(node) = raid_status.get_mut(&node_id) {
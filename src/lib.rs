@@ -23,6 +23,8 @@ pub mod ui;
 pub mod admin;
 pub mod workers;
 pub mod version;
+#[cfg(test)]
+mod test_util;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -72,7 +74,7 @@ impl Default for SystemInfo {
                 "workers".to_string(),
                 "version".to_string(),
             ],
-            build_date: env!("VERGEN_BUILD_TIMESTAMP").to_string(),
+            build_date: version::build_timestamp().to_string(),
         }
     }
 }
@@ -90,12 +92,27 @@ pub struct SystemStatus {
     pub uptime: u64,
     pub modules_loaded: usize,
     pub features_enabled: usize,
+    #[serde(default)]
+    pub skipped_modules: Vec<String>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
 /// Инициализация системы
 pub async fn initialize_system() -> Result<SystemStatus, Box<dyn std::error::Error>> {
+    initialize_system_with_config(&get_system_config()).await
+}
+
+/// Инициализация системы с учётом флагов функций из конфигурации
+pub async fn initialize_system_with_config(
+    config: &SystemConfig,
+) -> Result<SystemStatus, Box<dyn std::error::Error>> {
     log::info!("Initializing PoolAI v{}", VERSION);
+    once_cell::sync::Lazy::force(&PROCESS_START);
+
+    let features = features();
+    features.load(&config.features);
+
+    let mut skipped_modules = Vec::new();
     
     // Инициализация модулей
     core::initialize().await?;
@@ -106,11 +123,27 @@ pub async fn initialize_system() -> Result<SystemStatus, Box<dyn std::error::Err
     network::initialize().await?;
     platform::initialize().await?;
     vm::initialize().await?;
-    tgbot::initialize().await?;
-    raid::initialize().await?;
-    ui::initialize().await?;
+    if features.telegram_bot() && config.module_enabled("tgbot") {
+        tgbot::initialize().await?;
+    } else {
+        skipped_modules.push("tgbot".to_string());
+    }
+    if features.raid_system() && config.module_enabled("raid") {
+        raid::initialize().await?;
+    } else {
+        skipped_modules.push("raid".to_string());
+    }
+    if features.web_ui() && config.module_enabled("ui") {
+        ui::initialize().await?;
+    } else {
+        skipped_modules.push("ui".to_string());
+    }
     admin::initialize().await?;
     workers::initialize().await?;
+
+    for module in &skipped_modules {
+        log::info!("Module {} disabled by configuration", module);
+    }
     
     log::info!("PoolAI v{} initialized successfully", VERSION);
     
@@ -118,74 +151,266 @@ pub async fn initialize_system() -> Result<SystemStatus, Box<dyn std::error::Err
         status: "initialized".to_string(),
         version: VERSION.to_string(),
         uptime: 0,
-        modules_loaded: 14,
-        features_enabled: 7,
+        modules_loaded: 14 - skipped_modules.len(),
+        features_enabled: features.enabled_count(),
+        skipped_modules,
         timestamp: chrono::Utc::now(),
     })
 }
 
+/// Порядок остановки модулей: обратный порядку инициализации
+pub const SHUTDOWN_ORDER: &[&str] = &[
+    "workers", "admin", "ui", "raid", "tgbot", "vm", "platform",
+    "network", "runtime", "monitoring", "pool", "libs", "core",
+];
+
+/// Остановка модулей и завершение процесса; в тестах подменяется
+#[async_trait::async_trait]
+pub trait ShutdownHook: Send + Sync {
+    async fn stop_module(&self, module: &'static str) -> Result<(), String>;
+
+    /// Вызывается после того, как ответ с результатами отправлен;
+    /// `code` - код завершения процесса (см. `shutdown_exit_code`)
+    fn exit(&self, code: i32);
+}
+
+/// Останавливает настоящие модули и завершает процесс
+#[derive(Debug, Default)]
+pub struct ProcessShutdown;
+
+#[async_trait::async_trait]
+impl ShutdownHook for ProcessShutdown {
+    async fn stop_module(&self, module: &'static str) -> Result<(), String> {
+        let result = match module {
+            "workers" => workers::shutdown().await,
+            "admin" => admin::shutdown().await,
+            "ui" => ui::shutdown().await,
+            "raid" => raid::shutdown().await,
+            "tgbot" => tgbot::shutdown().await,
+            "vm" => vm::shutdown().await,
+            "platform" => platform::shutdown().await,
+            "network" => network::shutdown().await,
+            "runtime" => runtime::shutdown().await,
+            "monitoring" => monitoring::shutdown().await,
+            "pool" => pool::shutdown().await,
+            "libs" => libs::shutdown().await,
+            "core" => core::shutdown().await,
+            _ => return Err(format!("Unknown module {}", module)),
+        };
+        result.map_err(|e| e.to_string())
+    }
+
+    fn exit(&self, code: i32) {
+        std::process::exit(code);
+    }
+}
+
+/// Результат остановки модуля
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleShutdown {
+    pub module: String,
+    pub status: String,
+    pub message: String,
+}
+
+/// Код завершения после остановки: ненулевой, если хотя бы один модуль
+/// не остановился, чтобы супервизор процесса увидел сбой
+pub fn shutdown_exit_code(results: &[ModuleShutdown]) -> i32 {
+    if results.iter().any(|result| result.status != "stopped") {
+        1
+    } else {
+        0
+    }
+}
+
 /// Остановка системы
-pub async fn shutdown_system() -> Result<(), Box<dyn std::error::Error>> {
+pub async fn shutdown_system() -> Result<Vec<ModuleShutdown>, Box<dyn std::error::Error>> {
+    Ok(shutdown_system_with(&ProcessShutdown).await)
+}
+
+/// Останавливает модули в порядке `SHUTDOWN_ORDER`. Отказ одного модуля
+/// не прерывает остановку остальных: каждый получает свою запись в результате
+pub async fn shutdown_system_with(hook: &dyn ShutdownHook) -> Vec<ModuleShutdown> {
     log::info!("Shutting down PoolAI v{}", VERSION);
-    
-    // Остановка модулей
-    workers::shutdown().await?;
-    admin::shutdown().await?;
-    ui::shutdown().await?;
-    raid::shutdown().await?;
-    tgbot::shutdown().await?;
-    vm::shutdown().await?;
-    platform::shutdown().await?;
-    network::shutdown().await?;
-    runtime::shutdown().await?;
-    monitoring::shutdown().await?;
-    pool::shutdown().await?;
-    libs::shutdown().await?;
-    core::shutdown().await?;
-    
-    log::info!("PoolAI v{} shut down successfully", VERSION);
-    Ok(())
+
+    let mut results = Vec::with_capacity(SHUTDOWN_ORDER.len());
+    for module in SHUTDOWN_ORDER {
+        let result = hook.stop_module(module).await;
+        if let Err(e) = &result {
+            log::error!("Failed to shut down module {}: {}", module, e);
+        }
+        results.push(ModuleShutdown {
+            module: module.to_string(),
+            status: if result.is_ok() { "stopped".to_string() } else { "failed".to_string() },
+            message: result.map(|_| "OK".to_string()).unwrap_or_else(|e| e),
+        });
+    }
+
+    log::info!("PoolAI v{} shut down", VERSION);
+    results
 }
 
+/// Модули, без которых узел не может обслуживать запросы:
+/// их отказ делает общий статус `critical`
+pub const CRITICAL_MODULES: &[&str] = &["core", "network", "pool"];
+
 /// Проверка здоровья системы
 pub async fn health_check() -> Result<SystemHealth, Box<dyn std::error::Error>> {
-    let mut health = SystemHealth {
-        status: "healthy".to_string(),
-        checks: Vec::new(),
-        timestamp: chrono::Utc::now(),
-    };
-    
     // Проверка модулей
     let module_checks = vec![
-        ("core", core::health_check().await),
-        ("libs", libs::health_check().await),
-        ("pool", pool::health_check().await),
-        ("monitoring", monitoring::health_check().await),
-        ("runtime", runtime::health_check().await),
-        ("network", network::health_check().await),
-        ("platform", platform::health_check().await),
-        ("vm", vm::health_check().await),
-        ("tgbot", tgbot::health_check().await),
-        ("raid", raid::health_check().await),
-        ("ui", ui::health_check().await),
-        ("admin", admin::health_check().await),
-        ("workers", workers::health_check().await),
+        ("core", core::health_check().await.map_err(|e| e.to_string())),
+        ("libs", libs::health_check().await.map_err(|e| e.to_string())),
+        ("pool", pool::health_check().await.map_err(|e| e.to_string())),
+        ("monitoring", monitoring::health_check().await.map_err(|e| e.to_string())),
+        ("runtime", runtime::health_check().await.map_err(|e| e.to_string())),
+        ("network", network::health_check().await.map_err(|e| e.to_string())),
+        ("platform", platform::health_check().await.map_err(|e| e.to_string())),
+        ("vm", vm::health_check().await.map_err(|e| e.to_string())),
+        ("tgbot", tgbot::health_check().await.map_err(|e| e.to_string())),
+        ("raid", raid::health_check().await.map_err(|e| e.to_string())),
+        ("ui", ui::health_check().await.map_err(|e| e.to_string())),
+        ("admin", admin::health_check().await.map_err(|e| e.to_string())),
+        ("workers", workers::health_check().await.map_err(|e| e.to_string())),
     ];
-    
-    for (module, check_result) in module_checks {
-        health.checks.push(ModuleHealth {
+
+    Ok(summarize_health(module_checks))
+}
+
+/// Сводит результаты проверок модулей: `warning`, если отказал любой модуль,
+/// `critical`, если отказал модуль из `CRITICAL_MODULES`
+pub fn summarize_health(module_checks: Vec<(&str, Result<(), String>)>) -> SystemHealth {
+    let checks: Vec<ModuleHealth> = module_checks
+        .into_iter()
+        .map(|(module, result)| ModuleHealth {
             module: module.to_string(),
-            status: if check_result.is_ok() { "healthy".to_string() } else { "unhealthy".to_string() },
-            message: check_result.map(|_| "OK".to_string()).unwrap_or_else(|e| e.to_string()),
-        });
+            status: if result.is_ok() { "healthy".to_string() } else { "unhealthy".to_string() },
+            message: result.map(|_| "OK".to_string()).unwrap_or_else(|e| e),
+        })
+        .collect();
+
+    let failed = |check: &&ModuleHealth| check.status == "unhealthy";
+    let status = if checks.iter().filter(failed).any(|c| CRITICAL_MODULES.contains(&c.module.as_str())) {
+        "critical"
+    } else if checks.iter().any(|c| failed(&c)) {
+        "warning"
+    } else {
+        "healthy"
+    };
+
+    SystemHealth {
+        status: status.to_string(),
+        checks,
+        timestamp: chrono::Utc::now(),
     }
-    
-    // Обновляем общий статус
-    if health.checks.iter().any(|check| check.status == "unhealthy") {
-        health.status = "warning".to_string();
+}
+
+/// Зависимости модулей: (модуль, от чего зависит)
+pub const MODULE_DEPENDENCIES: &[(&str, &str)] = &[
+    ("libs", "core"),
+    ("monitoring", "core"),
+    ("runtime", "core"),
+    ("runtime", "libs"),
+    ("network", "core"),
+    ("platform", "core"),
+    ("vm", "platform"),
+    ("raid", "platform"),
+    ("workers", "monitoring"),
+    ("workers", "runtime"),
+    ("pool", "workers"),
+    ("pool", "vm"),
+    ("tgbot", "network"),
+    ("tgbot", "pool"),
+    ("ui", "monitoring"),
+    ("ui", "network"),
+    ("admin", "pool"),
+    ("admin", "monitoring"),
+];
+
+/// Граф зависимостей модулей с их здоровьем
+pub async fn health_graph() -> Result<HealthGraph, Box<dyn std::error::Error>> {
+    let health = health_check().await?;
+    Ok(build_health_graph(&health.checks))
+}
+
+/// Строит граф из результатов проверок модулей
+pub fn build_health_graph(checks: &[ModuleHealth]) -> HealthGraph {
+    let healthy: HashMap<&str, bool> = checks
+        .iter()
+        .map(|check| (check.module.as_str(), check.status == "healthy"))
+        .collect();
+    let is_healthy = |module: &str| healthy.get(module).copied().unwrap_or(true);
+
+    let edges: Vec<HealthEdge> = MODULE_DEPENDENCIES
+        .iter()
+        .map(|(from, to)| HealthEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            healthy: is_healthy(to),
+        })
+        .collect();
+
+    let nodes = checks
+        .iter()
+        .map(|check| {
+            // Все нездоровые модули, от которых транзитивно зависит данный
+            let mut impacted_by = Vec::new();
+            let mut stack = vec![check.module.as_str()];
+            let mut visited = std::collections::HashSet::new();
+            while let Some(module) = stack.pop() {
+                for (_, dependency) in MODULE_DEPENDENCIES.iter().filter(|(from, _)| *from == module) {
+                    if visited.insert(*dependency) {
+                        if !is_healthy(dependency) {
+                            impacted_by.push(dependency.to_string());
+                        }
+                        stack.push(dependency);
+                    }
+                }
+            }
+            impacted_by.sort();
+
+            HealthNode {
+                module: check.module.clone(),
+                status: check.status.clone(),
+                message: check.message.clone(),
+                root_cause: !is_healthy(&check.module) && impacted_by.is_empty(),
+                impacted_by,
+            }
+        })
+        .collect();
+
+    HealthGraph {
+        nodes,
+        edges,
+        timestamp: chrono::Utc::now(),
     }
-    
-    Ok(health)
+}
+
+/// Граф здоровья модулей
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthGraph {
+    pub nodes: Vec<HealthNode>,
+    pub edges: Vec<HealthEdge>,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Узел графа здоровья
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthNode {
+    pub module: String,
+    pub status: String,
+    pub message: String,
+    /// Модуль нездоров, и все его зависимости здоровы
+    pub root_cause: bool,
+    /// Нездоровые модули, от которых зависит данный
+    pub impacted_by: Vec<String>,
+}
+
+/// Ребро графа: `from` зависит от `to`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthEdge {
+    pub from: String,
+    pub to: String,
+    pub healthy: bool,
 }
 
 /// Здоровье системы
@@ -249,33 +474,391 @@ impl Default for SystemConfig {
     }
 }
 
+/// Названия флагов функций
+pub mod feature_names {
+    pub const GPU_OPTIMIZATION: &str = "gpu_optimization";
+    pub const MODEL_INTEGRATION: &str = "model_integration";
+    pub const TELEGRAM_BOT: &str = "telegram_bot";
+    pub const WEB_UI: &str = "web_ui";
+    pub const RAID_SYSTEM: &str = "raid_system";
+    pub const MONITORING: &str = "monitoring";
+    pub const REWARD_SYSTEM: &str = "reward_system";
+}
+
+lazy_static::lazy_static! {
+    static ref FEATURES: Features = Features::from_map(&SystemConfig::default().features);
+}
+
+/// Флаги функций системы с возможностью переключения во время работы
+#[derive(Debug, Clone)]
+pub struct Features {
+    flags: std::sync::Arc<parking_lot::RwLock<HashMap<String, bool>>>,
+}
+
+impl Features {
+    pub fn from_map(flags: &HashMap<String, bool>) -> Self {
+        Self {
+            flags: std::sync::Arc::new(parking_lot::RwLock::new(flags.clone())),
+        }
+    }
+
+    /// Заменяет все флаги значениями из конфигурации
+    pub fn load(&self, flags: &HashMap<String, bool>) {
+        *self.flags.write() = flags.clone();
+    }
+
+    /// Неизвестные флаги считаются выключенными
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.flags.read().get(name).copied().unwrap_or(false)
+    }
+
+    pub fn set(&self, name: &str, enabled: bool) {
+        log::info!("Feature '{}' {}", name, if enabled { "enabled" } else { "disabled" });
+        self.flags.write().insert(name.to_string(), enabled);
+    }
+
+    pub fn enabled_count(&self) -> usize {
+        self.flags.read().values().filter(|enabled| **enabled).count()
+    }
+
+    pub fn snapshot(&self) -> HashMap<String, bool> {
+        self.flags.read().clone()
+    }
+
+    pub fn gpu_optimization(&self) -> bool {
+        self.is_enabled(feature_names::GPU_OPTIMIZATION)
+    }
+
+    pub fn model_integration(&self) -> bool {
+        self.is_enabled(feature_names::MODEL_INTEGRATION)
+    }
+
+    pub fn telegram_bot(&self) -> bool {
+        self.is_enabled(feature_names::TELEGRAM_BOT)
+    }
+
+    pub fn web_ui(&self) -> bool {
+        self.is_enabled(feature_names::WEB_UI)
+    }
+
+    pub fn raid_system(&self) -> bool {
+        self.is_enabled(feature_names::RAID_SYSTEM)
+    }
+
+    pub fn monitoring(&self) -> bool {
+        self.is_enabled(feature_names::MONITORING)
+    }
+
+    pub fn reward_system(&self) -> bool {
+        self.is_enabled(feature_names::REWARD_SYSTEM)
+    }
+}
+
+/// Глобальные флаги функций
+pub fn features() -> &'static Features {
+    &FEATURES
+}
+
+/// Guard для маршрутов actix: при выключенном флаге маршрут отвечает 404
+pub fn feature_guard(features: Features, name: &'static str) -> impl actix_web::guard::Guard {
+    actix_web::guard::fn_guard(move |_| features.is_enabled(name))
+}
+
+/// Файл, в котором сохраняется применённая конфигурация системы
+pub const SYSTEM_CONFIG_PATH: &str = "config/system.json";
+
+/// Модули, которые нельзя отключить
+pub const REQUIRED_MODULES: &[&str] = &["core"];
+
+lazy_static::lazy_static! {
+    static ref SYSTEM_CONFIG: parking_lot::RwLock<SystemConfig> =
+        parking_lot::RwLock::new(load_system_config(std::path::Path::new(SYSTEM_CONFIG_PATH)));
+}
+
+/// Ошибка обновления конфигурации системы
+#[derive(Debug, thiserror::Error)]
+pub enum SystemConfigError {
+    #[error("Invalid log level: {0}")]
+    InvalidLogLevel(String),
+    #[error("Unknown module: {0}")]
+    UnknownModule(String),
+    #[error("Unknown feature: {0}")]
+    UnknownFeature(String),
+    #[error("Module {0} is required and cannot be disabled")]
+    RequiredModule(String),
+    #[error("Module {module} cannot be disabled: {dependent} depends on it")]
+    DependentModule { module: String, dependent: String },
+    #[error("Failed to persist system config: {0}")]
+    Persist(String),
+}
+
+impl SystemConfig {
+    /// Отсутствующий в конфигурации модуль считается включённым
+    pub fn module_enabled(&self, module: &str) -> bool {
+        self.modules.get(module).copied().unwrap_or(true)
+    }
+
+    /// Проверяет конфигурацию и возвращает уровень логирования
+    pub fn validate(&self) -> Result<log::LevelFilter, SystemConfigError> {
+        let level = self
+            .log_level
+            .parse::<log::LevelFilter>()
+            .map_err(|_| SystemConfigError::InvalidLogLevel(self.log_level.clone()))?;
+
+        let known_modules = SystemInfo::default().modules;
+        for (module, enabled) in &self.modules {
+            if !known_modules.contains(module) {
+                return Err(SystemConfigError::UnknownModule(module.clone()));
+            }
+            if !enabled && REQUIRED_MODULES.contains(&module.as_str()) {
+                return Err(SystemConfigError::RequiredModule(module.clone()));
+            }
+        }
+
+        for (dependent, module) in MODULE_DEPENDENCIES {
+            if self.module_enabled(dependent) && !self.module_enabled(module) {
+                return Err(SystemConfigError::DependentModule {
+                    module: module.to_string(),
+                    dependent: dependent.to_string(),
+                });
+            }
+        }
+
+        let known_features = SystemConfig::default().features;
+        for feature in self.features.keys() {
+            if !known_features.contains_key(feature) {
+                return Err(SystemConfigError::UnknownFeature(feature.clone()));
+            }
+        }
+
+        Ok(level)
+    }
+}
+
+/// Секция `ConfigSystem`, в которой хранится конфигурация системы
+pub const SYSTEM_CONFIG_SECTION: &str = "system";
+
+impl SystemConfig {
+    /// Представление конфигурации в виде секции `ConfigSystem`:
+    /// модули и функции хранятся с префиксами `modules.` и `features.`
+    fn to_section(&self) -> crate::core::config::ConfigSection {
+        let mut values = HashMap::new();
+        values.insert("version".to_string(), self.version.clone());
+        values.insert("debug".to_string(), self.debug.to_string());
+        values.insert("log_level".to_string(), self.log_level.clone());
+        for (module, enabled) in &self.modules {
+            values.insert(format!("modules.{}", module), enabled.to_string());
+        }
+        for (feature, enabled) in &self.features {
+            values.insert(format!("features.{}", feature), enabled.to_string());
+        }
+
+        crate::core::config::ConfigSection {
+            id: SYSTEM_CONFIG_SECTION.to_string(),
+            name: "System".to_string(),
+            description: "PoolAI system configuration".to_string(),
+            values,
+            last_modified: Some(chrono::Utc::now()),
+            active: true,
+        }
+    }
+
+    fn from_section(section: &crate::core::config::ConfigSection) -> Result<Self, String> {
+        let value = |key: &str| {
+            section
+                .values
+                .get(key)
+                .cloned()
+                .ok_or_else(|| format!("Missing value '{}'", key))
+        };
+        let flag = |key: &str, raw: &str| {
+            raw.parse::<bool>()
+                .map_err(|_| format!("Invalid boolean for '{}': {}", key, raw))
+        };
+
+        let mut config = SystemConfig {
+            version: value("version")?,
+            debug: flag("debug", &value("debug")?)?,
+            log_level: value("log_level")?,
+            modules: HashMap::new(),
+            features: HashMap::new(),
+        };
+        for (key, raw) in &section.values {
+            if let Some(module) = key.strip_prefix("modules.") {
+                config.modules.insert(module.to_string(), flag(key, raw)?);
+            } else if let Some(feature) = key.strip_prefix("features.") {
+                config.features.insert(feature.to_string(), flag(key, raw)?);
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Загружает сохранённую конфигурацию; при отсутствии или ошибке - по умолчанию
+fn load_system_config(path: &std::path::Path) -> SystemConfig {
+    let data = match std::fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(_) => return SystemConfig::default(),
+    };
+    let config = serde_json::from_str::<crate::core::config::ConfigMetrics>(&data)
+        .map_err(|e| e.to_string())
+        .and_then(|metrics| {
+            metrics
+                .sections
+                .get(SYSTEM_CONFIG_SECTION)
+                .ok_or_else(|| format!("Missing section '{}'", SYSTEM_CONFIG_SECTION))
+                .and_then(SystemConfig::from_section)
+        });
+
+    match config {
+        Ok(config) if config.validate().is_ok() => config,
+        _ => {
+            log::warn!("Ignoring invalid system config at {}", path.display());
+            SystemConfig::default()
+        }
+    }
+}
+
+/// Сохраняет конфигурацию через `ConfigSystem`, не затрагивая другие секции файла
+async fn save_system_config(config: &SystemConfig, path: &std::path::Path) -> Result<(), SystemConfigError> {
+    let store = crate::core::config::ConfigSystem::new(&path.to_string_lossy());
+    if path.exists() {
+        if let Err(e) = store.load_config().await {
+            log::warn!("Overwriting unreadable system config at {}: {}", path.display(), e);
+        }
+    }
+
+    let section = config.to_section();
+    let result = if store.get_section(SYSTEM_CONFIG_SECTION).await.is_ok() {
+        store.update_section(SYSTEM_CONFIG_SECTION, section).await
+    } else {
+        store.add_section(section).await
+    };
+    result.map_err(SystemConfigError::Persist)?;
+
+    store.save_config().await.map_err(SystemConfigError::Persist)
+}
+
 /// Получение конфигурации системы
 pub fn get_system_config() -> SystemConfig {
-    SystemConfig::default()
+    SYSTEM_CONFIG.read().clone()
+}
+
+/// Обновление конфигурации системы; возвращает применённую конфигурацию
+pub async fn update_system_config(config: SystemConfig) -> Result<SystemConfig, SystemConfigError> {
+    update_system_config_at(config, std::path::Path::new(SYSTEM_CONFIG_PATH)).await
 }
 
-/// Обновление конфигурации системы
-pub async fn update_system_config(config: SystemConfig) -> Result<(), Box<dyn std::error::Error>> {
+/// Проверяет, сохраняет и применяет конфигурацию: флаги функций,
+/// включённые модули и уровень логирования
+pub async fn update_system_config_at(
+    config: SystemConfig,
+    path: &std::path::Path,
+) -> Result<SystemConfig, SystemConfigError> {
     log::info!("Updating system configuration");
-    
-    // Здесь должна быть логика обновления конфигурации
-    // Пока что просто логируем
-    
-    log::info!("System configuration updated successfully");
-    Ok(())
+
+    let level = config.validate()?;
+    save_system_config(&config, path).await?;
+
+    features().load(&config.features);
+    log::set_max_level(level);
+    *SYSTEM_CONFIG.write() = config;
+
+    log::info!("System configuration updated successfully (log level: {})", level);
+    Ok(get_system_config())
+}
+
+/// Файл конфигурации системы для HTTP-обработчиков
+#[derive(Debug, Clone)]
+pub struct SystemConfigFile(pub std::path::PathBuf);
+
+impl Default for SystemConfigFile {
+    fn default() -> Self {
+        Self(std::path::PathBuf::from(SYSTEM_CONFIG_PATH))
+    }
+}
+
+/// GET /api/v1/system/config - текущая конфигурация системы
+pub async fn get_system_config_handler() -> actix_web::HttpResponse {
+    actix_web::HttpResponse::Ok().json(get_system_config())
+}
+
+/// PUT /api/v1/system/config - проверяет, сохраняет и возвращает применённую
+/// конфигурацию. Требует административный bearer-токен.
+pub async fn put_system_config_handler(
+    req: actix_web::HttpRequest,
+    admin: actix_web::web::Data<crate::admin::admin_panel::AdminConfig>,
+    file: actix_web::web::Data<SystemConfigFile>,
+    config: actix_web::web::Json<SystemConfig>,
+) -> actix_web::HttpResponse {
+    if !crate::admin::admin_panel::bearer_token(&req).map_or(false, |token| admin.verify_token(token)) {
+        return actix_web::HttpResponse::Unauthorized()
+            .json(serde_json::json!({ "error": "Invalid token" }));
+    }
+
+    match update_system_config_at(config.into_inner(), &file.0).await {
+        Ok(effective) => actix_web::HttpResponse::Ok().json(effective),
+        Err(SystemConfigError::Persist(e)) => actix_web::HttpResponse::InternalServerError()
+            .json(serde_json::json!({ "error": format!("Failed to persist system config: {}", e) })),
+        Err(e) => actix_web::HttpResponse::BadRequest()
+            .json(serde_json::json!({ "error": e.to_string() })),
+    }
+}
+
+/// Момент запуска процесса; фиксируется при первом вызове `initialize_system`
+static PROCESS_START: once_cell::sync::Lazy<std::time::Instant> =
+    once_cell::sync::Lazy::new(std::time::Instant::now);
+
+/// Время работы системы
+pub fn uptime() -> std::time::Duration {
+    PROCESS_START.elapsed()
+}
+
+fn percent(used: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        used as f64 / total as f64 * 100.0
+    }
 }
 
 /// Получение статистики системы
 pub async fn get_system_stats() -> SystemStats {
+    use crate::platform::SystemInfo as _;
+
+    let system_info = platform::create_system_info();
+
+    let memory_usage = match system_info.get_memory_info().await {
+        Ok(memory) => percent(memory.used, memory.total),
+        Err(e) => {
+            log::warn!("Failed to read memory info: {}", e);
+            0.0
+        }
+    };
+    let cpu_usage = match system_info.get_cpu_info().await {
+        Ok(cpu) => cpu.usage as f64,
+        Err(e) => {
+            log::warn!("Failed to read CPU info: {}", e);
+            0.0
+        }
+    };
+    let disk_usage = match system_info.get_disk_info().await {
+        Ok(disk) => percent(disk.used, disk.total),
+        Err(e) => {
+            log::warn!("Failed to read disk info: {}", e);
+            0.0
+        }
+    };
+
     SystemStats {
         version: VERSION.to_string(),
-        uptime: std::time::Duration::from_secs(0), // TODO: реализовать
+        uptime: uptime(),
         modules_loaded: 13,
-        features_enabled: 7,
-        memory_usage: 0.0, // TODO: реализовать
-        cpu_usage: 0.0, // TODO: реализовать
-        disk_usage: 0.0, // TODO: реализовать
-        network_usage: 0.0, // TODO: реализовать
+        features_enabled: features().enabled_count(),
+        memory_usage,
+        cpu_usage,
+        disk_usage,
+        network_usage: 0.0, // Сетевая статистика платформой пока не предоставляется
         timestamp: chrono::Utc::now(),
     }
 }
@@ -306,4 +889,235 @@ pub use tgbot::*;
 pub use raid::*;
 pub use ui::*;
 pub use admin::*;
-pub use libs::*; 
\ No newline at end of file
+pub use libs::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{test, web, App, HttpResponse};
+
+    /// Тесты конфигурации меняют глобальное состояние и не должны пересекаться
+    static SYSTEM_CONFIG_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+    fn test_admin_config() -> crate::admin::admin_panel::AdminConfig {
+        crate::admin::admin_panel::AdminConfig::with_token(
+            "token",
+            crate::admin::ip_allowlist::IpAllowlist::default(),
+            100,
+        )
+    }
+
+    #[test]
+    fn test_health_graph_reports_root_cause() {
+        let checks: Vec<ModuleHealth> = ["core", "monitoring", "runtime", "workers", "pool", "ui"]
+            .iter()
+            .map(|module| ModuleHealth {
+                module: module.to_string(),
+                status: if *module == "workers" { "unhealthy" } else { "healthy" }.to_string(),
+                message: "OK".to_string(),
+            })
+            .collect();
+
+        let graph = build_health_graph(&checks);
+
+        let has_edge = |from: &str, to: &str| {
+            graph.edges.iter().find(|e| e.from == from && e.to == to).cloned()
+        };
+        assert!(has_edge("pool", "workers").map_or(false, |e| !e.healthy));
+        assert!(has_edge("ui", "monitoring").map_or(false, |e| e.healthy));
+
+        let node = |module: &str| graph.nodes.iter().find(|n| n.module == module).unwrap();
+        assert!(node("workers").root_cause);
+        assert!(!node("pool").root_cause);
+        assert_eq!(node("pool").impacted_by, vec!["workers".to_string()]);
+        assert!(node("ui").impacted_by.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_telegram_bot_skips_startup() {
+        let _guard = SYSTEM_CONFIG_LOCK.lock().await;
+        let mut config = SystemConfig::default();
+        config.features.insert(feature_names::TELEGRAM_BOT.to_string(), false);
+
+        let status = initialize_system_with_config(&config).await.unwrap();
+
+        assert!(status.skipped_modules.contains(&"tgbot".to_string()));
+        assert!(!features().telegram_bot());
+
+        features().load(&SystemConfig::default().features);
+    }
+
+    #[actix_rt::test]
+    async fn test_disabled_reward_system_hides_reward_routes() {
+        let features = Features::from_map(&SystemConfig::default().features);
+
+        let app = test::init_service(
+            App::new().route(
+                "/api/v1/rewards/stats",
+                web::get()
+                    .guard(feature_guard(features.clone(), feature_names::REWARD_SYSTEM))
+                    .to(|| async { HttpResponse::Ok().finish() }),
+            ),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/api/v1/rewards/stats").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+        features.set(feature_names::REWARD_SYSTEM, false);
+
+        let req = test::TestRequest::get().uri("/api/v1/rewards/stats").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 404);
+    }
+
+    #[tokio::test]
+    async fn test_update_system_config_applies_and_persists() {
+        let _guard = SYSTEM_CONFIG_LOCK.lock().await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("system.json");
+
+        let mut config = SystemConfig::default();
+        config.modules.insert("raid".to_string(), false);
+        config.log_level = "debug".to_string();
+        update_system_config_at(config, &path).await.unwrap();
+
+        assert!(!get_system_config().module_enabled("raid"));
+        assert_eq!(log::max_level(), log::LevelFilter::Debug);
+        assert!(!load_system_config(&path).module_enabled("raid"));
+
+        let status = initialize_system().await.unwrap();
+        assert!(status.skipped_modules.contains(&"raid".to_string()));
+
+        update_system_config_at(SystemConfig::default(), &path).await.unwrap();
+        assert!(get_system_config().module_enabled("raid"));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_system_config_rejected() {
+        let _guard = SYSTEM_CONFIG_LOCK.lock().await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("system.json");
+        let before = get_system_config();
+
+        let mut config = SystemConfig::default();
+        config.log_level = "verbose".to_string();
+        let result = update_system_config_at(config, &path).await;
+        assert!(matches!(result, Err(SystemConfigError::InvalidLogLevel(_))));
+
+        let mut config = SystemConfig::default();
+        config.modules.insert("core".to_string(), false);
+        let result = update_system_config_at(config, &path).await;
+        assert!(matches!(result, Err(SystemConfigError::RequiredModule(_))));
+
+        let mut config = SystemConfig::default();
+        config.features.insert("teleport".to_string(), true);
+        let result = update_system_config_at(config, &path).await;
+        assert!(matches!(result, Err(SystemConfigError::UnknownFeature(_))));
+
+        assert!(!path.exists());
+        assert_eq!(get_system_config().log_level, before.log_level);
+    }
+
+    #[actix_rt::test]
+    async fn test_system_config_round_trip_over_http() {
+        let _guard = SYSTEM_CONFIG_LOCK.lock().await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("system.json");
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(SystemConfigFile(path.clone())))
+                .app_data(web::Data::new(test_admin_config()))
+                .route("/api/v1/system/config", web::get().to(get_system_config_handler))
+                .route("/api/v1/system/config", web::put().to(put_system_config_handler)),
+        )
+        .await;
+
+        let mut config = SystemConfig::default();
+        config.modules.insert("tgbot".to_string(), false);
+        config.debug = true;
+        // Без токена конфигурация не меняется
+        let req = test::TestRequest::put()
+            .uri("/api/v1/system/config")
+            .set_json(&config)
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 401);
+        assert!(!path.exists());
+
+        let req = test::TestRequest::put()
+            .uri("/api/v1/system/config")
+            .insert_header(("Authorization", "Bearer token"))
+            .set_json(&config)
+            .to_request();
+        let effective: SystemConfig = test::call_and_read_body_json(&app, req).await;
+        assert!(!effective.module_enabled("tgbot"));
+        assert!(effective.debug);
+
+        let req = test::TestRequest::get().uri("/api/v1/system/config").to_request();
+        let current: SystemConfig = test::call_and_read_body_json(&app, req).await;
+        assert!(!current.module_enabled("tgbot"));
+        assert!(current.debug);
+
+        let store = crate::core::config::ConfigSystem::new(&path.to_string_lossy());
+        store.load_config().await.unwrap();
+        assert_eq!(
+            store.get_value(SYSTEM_CONFIG_SECTION, "modules.tgbot").await.unwrap(),
+            "false"
+        );
+        let persisted = load_system_config(&path);
+        assert!(!persisted.module_enabled("tgbot"));
+        assert_eq!(persisted.features, config.features);
+
+        update_system_config_at(SystemConfig::default(), &path).await.unwrap();
+    }
+
+    #[actix_rt::test]
+    async fn test_disabling_depended_on_module_rejected() {
+        let _guard = SYSTEM_CONFIG_LOCK.lock().await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("system.json");
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(SystemConfigFile(path.clone())))
+                .app_data(web::Data::new(test_admin_config()))
+                .route("/api/v1/system/config", web::put().to(put_system_config_handler)),
+        )
+        .await;
+
+        let mut config = SystemConfig::default();
+        config.modules.insert("monitoring".to_string(), false);
+        let result = config.validate();
+        assert!(matches!(
+            result,
+            Err(SystemConfigError::DependentModule { ref module, .. }) if module == "monitoring"
+        ));
+
+        let req = test::TestRequest::put()
+            .uri("/api/v1/system/config")
+            .insert_header(("Authorization", "Bearer token"))
+            .set_json(&config)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 400);
+
+        assert!(!path.exists());
+        assert!(get_system_config().module_enabled("monitoring"));
+
+        // Если зависимые модули тоже отключены, изменение допустимо
+        for dependent in ["workers", "pool", "ui", "admin", "tgbot"] {
+            config.modules.insert(dependent.to_string(), false);
+        }
+        assert!(config.validate().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_system_stats_uptime_increases() {
+        let first = get_system_stats().await;
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        let second = get_system_stats().await;
+
+        assert!(second.uptime > first.uptime);
+        assert!(second.uptime - first.uptime >= std::time::Duration::from_millis(50));
+        assert!((0.0..=100.0).contains(&second.memory_usage));
+        assert!((0.0..=100.0).contains(&second.disk_usage));
+    }
+}
@@ -7,7 +7,7 @@
 //! - Управление зависимостями
 //! - Версионирование моделей
 
-use crate::platform::gpu::GpuInfo;
+use crate::platform::gpu::{GpuConfig, GpuInfo};
 use crate::core::error::AppError;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -439,6 +439,61 @@ pub enum Priority {
     Critical,
 }
 
+/// Рекомендуемое действие по температуре GPU
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ThrottleAction {
+    /// Температура в норме
+    None,
+    /// Снизить лимит мощности до указанного значения, Вт
+    ReducePower(u32),
+    /// Остановить нагрузку на GPU
+    Shutdown,
+}
+
+/// Пороги троттлинга: запас в °C до `temperature_limit`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ThrottleThresholds {
+    /// Запас до лимита, при котором начинается снижение мощности
+    pub warning_margin: f32,
+    /// Запас до лимита, при котором GPU нужно остановить
+    pub critical_margin: f32,
+    /// Доля, на которую снижается лимит мощности (0.0 - 1.0)
+    pub power_reduction: f32,
+}
+
+impl Default for ThrottleThresholds {
+    fn default() -> Self {
+        Self {
+            warning_margin: 10.0,
+            critical_margin: 0.0,
+            power_reduction: 0.2,
+        }
+    }
+}
+
+/// Рекомендация по троттлингу с порогами по умолчанию
+pub fn recommend_throttle(current_temp: f32, config: &GpuConfig) -> ThrottleAction {
+    recommend_throttle_with(current_temp, config, &ThrottleThresholds::default())
+}
+
+/// Рекомендация по троттлингу по расстоянию до `temperature_limit`
+pub fn recommend_throttle_with(
+    current_temp: f32,
+    config: &GpuConfig,
+    thresholds: &ThrottleThresholds,
+) -> ThrottleAction {
+    let margin = config.temperature_limit as f32 - current_temp;
+
+    if margin <= thresholds.critical_margin {
+        ThrottleAction::Shutdown
+    } else if margin <= thresholds.warning_margin {
+        let reduction = thresholds.power_reduction.clamp(0.0, 1.0);
+        ThrottleAction::ReducePower((config.power_limit as f32 * (1.0 - reduction)).round() as u32)
+    } else {
+        ThrottleAction::None
+    }
+}
+
 /// ASIC интеграция
 pub struct AsicIntegration {
     asic_devices: Arc<RwLock<HashMap<String, AsicDevice>>>,
@@ -578,4 +633,33 @@ impl Default for AsicOptimizationConfig {
             enable_power_optimization: true,
         }
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttle_safe_temperature() {
+        let config = GpuConfig::default();
+        assert_eq!(recommend_throttle(60.0, &config), ThrottleAction::None);
+    }
+
+    #[test]
+    fn test_throttle_warning_reduces_power() {
+        let config = GpuConfig { power_limit: 250, temperature_limit: 85.0, ..GpuConfig::default() };
+        assert_eq!(recommend_throttle(80.0, &config), ThrottleAction::ReducePower(200));
+
+        let thresholds = ThrottleThresholds { warning_margin: 3.0, ..ThrottleThresholds::default() };
+        assert_eq!(recommend_throttle_with(80.0, &config, &thresholds), ThrottleAction::None);
+    }
+
+    #[test]
+    fn test_throttle_critical_shuts_down() {
+        let config = GpuConfig::default();
+        assert_eq!(recommend_throttle(85.0, &config), ThrottleAction::Shutdown);
+        assert_eq!(recommend_throttle(92.0, &config), ThrottleAction::Shutdown);
+
+        let thresholds = ThrottleThresholds { critical_margin: 5.0, ..ThrottleThresholds::default() };
+        assert_eq!(recommend_throttle_with(81.0, &config, &thresholds), ThrottleAction::Shutdown);
+    }
+}
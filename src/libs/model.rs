@@ -87,6 +87,7 @@ impl LanguageModel {
                 retry_attempts: 3,
                 enable_caching: true,
                 cache_size: 1024 * 1024 * 1024, // 1GB
+                batch_window_ms: 0,
             },
             memory: MemoryConfig {
                 max_memory_usage: 16384, // 16GB
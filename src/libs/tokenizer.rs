@@ -8,6 +8,14 @@ use tokio::sync::Mutex;
 
 pub const POOL_COMMISSION_ADDRESS: &str = "GcdgNtdE8NEk3z9sQ5jXv2tqguZjSYqPqNAtjsjPNJx8";
 
+/// Размер словаря для `Tokenizer::encode`, если не задан явно
+pub const DEFAULT_VOCAB_SIZE: u32 = 50_257;
+
+/// Идентификатор токена слова: сумма байтов по модулю размера словаря
+fn token_id(word: &str, vocab_size: u32) -> u32 {
+    word.as_bytes().iter().fold(0u32, |acc, &x| acc.wrapping_add(x as u32)) % vocab_size
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenizerConfig {
     pub id: String,
@@ -100,6 +108,7 @@ impl RewardCalculation {
 pub struct Tokenizer {
     calculations: Arc<RwLock<HashMap<String, RewardCalculation>>>,
     tokenizers: Arc<Mutex<HashMap<String, TokenizerMetrics>>>,
+    vocab_size: u32,
 }
 
 impl Tokenizer {
@@ -107,9 +116,41 @@ impl Tokenizer {
         Self {
             calculations: Arc::new(RwLock::new(HashMap::new())),
             tokenizers: Arc::new(Mutex::new(HashMap::new())),
+            vocab_size: DEFAULT_VOCAB_SIZE,
         }
     }
 
+    /// Размер словаря для `encode`
+    pub fn with_vocab_size(mut self, vocab_size: u32) -> Self {
+        self.vocab_size = vocab_size.max(1);
+        self
+    }
+
+    /// Кодирует текст в токены (по токену на слово)
+    pub fn encode(&self, text: &str) -> Vec<u32> {
+        text.split_whitespace()
+            .map(|word| token_id(word, self.vocab_size))
+            .collect()
+    }
+
+    /// Число токенов текста без построения самих токенов
+    pub fn count_tokens(&self, text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    /// Кодирует тексты, обрезая каждый до `max_len` токенов
+    pub fn encode_batch(&self, texts: &[&str], max_len: usize) -> Vec<Vec<u32>> {
+        texts
+            .iter()
+            .map(|text| {
+                text.split_whitespace()
+                    .take(max_len)
+                    .map(|word| token_id(word, self.vocab_size))
+                    .collect()
+            })
+            .collect()
+    }
+
     pub fn add_calculation(&self, id: String, calculation: RewardCalculation) {
         self.calculations.write().insert(id, calculation);
     }
@@ -219,8 +260,7 @@ impl Tokenizer {
             }
 
             // Simple hash-based tokenization
-            let token = word.as_bytes().iter().fold(0u32, |acc, &x| acc.wrapping_add(x as u32));
-            tokens.push(token % config.vocab_size as u32);
+            tokens.push(token_id(word, config.vocab_size as u32));
         }
 
         Ok(tokens)
@@ -309,4 +349,37 @@ impl Tokenizer {
         info!("Updated tokenizer configuration: {}", id);
         Ok(())
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_tokens_matches_encode() {
+        let tokenizer = Tokenizer::new();
+        let text = "  the quick brown\tfox\njumps  ";
+
+        assert_eq!(tokenizer.count_tokens(text), 5);
+        assert_eq!(tokenizer.encode(text).len(), 5);
+        assert_eq!(tokenizer.count_tokens(""), 0);
+        assert_eq!(tokenizer.encode("fox"), tokenizer.encode("fox"));
+    }
+
+    #[test]
+    fn test_encode_respects_vocab_size() {
+        let tokenizer = Tokenizer::new().with_vocab_size(10);
+        assert!(tokenizer.encode("alpha beta gamma delta").iter().all(|&t| t < 10));
+    }
+
+    #[test]
+    fn test_encode_batch_truncates() {
+        let tokenizer = Tokenizer::new();
+        let batch = tokenizer.encode_batch(&["one two three four", "five", ""], 2);
+
+        assert_eq!(batch.len(), 3);
+        assert_eq!(batch[0], tokenizer.encode("one two"));
+        assert_eq!(batch[1], tokenizer.encode("five"));
+        assert!(batch[2].is_empty());
+    }
+}
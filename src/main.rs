@@ -47,12 +47,33 @@ use crate::admin::admin_panel::{
     remove_worker,
     get_reward_stats,
     toggle_maintenance_mode,
+    shutdown_system,
+    get_raid_status,
 };
 use crate::monitoring::metrics::SystemMetrics;
+use crate::monitoring::alert::{AlertSystem, ALERT_EVALUATION_INTERVAL};
 use crate::network::api::ApiServer;
+use crate::monitoring::events::EventBus;
 
 const VERSION: &str = "Beta_bolvanka_v1";
-const BUILD_DATE: &str = env!("VERGEN_BUILD_TIMESTAMP");
+/// Адрес HTTP-сервера, если конфигурацию загрузить не удалось
+const DEFAULT_BIND_ADDRESS: &str = "127.0.0.1:8080";
+
+/// Адрес HTTP-сервера из `server.bind_address` и `server.http_port`
+fn resolve_bind_address(
+    server: Option<&crate::core::config::ServerConfig>,
+) -> Result<std::net::SocketAddr, String> {
+    let addr = match server {
+        Some(server) => std::net::SocketAddr::new(server.bind_address, server.http_port),
+        None => DEFAULT_BIND_ADDRESS
+            .parse()
+            .map_err(|e| format!("Invalid bind address '{}': {}", DEFAULT_BIND_ADDRESS, e))?,
+    };
+    if addr.port() == 0 {
+        return Err(format!("Invalid bind address {}: port must be non-zero", addr));
+    }
+    Ok(addr)
+}
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -61,34 +82,97 @@ async fn main() -> std::io::Result<()> {
         .filter_level(LevelFilter::Info)
         .init();
 
-    info!("Starting PoolAI v{} (Build: {})", VERSION, BUILD_DATE);
+    // Сквозная самопроверка без запуска сервера
+    if env::args().any(|arg| arg == "--self-test") {
+        let report = crate::admin::self_test::run_self_test().await;
+        println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+        process::exit(if report.passed { 0 } else { 1 });
+    }
+
+    info!("Starting PoolAI v{} (Build: {})", VERSION, crate::version::build_timestamp());
     info!("PoolAI - AI Mining Pool Management System");
     info!("Features: GPU/ASIC/CPU optimization, Model integration, Telegram bot, Web UI");
 
+    let app_config = match AppConfig::load() {
+        Ok(config) => Some(config),
+        Err(e) => {
+            error!("Failed to load configuration, using defaults: {}", e);
+            None
+        }
+    };
+
     // Инициализация основных систем
     let app_state = Arc::new(AppState::new());
     let pool_manager = Arc::new(PoolManager::new(PoolConfig::default()));
     let raid_manager = Arc::new(BurstRaidManager::new());
     let metrics = Arc::new(RwLock::new(SystemMetrics::default()));
     let api_server = Arc::new(ApiServer::new());
+    let alert_system = Arc::new(AlertSystem::new());
+    if let Some(config) = &app_config {
+        if let Err(e) = alert_system.load_rules(&config.alert_rules).await {
+            error!("Invalid alert rules: {}", e);
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, e));
+        }
+    }
+    // Правила проверяются по метрикам, которые обновляет сборщик API сервера
+    alert_system.clone().spawn_evaluator(api_server.system_metrics(), ALERT_EVALUATION_INTERVAL);
+    // Общая шина событий: сюда попадают остановки и запуски компонентов при перезапуске
+    let events = Arc::new(EventBus::default());
     
     // Инициализация административной панели
+    let allowed_ips = match crate::admin::ip_allowlist::IpAllowlist::parse(&["127.0.0.1", "::1"]) {
+        Ok(allowed_ips) => allowed_ips,
+        Err(e) => {
+            error!("Invalid admin configuration: {}", e);
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, e));
+        }
+    };
+    // Токен администратора: готовый SHA-256 или токен, который хэшируется при старте
+    let admin_token_hash = match (env::var("ADMIN_TOKEN_HASH"), env::var("ADMIN_TOKEN")) {
+        (Ok(hash), _) => crate::admin::admin_token::AdminTokenHash::from_hex(&hash),
+        (Err(_), Ok(token)) => Ok(crate::admin::admin_token::AdminTokenHash::from_plaintext(&token)),
+        _ => Err("ADMIN_TOKEN_HASH or ADMIN_TOKEN must be set".to_string()),
+    };
+    let admin_token_hash = match admin_token_hash {
+        Ok(hash) => hash,
+        Err(e) => {
+            error!("Invalid admin configuration: {}", e);
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, e));
+        }
+    };
     let admin_config = crate::admin::admin_panel::AdminConfig {
-        admin_token: "admin_token_123".to_string(),
-        allowed_ips: vec!["127.0.0.1".to_string(), "::1".to_string()],
+        admin_token_hash,
+        allowed_ips,
         rate_limit: 100,
+        trust_proxy: env::var("ADMIN_TRUST_PROXY").map_or(false, |value| value == "true" || value == "1"),
     };
     
-    let admin_panel = Arc::new(AdminPanel::new(
-        app_state.clone(),
-        pool_manager.clone(),
-        metrics.clone(),
-        api_server.clone(),
-        admin_config,
-    ));
+    let admin_panel = Arc::new(
+        AdminPanel::new(
+            app_state.clone(),
+            pool_manager.clone(),
+            metrics.clone(),
+            api_server.clone(),
+            admin_config.clone(),
+        )
+        .with_event_bus(events.clone()),
+    );
+    let shutdown_hook: Arc<dyn crate::ShutdownHook> = Arc::new(crate::ProcessShutdown);
     
+    let maintenance = admin_panel.maintenance_scheduler();
+    maintenance.start(Duration::from_secs(30));
+
     info!("All subsystems initialized successfully");
 
+    let server_config = app_config.as_ref().map(|config| config.server.clone());
+    let bind_address = match resolve_bind_address(server_config.as_ref()) {
+        Ok(addr) => addr,
+        Err(e) => {
+            error!("{}", e);
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, e));
+        }
+    };
+
     // Запуск HTTP сервера
     let server = HttpServer::new(move || {
         App::new()
@@ -96,8 +180,14 @@ async fn main() -> std::io::Result<()> {
             .app_data(web::Data::new(pool_manager.clone()))
             .app_data(web::Data::new(raid_manager.clone()))
             .app_data(web::Data::new(metrics.clone()))
+            .app_data(web::Data::new(alert_system.clone()))
             .app_data(web::Data::new(api_server.clone()))
             .app_data(web::Data::new(admin_panel.clone()))
+            .app_data(web::Data::new(maintenance.clone()))
+            .app_data(web::Data::new(events.clone()))
+            .app_data(web::Data::new(crate::SystemConfigFile::default()))
+            .app_data(web::Data::new(admin_config.clone()))
+            .app_data(web::Data::new(shutdown_hook.clone()))
             .wrap(Logger::default())
             .wrap(middleware::DefaultHeaders::new().add(("X-PoolAI-Version", VERSION)))
             .service(
@@ -108,24 +198,42 @@ async fn main() -> std::io::Result<()> {
                     .route("/pool/config", web::put().to(update_pool_config))
                     .route("/workers/add", web::post().to(add_worker))
                     .route("/workers/remove", web::delete().to(remove_worker))
-                    .route("/rewards/stats", web::get().to(get_reward_stats))
+                    .route("/system/config", web::get().to(crate::get_system_config_handler))
+                    .route("/system/config", web::put().to(crate::put_system_config_handler))
+                    .route(
+                        "/rewards/stats",
+                        web::get()
+                            .guard(crate::feature_guard(
+                                crate::features().clone(),
+                                crate::feature_names::REWARD_SYSTEM,
+                            ))
+                            .to(get_reward_stats),
+                    )
                     .route("/maintenance/toggle", web::post().to(toggle_maintenance_mode))
+                    .route("/raid/status", web::get().to(get_raid_status))
+                    .route("/monitoring/alerts", web::get().to(get_active_alerts))
             )
             .service(
                 web::scope("/admin")
+                    .wrap(middleware::from_fn(crate::admin::ip_filter::admin_ip_filter))
                     .route("/system/stats", web::get().to(get_admin_system_stats))
                     .route("/pool/status", web::get().to(get_admin_pool_status))
+                    .route("/system/restart/plan", web::get().to(get_restart_plan))
                     .route("/system/restart", web::post().to(restart_system))
+                    .route("/system/shutdown", web::post().to(shutdown_system))
                     .route("/maintenance/enable", web::post().to(enable_maintenance))
                     .route("/maintenance/disable", web::post().to(disable_maintenance))
+                    .service(crate::admin::admin_panel::list_maintenance_windows)
+                    .service(crate::admin::admin_panel::schedule_maintenance_window)
                     .route("/logs", web::get().to(get_admin_logs))
+                    .route("/self-test", web::post().to(run_admin_self_test))
             )
     })
-    .bind("127.0.0.1:8080")?;
+    .bind(bind_address)?;
 
-    info!("HTTP server started on http://127.0.0.1:8080");
-    info!("API available at http://127.0.0.1:8080/api/v1/status");
-    info!("Admin panel available at http://127.0.0.1:8080/admin");
+    info!("HTTP server started on http://{}", bind_address);
+    info!("API available at http://{}/api/v1/status", bind_address);
+    info!("Admin panel available at http://{}/admin", bind_address);
 
     // Запуск сервера
     server.run().await?;
@@ -137,7 +245,7 @@ async fn get_status() -> impl Responder {
     serde_json::json!({
         "status": "running",
         "version": VERSION,
-        "build_date": BUILD_DATE,
+        "build_date": crate::version::build_timestamp(),
         "features": [
             "GPU/ASIC/CPU optimization",
             "Model integration", 
@@ -152,6 +260,10 @@ async fn get_status() -> impl Responder {
     })
 }
 
+async fn get_active_alerts(alert_system: web::Data<Arc<AlertSystem>>) -> impl Responder {
+    actix_web::HttpResponse::Ok().json(alert_system.active_alerts().await)
+}
+
 // Административные функции
 async fn get_admin_system_stats(
     app_state: web::Data<Arc<AppState>>,
@@ -184,18 +296,30 @@ async fn get_admin_pool_status(
     })
 }
 
+async fn get_restart_plan(
+    pool_manager: web::Data<Arc<PoolManager>>,
+    api_server: web::Data<Arc<ApiServer>>,
+) -> impl Responder {
+    let components = crate::admin::restart::system_components(
+        pool_manager.get_ref().clone(),
+        api_server.get_ref().clone(),
+    );
+    actix_web::HttpResponse::Ok().json(crate::admin::restart::restart_plan(&components).await)
+}
+
 async fn restart_system(
+    params: web::Query<crate::admin::admin_panel::RestartParams>,
     pool_manager: web::Data<Arc<PoolManager>>,
     api_server: web::Data<Arc<ApiServer>>,
+    events: web::Data<Arc<EventBus>>,
 ) -> impl Responder {
-    match restart_system_internal(pool_manager.as_ref(), api_server.as_ref()).await {
-        Ok(_) => serde_json::json!({
-            "status": "system restarted"
-        }),
-        Err(e) => serde_json::json!({
-            "error": e.to_string()
-        }),
-    }
+    crate::admin::admin_panel::restart_system_internal(
+        pool_manager.get_ref().clone(),
+        api_server.get_ref().clone(),
+        events.get_ref().clone(),
+        params.confirm,
+    )
+    .await
 }
 
 async fn enable_maintenance(
@@ -228,20 +352,50 @@ async fn get_admin_logs() -> impl Responder {
     serde_json::json!(logs)
 }
 
-async fn restart_system_internal(
-    pool_manager: &PoolManager,
-    api_server: &ApiServer,
-) -> Result<(), Box<dyn std::error::Error>> {
-    log::info!("Admin: Restarting system");
-    
-    // Остановка компонентов
-    pool_manager.stop().await?;
-    api_server.stop().await?;
-    
-    // Запуск компонентов
-    pool_manager.start().await?;
-    api_server.start().await?;
-    
-    log::info!("Admin: System restarted successfully");
-    Ok(())
-} 
\ No newline at end of file
+async fn run_admin_self_test() -> impl Responder {
+    let report = crate::admin::self_test::run_self_test().await;
+
+    if report.passed {
+        actix_web::HttpResponse::Ok().json(report)
+    } else {
+        actix_web::HttpResponse::InternalServerError().json(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bind_address_defaults_without_config() {
+        let addr = resolve_bind_address(None).unwrap();
+        assert_eq!(addr.to_string(), DEFAULT_BIND_ADDRESS);
+    }
+
+    #[test]
+    fn test_default_config_binds_to_loopback() {
+        let server = AppConfig::default().server;
+        let addr = resolve_bind_address(Some(&server)).unwrap();
+        assert!(addr.ip().is_loopback(), "{}", addr);
+    }
+
+    #[test]
+    fn test_bind_address_rejects_zero_port() {
+        let mut server = AppConfig::default().server;
+        server.http_port = 0;
+        assert!(resolve_bind_address(Some(&server)).unwrap_err().contains("non-zero"));
+    }
+
+    #[actix_web::test]
+    async fn test_server_binds_to_configured_address() {
+        // Свободный порт на loopback
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let mut server = AppConfig::default().server;
+        server.bind_address = "127.0.0.1".parse().unwrap();
+        server.http_port = port;
+
+        let addr = resolve_bind_address(Some(&server)).unwrap();
+        let http = HttpServer::new(|| App::new()).bind(addr).unwrap();
+        assert_eq!(http.addrs(), vec![addr]);
+    }
+}
@@ -8,6 +8,8 @@ use std::time::Duration;
 use cursor_codes::core::error::CursorError;
 use cursor_codes::monitoring::logger::LoggerSystem;
 use cursor_codes::monitoring::metrics::MetricsSystem;
+use crate::admin::maintenance::{Clock, SystemClock};
+use super::metrics::SystemMetrics;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlertConfig {
@@ -51,19 +53,302 @@ pub struct AlertEvent {
     pub metadata: HashMap<String, String>,
 }
 
+/// Метрика `SystemMetrics`, по которой срабатывает правило
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricField {
+    CpuUsage,
+    MemoryUsage,
+    DiskUsage,
+    NetworkUsage,
+    SystemLoad,
+    GpuTemperature,
+}
+
+impl MetricField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetricField::CpuUsage => "cpu_usage",
+            MetricField::MemoryUsage => "memory_usage",
+            MetricField::DiskUsage => "disk_usage",
+            MetricField::NetworkUsage => "network_usage",
+            MetricField::SystemLoad => "system_load",
+            MetricField::GpuTemperature => "gpu_temperature",
+        }
+    }
+
+    /// Значение метрики; `None`, если она сейчас недоступна
+    pub fn read(&self, metrics: &SystemMetrics) -> Option<f64> {
+        match self {
+            MetricField::CpuUsage => Some(metrics.cpu_usage),
+            MetricField::MemoryUsage => Some(metrics.memory_usage),
+            MetricField::DiskUsage => Some(metrics.disk_usage),
+            MetricField::NetworkUsage => Some(metrics.network_usage),
+            MetricField::SystemLoad => Some(metrics.system_load),
+            MetricField::GpuTemperature => metrics.gpu_temperature,
+        }
+    }
+}
+
+impl std::str::FromStr for MetricField {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.trim().to_ascii_lowercase().replace(' ', "_").as_str() {
+            "cpu_usage" | "cpu" => Ok(MetricField::CpuUsage),
+            "memory_usage" | "memory" => Ok(MetricField::MemoryUsage),
+            "disk_usage" | "disk" => Ok(MetricField::DiskUsage),
+            "network_usage" | "network" => Ok(MetricField::NetworkUsage),
+            "system_load" | "load" => Ok(MetricField::SystemLoad),
+            "gpu_temperature" | "gpu_temp" => Ok(MetricField::GpuTemperature),
+            other => Err(format!("Unknown metric: {}", other)),
+        }
+    }
+}
+
+/// Направление сравнения с порогом
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Comparison {
+    Above,
+    Below,
+}
+
+impl Comparison {
+    fn breached(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Above => value > threshold,
+            Comparison::Below => value < threshold,
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            Comparison::Above => ">",
+            Comparison::Below => "<",
+        }
+    }
+}
+
+/// Пороговое правило: алерт поднимается, если условие держится `duration`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub id: String,
+    pub metric: MetricField,
+    pub comparison: Comparison,
+    pub threshold: f64,
+    pub duration: Duration,
+    pub severity: String,
+}
+
+impl AlertRule {
+    /// Разбирает выражение вида `cpu_usage > 90 for 30s` или `gpu temperature > 85`
+    pub fn parse(id: &str, expression: &str, severity: &str) -> Result<Self, String> {
+        let (condition, duration) = match expression.split_once(" for ") {
+            Some((condition, duration)) => (condition, parse_duration(duration.trim())?),
+            None => (expression, Duration::ZERO),
+        };
+
+        let (metric, comparison, threshold) = if let Some((metric, value)) = condition.split_once('>') {
+            (metric, Comparison::Above, value)
+        } else if let Some((metric, value)) = condition.split_once('<') {
+            (metric, Comparison::Below, value)
+        } else {
+            return Err(format!("Expected '>' or '<' in rule: {}", expression));
+        };
+
+        let threshold: f64 = threshold
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid threshold in rule: {}", expression))?;
+        if !threshold.is_finite() {
+            return Err(format!("Invalid threshold in rule: {}", expression));
+        }
+
+        Ok(Self {
+            id: id.to_string(),
+            metric: metric.parse()?,
+            comparison,
+            threshold,
+            duration,
+            severity: severity.to_string(),
+        })
+    }
+
+    pub fn describe(&self) -> String {
+        format!("{} {} {}", self.metric.as_str(), self.comparison.symbol(), self.threshold)
+    }
+}
+
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| format!("Missing unit in duration: {}", value))?;
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("Invalid duration: {}", value))?;
+    let seconds = |multiplier: u64| {
+        amount
+            .checked_mul(multiplier)
+            .map(Duration::from_secs)
+            .ok_or_else(|| format!("Duration out of range: {}", value))
+    };
+    match unit {
+        "ms" => Ok(Duration::from_millis(amount)),
+        "s" => seconds(1),
+        "m" => seconds(60),
+        "h" => seconds(3600),
+        _ => Err(format!("Unknown duration unit: {}", value)),
+    }
+}
+
+/// Как часто фоновая проверка сверяет правила с метриками
+pub const ALERT_EVALUATION_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Правило в конфигурации: `rule` в формате `AlertRule::parse`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRuleConfig {
+    pub id: String,
+    pub rule: String,
+    pub severity: String,
+}
+
+/// Сработавший алерт
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveAlert {
+    pub rule_id: String,
+    pub severity: String,
+    pub message: String,
+    pub value: f64,
+    pub since: DateTime<Utc>,
+}
+
+struct RuleState {
+    rule: AlertRule,
+    breach_started: Option<DateTime<Utc>>,
+    active: Option<ActiveAlert>,
+}
+
 pub struct AlertSystem {
     alerts: Arc<Mutex<HashMap<String, AlertMetrics>>>,
     events: Arc<Mutex<HashMap<String, AlertEvent>>>,
+    rules: Arc<Mutex<HashMap<String, RuleState>>>,
+    clock: Arc<dyn Clock>,
 }
 
 impl AlertSystem {
     pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
         Self {
             alerts: Arc::new(Mutex::new(HashMap::new())),
             events: Arc::new(Mutex::new(HashMap::new())),
+            rules: Arc::new(Mutex::new(HashMap::new())),
+            clock,
         }
     }
 
+    /// Регистрирует правило; правило с тем же id заменяется
+    pub async fn add_rule(&self, rule: AlertRule) {
+        info!("Added alert rule {}: {} for {:?}", rule.id, rule.describe(), rule.duration);
+        self.rules.lock().await.insert(
+            rule.id.clone(),
+            RuleState {
+                rule,
+                breach_started: None,
+                active: None,
+            },
+        );
+    }
+
+    /// Разбирает и регистрирует правила из конфигурации; при ошибке в любом
+    /// правиле не регистрируется ни одно
+    pub async fn load_rules(&self, configs: &[AlertRuleConfig]) -> Result<(), String> {
+        let rules = configs
+            .iter()
+            .map(|config| AlertRule::parse(&config.id, &config.rule, &config.severity))
+            .collect::<Result<Vec<_>, _>>()?;
+        for rule in rules {
+            self.add_rule(rule).await;
+        }
+        Ok(())
+    }
+
+    pub async fn remove_rule(&self, id: &str) -> bool {
+        self.rules.lock().await.remove(id).is_some()
+    }
+
+    /// Проверяет правила по текущим метрикам: поднимает алерты, условие
+    /// которых держится дольше `duration`, и снимает восстановившиеся
+    pub async fn evaluate(&self, metrics: &SystemMetrics) {
+        let now = self.clock.now();
+        let mut rules = self.rules.lock().await;
+
+        for state in rules.values_mut() {
+            let value = state.rule.metric.read(metrics);
+            let breached = value.map_or(false, |v| state.rule.comparison.breached(v, state.rule.threshold));
+
+            if !breached {
+                state.breach_started = None;
+                if let Some(alert) = state.active.take() {
+                    info!("Alert {} resolved: {}", alert.rule_id, state.rule.describe());
+                }
+                continue;
+            }
+
+            let value = value.unwrap_or_default();
+            let started = *state.breach_started.get_or_insert(now);
+            let held = (now - started).to_std().unwrap_or_default();
+
+            match state.active.as_mut() {
+                Some(alert) => alert.value = value,
+                None if held >= state.rule.duration => {
+                    let message = format!(
+                        "{} (current value {:.1})",
+                        state.rule.describe(),
+                        value
+                    );
+                    warn!("Alert {} raised: {}", state.rule.id, message);
+                    state.active = Some(ActiveAlert {
+                        rule_id: state.rule.id.clone(),
+                        severity: state.rule.severity.clone(),
+                        message,
+                        value,
+                        since: now,
+                    });
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// Текущие сработавшие алерты, от старых к новым
+    pub async fn active_alerts(&self) -> Vec<ActiveAlert> {
+        let rules = self.rules.lock().await;
+        let mut alerts: Vec<ActiveAlert> = rules.values().filter_map(|s| s.active.clone()).collect();
+        alerts.sort_by_key(|a| a.since);
+        alerts
+    }
+
+    /// Фоновая проверка правил по разделяемым метрикам
+    pub fn spawn_evaluator(
+        self: Arc<Self>,
+        metrics: Arc<tokio::sync::RwLock<SystemMetrics>>,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let snapshot = metrics.read().await.clone();
+                self.evaluate(&snapshot).await;
+            }
+        })
+    }
+
     pub async fn add_alert(&self, config: AlertConfig) -> Result<(), String> {
         let mut alerts = self.alerts.lock().await;
         
@@ -300,4 +585,98 @@ impl AlertSystem {
         info!("Updated alert configuration: {}", id);
         Ok(())
     }
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::ManualClock;
+
+
+    fn cpu(value: f64) -> SystemMetrics {
+        SystemMetrics { cpu_usage: value, ..SystemMetrics::default() }
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        let rule = AlertRule::parse("cpu", "cpu_usage > 90 for 30s", "critical").unwrap();
+        assert_eq!(rule.metric, MetricField::CpuUsage);
+        assert_eq!(rule.comparison, Comparison::Above);
+        assert_eq!(rule.threshold, 90.0);
+        assert_eq!(rule.duration, Duration::from_secs(30));
+
+        let rule = AlertRule::parse("gpu", "gpu temperature > 85", "warning").unwrap();
+        assert_eq!(rule.metric, MetricField::GpuTemperature);
+        assert_eq!(rule.duration, Duration::ZERO);
+
+        assert!(AlertRule::parse("bad", "fan_speed > 1", "warning").is_err());
+        assert!(AlertRule::parse("bad", "cpu_usage = 1", "warning").is_err());
+        assert!(AlertRule::parse("bad", "cpu_usage > 1 for 5 days", "warning").is_err());
+
+        let err = AlertRule::parse("bad", "cpu_usage > 1 for 18446744073709551615h", "warning").unwrap_err();
+        assert!(err.contains("out of range"), "{}", err);
+        assert!(AlertRule::parse("bad", "cpu_usage > 1 for 99999999999999999999s", "warning").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_load_rules_rejects_invalid_config() {
+        let alerts = AlertSystem::new();
+        let configs = vec![
+            AlertRuleConfig { id: "cpu".to_string(), rule: "cpu_usage > 90".to_string(), severity: "critical".to_string() },
+            AlertRuleConfig { id: "bad".to_string(), rule: "cpu_usage > 1 for 99999999999999999h".to_string(), severity: "warning".to_string() },
+        ];
+        assert!(alerts.load_rules(&configs).await.is_err());
+        alerts.evaluate(&cpu(95.0)).await;
+        assert!(alerts.active_alerts().await.is_empty());
+
+        alerts.load_rules(&configs[..1]).await.unwrap();
+        alerts.evaluate(&cpu(95.0)).await;
+        assert_eq!(alerts.active_alerts().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rule_breach_and_recovery() {
+        let clock = Arc::new(ManualClock::new(Utc::now()));
+        let alerts = AlertSystem::with_clock(clock.clone());
+        alerts
+            .add_rule(AlertRule::parse("cpu_high", "cpu_usage > 90 for 30s", "critical").unwrap())
+            .await;
+
+        // Кратковременный всплеск не поднимает алерт
+        alerts.evaluate(&cpu(95.0)).await;
+        clock.advance(chrono::Duration::seconds(10));
+        alerts.evaluate(&cpu(96.0)).await;
+        assert!(alerts.active_alerts().await.is_empty());
+
+        clock.advance(chrono::Duration::seconds(25));
+        alerts.evaluate(&cpu(97.0)).await;
+        let active = alerts.active_alerts().await;
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].rule_id, "cpu_high");
+        assert_eq!(active[0].severity, "critical");
+        assert_eq!(active[0].value, 97.0);
+
+        // Восстановление снимает алерт и сбрасывает отсчёт
+        clock.advance(chrono::Duration::seconds(5));
+        alerts.evaluate(&cpu(40.0)).await;
+        assert!(alerts.active_alerts().await.is_empty());
+
+        alerts.evaluate(&cpu(99.0)).await;
+        assert!(alerts.active_alerts().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_missing_metric_does_not_fire() {
+        let alerts = AlertSystem::new();
+        alerts
+            .add_rule(AlertRule::parse("gpu_hot", "gpu_temperature > 85", "warning").unwrap())
+            .await;
+
+        alerts.evaluate(&SystemMetrics::default()).await;
+        assert!(alerts.active_alerts().await.is_empty());
+
+        let hot = SystemMetrics { gpu_temperature: Some(90.0), ..SystemMetrics::default() };
+        alerts.evaluate(&hot).await;
+        assert_eq!(alerts.active_alerts().await.len(), 1);
+    }
+}
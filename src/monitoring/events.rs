@@ -0,0 +1,163 @@
+//! Шина событий системы
+//!
+//! Компоненты публикуют события (загрузка модели, изменения воркеров,
+//! масштабирование пулов, отказы дисков RAID), а API отдаёт последние из них.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Сколько событий хранит шина по умолчанию
+pub const DEFAULT_EVENT_BUS_CAPACITY: usize = 1_000;
+
+/// Сколько событий может отстать подписчик, прежде чем пропустит старые
+const SUBSCRIBER_BUFFER: usize = 256;
+
+/// Тип события
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    ModelLoaded,
+    WorkerAdded,
+    WorkerRemoved,
+    PoolCreated,
+    PoolScaled,
+    RaidDiskFailed,
+    ComponentStopped,
+    ComponentStarted,
+}
+
+impl EventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::ModelLoaded => "model_loaded",
+            EventKind::WorkerAdded => "worker_added",
+            EventKind::WorkerRemoved => "worker_removed",
+            EventKind::PoolCreated => "pool_created",
+            EventKind::PoolScaled => "pool_scaled",
+            EventKind::RaidDiskFailed => "raid_disk_failed",
+            EventKind::ComponentStopped => "component_stopped",
+            EventKind::ComponentStarted => "component_started",
+        }
+    }
+}
+
+/// Опубликованное событие
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemEvent {
+    pub id: u64,
+    pub kind: EventKind,
+    pub data: serde_json::Value,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Шина событий на кольцевом буфере: при переполнении вытесняются самые старые
+pub struct EventBus {
+    capacity: usize,
+    next_id: AtomicU64,
+    events: parking_lot::RwLock<VecDeque<SystemEvent>>,
+    subscribers: tokio::sync::broadcast::Sender<SystemEvent>,
+}
+
+impl EventBus {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            next_id: AtomicU64::new(1),
+            events: parking_lot::RwLock::new(VecDeque::with_capacity(capacity.min(1024))),
+            subscribers: tokio::sync::broadcast::channel(SUBSCRIBER_BUFFER).0,
+        }
+    }
+
+    /// Подписка на события, опубликованные после вызова
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<SystemEvent> {
+        self.subscribers.subscribe()
+    }
+
+    /// Публикует событие и возвращает его идентификатор
+    pub fn publish(&self, kind: EventKind, data: serde_json::Value) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut events = self.events.write();
+        if events.len() == self.capacity {
+            events.pop_front();
+        }
+        let event = SystemEvent {
+            id,
+            kind,
+            data,
+            timestamp: Utc::now(),
+        };
+        events.push_back(event.clone());
+        // Рассылка под блокировкой сохраняет порядок событий; без подписчиков ничего не делает
+        let _ = self.subscribers.send(event);
+        log::debug!("Published event {} ({})", id, kind.as_str());
+        id
+    }
+
+    /// До `limit` последних событий новее `since` (все, если `None`),
+    /// в порядке публикации
+    pub fn recent(&self, since: Option<DateTime<Utc>>, limit: usize) -> Vec<SystemEvent> {
+        let events = self.events.read();
+        let mut page: Vec<SystemEvent> = events
+            .iter()
+            .rev()
+            .filter(|event| since.map_or(true, |since| event.timestamp > since))
+            .take(limit)
+            .cloned()
+            .collect();
+        page.reverse();
+        page
+    }
+
+    /// До `limit` событий с идентификатором больше `after`, в порядке публикации.
+    /// Вытесненные из буфера события не возвращаются
+    pub fn after(&self, after: u64, limit: usize) -> Vec<SystemEvent> {
+        self.events
+            .read()
+            .iter()
+            .filter(|event| event.id > after)
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_BUS_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_and_since_filter() {
+        let bus = EventBus::new(3);
+        for i in 0..5 {
+            bus.publish(EventKind::WorkerAdded, serde_json::json!({ "worker": i }));
+        }
+
+        let all = bus.recent(None, 10);
+        assert_eq!(all.iter().map(|e| e.id).collect::<Vec<_>>(), vec![3, 4, 5]);
+
+        let cutoff = all[1].timestamp;
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        bus.publish(EventKind::PoolScaled, serde_json::json!({}));
+
+        let newer = bus.recent(Some(cutoff), 10);
+        assert!(newer.iter().all(|e| e.timestamp > cutoff));
+        assert_eq!(newer.last().unwrap().kind, EventKind::PoolScaled);
+        assert_eq!(bus.recent(None, 1).len(), 1);
+    }
+}
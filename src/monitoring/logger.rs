@@ -2,12 +2,12 @@ use serde::{Serialize, Deserialize};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use log::{info, warn, error};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use chrono::{DateTime, Utc};
 use std::time::Duration;
 use std::fs::{File, OpenOptions};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use cursor_codes::core::error::CursorError;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,11 +48,310 @@ pub struct LogEntry {
     pub level: String,
     pub message: String,
     pub metadata: HashMap<String, String>,
+    /// Воркер, от имени которого сделана запись
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worker_id: Option<String>,
+}
+
+/// Формат вывода логов процесса
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Человекочитаемый текст (env_logger)
+    #[default]
+    Text,
+    /// Одна JSON-запись на строку для систем сбора логов
+    Json,
+}
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+    static WORKER_ID: String;
+}
+
+/// Выполняет future, добавляя `request_id` ко всем записям лога внутри неё
+pub async fn with_request_id<F: std::future::Future>(request_id: String, future: F) -> F::Output {
+    REQUEST_ID.scope(request_id, future).await
+}
+
+/// Идентификатор запроса текущей задачи, если он задан
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Выполняет future, помечая записи лога внутри неё идентификатором воркера
+pub async fn with_worker_id<F: std::future::Future>(worker_id: String, future: F) -> F::Output {
+    WORKER_ID.scope(worker_id, future).await
+}
+
+/// Идентификатор воркера текущей задачи, если он задан
+pub fn current_worker_id() -> Option<String> {
+    WORKER_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Строка лога в JSON-формате (без перевода строки)
+pub fn json_log_line(record: &log::Record, ts: DateTime<Utc>) -> String {
+    let mut line = serde_json::json!({
+        "ts": ts.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        "level": record.level().as_str(),
+        "module": record.module_path().unwrap_or_else(|| record.target()),
+        "msg": record.args().to_string(),
+    });
+    if let Some(request_id) = current_request_id() {
+        line["request_id"] = serde_json::Value::String(request_id);
+    }
+    if let Some(worker_id) = current_worker_id() {
+        line["worker_id"] = serde_json::Value::String(worker_id);
+    }
+    line.to_string()
+}
+
+/// Ёмкость кольцевого буфера логов по умолчанию
+pub const DEFAULT_LOG_BUFFER_CAPACITY: usize = 10_000;
+
+/// Сколько записей может отстать подписчик потока логов
+const LOG_STREAM_CAPACITY: usize = 1024;
+
+/// Кольцевой буфер последних записей лога: при переполнении вытесняются самые старые.
+/// Новые записи также рассылаются подписчикам.
+pub struct LogBuffer {
+    capacity: usize,
+    entries: parking_lot::RwLock<VecDeque<LogEntry>>,
+    subscribers: tokio::sync::broadcast::Sender<LogEntry>,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: parking_lot::RwLock::new(VecDeque::with_capacity(capacity.min(1024))),
+            subscribers: tokio::sync::broadcast::channel(LOG_STREAM_CAPACITY).0,
+        }
+    }
+
+    pub fn push(&self, entry: LogEntry) {
+        let mut entries = self.entries.write();
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry.clone());
+        let _ = self.subscribers.send(entry);
+    }
+
+    /// Подписка на новые записи; отписка - удаление получателя
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<LogEntry> {
+        self.subscribers.subscribe()
+    }
+
+    /// Последние `n` записей воркера и подписка на следующие, без разрыва между ними
+    pub fn subscribe_worker(
+        &self,
+        worker_id: &str,
+        n: usize,
+    ) -> (Vec<LogEntry>, tokio::sync::broadcast::Receiver<LogEntry>) {
+        // Чтение под блокировкой не даёт `push` вставить запись между хвостом и подпиской
+        let entries = self.entries.read();
+        let receiver = self.subscribers.subscribe();
+        let mut tail: Vec<LogEntry> = entries
+            .iter()
+            .rev()
+            .filter(|entry| entry.worker_id.as_deref() == Some(worker_id))
+            .take(n)
+            .cloned()
+            .collect();
+        tail.reverse();
+        (tail, receiver)
+    }
+
+    /// Число активных подписчиков
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.receiver_count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Записи не ниже уровня `level` (все, если `None`), от новых к старым.
+    /// Возвращает общее число подходящих записей и запрошенную страницу.
+    pub fn query(&self, level: Option<log::Level>, offset: usize, limit: usize) -> (usize, Vec<LogEntry>) {
+        let entries = self.entries.read();
+        let matching = entries.iter().rev().filter(|entry| match level {
+            Some(level) => entry
+                .level
+                .parse::<log::Level>()
+                .map_or(false, |entry_level| entry_level <= level),
+            None => true,
+        });
+
+        let mut total = 0;
+        let mut page = Vec::new();
+        for entry in matching {
+            if total >= offset && page.len() < limit {
+                page.push(entry.clone());
+            }
+            total += 1;
+        }
+        (total, page)
+    }
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOG_BUFFER_CAPACITY)
+    }
+}
+
+/// Глобальный логгер процесса: пишет через env_logger и сохраняет каждую
+/// прошедшую фильтр запись в кольцевой буфер
+pub struct BufferedLogger {
+    inner: env_logger::Logger,
+    buffer: Arc<LogBuffer>,
+}
+
+impl BufferedLogger {
+    pub fn new(inner: env_logger::Logger, buffer: Arc<LogBuffer>) -> Self {
+        Self { inner, buffer }
+    }
+
+    fn entry(record: &log::Record) -> LogEntry {
+        let mut metadata = HashMap::new();
+        if let Some(module) = record.module_path() {
+            metadata.insert("module".to_string(), module.to_string());
+        }
+        if let Some(request_id) = current_request_id() {
+            metadata.insert("request_id".to_string(), request_id);
+        }
+        LogEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            logger_id: record.target().to_string(),
+            timestamp: Utc::now(),
+            level: record.level().as_str().to_lowercase(),
+            message: record.args().to_string(),
+            metadata,
+            worker_id: current_worker_id(),
+        }
+    }
+}
+
+impl log::Log for BufferedLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.inner.matches(record) {
+            return;
+        }
+        self.inner.log(record);
+        self.buffer.push(Self::entry(record));
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Файл логов процесса с ротацией по размеру
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogFileConfig {
+    /// Путь к текущему файлу; ротированные получают суффиксы `.1`, `.2`, ...
+    pub path: PathBuf,
+    /// Размер файла в байтах, после которого выполняется ротация
+    pub max_size: u64,
+    /// Сколько ротированных файлов хранить, более старые удаляются
+    pub max_files: u32,
+}
+
+impl Default for LogFileConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("logs/app.log"),
+            max_size: 10 * 1024 * 1024,
+            max_files: 5,
+        }
+    }
+}
+
+/// Запись логов в файл с ротацией: `app.log` -> `app.log.1` -> ... -> `app.log.N`
+pub struct RotatingFileWriter {
+    config: LogFileConfig,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFileWriter {
+    pub fn new(config: LogFileConfig) -> std::io::Result<Self> {
+        if config.max_size == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Log file max_size must be greater than zero",
+            ));
+        }
+        if let Some(parent) = config.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&config.path)?;
+        let size = file.metadata()?.len();
+        Ok(Self { config, file, size })
+    }
+
+    fn rotated_path(&self, index: u32) -> PathBuf {
+        let mut path = self.config.path.clone().into_os_string();
+        path.push(format!(".{}", index));
+        PathBuf::from(path)
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.file.flush()?;
+
+        if self.config.max_files == 0 {
+            self.file = File::create(&self.config.path)?;
+            self.size = 0;
+            return Ok(());
+        }
+
+        let oldest = self.rotated_path(self.config.max_files);
+        if oldest.exists() {
+            std::fs::remove_file(&oldest)?;
+        }
+        for index in (1..self.config.max_files).rev() {
+            let from = self.rotated_path(index);
+            if from.exists() {
+                std::fs::rename(&from, self.rotated_path(index + 1))?;
+            }
+        }
+        std::fs::rename(&self.config.path, self.rotated_path(1))?;
+
+        self.file = OpenOptions::new().create(true).append(true).open(&self.config.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFileWriter {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        if self.size > 0 && self.size + data.len() as u64 > self.config.max_size {
+            self.rotate()?;
+        }
+        let written = self.file.write(data)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
 }
 
 pub struct LoggerSystem {
     loggers: Arc<Mutex<HashMap<String, LoggerMetrics>>>,
     entries: Arc<Mutex<HashMap<String, LogEntry>>>,
+    buffer: Arc<LogBuffer>,
 }
 
 impl LoggerSystem {
@@ -60,7 +359,73 @@ impl LoggerSystem {
         Self {
             loggers: Arc::new(Mutex::new(HashMap::new())),
             entries: Arc::new(Mutex::new(HashMap::new())),
+            buffer: Arc::new(LogBuffer::default()),
+        }
+    }
+
+    /// Устанавливает глобальный логгер процесса в заданном формате и с уровнем
+    /// `level` из конфигурации. Если задан `RUST_LOG`, уровни берутся из него.
+    /// Записи также попадают в буфер `buffer()`.
+    pub fn init(&self, format: LogFormat, level: &str) {
+        self.install(Self::builder(format), level);
+    }
+
+    /// Устанавливает глобальный логгер с записью в файл с ротацией.
+    /// Если файл открыть нельзя, логи пишутся в stderr.
+    pub fn init_with_file(&self, format: LogFormat, level: &str, file: &LogFileConfig) {
+        let mut builder = Self::builder(format);
+        match RotatingFileWriter::new(file.clone()) {
+            Ok(writer) => {
+                builder.target(env_logger::Target::Pipe(Box::new(writer)));
+            }
+            Err(e) => eprintln!("Failed to open log file {}: {}", file.path.display(), e),
+        }
+        self.install(builder, level);
+    }
+
+    fn install(&self, mut builder: env_logger::Builder, level: &str) {
+        let configured = Self::apply_level(&mut builder, level, std::env::var_os("RUST_LOG").is_some());
+        let inner = builder.build();
+        let max_level = configured.unwrap_or_else(|| inner.filter());
+        match log::set_boxed_logger(Box::new(BufferedLogger::new(inner, self.buffer.clone()))) {
+            Ok(()) => log::set_max_level(max_level),
+            Err(e) => warn!("Logger already initialized: {}", e),
+        }
+    }
+
+    /// Уровень процесса. Без `RUST_LOG` env_logger пропускает всё, а уровень
+    /// из конфигурации задаёт `log::max_level`, который меняет горячая
+    /// перезагрузка. С `RUST_LOG` фильтрует env_logger, и уровня нет.
+    fn apply_level(builder: &mut env_logger::Builder, level: &str, rust_log_set: bool) -> Option<log::LevelFilter> {
+        if rust_log_set {
+            return None;
+        }
+        builder.filter_level(log::LevelFilter::Trace);
+        Some(level.parse().unwrap_or_else(|_| {
+            eprintln!("Unknown log_level '{}', using info", level);
+            log::LevelFilter::Info
+        }))
+    }
+
+    /// Настроенный построитель логгера; вывод можно перенаправить через `target`
+    pub fn builder(format: LogFormat) -> env_logger::Builder {
+        let mut builder = env_logger::Builder::from_env(
+            env_logger::Env::default().default_filter_or("info"),
+        );
+        match format {
+            LogFormat::Text => {
+                builder.format_timestamp_millis();
+            }
+            LogFormat::Json => {
+                builder.format(|buf, record| writeln!(buf, "{}", json_log_line(record, Utc::now())));
+            }
         }
+        builder
+    }
+
+    /// Кольцевой буфер всех записанных сообщений
+    pub fn buffer(&self) -> Arc<LogBuffer> {
+        self.buffer.clone()
     }
 
     pub async fn add_logger(&self, config: LoggerConfig) -> Result<(), String> {
@@ -134,9 +499,11 @@ impl LoggerSystem {
             level: level.to_string(),
             message: message.to_string(),
             metadata,
+            worker_id: current_worker_id(),
         };
 
         entries.insert(entry.id.clone(), entry.clone());
+        self.buffer.push(entry.clone());
         logger.stats.total_logs += 1;
         logger.stats.last_log_time = Some(entry.timestamp);
 
@@ -250,4 +617,218 @@ impl LoggerSystem {
         info!("Updated logger configuration: {}", id);
         Ok(())
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writer, сохраняющий вывод в общий буфер
+    #[derive(Clone, Default)]
+    struct CaptureWriter(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl Write for CaptureWriter {
+        fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(data);
+            Ok(data.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn emit(logger: &env_logger::Logger, level: log::Level, message: &str) {
+        log::Log::log(
+            logger,
+            &log::Record::builder()
+                .level(level)
+                .target("poolai::test")
+                .module_path(Some("poolai::monitoring::logger"))
+                .args(format_args!("{}", message))
+                .build(),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_json_format_writes_one_object_per_line() {
+        let capture = CaptureWriter::default();
+        let logger = LoggerSystem::builder(LogFormat::Json)
+            .filter_level(log::LevelFilter::Info)
+            .target(env_logger::Target::Pipe(Box::new(capture.clone())))
+            .build();
+
+        emit(&logger, log::Level::Info, "plain");
+        with_request_id("req-42".to_string(), async {
+            emit(&logger, log::Level::Warn, "with \"quotes\"\nand newline");
+        })
+        .await;
+        log::Log::flush(&logger);
+
+        let output = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("valid JSON line"))
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["level"], "INFO");
+        assert_eq!(lines[0]["module"], "poolai::monitoring::logger");
+        assert_eq!(lines[0]["msg"], "plain");
+        assert!(lines[0]["ts"].is_string());
+        assert!(lines[0].get("request_id").is_none());
+
+        assert_eq!(lines[1]["level"], "WARN");
+        assert_eq!(lines[1]["msg"], "with \"quotes\"\nand newline");
+        assert_eq!(lines[1]["request_id"], "req-42");
+    }
+
+    #[test]
+    fn test_rotating_writer_keeps_max_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        let config = LogFileConfig { path: path.clone(), max_size: 100, max_files: 2 };
+        let logger = LoggerSystem::builder(LogFormat::Text)
+            .filter_level(log::LevelFilter::Info)
+            .target(env_logger::Target::Pipe(Box::new(RotatingFileWriter::new(config).unwrap())))
+            .build();
+
+        for i in 0..50 {
+            emit(&logger, log::Level::Info, &format!("message number {}", i));
+        }
+        log::Log::flush(&logger);
+
+        let mut names: Vec<String> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["app.log", "app.log.1", "app.log.2"]);
+
+        for name in &names {
+            let len = std::fs::metadata(dir.path().join(name)).unwrap().len();
+            assert!(len <= 100, "{} has {} bytes", name, len);
+        }
+        let current = std::fs::read_to_string(&path).unwrap();
+        assert!(current.contains("message number 49"));
+    }
+
+    #[tokio::test]
+    async fn test_buffered_logger_fills_buffer() {
+        let buffer = Arc::new(LogBuffer::new(16));
+        let logger = BufferedLogger::new(
+            LoggerSystem::builder(LogFormat::Text)
+                .filter_level(log::LevelFilter::Info)
+                .target(env_logger::Target::Pipe(Box::new(CaptureWriter::default())))
+                .build(),
+            buffer.clone(),
+        );
+
+        log::Log::log(
+            &logger,
+            &log::Record::builder()
+                .level(log::Level::Debug)
+                .args(format_args!("filtered out"))
+                .build(),
+        );
+        with_worker_id("w1".to_string(), async {
+            log::Log::log(
+                &logger,
+                &log::Record::builder()
+                    .level(log::Level::Warn)
+                    .target("poolai::test")
+                    .args(format_args!("disk almost full"))
+                    .build(),
+            );
+        })
+        .await;
+
+        let (total, entries) = buffer.query(None, 0, 10);
+        assert_eq!(total, 1);
+        assert_eq!(entries[0].message, "disk almost full");
+        assert_eq!(entries[0].level, "warn");
+        assert_eq!(entries[0].logger_id, "poolai::test");
+        assert_eq!(entries[0].worker_id.as_deref(), Some("w1"));
+    }
+
+    #[test]
+    fn test_configured_level_applied_without_rust_log() {
+        let mut builder = LoggerSystem::builder(LogFormat::Text);
+        let level = LoggerSystem::apply_level(&mut builder, "debug", false);
+        assert_eq!(level, Some(log::LevelFilter::Debug));
+        assert_eq!(builder.build().filter(), log::LevelFilter::Trace);
+
+        let mut builder = LoggerSystem::builder(LogFormat::Text);
+        assert_eq!(
+            LoggerSystem::apply_level(&mut builder, "verbose", false),
+            Some(log::LevelFilter::Info)
+        );
+
+        // Уровни из RUST_LOG не перекрываются конфигурацией
+        let mut builder = LoggerSystem::builder(LogFormat::Text);
+        builder.parse_filters("warn");
+        assert_eq!(LoggerSystem::apply_level(&mut builder, "debug", true), None);
+        assert_eq!(builder.build().filter(), log::LevelFilter::Warn);
+    }
+
+    fn entry(i: usize) -> LogEntry {
+        let level = match i % 3 {
+            0 => "error",
+            1 => "warn",
+            _ => "info",
+        };
+        LogEntry {
+            id: i.to_string(),
+            logger_id: "test".to_string(),
+            timestamp: Utc::now(),
+            level: level.to_string(),
+            message: format!("message {}", i),
+            metadata: HashMap::new(),
+            worker_id: None,
+        }
+    }
+
+    #[test]
+    fn test_log_buffer_filters_and_paginates() {
+        let buffer = LogBuffer::new(100);
+        for i in 0..50 {
+            buffer.push(entry(i));
+        }
+
+        let (total, page) = buffer.query(None, 0, 10);
+        assert_eq!(total, 50);
+        assert_eq!(page.len(), 10);
+        assert_eq!(page[0].message, "message 49");
+
+        let (total, page) = buffer.query(None, 45, 10);
+        assert_eq!(total, 50);
+        let ids: Vec<_> = page.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["4", "3", "2", "1", "0"]);
+
+        // 17 записей error (0, 3, ..., 48)
+        let (total, page) = buffer.query(Some(log::Level::Error), 5, 5);
+        assert_eq!(total, 17);
+        assert!(page.iter().all(|e| e.level == "error"));
+        assert_eq!(page[0].id, "33");
+
+        // warn включает error: 17 + 17
+        let (total, _) = buffer.query(Some(log::Level::Warn), 0, 100);
+        assert_eq!(total, 34);
+
+        let (total, page) = buffer.query(None, 60, 10);
+        assert_eq!(total, 50);
+        assert!(page.is_empty());
+    }
+
+    #[test]
+    fn test_log_buffer_evicts_oldest() {
+        let buffer = LogBuffer::new(10);
+        for i in 0..50 {
+            buffer.push(entry(i));
+        }
+
+        assert_eq!(buffer.len(), 10);
+        let (_, page) = buffer.query(None, 9, 1);
+        assert_eq!(page[0].id, "40");
+    }
+}
@@ -7,6 +7,7 @@ use chrono::{DateTime, Utc};
 use std::time::Duration;
 use cursor_codes::core::error::CursorError;
 use cursor_codes::monitoring::logger::LoggerSystem;
+use crate::workers::WorkerStatus;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricConfig {
@@ -47,6 +48,130 @@ pub struct Sample {
     pub labels: HashMap<String, String>,
 }
 
+/// Сводные метрики системы и пула
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SystemMetrics {
+    /// Загрузка CPU, %
+    pub cpu_usage: f64,
+    /// Использование памяти, %
+    pub memory_usage: f64,
+    /// Использование диска, %
+    pub disk_usage: f64,
+    /// Загрузка сети, %
+    pub network_usage: f64,
+    /// Средняя загрузка системы (load average)
+    pub system_load: f64,
+    pub uptime: Duration,
+    pub total_workers: u32,
+    pub active_workers: u32,
+    pub total_hashrate: f64,
+    pub active_tasks: u64,
+    pub queue_size: u64,
+    /// Максимальная температура GPU, °C (если GPU доступен)
+    #[serde(default)]
+    pub gpu_temperature: Option<f64>,
+}
+
+impl SystemMetrics {
+    /// Проценты в пределах 0..=100, остальные значения конечны и неотрицательны
+    pub fn is_valid(&self) -> bool {
+        let percent = |v: f64| v.is_finite() && (0.0..=100.0).contains(&v);
+        percent(self.cpu_usage)
+            && percent(self.memory_usage)
+            && percent(self.disk_usage)
+            && percent(self.network_usage)
+            && self.system_load.is_finite()
+            && self.system_load >= 0.0
+            && self.total_hashrate.is_finite()
+            && self.total_hashrate >= 0.0
+            && self.active_workers <= self.total_workers
+    }
+}
+
+/// Метрики одного воркера
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerMetrics {
+    pub cpu_usage: f64,
+    pub memory_usage: f64,
+    pub gpu_usage: f64,
+    pub hashrate: f64,
+    pub uptime: Duration,
+    pub status: WorkerStatus,
+    /// Температура GPU воркера, °C, если воркер её сообщает
+    #[serde(default)]
+    pub gpu_temperature: Option<f64>,
+}
+
+/// Сводка по набору воркеров
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkerSummary {
+    pub total_workers: usize,
+    /// Число воркеров в каждом статусе
+    pub by_status: HashMap<WorkerStatus, usize>,
+    pub total_hashrate: f64,
+    pub total_cpu_usage: f64,
+    pub total_memory_usage: f64,
+    pub total_gpu_usage: f64,
+}
+
+impl WorkerSummary {
+    pub fn count(&self, status: &WorkerStatus) -> usize {
+        self.by_status.get(status).copied().unwrap_or(0)
+    }
+}
+
+impl WorkerMetrics {
+    /// Считает воркеров по статусам и суммирует хешрейт и загрузку ресурсов
+    pub fn summarize(metrics: &HashMap<String, WorkerMetrics>) -> WorkerSummary {
+        metrics.values().fold(WorkerSummary::default(), |mut summary, worker| {
+            summary.total_workers += 1;
+            *summary.by_status.entry(worker.status.clone()).or_insert(0) += 1;
+            summary.total_hashrate += worker.hashrate;
+            summary.total_cpu_usage += worker.cpu_usage;
+            summary.total_memory_usage += worker.memory_usage;
+            summary.total_gpu_usage += worker.gpu_usage;
+            summary
+        })
+    }
+}
+
+/// Формирует метрики в текстовом формате Prometheus
+pub fn to_prometheus(metrics: &SystemMetrics) -> String {
+    use std::fmt::Write;
+
+    let gauges: [(&str, &str, f64); 11] = [
+        ("system_cpu_usage", "CPU usage, percent", metrics.cpu_usage),
+        ("system_memory_usage", "Memory usage, percent", metrics.memory_usage),
+        ("system_disk_usage", "Disk usage, percent", metrics.disk_usage),
+        ("system_network_usage", "Network usage, percent", metrics.network_usage),
+        ("system_load", "System load average", metrics.system_load),
+        ("system_uptime_seconds", "Process uptime in seconds", metrics.uptime.as_secs_f64()),
+        ("pool_total_workers", "Workers registered across all pools", metrics.total_workers as f64),
+        ("pool_active_workers", "Active workers across all pools", metrics.active_workers as f64),
+        ("pool_total_hashrate", "Combined hashrate of all pools", metrics.total_hashrate),
+        ("pool_active_tasks", "Tasks currently executing", metrics.active_tasks as f64),
+        ("pool_queue_size", "Tasks waiting in queues", metrics.queue_size as f64),
+    ];
+
+    let mut output = String::new();
+    for (name, help, value) in gauges {
+        let _ = writeln!(output, "# HELP {} {}", name, help);
+        let _ = writeln!(output, "# TYPE {} gauge", name);
+        let _ = writeln!(output, "{} {}", name, prometheus_value(value));
+    }
+    output
+}
+
+fn prometheus_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf".to_string() } else { "-Inf".to_string() }
+    } else {
+        value.to_string()
+    }
+}
+
 pub struct MetricsSystem {
     metrics: Arc<Mutex<HashMap<String, MetricMetrics>>>,
     samples: Arc<Mutex<HashMap<String, Sample>>>,
@@ -242,4 +367,96 @@ impl MetricsSystem {
 
         Ok(result)
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn is_metric_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == ':')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+    }
+
+    #[test]
+    fn test_prometheus_output_is_well_formed() {
+        let metrics = SystemMetrics {
+            cpu_usage: 42.5,
+            memory_usage: 61.0,
+            uptime: Duration::from_secs(3600),
+            total_workers: 8,
+            active_workers: 6,
+            total_hashrate: 1250.75,
+            queue_size: 3,
+            ..SystemMetrics::default()
+        };
+        let output = to_prometheus(&metrics);
+        assert!(!output.is_empty());
+
+        let mut typed = HashSet::new();
+        let mut samples = HashMap::new();
+        for line in output.lines() {
+            if let Some(rest) = line.strip_prefix("# HELP ") {
+                let (name, help) = rest.split_once(' ').expect("HELP without text");
+                assert!(is_metric_name(name), "{}", line);
+                assert!(!help.is_empty());
+            } else if let Some(rest) = line.strip_prefix("# TYPE ") {
+                let (name, kind) = rest.split_once(' ').expect("TYPE without kind");
+                assert!(is_metric_name(name), "{}", line);
+                assert!(["counter", "gauge", "histogram", "summary", "untyped"].contains(&kind));
+                typed.insert(name.to_string());
+            } else {
+                let (name, value) = line.split_once(' ').expect("sample without value");
+                assert!(is_metric_name(name), "{}", line);
+                assert!(typed.contains(name), "sample {} has no TYPE", name);
+                samples.insert(name.to_string(), value.parse::<f64>().expect("invalid value"));
+            }
+        }
+
+        assert_eq!(samples["system_cpu_usage"], 42.5);
+        assert_eq!(samples["pool_active_workers"], 6.0);
+        assert_eq!(samples["pool_total_hashrate"], 1250.75);
+        assert_eq!(samples["system_uptime_seconds"], 3600.0);
+    }
+
+    #[test]
+    fn test_worker_summary_counts_by_status() {
+        let worker = |status: WorkerStatus, hashrate: f64, cpu: f64| WorkerMetrics {
+            cpu_usage: cpu,
+            memory_usage: 25.0,
+            gpu_usage: 50.0,
+            hashrate,
+            uptime: Duration::from_secs(60),
+            status,
+            gpu_temperature: None,
+        };
+        let metrics: HashMap<String, WorkerMetrics> = vec![
+            ("a", worker(WorkerStatus::Active, 100.0, 10.0)),
+            ("b", worker(WorkerStatus::Active, 150.0, 20.0)),
+            ("c", worker(WorkerStatus::Busy, 200.0, 90.0)),
+            ("d", worker(WorkerStatus::Error, 0.0, 0.0)),
+            ("e", worker(WorkerStatus::Maintenance, 0.0, 5.0)),
+            ("f", worker(WorkerStatus::Active, 50.0, 15.0)),
+        ]
+        .into_iter()
+        .map(|(id, m)| (id.to_string(), m))
+        .collect();
+
+        let summary = WorkerMetrics::summarize(&metrics);
+
+        assert_eq!(summary.total_workers, 6);
+        assert_eq!(summary.count(&WorkerStatus::Active), 3);
+        assert_eq!(summary.count(&WorkerStatus::Busy), 1);
+        assert_eq!(summary.count(&WorkerStatus::Error), 1);
+        assert_eq!(summary.count(&WorkerStatus::Maintenance), 1);
+        assert_eq!(summary.count(&WorkerStatus::Inactive), 0);
+        assert_eq!(summary.total_hashrate, 500.0);
+        assert_eq!(summary.total_cpu_usage, 140.0);
+        assert_eq!(summary.total_memory_usage, 150.0);
+        assert_eq!(summary.total_gpu_usage, 300.0);
+
+        assert_eq!(WorkerMetrics::summarize(&HashMap::new()), WorkerSummary::default());
+    }
+}
@@ -2,11 +2,15 @@ pub mod alert;
 pub mod metrics;
 pub mod logger;
 pub mod monitor;
+pub mod events;
+pub mod webhook;
 
 pub use alert::*;
 pub use metrics::*;
 pub use logger::*;
 pub use monitor::*;
+pub use events::*;
+pub use webhook::*;
 
 use std::error::Error;
 
@@ -0,0 +1,387 @@
+//! Исходящие уведомления о событиях
+//!
+//! `WebhookNotifier` подписывается на `EventBus` и отправляет выбранные события
+//! POST-запросом с JSON-телом. Тело подписывается HMAC-SHA256 секретом из
+//! конфигурации, подпись передаётся в заголовке `X-Signature`. Ответы 5xx и
+//! сетевые ошибки повторяются с экспоненциальной задержкой; недоставленные
+//! события, в том числе пропущенные отставшим подписчиком, попадают в лог
+//! недоставленных (dead letters).
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+
+use super::events::{EventBus, EventKind, SystemEvent};
+
+/// Заголовок с подписью тела запроса
+pub const SIGNATURE_HEADER: &str = "X-Signature";
+
+/// Сколько недоставленных событий хранится в памяти
+const DEAD_LETTER_CAPACITY: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// Секрет для HMAC-SHA256 подписи
+    pub secret: String,
+    /// Повторов после первой неудачной попытки
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Задержка перед первым повтором; удваивается с каждым следующим
+    #[serde(default = "default_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Отправляемые события
+    #[serde(default = "default_webhook_events")]
+    pub events: Vec<EventKind>,
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_retry_backoff_ms() -> u64 {
+    500
+}
+
+fn default_timeout_secs() -> u64 {
+    10
+}
+
+fn default_webhook_events() -> Vec<EventKind> {
+    vec![EventKind::WorkerAdded, EventKind::WorkerRemoved, EventKind::RaidDiskFailed]
+}
+
+/// Ошибка доставки уведомления
+#[derive(Debug, thiserror::Error)]
+pub enum DeliveryError {
+    #[error("Webhook responded with status {0}")]
+    Status(u16),
+    #[error("Webhook request failed: {0}")]
+    Transport(String),
+    #[error("Failed to serialize event: {0}")]
+    Serialize(String),
+    #[error("Notifier lagged behind the event bus, {0} events skipped")]
+    Lagged(u64),
+}
+
+impl DeliveryError {
+    /// Ответы 5xx и сетевые ошибки могут пройти при повторе, 4xx - нет
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            DeliveryError::Status(status) => *status >= 500,
+            DeliveryError::Transport(_) => true,
+            DeliveryError::Serialize(_) | DeliveryError::Lagged(_) => false,
+        }
+    }
+}
+
+/// Событие, которое не удалось доставить
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadLetter {
+    pub event: SystemEvent,
+    pub attempts: u32,
+    pub error: String,
+    pub failed_at: DateTime<Utc>,
+}
+
+/// Подпись тела: `sha256=<hex>`
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    format!("sha256={}", hex::encode(hmac::sign(&key, body).as_ref()))
+}
+
+/// Проверка подписи за постоянное время (для получателей и тестов)
+pub fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Some(tag) = signature
+        .strip_prefix("sha256=")
+        .and_then(|hex_tag| hex::decode(hex_tag).ok())
+    else {
+        return false;
+    };
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hmac::verify(&key, body, &tag).is_ok()
+}
+
+pub struct WebhookNotifier {
+    config: WebhookConfig,
+    client: reqwest::Client,
+    dead_letters: parking_lot::Mutex<VecDeque<DeadLetter>>,
+}
+
+impl WebhookNotifier {
+    pub fn new(config: WebhookConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs.max(1)))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            config,
+            client,
+            dead_letters: parking_lot::Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Отправляет подписанные события из шины, пока она существует.
+    /// События, пропущенные из-за отставания, берутся из истории шины
+    /// и записываются в dead letters
+    pub fn spawn(self: Arc<Self>, bus: &Arc<EventBus>) -> tokio::task::JoinHandle<()> {
+        let mut last_seen = bus.recent(None, 1).last().map_or(0, |event| event.id);
+        let mut events = bus.subscribe();
+        let bus = Arc::downgrade(bus);
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => {
+                        last_seen = event.id;
+                        if self.config.events.contains(&event.kind) {
+                            let _ = self.deliver(&event).await;
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        log::warn!("Webhook notifier lagging, skipped {} events", skipped);
+                        let Some(bus) = bus.upgrade() else {
+                            continue;
+                        };
+                        let missed = bus.after(last_seen, skipped as usize);
+                        if let Some(event) = missed.last() {
+                            last_seen = event.id;
+                        }
+                        for event in missed.iter().filter(|event| self.config.events.contains(&event.kind)) {
+                            self.dead_letter(event, 0, &DeliveryError::Lagged(skipped));
+                        }
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Доставляет событие с повторами; после исчерпания попыток событие
+    /// записывается в dead letters
+    pub async fn deliver(&self, event: &SystemEvent) -> Result<(), DeliveryError> {
+        let mut attempts = 0;
+        let result = match serde_json::to_vec(event) {
+            Ok(body) => loop {
+                attempts += 1;
+                match self.send(&body).await {
+                    Ok(()) => break Ok(()),
+                    Err(e) if e.is_retryable() && attempts <= self.config.max_retries => {
+                        let delay = self.retry_delay(attempts);
+                        log::warn!(
+                            "Webhook delivery of event {} failed ({}), retry {}/{} in {:?}",
+                            event.id, e, attempts, self.config.max_retries, delay
+                        );
+                        tokio::time::sleep(delay).await;
+                    }
+                    Err(e) => break Err(e),
+                }
+            },
+            Err(e) => Err(DeliveryError::Serialize(e.to_string())),
+        };
+
+        if let Err(e) = &result {
+            self.dead_letter(event, attempts, e);
+        }
+        result
+    }
+
+    async fn send(&self, body: &[u8]) -> Result<(), DeliveryError> {
+        let response = self
+            .client
+            .post(&self.config.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, sign_payload(&self.config.secret, body))
+            .body(body.to_vec())
+            .send()
+            .await
+            .map_err(|e| DeliveryError::Transport(e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(DeliveryError::Status(status.as_u16()))
+        }
+    }
+
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        let factor = 2u64.saturating_pow(attempt.saturating_sub(1));
+        Duration::from_millis(self.config.retry_backoff_ms.saturating_mul(factor))
+    }
+
+    fn dead_letter(&self, event: &SystemEvent, attempts: u32, error: &DeliveryError) {
+        log::error!(
+            "Webhook dead letter: event {} ({}) not delivered after {} attempts: {}; payload: {}",
+            event.id,
+            event.kind.as_str(),
+            attempts,
+            error,
+            event.data
+        );
+
+        let mut dead_letters = self.dead_letters.lock();
+        if dead_letters.len() == DEAD_LETTER_CAPACITY {
+            dead_letters.pop_front();
+        }
+        dead_letters.push_back(DeadLetter {
+            event: event.clone(),
+            attempts,
+            error: error.to_string(),
+            failed_at: Utc::now(),
+        });
+    }
+
+    /// Недоставленные события, от старых к новым
+    pub fn dead_letters(&self) -> Vec<DeadLetter> {
+        self.dead_letters.lock().iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Bytes, extract::State, http::{HeaderMap, StatusCode}, routing::post, Router};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    const SECRET: &str = "webhook-secret";
+
+    /// Получатель, отвечающий 500 на первые `failures` запросов
+    #[derive(Clone, Default)]
+    struct Receiver {
+        failures: u32,
+        attempts: Arc<AtomicU32>,
+        delivered: Arc<parking_lot::Mutex<Vec<(Option<String>, Bytes)>>>,
+    }
+
+    async fn receive(State(receiver): State<Receiver>, headers: HeaderMap, body: Bytes) -> StatusCode {
+        let attempt = receiver.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+        if attempt <= receiver.failures {
+            return StatusCode::INTERNAL_SERVER_ERROR;
+        }
+        let signature = headers
+            .get(SIGNATURE_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        receiver.delivered.lock().push((signature, body));
+        StatusCode::OK
+    }
+
+    async fn start_receiver(receiver: Receiver) -> String {
+        let app = Router::new().route("/hook", post(receive)).with_state(receiver);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        format!("http://{}/hook", addr)
+    }
+
+    fn config(url: String, max_retries: u32) -> WebhookConfig {
+        WebhookConfig {
+            url,
+            secret: SECRET.to_string(),
+            max_retries,
+            retry_backoff_ms: 10,
+            timeout_secs: 5,
+            events: default_webhook_events(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribed_events_are_signed() {
+        let receiver = Receiver::default();
+        let url = start_receiver(receiver.clone()).await;
+        let bus = Arc::new(EventBus::default());
+        let notifier = Arc::new(WebhookNotifier::new(config(url, 0)));
+        notifier.clone().spawn(&bus);
+
+        bus.publish(EventKind::PoolCreated, serde_json::json!({ "pool": "ignored" }));
+        bus.publish(EventKind::WorkerAdded, serde_json::json!({ "worker": "w1" }));
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while receiver.delivered.lock().is_empty() {
+            assert!(tokio::time::Instant::now() < deadline, "webhook was not delivered");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let delivered = receiver.delivered.lock().clone();
+        assert_eq!(delivered.len(), 1);
+        let (signature, body) = &delivered[0];
+        let signature = signature.as_deref().expect("signature header missing");
+        assert!(verify_signature(SECRET, body, signature));
+        assert!(!verify_signature("other-secret", body, signature));
+
+        let event: SystemEvent = serde_json::from_slice(body).unwrap();
+        assert_eq!(event.kind, EventKind::WorkerAdded);
+        assert_eq!(event.data["worker"], "w1");
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_are_retried() {
+        let receiver = Receiver { failures: 2, ..Default::default() };
+        let url = start_receiver(receiver.clone()).await;
+        let notifier = WebhookNotifier::new(config(url, 3));
+        let bus = EventBus::default();
+        bus.publish(EventKind::RaidDiskFailed, serde_json::json!({ "disk": "d1" }));
+        let event = bus.recent(None, 1).remove(0);
+
+        notifier.deliver(&event).await.unwrap();
+
+        assert_eq!(receiver.attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(receiver.delivered.lock().len(), 1);
+        assert!(notifier.dead_letters().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_retries_go_to_dead_letters() {
+        let receiver = Receiver { failures: u32::MAX, ..Default::default() };
+        let url = start_receiver(receiver.clone()).await;
+        let notifier = WebhookNotifier::new(config(url, 2));
+        let bus = EventBus::default();
+        bus.publish(EventKind::WorkerRemoved, serde_json::json!({ "worker": "w1" }));
+        let event = bus.recent(None, 1).remove(0);
+
+        let result = notifier.deliver(&event).await;
+
+        assert!(matches!(result, Err(DeliveryError::Status(500))));
+        assert_eq!(receiver.attempts.load(Ordering::SeqCst), 3);
+        let dead_letters = notifier.dead_letters();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].attempts, 3);
+        assert_eq!(dead_letters[0].event.id, event.id);
+    }
+
+    #[tokio::test]
+    async fn test_lagged_events_go_to_dead_letters() {
+        let receiver = Receiver::default();
+        let url = start_receiver(receiver.clone()).await;
+        let bus = Arc::new(EventBus::default());
+        let notifier = Arc::new(WebhookNotifier::new(config(url, 0)));
+        notifier.clone().spawn(&bus);
+
+        // Публикуем больше, чем вмещает буфер подписчика, не давая ему читать
+        let total = 300;
+        for i in 0..total {
+            bus.publish(EventKind::WorkerAdded, serde_json::json!({ "worker": i }));
+        }
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+        while receiver.delivered.lock().len() + notifier.dead_letters().len() < total {
+            assert!(tokio::time::Instant::now() < deadline, "events were neither delivered nor dead-lettered");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let dead_letters = notifier.dead_letters();
+        assert!(!dead_letters.is_empty());
+        assert!(dead_letters.iter().all(|d| d.attempts == 0 && d.error.contains("lagged")));
+        assert_eq!(dead_letters[0].event.data["worker"], 0);
+    }
+}
@@ -7,18 +7,31 @@
 //! - Rate limiting
 
 use crate::core::model_interface::{
-    ModelInterface, ModelRequest, ModelResponse, ModelInfo, ModelConfig, ModelMetrics
+    ModelInterface, ModelRequest, ModelResponse, ModelInfo, ModelConfig, ModelMetrics,
+    InferenceDefaults, TokenChunk,
 };
 use crate::core::error::AppError;
 use crate::monitoring::metrics::SystemMetrics;
-use crate::pool::worker::WorkerStatus;
-use crate::runtime::instance::InstanceManager;
-use crate::platform::gpu::GpuManager;
+use crate::monitoring::logger::{current_request_id, LogBuffer};
+use crate::monitoring::events::{EventBus, SystemEvent};
+use crate::monitoring::alert::AlertSystem;
+use crate::workers::{WorkerManager, WorkerStatus};
+use crate::runtime::instance::{self, InstanceManager};
+use crate::platform::gpu::{GpuConfig, GpuInfo, GpuManager};
+use crate::libs::gpu::{recommend_throttle_with, ThrottleAction, ThrottleThresholds};
+use crate::libs::tokenizer::Tokenizer;
+use crate::pool::{PayoutSummary, PoolConfig, PoolError, PoolManager, PoolMetrics, PoolStats};
+use crate::version::BuildInfo;
+use crate::SystemHealth;
+use crate::network::correlation::{
+    TraceSampler, correlation_middleware, request_id_middleware,
+    CORRELATION_ID_HEADER, REQUEST_ID_HEADER, TRACE_SAMPLED_HEADER,
+};
 
 use axum::{
-    routing::{get, post, put, delete},
+    routing::{get, post, put, patch, delete},
     Router,
-    extract::{State, Path, Json, Query},
+    extract::{State, Path, Json, Query, ConnectInfo, FromRef},
     response::{Json as JsonResponse, Html},
     http::{StatusCode, HeaderMap},
     headers::{Authorization, Bearer},
@@ -26,11 +39,12 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
-use tower_http::cors::{CorsLayer, Any};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use tower_http::limit::RequestBodyLimitLayer;
-use tower_http::trace::TraceLayer;
 
 /// Состояние API сервера
 #[derive(Clone)]
@@ -40,6 +54,329 @@ pub struct ApiState {
     pub gpu_manager: Arc<GpuManager>,
     pub system_metrics: Arc<RwLock<SystemMetrics>>,
     pub rate_limiter: Arc<RateLimiter>,
+    pub model_rate_limiter: Arc<ModelRateLimiter>,
+    pub trace_sampler: Arc<TraceSampler>,
+    pub log_buffer: Arc<LogBuffer>,
+    pub pool_manager: Arc<PoolManager>,
+    /// Реестр воркеров: статус и текущие метрики
+    pub worker_manager: Arc<WorkerManager>,
+    pub event_bus: Arc<EventBus>,
+    pub alert_system: Arc<AlertSystem>,
+    pub throttle_thresholds: ThrottleThresholds,
+    pub health_probe: HealthProbe,
+    pub tokenizer: Arc<Tokenizer>,
+}
+
+impl FromRef<ApiState> for Arc<InstanceManager> {
+    fn from_ref(state: &ApiState) -> Self {
+        state.instance_manager.clone()
+    }
+}
+
+impl FromRef<ApiState> for Arc<LogBuffer> {
+    fn from_ref(state: &ApiState) -> Self {
+        state.log_buffer.clone()
+    }
+}
+
+impl FromRef<ApiState> for Arc<PoolManager> {
+    fn from_ref(state: &ApiState) -> Self {
+        state.pool_manager.clone()
+    }
+}
+
+/// Источник проверок здоровья модулей; в тестах подменяется
+#[derive(Clone)]
+pub struct HealthProbe(Arc<dyn Fn() -> futures::future::BoxFuture<'static, SystemHealth> + Send + Sync>);
+
+impl HealthProbe {
+    pub fn new<F>(probe: F) -> Self
+    where
+        F: Fn() -> futures::future::BoxFuture<'static, SystemHealth> + Send + Sync + 'static,
+    {
+        Self(Arc::new(probe))
+    }
+
+    /// Проверки всех модулей системы (`crate::health_check`)
+    pub fn system() -> Self {
+        Self::new(|| {
+            Box::pin(async {
+                match crate::health_check().await {
+                    Ok(health) => health,
+                    Err(e) => crate::summarize_health(vec![("system", Err(e.to_string()))]),
+                }
+            })
+        })
+    }
+
+    pub async fn run(&self) -> SystemHealth {
+        (self.0)().await
+    }
+}
+
+impl FromRef<ApiState> for HealthProbe {
+    fn from_ref(state: &ApiState) -> Self {
+        state.health_probe.clone()
+    }
+}
+
+/// Предельная длительность потока, когда у модели нет экземпляра и её
+/// таймаут неизвестен
+pub const DIRECT_STREAM_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Период обновления `ApiState.system_metrics`
+pub const SYSTEM_METRICS_INTERVAL: Duration = Duration::from_secs(15);
+
+impl ApiState {
+    /// Текущие метрики: загрузка системы из `crate::get_system_stats`,
+    /// воркеры из пулов. Показатели без источника остаются прежними.
+    pub async fn collect_system_metrics(&self) -> SystemMetrics {
+        let stats = crate::get_system_stats().await;
+        let pools = self.pool_manager.list_pools().await;
+        let clamp_percent = |value: f64| if value.is_finite() { value.clamp(0.0, 100.0) } else { 0.0 };
+
+        SystemMetrics {
+            cpu_usage: clamp_percent(stats.cpu_usage),
+            memory_usage: clamp_percent(stats.memory_usage),
+            disk_usage: clamp_percent(stats.disk_usage),
+            network_usage: clamp_percent(stats.network_usage),
+            uptime: stats.uptime,
+            total_workers: pools.iter().map(|p| p.stats.total_workers).sum(),
+            active_workers: pools.iter().map(|p| p.stats.active_workers).sum(),
+            ..self.system_metrics.read().await.clone()
+        }
+    }
+
+    /// Периодически обновляет `system_metrics`, которые отдают `/metrics`
+    /// и `/api/v1/metrics`
+    pub fn spawn_metrics_collector(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let state = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let metrics = state.collect_system_metrics().await;
+                *state.system_metrics.write().await = metrics;
+            }
+        })
+    }
+
+    /// Идентификатор клиента для rate limiting - IP-адрес соединения.
+    /// Bearer-токен на этом этапе ещё не проверен, и ключ по нему позволил бы
+    /// обойти лимит, меняя токен в каждом запросе.
+    pub fn client_id_from(addr: SocketAddr) -> String {
+        format!("ip:{}", addr.ip())
+    }
+}
+
+/// Проверяет общий лимит запросов клиента
+pub async fn enforce_rate_limit(
+    limiter: &RateLimiter,
+    addr: SocketAddr,
+) -> Result<RateLimitStatus, RateLimitStatus> {
+    let client_id = ApiState::client_id_from(addr);
+    let status = match limiter.check_rate_limit(&client_id).await {
+        Ok(status) => status,
+        Err(e) => {
+            log::error!("Rate limit check failed for client {}: {}", client_id, e);
+            return Err(RateLimitStatus::exhausted(limiter.limit(), limiter.window));
+        }
+    };
+
+    if status.allowed {
+        Ok(status)
+    } else {
+        log::debug!("Rate limit exceeded for client {}", client_id);
+        Err(status)
+    }
+}
+
+pub const RATE_LIMIT_LIMIT_HEADER: &str = "x-ratelimit-limit";
+pub const RATE_LIMIT_REMAINING_HEADER: &str = "x-ratelimit-remaining";
+pub const RATE_LIMIT_RESET_HEADER: &str = "x-ratelimit-reset";
+
+/// Middleware: общий лимит запросов клиента. К каждому ответу добавляются
+/// заголовки `X-RateLimit-*`, к ответу 429 - ещё и `Retry-After`
+pub async fn rate_limit_middleware(
+    State(limiter): State<Arc<RateLimiter>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    let (status, mut response) = match enforce_rate_limit(&limiter, addr).await {
+        Ok(status) => (status, next.run(request).await),
+        Err(status) => {
+            let mut response = (
+                StatusCode::TOO_MANY_REQUESTS,
+                JsonResponse(ApiResponse::<()>::error(
+                    "Rate limit exceeded".to_string(),
+                    StatusCode::TOO_MANY_REQUESTS,
+                )),
+            )
+                .into_response();
+            response
+                .headers_mut()
+                .insert(axum::http::header::RETRY_AFTER, status.retry_after().into());
+            (status, response)
+        }
+    };
+
+    let headers = response.headers_mut();
+    headers.insert(RATE_LIMIT_LIMIT_HEADER, status.limit.into());
+    headers.insert(RATE_LIMIT_REMAINING_HEADER, status.remaining.into());
+    headers.insert(RATE_LIMIT_RESET_HEADER, status.reset_at.into());
+    response
+}
+
+/// Actix middleware с тем же лимитом:
+/// `App::new().app_data(web::Data::new(limiter)).wrap(from_fn(actix_rate_limit_filter))`.
+/// Limiter берётся из `web::Data<Arc<RateLimiter>>`; без него запрос пропускается
+pub async fn actix_rate_limit_filter<B: actix_web::body::MessageBody>(
+    req: actix_web::dev::ServiceRequest,
+    next: actix_web::middleware::Next<B>,
+) -> Result<actix_web::dev::ServiceResponse<actix_web::body::EitherBody<B>>, actix_web::Error> {
+    let limiter = req.app_data::<actix_web::web::Data<Arc<RateLimiter>>>().cloned();
+    let (Some(limiter), Some(addr)) = (limiter, req.peer_addr()) else {
+        return Ok(next.call(req).await?.map_into_left_body());
+    };
+
+    match enforce_rate_limit(&limiter, addr).await {
+        Ok(_) => Ok(next.call(req).await?.map_into_left_body()),
+        Err(status) => {
+            let response = actix_web::HttpResponse::TooManyRequests()
+                .insert_header((actix_web::http::header::RETRY_AFTER, status.retry_after()))
+                .insert_header((RATE_LIMIT_LIMIT_HEADER, status.limit))
+                .insert_header((RATE_LIMIT_REMAINING_HEADER, status.remaining))
+                .insert_header((RATE_LIMIT_RESET_HEADER, status.reset_at))
+                .json(ApiResponse::<()>::error(
+                    "Rate limit exceeded".to_string(),
+                    StatusCode::TOO_MANY_REQUESTS,
+                ));
+            Ok(req.into_response(response).map_into_right_body())
+        }
+    }
+}
+
+/// Ответ на ошибку менеджера пулов со статусом из `PoolError::status_code`
+fn pool_error_response<T>(error: PoolError) -> (StatusCode, JsonResponse<ApiResponse<T>>) {
+    let status = StatusCode::from_u16(error.status_code().as_u16())
+        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    (status, JsonResponse(ApiResponse::error(error.to_string(), status)))
+}
+
+/// Токены доступа к изменяющим endpoints (`ApiConfig.enable_auth`/`auth_tokens`)
+#[derive(Clone)]
+pub struct ApiAuth {
+    enabled: bool,
+    tokens: Arc<Vec<String>>,
+}
+
+impl ApiAuth {
+    pub fn from_config(config: &ApiConfig) -> Self {
+        Self {
+            enabled: config.enable_auth,
+            tokens: Arc::new(config.auth_tokens.clone()),
+        }
+    }
+
+    /// При выключенной авторизации пропускает всё; при включённой и пустом
+    /// списке токенов не пропускает ничего
+    pub fn is_authorized(&self, headers: &HeaderMap) -> bool {
+        use subtle::ConstantTimeEq;
+
+        if !self.enabled {
+            return true;
+        }
+        let token = headers
+            .get(axum::http::header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+            .filter(|token| !token.is_empty());
+        token.map_or(false, |token| {
+            self.tokens
+                .iter()
+                .any(|allowed| bool::from(allowed.as_bytes().ct_eq(token.as_bytes())))
+        })
+    }
+}
+
+/// Middleware: пропускает запрос только с допустимым bearer-токеном
+pub async fn auth_middleware(
+    State(auth): State<ApiAuth>,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    if auth.is_authorized(request.headers()) {
+        next.run(request).await
+    } else {
+        (
+            StatusCode::UNAUTHORIZED,
+            JsonResponse(ApiResponse::<()>::error("Unauthorized".to_string(), StatusCode::UNAUTHORIZED)),
+        )
+            .into_response()
+    }
+}
+
+/// Тело запроса масштабирования пула
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScaleRequest {
+    pub target_workers: u32,
+}
+
+/// Тело запроса на вход воркера в пул
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JoinPoolRequest {
+    pub worker_id: String,
+    /// Модель GPU воркера; сверяется с `allowed_gpu_models` пула
+    pub gpu_model: String,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+}
+
+/// Проверяет, что промпт помещается в контекст модели
+pub fn ensure_prompt_fits(
+    tokenizer: &Tokenizer,
+    prompt: &str,
+    context_length: u32,
+) -> Result<(), AppError> {
+    let tokens = tokenizer.count_tokens(prompt);
+    if tokens > context_length as usize {
+        return Err(AppError::InvalidInput(format!(
+            "Prompt is {} tokens, exceeding the model context length of {}",
+            tokens, context_length
+        )));
+    }
+    Ok(())
+}
+
+/// Проверяет промпт по длине контекста модели `model_name`. Неизвестная
+/// модель не проверяется: запрос отклонит или обработает сама модель.
+pub async fn ensure_prompt_fits_model(
+    state: &ApiState,
+    model_name: &str,
+    prompt: &str,
+) -> Result<(), AppError> {
+    let info = match state.instance_manager.get_model_info(model_name).await {
+        Some(info) => Some(info),
+        None => state
+            .model_manager
+            .get_model_info()
+            .await
+            .ok()
+            .filter(|info| info.name == model_name),
+    };
+    match info {
+        Some(info) => ensure_prompt_fits(&state.tokenizer, prompt, info.context_length),
+        None => {
+            log::debug!("Context length of model {} is unknown, skipping prompt check", model_name);
+            Ok(())
+        }
+    }
 }
 
 /// API сервер
@@ -47,40 +384,145 @@ pub struct ApiServer {
     state: ApiState,
     router: Router,
     config: ApiConfig,
+    shutdown: Arc<ServerShutdown>,
+}
+
+/// Управление остановкой HTTP сервера
+pub struct ServerShutdown {
+    requested: tokio::sync::watch::Sender<bool>,
+    running: tokio::sync::watch::Sender<bool>,
+}
+
+impl ServerShutdown {
+    pub fn new() -> Self {
+        Self {
+            requested: tokio::sync::watch::channel(false).0,
+            running: tokio::sync::watch::channel(false).0,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        *self.running.borrow()
+    }
+
+    /// Обслуживает соединения до сигнала остановки. После сигнала новые
+    /// соединения не принимаются, а текущие запросы дорабатывают не дольше
+    /// `timeout`.
+    pub async fn serve(
+        &self,
+        listener: tokio::net::TcpListener,
+        router: Router,
+        timeout: Duration,
+    ) -> std::io::Result<()> {
+        use std::future::IntoFuture;
+
+        self.requested.send_replace(false);
+        self.running.send_replace(true);
+
+        let mut requested = self.requested.subscribe();
+        let mut deadline = self.requested.subscribe();
+
+        let server = axum::serve(
+            listener,
+            router.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(async move {
+            let _ = requested.wait_for(|requested| *requested).await;
+        });
+        let hard_stop = async move {
+            let _ = deadline.wait_for(|requested| *requested).await;
+            tokio::time::sleep(timeout).await;
+        };
+
+        let result = tokio::select! {
+            result = server.into_future() => result,
+            _ = hard_stop => {
+                log::warn!("In-flight requests did not finish within {:?}, forcing shutdown", timeout);
+                Ok(())
+            }
+        };
+
+        self.running.send_replace(false);
+        result
+    }
+
+    /// Подаёт сигнал остановки и ждёт завершения сервера
+    pub async fn shutdown(&self, timeout: Duration) -> Result<(), AppError> {
+        if !self.is_running() {
+            return Ok(());
+        }
+
+        let mut running = self.running.subscribe();
+        self.requested.send_replace(true);
+
+        // Сервер сам прерывает запросы по истечении `timeout`; запас на закрытие
+        let wait = tokio::time::timeout(
+            timeout + Duration::from_secs(1),
+            running.wait_for(|running| !*running),
+        )
+        .await;
+        match wait {
+            Ok(_) => Ok(()),
+            Err(_) => Err(AppError::Timeout(format!(
+                "API server did not stop within {:?}",
+                timeout
+            ))),
+        }
+    }
+}
+
+impl Default for ServerShutdown {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ApiServer {
     /// Создает новый API сервер
     pub fn new(state: ApiState, config: ApiConfig) -> Self {
-        let router = Self::create_router(state.clone());
+        let router = Self::create_router(state.clone(), &config);
         
         Self {
             state,
             router,
             config,
+            shutdown: Arc::new(ServerShutdown::new()),
         }
     }
 
-    /// Создает роутер с маршрутами
-    fn create_router(state: ApiState) -> Router {
-        let cors = CorsLayer::new()
-            .allow_origin(Any)
-            .allow_methods(Any)
-            .allow_headers(Any);
+    /// Метрики системы, которые обновляет сборщик сервера
+    pub fn system_metrics(&self) -> Arc<RwLock<SystemMetrics>> {
+        self.state.system_metrics.clone()
+    }
 
-        Router::new()
-            // Системные endpoints
+    /// Создает роутер с маршрутами
+    fn create_router(state: ApiState, config: &ApiConfig) -> Router {
+        let auth = axum::middleware::from_fn_with_state(ApiAuth::from_config(config), auth_middleware);
+        // Проверки здоровья, метрики, версия и долгие SSE-потоки не
+        // расходуют общий лимит клиента
+        let unlimited = Router::new()
             .route("/api/v1/status", get(api::get_status))
             .route("/api/v1/health", get(api::get_health))
+            .route("/api/v1/health/graph", get(api::get_health_graph))
             .route("/api/v1/metrics", get(api::get_metrics))
+            .route("/metrics", get(api::get_prometheus_metrics))
+            .route("/api/v1/version", get(api::get_version))
+            .route("/api/v1/workers/:id/logs/stream", get(api::stream_worker_logs));
+
+        let router = Router::new()
+            // Системные endpoints
             .route("/api/v1/info", get(api::get_info))
             
             // Модели
             .route("/api/v1/models", get(api::get_models))
             .route("/api/v1/models/:name", get(api::get_model))
             .route("/api/v1/models/:name/request", post(api::process_request))
+            .route("/api/v1/models/:name/stream", post(api::stream_request))
             .route("/api/v1/models/:name/config", get(api::get_model_config))
             .route("/api/v1/models/:name/config", put(api::update_model_config))
+            .route("/api/v1/models/:name/config", patch(api::patch_model_config).route_layer(auth.clone()))
+            .route("/api/v1/models/:name/inference-defaults", get(api::get_inference_defaults))
+            .route("/api/v1/models/:name/inference-defaults", put(api::update_inference_defaults).route_layer(auth.clone()))
             .route("/api/v1/models/:name/metrics", get(api::get_model_metrics))
             .route("/api/v1/models/:name/health", get(api::get_model_health))
             
@@ -88,12 +530,19 @@ impl ApiServer {
             .route("/api/v1/workers", get(api::get_workers))
             .route("/api/v1/workers/:id", get(api::get_worker))
             .route("/api/v1/workers/:id/status", get(api::get_worker_status))
+            .route("/api/v1/pools", get(api::list_pools))
+            .route("/api/v1/pools", post(api::create_pool).route_layer(auth.clone()))
+            .route("/api/v1/pools/:name/scale", post(api::scale_pool).route_layer(auth.clone()))
+            .route("/api/v1/pools/:name/workers", get(api::get_pool_workers))
+            .route("/api/v1/pools/:name/workers", post(api::join_pool).route_layer(auth.clone()))
+            .route("/api/v1/pools/:name/payouts", get(api::get_pool_payouts))
             
             // GPU
             .route("/api/v1/gpu", get(api::get_gpu_info))
-            .route("/api/v1/gpu/optimize", post(api::optimize_gpu))
+            .route("/api/v1/gpu/optimize", post(api::optimize_gpu).route_layer(auth.clone()))
             .route("/api/v1/gpu/config", get(api::get_gpu_config))
-            .route("/api/v1/gpu/config", put(api::update_gpu_config))
+            .route("/api/v1/gpu/config", put(api::update_gpu_config).route_layer(auth))
+            .route("/api/v1/gpu/throttle", get(api::get_gpu_throttle))
             
             // Память
             .route("/api/v1/memory", get(api::get_memory_info))
@@ -101,7 +550,6 @@ impl ApiServer {
             
             // Система
             .route("/api/v1/system/restart", post(api::restart_system))
-            .route("/api/v1/system/shutdown", post(api::shutdown_system))
             .route("/api/v1/system/update", post(api::update_system))
             
             // Мониторинг
@@ -112,9 +560,24 @@ impl ApiServer {
             // Документация
             .route("/api/docs", get(api::get_docs))
             .route("/api/openapi.json", get(api::get_openapi))
-            
-            .layer(cors)
-            .layer(TraceLayer::new_for_http())
+            .route_layer(axum::middleware::from_fn_with_state(
+                state.rate_limiter.clone(),
+                rate_limit_middleware,
+            ))
+            .merge(unlimited);
+
+        let router = match cors_layer(config) {
+            Some(cors) => router.layer(cors),
+            None => router,
+        };
+
+        // Трассировку пишет correlation_middleware, только для выбранных запросов
+        router
+            .layer(axum::middleware::from_fn_with_state(
+                state.trace_sampler.clone(),
+                correlation_middleware,
+            ))
+            .layer(axum::middleware::from_fn(request_id_middleware))
             .layer(RequestBodyLimitLayer::new(10 * 1024 * 1024)) // 10MB limit
             .with_state(state)
     }
@@ -126,18 +589,87 @@ impl ApiServer {
         
         log::info!("API Server starting on {}", addr);
         
-        axum::serve(listener, self.router.clone()).await?;
+        let collector = self.state.spawn_metrics_collector(SYSTEM_METRICS_INTERVAL);
+        let served = self
+            .shutdown
+            .serve(listener, self.router.clone(), self.config.shutdown_timeout())
+            .await;
+        collector.abort();
+        served?;
         
+        log::info!("API Server stopped");
         Ok(())
     }
 
-    /// Останавливает API сервер
+    /// Сколько сервер ждёт завершения текущих запросов при остановке
+    pub fn shutdown_timeout(&self) -> Duration {
+        self.config.shutdown_timeout()
+    }
+
+    /// Останавливает API сервер, дожидаясь завершения текущих запросов
     pub async fn stop(&self) -> Result<(), Box<dyn std::error::Error>> {
         log::info!("API Server stopping");
+        self.shutdown.shutdown(self.config.shutdown_timeout()).await?;
         Ok(())
     }
 }
 
+/// Строит CORS-слой по конфигурации. `None`, если CORS выключен.
+/// `Any` используется только при `"*"` в списке; для конкретных источников
+/// разрешаются credentials, поэтому методы и заголовки перечисляются явно.
+pub fn cors_layer(config: &ApiConfig) -> Option<CorsLayer> {
+    use axum::http::{header, HeaderName, HeaderValue, Method};
+
+    if !config.enable_cors {
+        return None;
+    }
+
+    // Без явного списка браузер отдаёт скрипту только простые заголовки ответа
+    let exposed: Vec<HeaderName> = [
+        REQUEST_ID_HEADER,
+        CORRELATION_ID_HEADER,
+        TRACE_SAMPLED_HEADER,
+        RATE_LIMIT_LIMIT_HEADER,
+        RATE_LIMIT_REMAINING_HEADER,
+        RATE_LIMIT_RESET_HEADER,
+        header::RETRY_AFTER.as_str(),
+    ]
+    .iter()
+    .map(|name| HeaderName::from_bytes(name.as_bytes()).expect("valid header name"))
+    .collect();
+
+    if config.cors_origins.iter().any(|origin| origin == "*") {
+        return Some(
+            CorsLayer::new()
+                .allow_origin(Any)
+                .allow_methods(Any)
+                .allow_headers(Any)
+                .expose_headers(exposed),
+        );
+    }
+
+    let origins: Vec<HeaderValue> = config
+        .cors_origins
+        .iter()
+        .filter_map(|origin| match HeaderValue::from_str(origin.trim_end_matches('/')) {
+            Ok(value) => Some(value),
+            Err(_) => {
+                log::warn!("Ignoring invalid CORS origin: {}", origin);
+                None
+            }
+        })
+        .collect();
+
+    Some(
+        CorsLayer::new()
+            .allow_origin(AllowOrigin::list(origins))
+            .allow_credentials(true)
+            .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE, Method::OPTIONS])
+            .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE, header::ACCEPT])
+            .expose_headers(exposed),
+    )
+}
+
 /// Конфигурация API
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiConfig {
@@ -147,6 +679,12 @@ pub struct ApiConfig {
     pub ssl_cert_path: Option<String>,
     pub ssl_key_path: Option<String>,
     pub rate_limit: u32,
+    /// Потолок запросов в секунду для отдельных моделей
+    #[serde(default)]
+    pub model_rate_limits: HashMap<String, f64>,
+    /// Доля трассируемых успешных запросов (ошибки трассируются всегда)
+    #[serde(default = "default_trace_sample_rate")]
+    pub trace_sample_rate: f64,
     pub max_request_size: usize,
     pub enable_cors: bool,
     pub cors_origins: Vec<String>,
@@ -154,6 +692,15 @@ pub struct ApiConfig {
     pub auth_tokens: Vec<String>,
     pub enable_docs: bool,
     pub enable_metrics: bool,
+    /// Сколько ждать завершения текущих запросов при остановке, секунды
+    #[serde(default = "default_shutdown_timeout_secs")]
+    pub shutdown_timeout_secs: u64,
+}
+
+impl ApiConfig {
+    pub fn shutdown_timeout(&self) -> Duration {
+        Duration::from_secs(self.shutdown_timeout_secs)
+    }
 }
 
 impl Default for ApiConfig {
@@ -165,6 +712,8 @@ impl Default for ApiConfig {
             ssl_cert_path: None,
             ssl_key_path: None,
             rate_limit: 1000,
+            model_rate_limits: HashMap::new(),
+            trace_sample_rate: default_trace_sample_rate(),
             max_request_size: 10 * 1024 * 1024, // 10MB
             enable_cors: true,
             cors_origins: vec!["*".to_string()],
@@ -172,47 +721,236 @@ impl Default for ApiConfig {
             auth_tokens: vec![],
             enable_docs: true,
             enable_metrics: true,
+            shutdown_timeout_secs: default_shutdown_timeout_secs(),
+        }
+    }
+}
+
+fn default_trace_sample_rate() -> f64 {
+    0.01
+}
+
+fn default_shutdown_timeout_secs() -> u64 {
+    30
+}
+
+/// Состояние лимита клиента после проверки запроса
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RateLimitStatus {
+    /// Пропущен ли запрос
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// Unix-время (секунды), когда освободится следующий слот
+    pub reset_at: u64,
+}
+
+impl RateLimitStatus {
+    fn exhausted(limit: u32, window: u64) -> Self {
+        Self {
+            allowed: false,
+            limit,
+            remaining: 0,
+            reset_at: unix_now() + window,
         }
     }
+
+    /// Секунды до освобождения слота, не меньше одной
+    pub fn retry_after(&self) -> u64 {
+        self.reset_at.saturating_sub(unix_now()).max(1)
+    }
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
 }
 
 /// Rate limiter
 pub struct RateLimiter {
     requests: Arc<RwLock<HashMap<String, Vec<u64>>>>,
-    limit: u32,
+    /// Меняется без перезапуска (`server.rate_limit`)
+    limit: std::sync::atomic::AtomicU32,
     window: u64,
+    /// Когда в последний раз удалялись клиенты без запросов в окне
+    last_sweep: std::sync::atomic::AtomicU64,
 }
 
 impl RateLimiter {
     pub fn new(limit: u32, window: u64) -> Self {
         Self {
             requests: Arc::new(RwLock::new(HashMap::new())),
-            limit,
+            limit: std::sync::atomic::AtomicU32::new(limit),
             window,
+            last_sweep: std::sync::atomic::AtomicU64::new(0),
         }
     }
 
-    pub async fn check_rate_limit(&self, client_id: &str) -> Result<bool, AppError> {
+    pub fn limit(&self) -> u32 {
+        self.limit.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Меняет лимит; уже учтённые запросы клиентов сохраняются
+    pub fn set_limit(&self, limit: u32) {
+        self.limit.store(limit, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub async fn check_rate_limit(&self, client_id: &str) -> Result<RateLimitStatus, AppError> {
+        self.check_rate_limit_at(client_id, unix_now()).await
+    }
+
+    pub async fn check_rate_limit_at(&self, client_id: &str, now: u64) -> Result<RateLimitStatus, AppError> {
+        use std::sync::atomic::Ordering;
+
         let mut requests = self.requests.write().await;
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        
+        let in_window = |timestamp: u64| now.saturating_sub(timestamp) < self.window;
+
+        // Раз в окно забываем клиентов, у которых не осталось запросов
+        if now.saturating_sub(self.last_sweep.load(Ordering::Relaxed)) >= self.window {
+            requests.retain(|_, timestamps| {
+                timestamps.retain(|&timestamp| in_window(timestamp));
+                !timestamps.is_empty()
+            });
+            self.last_sweep.store(now, Ordering::Relaxed);
+        }
+
         let client_requests = requests.entry(client_id.to_string()).or_insert_with(Vec::new);
         
         // Удаляем старые запросы
-        client_requests.retain(|&timestamp| now - timestamp < self.window);
+        client_requests.retain(|&timestamp| in_window(timestamp));
         
-        // Проверяем лимит
-        if client_requests.len() >= self.limit as usize {
-            return Ok(false);
+        // Проверяем лимит и добавляем новый запрос
+        let limit = self.limit();
+        let allowed = client_requests.len() < limit as usize;
+        if allowed {
+            client_requests.push(now);
         }
-        
-        // Добавляем новый запрос
-        client_requests.push(now);
-        Ok(true)
+
+        Ok(RateLimitStatus {
+            allowed,
+            limit,
+            remaining: limit.saturating_sub(client_requests.len() as u32),
+            reset_at: client_requests.first().copied().unwrap_or(now) + self.window,
+        })
+    }
+
+    /// Число клиентов, о которых limiter хранит запросы
+    pub async fn tracked_clients(&self) -> usize {
+        self.requests.read().await.len()
+    }
+}
+
+/// Дольше этого клиент не ждёт токена, даже при очень малом лимите
+pub const MAX_MODEL_RETRY_AFTER: Duration = Duration::from_secs(3600);
+
+/// Token bucket одной модели
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, now: Instant) -> Self {
+        let capacity = rate.max(1.0);
+        Self { rate, capacity, tokens: capacity, last_refill: now }
+    }
+
+    fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            let wait = Duration::try_from_secs_f64((1.0 - self.tokens) / self.rate)
+                .unwrap_or(MAX_MODEL_RETRY_AFTER);
+            Err(wait.min(MAX_MODEL_RETRY_AFTER))
+        }
+    }
+}
+
+/// Ограничение частоты запросов к модели (запросов в секунду).
+/// Защищает общий upstream независимо от лимитов клиентов.
+pub struct ModelRateLimiter {
+    limits: HashMap<String, f64>,
+    buckets: parking_lot::Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl ModelRateLimiter {
+    /// Лимит должен быть положительным конечным числом запросов в секунду
+    pub fn new(limits: HashMap<String, f64>) -> Result<Self, String> {
+        if let Some((model, rate)) = limits.iter().find(|(_, rate)| !(rate.is_finite() && **rate > 0.0)) {
+            return Err(format!("Invalid rate limit for model '{}': {}", model, rate));
+        }
+        Ok(Self {
+            limits,
+            buckets: parking_lot::Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Забирает токен для модели; при превышении возвращает время до следующей попытки
+    pub fn check(&self, model: &str) -> Result<(), Duration> {
+        self.check_at(model, Instant::now())
+    }
+
+    fn check_at(&self, model: &str, now: Instant) -> Result<(), Duration> {
+        let rate = match self.limits.get(model) {
+            Some(rate) => *rate,
+            None => return Ok(()),
+        };
+
+        let mut buckets = self.buckets.lock();
+        buckets
+            .entry(model.to_string())
+            .or_insert_with(|| TokenBucket::new(rate, now))
+            .try_acquire(now)
+    }
+}
+
+/// Заголовок с дедлайном запроса
+pub const REQUEST_DEADLINE_HEADER: &str = "X-Request-Deadline";
+
+/// Разбирает заголовок `X-Request-Deadline`.
+///
+/// Поддерживаются относительные значения (`2s`, `500ms`, число миллисекунд)
+/// и абсолютные метки времени в формате RFC 3339.
+pub fn parse_request_deadline(headers: &HeaderMap) -> Result<Option<Instant>, String> {
+    let value = match headers.get(REQUEST_DEADLINE_HEADER) {
+        Some(value) => value
+            .to_str()
+            .map_err(|_| format!("{} must be ASCII", REQUEST_DEADLINE_HEADER))?
+            .trim(),
+        None => return Ok(None),
+    };
+
+    let now = Instant::now();
+    let invalid = || format!("Invalid {} value: {}", REQUEST_DEADLINE_HEADER, value);
+    let deadline_after = |duration: Duration| now.checked_add(duration).ok_or_else(invalid);
+
+    let relative = if let Some(ms) = value.strip_suffix("ms") {
+        Some(ms.trim().parse::<u64>().map_err(|_| invalid())?).map(Duration::from_millis)
+    } else if let Some(secs) = value.strip_suffix('s') {
+        let secs = secs.trim().parse::<f64>().map_err(|_| invalid())?;
+        Some(Duration::try_from_secs_f64(secs).map_err(|_| invalid())?)
+    } else {
+        value.parse::<u64>().ok().map(Duration::from_millis)
+    };
+
+    if let Some(duration) = relative {
+        return deadline_after(duration).map(Some);
     }
+
+    let absolute = chrono::DateTime::parse_from_rfc3339(value).map_err(|_| invalid())?;
+    let remaining = absolute.with_timezone(&chrono::Utc) - chrono::Utc::now();
+
+    // Дедлайн в прошлом превращается в немедленный таймаут
+    deadline_after(remaining.to_std().unwrap_or(Duration::ZERO)).map(Some)
 }
 
 // API handlers
@@ -234,31 +972,48 @@ mod api {
         JsonResponse(ApiResponse::success(status))
     }
 
+    /// Получение графа зависимостей модулей с их здоровьем
+    pub async fn get_health_graph(
+        State(_state): State<ApiState>,
+    ) -> (StatusCode, JsonResponse<ApiResponse<crate::HealthGraph>>) {
+        match crate::health_graph().await {
+            Ok(graph) => (StatusCode::OK, JsonResponse(ApiResponse::success(graph))),
+            Err(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                JsonResponse(ApiResponse::error(
+                    e.to_string(),
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            ),
+        }
+    }
+
     /// Получение здоровья системы
-    pub async fn get_health(State(state): State<ApiState>) -> JsonResponse<ApiResponse<HealthStatus>> {
+    pub async fn get_health(
+        State(probe): State<HealthProbe>,
+    ) -> (StatusCode, JsonResponse<ApiResponse<HealthStatus>>) {
+        let system = probe.run().await;
+        let status = if system.status == "critical" {
+            StatusCode::SERVICE_UNAVAILABLE
+        } else {
+            StatusCode::OK
+        };
+
         let health = HealthStatus {
-            status: "healthy".to_string(),
-            checks: vec![
-                HealthCheck {
-                    name: "api".to_string(),
-                    status: "healthy".to_string(),
-                    message: "API is running".to_string(),
-                },
-                HealthCheck {
-                    name: "models".to_string(),
-                    status: "healthy".to_string(),
-                    message: "Models are available".to_string(),
-                },
-                HealthCheck {
-                    name: "gpu".to_string(),
-                    status: "healthy".to_string(),
-                    message: "GPU is operational".to_string(),
-                },
-            ],
-            timestamp: chrono::Utc::now(),
+            status: system.status,
+            checks: system
+                .checks
+                .into_iter()
+                .map(|check| HealthCheck {
+                    name: check.module,
+                    status: check.status,
+                    message: check.message,
+                })
+                .collect(),
+            timestamp: system.timestamp,
         };
-        
-        JsonResponse(ApiResponse::success(health))
+
+        (status, JsonResponse(ApiResponse::success(health)))
     }
 
     /// Получение метрик системы
@@ -267,6 +1022,17 @@ mod api {
         JsonResponse(ApiResponse::success(metrics))
     }
 
+    /// Метрики в формате Prometheus
+    pub async fn get_prometheus_metrics(
+        State(state): State<ApiState>,
+    ) -> ([(axum::http::header::HeaderName, &'static str); 1], String) {
+        let metrics = state.system_metrics.read().await;
+        (
+            [(axum::http::header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+            crate::monitoring::metrics::to_prometheus(&metrics),
+        )
+    }
+
     /// Получение информации о системе
     pub async fn get_info(State(state): State<ApiState>) -> JsonResponse<ApiResponse<SystemInfo>> {
         let info = SystemInfo {
@@ -285,6 +1051,11 @@ mod api {
         JsonResponse(ApiResponse::success(info))
     }
 
+    /// Метаданные сборки: git SHA, ветка, версия rustc, профиль
+    pub async fn get_version() -> JsonResponse<ApiResponse<BuildInfo>> {
+        JsonResponse(ApiResponse::success(BuildInfo::current()))
+    }
+
     /// Получение списка моделей
     pub async fn get_models(State(state): State<ApiState>) -> JsonResponse<ApiResponse<Vec<ModelInfo>>> {
         // В реальной реализации здесь должен быть доступ к менеджеру моделей
@@ -351,92 +1122,291 @@ mod api {
         JsonResponse(ApiResponse::success(model_info))
     }
 
-    /// Обработка запроса к модели
-    pub async fn process_request(
+    /// Ответ 429 на превышение лимита модели с `Retry-After` в целых секундах
+    fn model_rate_limited<T>(
+        name: &str,
+        retry_after: Duration,
+    ) -> (StatusCode, HeaderMap, JsonResponse<ApiResponse<T>>) {
+        let mut headers = HeaderMap::new();
+        let seconds = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+        headers.insert(axum::http::header::RETRY_AFTER, seconds.into());
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            headers,
+            JsonResponse(ApiResponse::error(
+                format!("Model '{}' rate limit exceeded", name),
+                StatusCode::TOO_MANY_REQUESTS,
+            )),
+        )
+    }
+
+    /// Потоковая обработка запроса: фрагменты ответа передаются как SSE-события `token`,
+    /// ошибка - событием `error`
+    pub async fn stream_request(
         State(state): State<ApiState>,
         Path(name): Path<String>,
+        headers: HeaderMap,
         Json(request): Json<ModelRequest>,
-    ) -> JsonResponse<ApiResponse<ModelResponse>> {
-        // Проверяем rate limit
-        let client_id = "default"; // В реальной реализации извлекаем из запроса
-        if !state.rate_limiter.check_rate_limit(client_id).await.unwrap_or(false) {
-            return JsonResponse(ApiResponse::error(
-                "Rate limit exceeded".to_string(),
-                StatusCode::TOO_MANY_REQUESTS,
-            ));
+    ) -> axum::response::Response {
+        use axum::response::IntoResponse;
+        use axum::response::sse::{Event, KeepAlive, Sse};
+        use futures::{SinkExt, StreamExt};
+
+        if let Err(retry_after) = state.model_rate_limiter.check(&name) {
+            return model_rate_limited::<()>(&name, retry_after).into_response();
         }
 
-        // Обрабатываем запрос
-        match state.model_manager.process_request(request).await {
-            Ok(response) => JsonResponse(ApiResponse::success(response)),
-            Err(e) => JsonResponse(ApiResponse::error(
-                e.to_string(),
-                StatusCode::INTERNAL_SERVER_ERROR,
-            )),
+        let deadline = match parse_request_deadline(&headers) {
+            Ok(deadline) => deadline,
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    JsonResponse(ApiResponse::<()>::error(e, StatusCode::BAD_REQUEST)),
+                )
+                    .into_response();
+            }
+        };
+
+        if let Err(e) = ensure_prompt_fits_model(&state, &name, &request.prompt).await {
+            return (
+                StatusCode::BAD_REQUEST,
+                JsonResponse(ApiResponse::<()>::error(e.to_string(), StatusCode::BAD_REQUEST)),
+            )
+                .into_response();
         }
+
+        // Поток модели заимствует её, поэтому читаем его в отдельной задаче.
+        // Как и обычные запросы, поток идёт через экземпляр модели с её таймаутом
+        let instance_id = state.instance_manager.get_least_loaded_instance(&name).await;
+        let (mut tx, rx) = futures::channel::mpsc::channel::<Result<TokenChunk, AppError>>(16);
+        tokio::spawn(async move {
+            let streamed = match instance_id {
+                Some(instance_id) => {
+                    state.instance_manager
+                        .stream_request_with_deadline(&instance_id, request, deadline, tx.clone())
+                        .await
+                }
+                None => {
+                    let mut direct = tx.clone();
+                    let model = state.model_manager.clone();
+                    instance::with_deadline(
+                        async move {
+                            if !request.required_features.is_empty() {
+                                request.check_feature_support(&model.get_model_info().await?)?;
+                            }
+                            let mut stream = model.process_request_stream(request).await?;
+                            while let Some(item) = stream.next().await {
+                                if direct.send(item).await.is_err() {
+                                    break;
+                                }
+                            }
+                            Ok(())
+                        },
+                        Instant::now() + DIRECT_STREAM_TIMEOUT,
+                        deadline,
+                    )
+                    .await
+                }
+            };
+            if let Err(e) = streamed {
+                let _ = tx.send(Err(e)).await;
+            }
+        });
+
+        let events = rx.map(|item| {
+            let event = match item {
+                Ok(chunk) => Event::default()
+                    .event("token")
+                    .json_data(&chunk)
+                    .unwrap_or_else(|e| Event::default().event("error").data(e.to_string())),
+                Err(e) => Event::default().event("error").data(e.to_string()),
+            };
+            Ok::<_, std::convert::Infallible>(event)
+        });
+
+        Sse::new(events).keep_alive(KeepAlive::default()).into_response()
     }
 
-    /// Получение конфигурации модели
-    pub async fn get_model_config(
+    /// Обработка запроса к модели
+    pub async fn process_request(
         State(state): State<ApiState>,
         Path(name): Path<String>,
-    ) -> JsonResponse<ApiResponse<ModelConfig>> {
-        // В реальной реализации получаем конфигурацию модели
-        let config = ModelConfig {
-            model_path: Some(format!("/models/{}", name)),
-            device: crate::core::model_interface::DeviceConfig {
-                device_type: crate::core::model_interface::DeviceType::GPU,
-                device_id: Some(0),
-                memory_fraction: 0.8,
-                allow_growth: true,
-            },
-            performance: crate::core::model_interface::PerformanceConfig {
-                batch_size: 16,
-                max_concurrent_requests: 32,
-                timeout_seconds: 30,
-                retry_attempts: 3,
-                enable_caching: true,
-                cache_size: 1024 * 1024 * 1024,
-            },
-            memory: crate::core::model_interface::MemoryConfig {
-                max_memory_usage: 16384,
-                memory_pool_size: 8192,
-                enable_memory_optimization: true,
-                garbage_collection_threshold: 0.8,
-            },
-            inference: crate::core::model_interface::InferenceConfig {
-                default_temperature: 0.7,
-                default_max_tokens: 100,
-                default_top_p: 0.9,
-                enable_sampling: true,
-                enable_beam_search: false,
-                beam_width: 5,
-            },
-            optimization: crate::core::model_interface::OptimizationConfig {
-                enable_quantization: true,
-                quantization_type: Some(crate::core::model_interface::Precision::FP16),
-                enable_pruning: false,
-                enable_distillation: false,
-                enable_compilation: true,
-                optimization_level: crate::core::model_interface::OptimizationLevel::Advanced,
-            },
+        headers: HeaderMap,
+        Json(request): Json<ModelRequest>,
+    ) -> (StatusCode, HeaderMap, JsonResponse<ApiResponse<ModelResponse>>) {
+        // Проверяем потолок частоты запросов к модели
+        if let Err(retry_after) = state.model_rate_limiter.check(&name) {
+            return model_rate_limited(&name, retry_after);
+        }
+
+        let deadline = match parse_request_deadline(&headers) {
+            Ok(deadline) => deadline,
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    HeaderMap::new(),
+                    JsonResponse(ApiResponse::error(e, StatusCode::BAD_REQUEST)),
+                )
+            }
         };
-        
-        JsonResponse(ApiResponse::success(config))
+
+        // Отклоняем промпт, не помещающийся в контекст модели, до отправки
+        if let Err(e) = ensure_prompt_fits_model(&state, &name, &request.prompt).await {
+            return (
+                StatusCode::BAD_REQUEST,
+                HeaderMap::new(),
+                JsonResponse(ApiResponse::error(e.to_string(), StatusCode::BAD_REQUEST)),
+            );
+        }
+
+        // Обрабатываем запрос через экземпляр модели (с её таймаутом), либо напрямую
+        let result = match state.instance_manager.get_least_loaded_instance(&name).await {
+            Some(instance_id) => {
+                state.instance_manager
+                    .process_request_with_deadline(&instance_id, request, deadline)
+                    .await
+            }
+            None => {
+                let preflight = if request.required_features.is_empty() {
+                    Ok(())
+                } else {
+                    match state.model_manager.get_model_info().await {
+                        Ok(info) => request.check_feature_support(&info),
+                        Err(e) => Err(e),
+                    }
+                };
+
+                match preflight {
+                    Ok(()) => {
+                        let response = state.model_manager.process_request(request);
+                        match deadline {
+                            Some(deadline) => tokio::time::timeout_at(
+                                tokio::time::Instant::from_std(deadline),
+                                response,
+                            )
+                            .await
+                            .unwrap_or_else(|_| {
+                                Err(AppError::Timeout("request deadline exceeded".to_string()))
+                            }),
+                            None => response.await,
+                        }
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+        };
+
+        match result {
+            Ok(response) => (
+                StatusCode::OK,
+                HeaderMap::new(),
+                JsonResponse(ApiResponse::success(response)),
+            ),
+            Err(AppError::Timeout(msg)) => (
+                StatusCode::GATEWAY_TIMEOUT,
+                HeaderMap::new(),
+                JsonResponse(ApiResponse::error(msg, StatusCode::GATEWAY_TIMEOUT)),
+            ),
+            Err(AppError::InvalidInput(msg)) => (
+                StatusCode::BAD_REQUEST,
+                HeaderMap::new(),
+                JsonResponse(ApiResponse::error(msg, StatusCode::BAD_REQUEST)),
+            ),
+            Err(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                HeaderMap::new(),
+                JsonResponse(ApiResponse::error(
+                    e.to_string(),
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            ),
+        }
+    }
+
+    /// Получение конфигурации модели
+    pub async fn get_model_config(
+        State(instance_manager): State<Arc<InstanceManager>>,
+        Path(name): Path<String>,
+    ) -> (StatusCode, JsonResponse<ApiResponse<ModelConfig>>) {
+        match instance_manager.get_model_config(&name).await {
+            Some(config) => (StatusCode::OK, JsonResponse(ApiResponse::success(config))),
+            None => (
+                StatusCode::NOT_FOUND,
+                JsonResponse(ApiResponse::error(
+                    format!("Model '{}' has no instances", name),
+                    StatusCode::NOT_FOUND,
+                )),
+            ),
+        }
     }
 
     /// Обновление конфигурации модели
     pub async fn update_model_config(
-        State(state): State<ApiState>,
+        State(instance_manager): State<Arc<InstanceManager>>,
         Path(name): Path<String>,
         Json(config): Json<ModelConfig>,
-    ) -> JsonResponse<ApiResponse<()>> {
-        match state.model_manager.update_config(config).await {
-            Ok(()) => JsonResponse(ApiResponse::success(())),
-            Err(e) => JsonResponse(ApiResponse::error(
-                e.to_string(),
-                StatusCode::INTERNAL_SERVER_ERROR,
-            )),
+    ) -> (StatusCode, JsonResponse<ApiResponse<ModelConfig>>) {
+        model_config_response(instance_manager.set_model_config(&name, config).await)
+    }
+
+    /// Частичное обновление конфигурации модели (JSON merge patch, RFC 7386)
+    pub async fn patch_model_config(
+        State(instance_manager): State<Arc<InstanceManager>>,
+        Path(name): Path<String>,
+        Json(patch): Json<serde_json::Value>,
+    ) -> (StatusCode, JsonResponse<ApiResponse<ModelConfig>>) {
+        model_config_response(instance_manager.patch_model_config(&name, &patch).await)
+    }
+
+    fn model_config_response(
+        result: Result<ModelConfig, AppError>,
+    ) -> (StatusCode, JsonResponse<ApiResponse<ModelConfig>>) {
+        match result {
+            Ok(config) => (StatusCode::OK, JsonResponse(ApiResponse::success(config))),
+            Err(e) => {
+                let status = match e {
+                    AppError::InvalidInput(_) => StatusCode::BAD_REQUEST,
+                    AppError::NotFound(_) => StatusCode::NOT_FOUND,
+                    _ => StatusCode::INTERNAL_SERVER_ERROR,
+                };
+                (status, JsonResponse(ApiResponse::error(e.to_string(), status)))
+            }
+        }
+    }
+
+    /// Получение параметров генерации по умолчанию
+    pub async fn get_inference_defaults(
+        State(state): State<ApiState>,
+        Path(name): Path<String>,
+    ) -> (StatusCode, JsonResponse<ApiResponse<InferenceDefaults>>) {
+        match state.instance_manager.get_inference_defaults(&name).await {
+            Some(defaults) => (StatusCode::OK, JsonResponse(ApiResponse::success(defaults))),
+            None => (
+                StatusCode::NOT_FOUND,
+                JsonResponse(ApiResponse::error(
+                    format!("Model '{}' not found", name),
+                    StatusCode::NOT_FOUND,
+                )),
+            ),
+        }
+    }
+
+    /// Обновление параметров генерации по умолчанию
+    pub async fn update_inference_defaults(
+        State(state): State<ApiState>,
+        Path(name): Path<String>,
+        Json(defaults): Json<InferenceDefaults>,
+    ) -> (StatusCode, JsonResponse<ApiResponse<InferenceDefaults>>) {
+        match state.instance_manager.set_inference_defaults(&name, defaults).await {
+            Ok(defaults) => (StatusCode::OK, JsonResponse(ApiResponse::success(defaults))),
+            Err(e) => {
+                let status = match e {
+                    AppError::InvalidInput(_) => StatusCode::BAD_REQUEST,
+                    AppError::NotFound(_) => StatusCode::NOT_FOUND,
+                    _ => StatusCode::INTERNAL_SERVER_ERROR,
+                };
+                (status, JsonResponse(ApiResponse::error(e.to_string(), status)))
+            }
         }
     }
 
@@ -470,22 +1440,130 @@ mod api {
 
     /// Получение списка воркеров
     pub async fn get_workers(State(state): State<ApiState>) -> JsonResponse<ApiResponse<Vec<WorkerInfo>>> {
-        // В реальной реализации получаем список воркеров
-        let workers = vec![
-            WorkerInfo {
-                id: "worker_001".to_string(),
-                name: "GPU Worker 1".to_string(),
-                status: WorkerStatus::Running,
-                gpu_usage: 85.5,
-                memory_usage: 12.3,
-                temperature: 72.0,
-                hash_rate: 95.2,
-            }
-        ];
-        
+        let metrics = state.worker_manager.get_worker_metrics().await;
+        let mut workers: Vec<WorkerInfo> = state
+            .worker_manager
+            .get_workers()
+            .await
+            .into_iter()
+            .map(|worker| WorkerInfo {
+                status: worker.status,
+                gpu_usage: worker.gpu_usage,
+                memory_usage: worker.memory_usage,
+                temperature: metrics
+                    .get(&worker.id)
+                    .and_then(|live| live.gpu_temperature)
+                    .unwrap_or(0.0),
+                hash_rate: worker.hashrate,
+                pool: None,
+                name: worker.name,
+                id: worker.id,
+            })
+            .collect();
+        workers.sort_by(|a, b| a.id.cmp(&b.id));
         JsonResponse(ApiResponse::success(workers))
     }
 
+    /// Список пулов
+    pub async fn list_pools(
+        State(pool_manager): State<Arc<PoolManager>>,
+    ) -> JsonResponse<ApiResponse<Vec<PoolMetrics>>> {
+        let mut pools = pool_manager.list_pools().await;
+        pools.sort_by(|a, b| a.config.name.cmp(&b.config.name));
+        JsonResponse(ApiResponse::success(pools))
+    }
+
+    /// Создание пула
+    pub async fn create_pool(
+        State(pool_manager): State<Arc<PoolManager>>,
+        Json(config): Json<PoolConfig>,
+    ) -> (StatusCode, JsonResponse<ApiResponse<PoolMetrics>>) {
+        let name = config.name.clone();
+        let created = match pool_manager.create_pool(config).await {
+            Ok(()) => pool_manager.get_pool(&name).await,
+            Err(e) => return pool_error_response(e),
+        };
+
+        match created {
+            Some(pool) => (StatusCode::CREATED, JsonResponse(ApiResponse::success(pool))),
+            None => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                JsonResponse(ApiResponse::error(
+                    format!("Pool '{}' disappeared after creation", name),
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            ),
+        }
+    }
+
+    /// Масштабирование пула до заданного числа воркеров
+    pub async fn scale_pool(
+        State(pool_manager): State<Arc<PoolManager>>,
+        Path(name): Path<String>,
+        Json(request): Json<ScaleRequest>,
+    ) -> (StatusCode, JsonResponse<ApiResponse<PoolStats>>) {
+        match pool_manager.scale_pool(&name, request.target_workers).await {
+            Ok(stats) => (StatusCode::OK, JsonResponse(ApiResponse::success(stats))),
+            Err(e) => pool_error_response(e),
+        }
+    }
+
+    /// Вход воркера в пул. Воркер с неразрешённой моделью GPU получает 403
+    pub async fn join_pool(
+        State(pool_manager): State<Arc<PoolManager>>,
+        Path(name): Path<String>,
+        Json(request): Json<JoinPoolRequest>,
+    ) -> (StatusCode, JsonResponse<ApiResponse<()>>) {
+        match pool_manager
+            .add_pool_worker(&name, &request.worker_id, &request.gpu_model, request.capabilities)
+            .await
+        {
+            Ok(()) => (StatusCode::CREATED, JsonResponse(ApiResponse::success(()))),
+            Err(e) => pool_error_response(e),
+        }
+    }
+
+    /// Воркеры, входящие в пул, со статусом и метриками из реестра воркеров.
+    /// Участник пула, которого нет в реестре, показывается как `Inactive`
+    pub async fn get_pool_workers(
+        State(state): State<ApiState>,
+        Path(name): Path<String>,
+    ) -> (StatusCode, JsonResponse<ApiResponse<Vec<WorkerInfo>>>) {
+        match state.pool_manager.get_pool_workers(&name).await {
+            Ok(members) => {
+                let metrics = state.worker_manager.get_worker_metrics().await;
+                let mut workers = Vec::with_capacity(members.len());
+                for member in members {
+                    let registered = state.worker_manager.get_worker(&member.worker_id).await;
+                    let live = metrics.get(&member.worker_id);
+                    workers.push(WorkerInfo {
+                        name: registered.map_or_else(|| member.worker_id.clone(), |worker| worker.name),
+                        status: live.map_or(WorkerStatus::Inactive, |live| live.status.clone()),
+                        gpu_usage: live.map_or(0.0, |live| live.gpu_usage),
+                        memory_usage: live.map_or(0.0, |live| live.memory_usage),
+                        temperature: live.and_then(|live| live.gpu_temperature).unwrap_or(0.0),
+                        hash_rate: live.map_or(0.0, |live| live.hashrate),
+                        pool: Some(name.clone()),
+                        id: member.worker_id,
+                    });
+                }
+                (StatusCode::OK, JsonResponse(ApiResponse::success(workers)))
+            }
+            Err(e) => pool_error_response(e),
+        }
+    }
+
+    /// Награды пула: до комиссии, комиссия и остаток воркерам
+    pub async fn get_pool_payouts(
+        State(pool_manager): State<Arc<PoolManager>>,
+        Path(name): Path<String>,
+    ) -> (StatusCode, JsonResponse<ApiResponse<PayoutSummary>>) {
+        match pool_manager.payout_summary(&name) {
+            Ok(summary) => (StatusCode::OK, JsonResponse(ApiResponse::success(summary))),
+            Err(e) => pool_error_response(e),
+        }
+    }
+
     /// Получение информации о воркере
     pub async fn get_worker(
         State(state): State<ApiState>,
@@ -494,11 +1572,12 @@ mod api {
         let worker = WorkerInfo {
             id: id.clone(),
             name: format!("Worker {}", id),
-            status: WorkerStatus::Running,
+            status: WorkerStatus::Active,
             gpu_usage: 85.5,
             memory_usage: 12.3,
             temperature: 72.0,
             hash_rate: 95.2,
+            pool: None,
         };
         
         JsonResponse(ApiResponse::success(worker))
@@ -509,7 +1588,7 @@ mod api {
         State(state): State<ApiState>,
         Path(id): Path<String>,
     ) -> JsonResponse<ApiResponse<WorkerStatus>> {
-        JsonResponse(ApiResponse::success(WorkerStatus::Running))
+        JsonResponse(ApiResponse::success(WorkerStatus::Active))
     }
 
     /// Получение информации о GPU
@@ -524,31 +1603,82 @@ mod api {
     }
 
     /// Оптимизация GPU
-    pub async fn optimize_gpu(State(state): State<ApiState>) -> JsonResponse<ApiResponse<()>> {
-        // В реальной реализации выполняем оптимизацию GPU
-        JsonResponse(ApiResponse::success(()))
+    pub async fn optimize_gpu(
+        State(state): State<ApiState>,
+    ) -> (StatusCode, JsonResponse<ApiResponse<GpuConfig>>) {
+        match state.gpu_manager.optimize().await {
+            Ok(config) => (StatusCode::OK, JsonResponse(ApiResponse::success(config))),
+            Err(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                JsonResponse(ApiResponse::error(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR)),
+            ),
+        }
+    }
+
+    /// Получение действующей конфигурации GPU
+    pub async fn get_gpu_config(
+        State(state): State<ApiState>,
+    ) -> (StatusCode, JsonResponse<ApiResponse<GpuConfig>>) {
+        match state.gpu_manager.get_config().await {
+            Ok(config) => (StatusCode::OK, JsonResponse(ApiResponse::success(config))),
+            Err(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                JsonResponse(ApiResponse::error(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR)),
+            ),
+        }
     }
 
-    /// Получение конфигурации GPU
-    pub async fn get_gpu_config(State(state): State<ApiState>) -> JsonResponse<ApiResponse<GpuConfig>> {
-        let config = GpuConfig {
-            power_limit: 250,
-            temperature_limit: 85.0,
-            memory_clock: 16000,
-            gpu_clock: 2000,
-            fan_speed: 80,
+    /// Рекомендации по троттлингу GPU для каждого воркера из реестра,
+    /// сообщившего температуру GPU
+    pub async fn get_gpu_throttle(
+        State(state): State<ApiState>,
+    ) -> (StatusCode, JsonResponse<ApiResponse<Vec<ThrottleRecommendation>>>) {
+        let config = match state.gpu_manager.get_config().await {
+            Ok(config) => config,
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    JsonResponse(ApiResponse::error(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR)),
+                )
+            }
         };
-        
-        JsonResponse(ApiResponse::success(config))
+
+        let mut recommendations: Vec<ThrottleRecommendation> = state
+            .worker_manager
+            .get_worker_metrics()
+            .await
+            .into_iter()
+            .filter_map(|(worker_id, metrics)| {
+                let temperature = metrics.gpu_temperature?;
+                Some(ThrottleRecommendation {
+                    action: recommend_throttle_with(temperature as f32, &config, &state.throttle_thresholds),
+                    worker_id,
+                    temperature,
+                })
+            })
+            .collect();
+        recommendations.sort_by(|a, b| a.worker_id.cmp(&b.worker_id));
+
+        (StatusCode::OK, JsonResponse(ApiResponse::success(recommendations)))
     }
 
-    /// Обновление конфигурации GPU
+    /// Обновление конфигурации GPU: проверка, атомарное применение
+    /// и возврат считанных после применения значений
     pub async fn update_gpu_config(
         State(state): State<ApiState>,
         Json(config): Json<GpuConfig>,
-    ) -> JsonResponse<ApiResponse<()>> {
-        // В реальной реализации применяем новую конфигурацию GPU
-        JsonResponse(ApiResponse::success(()))
+    ) -> (StatusCode, JsonResponse<ApiResponse<GpuConfig>>) {
+        match state.gpu_manager.apply_config(&config).await {
+            Ok(effective) => (StatusCode::OK, JsonResponse(ApiResponse::success(effective))),
+            Err(AppError::InvalidInput(msg)) => (
+                StatusCode::BAD_REQUEST,
+                JsonResponse(ApiResponse::error(msg, StatusCode::BAD_REQUEST)),
+            ),
+            Err(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                JsonResponse(ApiResponse::error(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR)),
+            ),
+        }
     }
 
     /// Получение информации о памяти
@@ -575,12 +1705,6 @@ mod api {
         JsonResponse(ApiResponse::success(()))
     }
 
-    /// Выключение системы
-    pub async fn shutdown_system(State(state): State<ApiState>) -> JsonResponse<ApiResponse<()>> {
-        // В реальной реализации выполняем выключение системы
-        JsonResponse(ApiResponse::success(()))
-    }
-
     /// Обновление системы
     pub async fn update_system(State(state): State<ApiState>) -> JsonResponse<ApiResponse<()>> {
         // В реальной реализации выполняем обновление системы
@@ -589,45 +1713,114 @@ mod api {
 
     /// Получение алертов
     pub async fn get_alerts(State(state): State<ApiState>) -> JsonResponse<ApiResponse<Vec<Alert>>> {
-        let alerts = vec![
-            Alert {
-                id: "alert_001".to_string(),
-                level: "warning".to_string(),
-                message: "GPU temperature is high".to_string(),
-                timestamp: chrono::Utc::now(),
-            }
-        ];
-        
+        let alerts = state
+            .alert_system
+            .active_alerts()
+            .await
+            .into_iter()
+            .map(|alert| Alert {
+                id: alert.rule_id,
+                level: alert.severity,
+                message: alert.message,
+                timestamp: alert.since,
+            })
+            .collect();
+
         JsonResponse(ApiResponse::success(alerts))
     }
 
-    /// Получение логов
+    /// Получение логов из кольцевого буфера с фильтром по уровню и пагинацией.
+    /// Общее число подходящих записей возвращается в заголовке `X-Total-Count`.
     pub async fn get_logs(
         State(state): State<ApiState>,
         Query(params): Query<LogParams>,
-    ) -> JsonResponse<ApiResponse<Vec<LogEntry>>> {
-        let logs = vec![
-            LogEntry {
-                level: "info".to_string(),
-                message: "System started successfully".to_string(),
-                timestamp: chrono::Utc::now(),
+    ) -> (StatusCode, HeaderMap, JsonResponse<ApiResponse<Vec<LogEntry>>>) {
+        let level = match params.level.as_deref().map(str::parse::<log::Level>) {
+            None => None,
+            Some(Ok(level)) => Some(level),
+            Some(Err(_)) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    HeaderMap::new(),
+                    JsonResponse(ApiResponse::error(
+                        format!("Unknown log level: {}", params.level.unwrap_or_default()),
+                        StatusCode::BAD_REQUEST,
+                    )),
+                )
             }
-        ];
-        
-        JsonResponse(ApiResponse::success(logs))
+        };
+        let limit = params.limit.unwrap_or(DEFAULT_LOG_PAGE).min(MAX_LOG_PAGE) as usize;
+        let offset = params.offset.unwrap_or(0) as usize;
+
+        let (total, entries) = state.log_buffer.query(level, offset, limit);
+        let logs = entries
+            .into_iter()
+            .map(|entry| LogEntry {
+                level: entry.level,
+                message: entry.message,
+                timestamp: entry.timestamp,
+            })
+            .collect();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(TOTAL_COUNT_HEADER, total.into());
+        (StatusCode::OK, headers, JsonResponse(ApiResponse::success(logs)))
     }
 
-    /// Получение событий
-    pub async fn get_events(State(state): State<ApiState>) -> JsonResponse<ApiResponse<Vec<Event>>> {
-        let events = vec![
-            Event {
-                id: "event_001".to_string(),
-                type_: "model_loaded".to_string(),
-                data: serde_json::json!({"model": "gpt-3.5-turbo"}),
-                timestamp: chrono::Utc::now(),
+    /// Поток логов воркера в виде SSE-событий `log`. `?tail=N` сначала отдаёт
+    /// последние N записей воркера. Подписка снимается, когда клиент отключается
+    /// и поток удаляется.
+    pub async fn stream_worker_logs(
+        State(log_buffer): State<Arc<LogBuffer>>,
+        Path(id): Path<String>,
+        Query(params): Query<WorkerLogStreamParams>,
+    ) -> axum::response::sse::Sse<impl futures::Stream<Item = Result<axum::response::sse::Event, std::convert::Infallible>>> {
+        use axum::response::sse::{Event, KeepAlive, Sse};
+        use futures::StreamExt;
+        use tokio::sync::broadcast::error::RecvError;
+
+        let tail = params.tail.unwrap_or(0).min(MAX_LOG_PAGE) as usize;
+        let (recent, receiver) = log_buffer.subscribe_worker(&id, tail);
+
+        let live = futures::stream::unfold((receiver, id), |(mut receiver, id)| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(entry) if entry.worker_id.as_deref() == Some(id.as_str()) => {
+                        return Some((entry, (receiver, id)));
+                    }
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(skipped)) => {
+                        log::warn!("Log stream for worker {} lagging, skipped {} entries", id, skipped);
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
             }
-        ];
-        
+        });
+
+        let events = futures::stream::iter(recent).chain(live).map(|entry| {
+            let event = Event::default()
+                .event("log")
+                .json_data(&entry)
+                .unwrap_or_else(|e| Event::default().event("error").data(e.to_string()));
+            Ok(event)
+        });
+
+        Sse::new(events).keep_alive(KeepAlive::default())
+    }
+
+    /// Получение событий
+    pub async fn get_events(
+        State(state): State<ApiState>,
+        Query(params): Query<EventParams>,
+    ) -> JsonResponse<ApiResponse<Vec<Event>>> {
+        let limit = params.limit.unwrap_or(DEFAULT_EVENT_PAGE).min(MAX_EVENT_PAGE) as usize;
+        let events = state
+            .event_bus
+            .recent(params.since, limit)
+            .into_iter()
+            .map(Event::from)
+            .collect();
+
         JsonResponse(ApiResponse::success(events))
     }
 
@@ -722,6 +1915,14 @@ pub struct SystemInfo {
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
+/// Рекомендация по троттлингу GPU воркера
+#[derive(Debug, Serialize)]
+pub struct ThrottleRecommendation {
+    pub worker_id: String,
+    pub temperature: f64,
+    pub action: ThrottleAction,
+}
+
 /// Информация о воркере
 #[derive(Debug, Serialize)]
 pub struct WorkerInfo {
@@ -732,16 +1933,9 @@ pub struct WorkerInfo {
     pub memory_usage: f64,
     pub temperature: f64,
     pub hash_rate: f64,
-}
-
-/// Конфигурация GPU
-#[derive(Debug, Serialize, Deserialize)]
-pub struct GpuConfig {
-    pub power_limit: u32,
-    pub temperature_limit: f64,
-    pub memory_clock: u32,
-    pub gpu_clock: u32,
-    pub fan_speed: u32,
+    /// Пул, в который входит воркер
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pool: Option<String>,
 }
 
 /// Информация о памяти
@@ -770,6 +1964,20 @@ pub struct LogParams {
     pub offset: Option<u32>,
 }
 
+/// Размер страницы логов по умолчанию
+pub const DEFAULT_LOG_PAGE: u32 = 100;
+/// Максимальный размер страницы логов
+pub const MAX_LOG_PAGE: u32 = 1000;
+/// Заголовок с общим числом записей для пагинации
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+
+/// Параметры потока логов воркера
+#[derive(Debug, Deserialize)]
+pub struct WorkerLogStreamParams {
+    /// Сколько последних записей отправить перед новыми
+    pub tail: Option<u32>,
+}
+
 /// Запись лога
 #[derive(Debug, Serialize)]
 pub struct LogEntry {
@@ -779,7 +1987,7 @@ pub struct LogEntry {
 }
 
 /// Событие
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Event {
     pub id: String,
     pub type_: String,
@@ -787,6 +1995,30 @@ pub struct Event {
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
+impl From<SystemEvent> for Event {
+    fn from(event: SystemEvent) -> Self {
+        Self {
+            id: format!("event_{:06}", event.id),
+            type_: event.kind.as_str().to_string(),
+            data: event.data,
+            timestamp: event.timestamp,
+        }
+    }
+}
+
+/// Параметры выборки событий
+#[derive(Debug, Deserialize)]
+pub struct EventParams {
+    /// Только события новее этого момента
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    pub limit: Option<u32>,
+}
+
+/// Число событий в ответе по умолчанию
+pub const DEFAULT_EVENT_PAGE: u32 = 100;
+/// Максимальное число событий в ответе
+pub const MAX_EVENT_PAGE: u32 = 1000;
+
 /// API ответ
 #[derive(Debug, Serialize)]
 pub struct ApiResponse<T> {
@@ -794,6 +2026,9 @@ pub struct ApiResponse<T> {
     pub data: Option<T>,
     pub error: Option<String>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Идентификатор запроса, совпадает с заголовком `X-Request-Id`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 impl<T> ApiResponse<T> {
@@ -803,15 +2038,1086 @@ impl<T> ApiResponse<T> {
             data: Some(data),
             error: None,
             timestamp: chrono::Utc::now(),
+            request_id: current_request_id(),
         }
     }
 
-    pub fn error(message: String, _status: StatusCode) -> Self {
+    pub fn error(message: String, status: StatusCode) -> Self {
+        let request_id = current_request_id();
+        // Ошибки, требующие внимания, логируют сами обработчики
+        log::debug!(
+            "request_id={} status={} error: {}",
+            request_id.as_deref().unwrap_or("-"),
+            status.as_u16(),
+            message
+        );
         Self {
             success: false,
             data: None,
             error: Some(message),
             timestamp: chrono::Utc::now(),
+            request_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    /// Состояние API с заглушкой модели `dummy` (контекст 1024 токена)
+    fn test_api_state() -> ApiState {
+        ApiState {
+            model_manager: Arc::new(instance::DummyModel::new()),
+            instance_manager: Arc::new(InstanceManager::new(instance::InstanceManagerConfig {
+                initial_models: vec![],
+                ..instance::InstanceManagerConfig::default()
+            })),
+            gpu_manager: Arc::new(GpuManager::with_control(Arc::new(crate::platform::gpu::StubGpuControl::new()))),
+            system_metrics: Arc::new(RwLock::new(SystemMetrics::default())),
+            rate_limiter: Arc::new(RateLimiter::new(1000, 60)),
+            model_rate_limiter: Arc::new(ModelRateLimiter::new(HashMap::new()).unwrap()),
+            trace_sampler: Arc::new(TraceSampler::new(0.0)),
+            log_buffer: Arc::new(LogBuffer::new(16)),
+            pool_manager: Arc::new(PoolManager::new()),
+            worker_manager: Arc::new(WorkerManager::new()),
+            event_bus: Arc::new(EventBus::new(16)),
+            alert_system: Arc::new(AlertSystem::new()),
+            throttle_thresholds: ThrottleThresholds::default(),
+            health_probe: HealthProbe::system(),
+            tokenizer: Arc::new(Tokenizer::new()),
+        }
+    }
+
+    fn headers_with_deadline(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_DEADLINE_HEADER, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_parse_relative_deadline() {
+        let before = Instant::now();
+        let deadline = parse_request_deadline(&headers_with_deadline("2s")).unwrap().unwrap();
+        assert!(deadline >= before + Duration::from_secs(2));
+        assert!(deadline < before + Duration::from_secs(3));
+
+        let deadline = parse_request_deadline(&headers_with_deadline("250ms")).unwrap().unwrap();
+        assert!(deadline < before + Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_parse_absolute_deadline() {
+        let at = (chrono::Utc::now() + chrono::Duration::seconds(5)).to_rfc3339();
+        let deadline = parse_request_deadline(&headers_with_deadline(&at)).unwrap().unwrap();
+        assert!(deadline > Instant::now() + Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_parse_missing_and_invalid_deadline() {
+        assert!(parse_request_deadline(&HeaderMap::new()).unwrap().is_none());
+        assert!(parse_request_deadline(&headers_with_deadline("soon")).is_err());
+    }
+
+    #[test]
+    fn test_parse_out_of_range_deadline_rejected() {
+        for value in ["1e20s", "-1s", "NaNs", "infs"] {
+            assert!(parse_request_deadline(&headers_with_deadline(value)).is_err(), "{}", value);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_model_rate_limit_sends_retry_after() {
+        use tower::ServiceExt;
+
+        let mut state = test_api_state();
+        let mut limits = HashMap::new();
+        limits.insert("llama".to_string(), 1.0);
+        state.model_rate_limiter = Arc::new(ModelRateLimiter::new(limits).unwrap());
+        assert!(state.model_rate_limiter.check("llama").is_ok());
+
+        let app = Router::new()
+            .route("/api/v1/models/:name/stream", post(api::stream_request))
+            .with_state(state);
+        let request = axum::http::Request::builder()
+            .method("POST")
+            .uri("/api/v1/models/llama/stream")
+            .header("Content-Type", "application/json")
+            .body(axum::body::Body::from(serde_json::json!({ "prompt": "hello" }).to_string()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[axum::http::header::RETRY_AFTER], "1");
+    }
+
+    #[test]
+    fn test_model_rate_limit_throttles_only_that_model() {
+        let mut limits = HashMap::new();
+        limits.insert("busy".to_string(), 2.0);
+        limits.insert("other".to_string(), 2.0);
+        let limiter = ModelRateLimiter::new(limits).unwrap();
+        let now = Instant::now();
+
+        assert!(limiter.check_at("busy", now).is_ok());
+        assert!(limiter.check_at("busy", now).is_ok());
+        let retry_after = limiter.check_at("busy", now).unwrap_err();
+        assert!(retry_after > Duration::ZERO && retry_after <= Duration::from_millis(500));
+
+        // Другая модель не затронута
+        assert!(limiter.check_at("other", now).is_ok());
+        // Модель без лимита не ограничивается
+        for _ in 0..100 {
+            assert!(limiter.check_at("unlimited", now).is_ok());
+        }
+
+        // После пополнения токенов запросы снова проходят
+        assert!(limiter.check_at("busy", now + Duration::from_millis(500)).is_ok());
+    }
+
+    #[test]
+    fn test_model_rate_limit_rejects_non_positive_and_caps_wait() {
+        for rate in [0.0, -1.0, f64::NAN] {
+            let limits = HashMap::from([("broken".to_string(), rate)]);
+            assert!(ModelRateLimiter::new(limits).is_err());
+        }
+
+        let limits = HashMap::from([("trickle".to_string(), 1e-300)]);
+        let limiter = ModelRateLimiter::new(limits).unwrap();
+        let now = Instant::now();
+        assert!(limiter.check_at("trickle", now).is_ok());
+        assert_eq!(limiter.check_at("trickle", now).unwrap_err(), MAX_MODEL_RETRY_AFTER);
+    }
+
+    fn limited_app(limiter: Arc<RateLimiter>) -> Router {
+        Router::new()
+            .route("/limited", get(|| async { StatusCode::OK }))
+            .layer(axum::middleware::from_fn_with_state(limiter, rate_limit_middleware))
+    }
+
+    async fn call_limited_response(
+        app: &Router,
+        token: Option<&str>,
+        ip: [u8; 4],
+    ) -> axum::response::Response {
+        use tower::ServiceExt;
+
+        let mut builder = axum::http::Request::builder().uri("/limited");
+        if let Some(token) = token {
+            builder = builder.header("Authorization", format!("Bearer {}", token));
         }
+        let mut request = builder.body(axum::body::Body::empty()).unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from((ip, 4000))));
+        app.clone().oneshot(request).await.unwrap()
     }
-} 
\ No newline at end of file
+
+    async fn call_limited(app: &Router, token: Option<&str>, ip: [u8; 4]) -> StatusCode {
+        call_limited_response(app, token, ip).await.status()
+    }
+
+    #[test]
+    fn test_client_id_uses_peer_ip() {
+        assert_eq!(ApiState::client_id_from(SocketAddr::from(([10, 0, 0, 1], 1234))), "ip:10.0.0.1");
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_per_client() {
+        let limit = 3;
+        let app = limited_app(Arc::new(RateLimiter::new(limit, 60)));
+
+        for _ in 0..limit {
+            assert_eq!(call_limited(&app, Some("alpha"), [10, 0, 0, 1]).await, StatusCode::OK);
+        }
+        assert_eq!(
+            call_limited(&app, Some("alpha"), [10, 0, 0, 1]).await,
+            StatusCode::TOO_MANY_REQUESTS
+        );
+
+        // Смена непроверенного токена не сбрасывает лимит адреса
+        assert_eq!(
+            call_limited(&app, Some("beta"), [10, 0, 0, 1]).await,
+            StatusCode::TOO_MANY_REQUESTS
+        );
+        assert_eq!(call_limited(&app, None, [10, 0, 0, 2]).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_forgets_idle_clients() {
+        let limiter = RateLimiter::new(3, 60);
+        for client in 0..100 {
+            limiter.check_rate_limit_at(&format!("ip:10.0.0.{}", client), 1_000).await.unwrap();
+        }
+        assert_eq!(limiter.tracked_clients().await, 100);
+
+        limiter.check_rate_limit_at("ip:10.0.1.1", 1_060).await.unwrap();
+        assert_eq!(limiter.tracked_clients().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_changes_without_restart() {
+        let limiter = RateLimiter::new(1, 60);
+        assert!(limiter.check_rate_limit_at("ip:10.0.0.1", 1_000).await.unwrap().allowed);
+        assert!(!limiter.check_rate_limit_at("ip:10.0.0.1", 1_001).await.unwrap().allowed);
+
+        limiter.set_limit(3);
+        let status = limiter.check_rate_limit_at("ip:10.0.0.1", 1_002).await.unwrap();
+        assert!(status.allowed);
+        assert_eq!(status.limit, 3);
+        assert_eq!(status.remaining, 1);
+    }
+
+    #[test]
+    fn test_oversize_prompt_rejected() {
+        let tokenizer = Tokenizer::new();
+        let prompt = vec!["word"; 9].join(" ");
+
+        assert!(ensure_prompt_fits(&tokenizer, &prompt, 9).is_ok());
+        match ensure_prompt_fits(&tokenizer, &prompt, 8) {
+            Err(AppError::InvalidInput(msg)) => {
+                assert!(msg.contains("9 tokens"));
+                assert!(msg.contains("context length of 8"));
+            }
+            other => panic!("expected InvalidInput, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_headers_count_down() {
+        let limit = 3;
+        let app = limited_app(Arc::new(RateLimiter::new(limit, 60)));
+        let header = |response: &axum::response::Response, name: &str| -> u64 {
+            response.headers()[name].to_str().unwrap().parse().unwrap()
+        };
+
+        let mut reset_at = None;
+        for expected in (0..limit as u64).rev() {
+            let response = call_limited_response(&app, Some("alpha"), [10, 0, 0, 1]).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(header(&response, RATE_LIMIT_LIMIT_HEADER), limit as u64);
+            assert_eq!(header(&response, RATE_LIMIT_REMAINING_HEADER), expected);
+            assert!(response.headers().get(axum::http::header::RETRY_AFTER).is_none());
+            reset_at.get_or_insert(header(&response, RATE_LIMIT_RESET_HEADER));
+        }
+
+        let response = call_limited_response(&app, Some("alpha"), [10, 0, 0, 1]).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(header(&response, RATE_LIMIT_REMAINING_HEADER), 0);
+        assert_eq!(Some(header(&response, RATE_LIMIT_RESET_HEADER)), reset_at);
+        let retry_after = header(&response, axum::http::header::RETRY_AFTER.as_str());
+        assert!((1..=60).contains(&retry_after));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_in_flight_request() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let app = Router::new().route(
+            "/slow",
+            get(|| async {
+                tokio::time::sleep(Duration::from_millis(300)).await;
+                "done"
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let shutdown = Arc::new(ServerShutdown::new());
+        let server = {
+            let shutdown = shutdown.clone();
+            tokio::spawn(async move { shutdown.serve(listener, app, Duration::from_secs(5)).await })
+        };
+
+        let client = tokio::spawn(async move {
+            let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(b"GET /slow HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+                .await
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        });
+
+        // Запрос уже обрабатывается, когда приходит сигнал остановки
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(shutdown.is_running());
+        shutdown.shutdown(Duration::from_secs(5)).await.unwrap();
+
+        let response = client.await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.ends_with("done"));
+        assert!(!shutdown.is_running());
+        server.await.unwrap().unwrap();
+    }
+
+    async fn cors_request(config: &ApiConfig, origin: &str) -> axum::http::Response<axum::body::Body> {
+        use tower::ServiceExt;
+
+        let mut app = Router::new().route("/cors", get(|| async { "ok" }));
+        if let Some(cors) = cors_layer(config) {
+            app = app.layer(cors);
+        }
+        let request = axum::http::Request::builder()
+            .uri("/cors")
+            .header("Origin", origin)
+            .body(axum::body::Body::empty())
+            .unwrap();
+        app.oneshot(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cors_restricts_to_configured_origins() {
+        let config = ApiConfig {
+            enable_cors: true,
+            cors_origins: vec!["https://pool.example.com".to_string()],
+            ..ApiConfig::default()
+        };
+
+        let allowed = cors_request(&config, "https://pool.example.com").await;
+        assert_eq!(
+            allowed.headers().get("access-control-allow-origin").unwrap(),
+            "https://pool.example.com"
+        );
+        assert_eq!(
+            allowed.headers().get("access-control-allow-credentials").unwrap(),
+            "true"
+        );
+
+        let denied = cors_request(&config, "https://evil.example.com").await;
+        assert!(denied.headers().get("access-control-allow-origin").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cors_exposes_custom_headers() {
+        let restricted = ApiConfig {
+            enable_cors: true,
+            cors_origins: vec!["https://pool.example.com".to_string()],
+            ..ApiConfig::default()
+        };
+        let wildcard = ApiConfig {
+            cors_origins: vec!["*".to_string()],
+            ..restricted.clone()
+        };
+
+        for config in [restricted, wildcard] {
+            let response = cors_request(&config, "https://pool.example.com").await;
+            let exposed = response
+                .headers()
+                .get("access-control-expose-headers")
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .to_lowercase();
+            for header in [
+                REQUEST_ID_HEADER,
+                CORRELATION_ID_HEADER,
+                TRACE_SAMPLED_HEADER,
+                RATE_LIMIT_REMAINING_HEADER,
+                "retry-after",
+            ] {
+                assert!(exposed.contains(&header.to_lowercase()), "{} not in {}", header, exposed);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cors_wildcard_and_disabled() {
+        let wildcard = ApiConfig {
+            enable_cors: true,
+            cors_origins: vec!["*".to_string()],
+            ..ApiConfig::default()
+        };
+        let response = cors_request(&wildcard, "https://any.example.com").await;
+        assert_eq!(response.headers().get("access-control-allow-origin").unwrap(), "*");
+
+        let disabled = ApiConfig {
+            enable_cors: false,
+            ..wildcard
+        };
+        let response = cors_request(&disabled, "https://any.example.com").await;
+        assert!(response.headers().get("access-control-allow-origin").is_none());
+    }
+
+    fn probe_with(results: Vec<(&'static str, Result<(), String>)>) -> HealthProbe {
+        HealthProbe::new(move || {
+            let results = results.clone();
+            Box::pin(async move { crate::summarize_health(results) })
+        })
+    }
+
+    async fn call_health(probe: HealthProbe) -> (StatusCode, serde_json::Value) {
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route("/api/v1/health", get(api::get_health))
+            .with_state(probe);
+        let request = axum::http::Request::builder()
+            .uri("/api/v1/health")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_failed_module() {
+        let (status, body) = call_health(probe_with(vec![
+            ("core", Ok(())),
+            ("pool", Ok(())),
+            ("vm", Err("hypervisor unreachable".to_string())),
+        ]))
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["status"], "warning");
+        assert_eq!(body["data"]["checks"][2]["name"], "vm");
+        assert_eq!(body["data"]["checks"][2]["status"], "unhealthy");
+        assert_eq!(body["data"]["checks"][2]["message"], "hypervisor unreachable");
+
+        let (status, body) = call_health(probe_with(vec![
+            ("core", Ok(())),
+            ("pool", Err("storage unavailable".to_string())),
+        ]))
+        .await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["data"]["status"], "critical");
+
+        let (status, body) = call_health(probe_with(vec![("core", Ok(()))])).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["status"], "healthy");
+    }
+
+    #[tokio::test]
+    async fn test_version_endpoint_returns_git_sha() {
+        use tower::ServiceExt;
+
+        let app: Router = Router::new().route("/api/v1/version", get(api::get_version));
+        let request = axum::http::Request::builder()
+            .uri("/api/v1/version")
+            .body(axum::body::Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let expected = serde_json::to_value(BuildInfo::current()).unwrap();
+        assert_eq!(body["data"], expected);
+    }
+
+    #[tokio::test]
+    async fn test_list_pool_workers() {
+        use tower::ServiceExt;
+
+        let state = test_api_state();
+        let pool_manager = state.pool_manager.clone();
+        pool_manager.create_pool(crate::pool::test_pool_config("gpu-pool")).await.unwrap();
+        pool_manager.add_pool_worker("gpu-pool", "worker-b", "", vec![]).await.unwrap();
+        pool_manager.add_pool_worker("gpu-pool", "worker-a", "", vec!["cuda".to_string()]).await.unwrap();
+
+        // Зарегистрирован только worker-a
+        state
+            .worker_manager
+            .add_worker(crate::workers::Worker {
+                id: "worker-a".to_string(),
+                name: "GPU A".to_string(),
+                status: WorkerStatus::Busy,
+                hashrate: 42.0,
+                cpu_usage: 10.0,
+                memory_usage: 30.0,
+                gpu_usage: 75.0,
+                uptime: Duration::from_secs(60),
+                last_seen: chrono::Utc::now(),
+                capabilities: vec!["cuda".to_string()],
+                calibrated_hashrate: None,
+                endpoint: None,
+            })
+            .await
+            .unwrap();
+
+        let app = Router::new()
+            .route("/api/v1/pools/:name/workers", get(api::get_pool_workers))
+            .with_state(state);
+        let get = |uri: &str| {
+            axum::http::Request::builder()
+                .uri(uri)
+                .body(axum::body::Body::empty())
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(get("/api/v1/pools/gpu-pool/workers")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let ids: Vec<_> = body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|worker| worker["id"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(ids, vec!["worker-a", "worker-b"]);
+        assert_eq!(body["data"][0]["pool"], "gpu-pool");
+        assert_eq!(body["data"][0]["name"], "GPU A");
+        assert_eq!(body["data"][0]["status"], "Busy");
+        assert_eq!(body["data"][0]["gpu_usage"], 75.0);
+        assert_eq!(body["data"][0]["hash_rate"], 42.0);
+        assert_eq!(body["data"][1]["status"], "Inactive");
+        assert_eq!(body["data"][1]["hash_rate"], 0.0);
+
+        let response = app.oneshot(get("/api/v1/pools/missing/workers")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_gpu_throttle_uses_registered_workers() {
+        let state = test_api_state();
+        let limit = state.gpu_manager.get_config().await.unwrap().temperature_limit;
+
+        for (id, temperature) in [("cool", Some(limit - 30.0)), ("hot", Some(limit + 1.0)), ("silent", None)] {
+            let worker = crate::workers::Worker {
+                id: id.to_string(),
+                name: id.to_string(),
+                status: WorkerStatus::Active,
+                hashrate: 1.0,
+                cpu_usage: 0.0,
+                memory_usage: 0.0,
+                gpu_usage: 0.0,
+                uptime: Duration::from_secs(0),
+                last_seen: chrono::Utc::now(),
+                capabilities: vec![],
+                calibrated_hashrate: None,
+                endpoint: None,
+            };
+            let metrics = crate::monitoring::metrics::WorkerMetrics {
+                gpu_temperature: temperature,
+                ..crate::monitoring::metrics::WorkerMetrics::from(&worker)
+            };
+            state.worker_manager.add_worker(worker).await.unwrap();
+            state.worker_manager.update_worker_metrics(id, metrics).await.unwrap();
+        }
+
+        let (status, JsonResponse(body)) = api::get_gpu_throttle(State(state)).await;
+        assert_eq!(status, StatusCode::OK);
+        // Воркер без температуры не попадает в рекомендации
+        let recommendations: Vec<(String, ThrottleAction)> = body
+            .data
+            .unwrap()
+            .into_iter()
+            .map(|r| (r.worker_id, r.action))
+            .collect();
+        assert_eq!(
+            recommendations,
+            vec![("cool".to_string(), ThrottleAction::None), ("hot".to_string(), ThrottleAction::Shutdown)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_join_pool_checks_gpu_model() {
+        use tower::ServiceExt;
+
+        let pool_manager = Arc::new(PoolManager::new());
+        pool_manager
+            .create_pool(PoolConfig {
+                allowed_gpu_models: vec!["RTX 4090".to_string()],
+                ..crate::pool::test_pool_config("rtx")
+            })
+            .await
+            .unwrap();
+        let app = Router::new()
+            .route("/api/v1/pools/:name/workers", post(api::join_pool))
+            .with_state(pool_manager.clone());
+        let join = |worker: &str, gpu: &str| {
+            axum::http::Request::builder()
+                .method("POST")
+                .uri("/api/v1/pools/rtx/workers")
+                .header("Content-Type", "application/json")
+                .body(axum::body::Body::from(
+                    serde_json::json!({ "worker_id": worker, "gpu_model": gpu }).to_string(),
+                ))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(join("w1", "GTX 1060")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = app.oneshot(join("w2", "rtx 4090")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let members = pool_manager.get_pool_workers("rtx").await.unwrap();
+        assert_eq!(members.iter().map(|w| w.worker_id.as_str()).collect::<Vec<_>>(), vec!["w2"]);
+    }
+
+    #[tokio::test]
+    async fn test_pool_payouts_report_fees() {
+        use tower::ServiceExt;
+
+        let pool_manager = Arc::new(PoolManager::new());
+        let config = crate::pool::PoolConfig {
+            fee_percentage: 10.0,
+            ..crate::pool::test_pool_config("fee-pool")
+        };
+        pool_manager.create_pool(config).await.unwrap();
+        pool_manager.credit_reward("fee-pool", 5.0).unwrap();
+        pool_manager.credit_reward("fee-pool", 15.0).unwrap();
+
+        let app = Router::new()
+            .route("/api/v1/pools/:name/payouts", get(api::get_pool_payouts))
+            .with_state(pool_manager);
+        let get = |uri: &str| {
+            axum::http::Request::builder()
+                .uri(uri)
+                .body(axum::body::Body::empty())
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(get("/api/v1/pools/fee-pool/payouts")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let summary: PayoutSummary = serde_json::from_value(body["data"].clone()).unwrap();
+        assert!((summary.gross_rewards - 20.0).abs() < 1e-9);
+        assert!((summary.fees - 2.0).abs() < 1e-9);
+        assert!((summary.net_rewards - 18.0).abs() < 1e-9);
+
+        let response = app.oneshot(get("/api/v1/pools/missing/payouts")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_pool_routes_create_list_and_scale() {
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route("/api/v1/pools", get(api::list_pools).post(api::create_pool))
+            .route("/api/v1/pools/:name/scale", post(api::scale_pool))
+            .with_state(Arc::new(PoolManager::new()));
+        let send = |method: &str, uri: &str, body: serde_json::Value| {
+            axum::http::Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("Content-Type", "application/json")
+                .body(axum::body::Body::from(body.to_string()))
+                .unwrap()
+        };
+        let read = |response: axum::response::Response| async move {
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+        let pool = serde_json::to_value(crate::pool::PoolConfig {
+            auto_scale: true,
+            min_workers: 1,
+            ..crate::pool::test_pool_config("scalable")
+        })
+        .unwrap();
+
+        let response = app.clone().oneshot(send("POST", "/api/v1/pools", pool.clone())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(read(response).await["data"]["config"]["name"], "scalable");
+
+        let response = app.clone().oneshot(send("POST", "/api/v1/pools", pool)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = app
+            .clone()
+            .oneshot(send("GET", "/api/v1/pools", serde_json::Value::Null))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let pools = read(response).await;
+        assert_eq!(pools["data"].as_array().unwrap().len(), 1);
+
+        let scale = |target: u32| serde_json::json!({ "target_workers": target });
+        let response = app
+            .clone()
+            .oneshot(send("POST", "/api/v1/pools/scalable/scale", scale(3)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(read(response).await["data"]["total_workers"], 3);
+
+        let response = app
+            .clone()
+            .oneshot(send("POST", "/api/v1/pools/scalable/scale", scale(10)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app
+            .oneshot(send("POST", "/api/v1/pools/missing/scale", scale(2)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_pool_mutations_require_token() {
+        use tower::ServiceExt;
+
+        let config = ApiConfig {
+            enable_auth: true,
+            auth_tokens: vec!["secret".to_string()],
+            ..ApiConfig::default()
+        };
+        let auth = axum::middleware::from_fn_with_state(ApiAuth::from_config(&config), auth_middleware);
+        let app = Router::new()
+            .route("/api/v1/pools", post(api::create_pool).route_layer(auth))
+            .with_state(Arc::new(PoolManager::new()));
+        let create = |token: Option<&str>| {
+            let mut builder = axum::http::Request::builder()
+                .method("POST")
+                .uri("/api/v1/pools")
+                .header("Content-Type", "application/json");
+            if let Some(token) = token {
+                builder = builder.header("Authorization", format!("Bearer {}", token));
+            }
+            let body = serde_json::to_string(&crate::pool::test_pool_config("guarded")).unwrap();
+            builder.body(axum::body::Body::from(body)).unwrap()
+        };
+
+        assert_eq!(app.clone().oneshot(create(None)).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(app.clone().oneshot(create(Some("wrong"))).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(app.oneshot(create(Some("secret"))).await.unwrap().status(), StatusCode::CREATED);
+    }
+
+    /// Статус запроса без токена к роутеру сервера с включённой авторизацией
+    async fn status_without_token(method: &str, uri: &str) -> StatusCode {
+        use tower::ServiceExt;
+
+        let config = ApiConfig {
+            enable_auth: true,
+            auth_tokens: vec!["secret".to_string()],
+            ..ApiConfig::default()
+        };
+        let server = ApiServer::new(test_api_state(), config);
+        let mut request = axum::http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/json")
+            .body(axum::body::Body::from("{}"))
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+        server.router.clone().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_server_mutations_require_token() {
+        for (method, uri) in [
+            ("PATCH", "/api/v1/models/llama/config"),
+            ("PUT", "/api/v1/models/llama/inference-defaults"),
+            ("POST", "/api/v1/gpu/optimize"),
+            ("PUT", "/api/v1/gpu/config"),
+        ] {
+            assert_eq!(status_without_token(method, uri).await, StatusCode::UNAUTHORIZED, "{} {}", method, uri);
+        }
+        assert_ne!(status_without_token("GET", "/api/v1/models/llama/config").await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_patch_model_config_merges_single_field() {
+        use tower::ServiceExt;
+
+        let instance_manager = Arc::new(InstanceManager::new(instance::InstanceManagerConfig {
+            initial_models: vec![],
+            ..Default::default()
+        }));
+        for _ in 0..2 {
+            instance_manager
+                .create_instance(
+                    "llama".to_string(),
+                    Arc::new(instance::DummyModel::new()),
+                    instance::default_model_config("llama"),
+                )
+                .await
+                .unwrap();
+        }
+        let before = instance_manager.get_model_config("llama").await.unwrap();
+        let app = Router::new()
+            .route("/api/v1/models/:name/config", patch(api::patch_model_config))
+            .with_state(instance_manager.clone());
+        let send = |uri: &str, body: serde_json::Value| {
+            axum::http::Request::builder()
+                .method("PATCH")
+                .uri(uri)
+                .header("Content-Type", "application/merge-patch+json")
+                .body(axum::body::Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(send(
+                "/api/v1/models/llama/config",
+                serde_json::json!({ "inference": { "default_temperature": 1.2 }, "model_path": null }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let after = instance_manager.get_model_config("llama").await.unwrap();
+        assert_eq!(after.inference.default_temperature, 1.2);
+        assert_eq!(after.model_path, None);
+        assert_eq!(after.inference.default_top_p, before.inference.default_top_p);
+        assert_eq!(after.inference.default_max_tokens, before.inference.default_max_tokens);
+        assert_eq!(after.performance.batch_size, before.performance.batch_size);
+        assert_eq!(after.device.memory_fraction, before.device.memory_fraction);
+        assert_eq!(after.memory.max_memory_usage, before.memory.max_memory_usage);
+        for info in instance_manager.list_instances().await {
+            let instance = instance_manager.get_instance(&info.id).await.unwrap();
+            assert_eq!(instance.config.inference.default_temperature, 1.2);
+        }
+
+        let response = app
+            .clone()
+            .oneshot(send(
+                "/api/v1/models/llama/config",
+                serde_json::json!({ "performance": { "batch_size": 0 } }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let unchanged = instance_manager.get_model_config("llama").await.unwrap();
+        assert_eq!(unchanged.performance.batch_size, before.performance.batch_size);
+
+        let response = app
+            .oneshot(send("/api/v1/models/missing/config", serde_json::json!({})))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_model_config_endpoints_share_one_store() {
+        use tower::ServiceExt;
+
+        let instance_manager = Arc::new(InstanceManager::new(instance::InstanceManagerConfig {
+            initial_models: vec![],
+            ..Default::default()
+        }));
+        instance_manager
+            .create_instance(
+                "llama".to_string(),
+                Arc::new(instance::DummyModel::new()),
+                instance::default_model_config("llama"),
+            )
+            .await
+            .unwrap();
+        let app = Router::new()
+            .route(
+                "/api/v1/models/:name/config",
+                get(api::get_model_config).put(api::update_model_config).patch(api::patch_model_config),
+            )
+            .with_state(instance_manager.clone());
+        let send = |method: &str, body: Option<serde_json::Value>| {
+            let builder = axum::http::Request::builder()
+                .method(method)
+                .uri("/api/v1/models/llama/config")
+                .header("Content-Type", "application/json");
+            match body {
+                Some(body) => builder.body(axum::body::Body::from(body.to_string())).unwrap(),
+                None => builder.body(axum::body::Body::empty()).unwrap(),
+            }
+        };
+        let read = |response: axum::response::Response| async move {
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+
+        let mut config = instance::default_model_config("llama");
+        config.performance.timeout_seconds = 45;
+        let response = app.clone().oneshot(send("PUT", Some(serde_json::to_value(&config).unwrap()))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = app
+            .clone()
+            .oneshot(send("PATCH", Some(serde_json::json!({ "inference": { "default_max_tokens": 64 } }))))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // GET отдаёт то, что записали PUT и PATCH
+        let response = app.oneshot(send("GET", None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = read(response).await;
+        assert_eq!(body["data"]["performance"]["timeout_seconds"], 45);
+        assert_eq!(body["data"]["inference"]["default_max_tokens"], 64);
+    }
+
+    #[tokio::test]
+    async fn test_worker_log_stream_filters_by_worker() {
+        use crate::monitoring::logger::{with_worker_id, LoggerConfig, LoggerSystem};
+        use futures::StreamExt;
+        use tower::ServiceExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let logger = Arc::new(LoggerSystem::new());
+        logger
+            .add_logger(LoggerConfig {
+                id: "workers".to_string(),
+                name: "workers".to_string(),
+                description: String::new(),
+                log_level: "info".to_string(),
+                log_file: dir.path().join("workers.log").to_string_lossy().into_owned(),
+                max_file_size: 1024 * 1024,
+                max_files: 1,
+                active: true,
+            })
+            .await
+            .unwrap();
+        let emit = |worker_id: &'static str, message: &'static str| {
+            let logger = logger.clone();
+            with_worker_id(worker_id.to_string(), async move {
+                logger.log("workers", "info", message, HashMap::new()).await.unwrap();
+            })
+        };
+
+        emit("w1", "before 1").await;
+        emit("w1", "before 2").await;
+        emit("w2", "other worker").await;
+
+        let log_buffer = logger.buffer();
+        let app = Router::new()
+            .route("/api/v1/workers/:id/logs/stream", get(api::stream_worker_logs))
+            .with_state(log_buffer.clone());
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/v1/workers/w1/logs/stream?tail=1")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(log_buffer.subscriber_count(), 1);
+
+        emit("w1", "after 1").await;
+        emit("w2", "other worker again").await;
+        emit("w1", "after 2").await;
+
+        let mut body = response.into_body().into_data_stream();
+        let mut received = String::new();
+        while !received.contains("after 2") {
+            let chunk = tokio::time::timeout(Duration::from_secs(5), body.next())
+                .await
+                .expect("log line did not arrive")
+                .unwrap()
+                .unwrap();
+            received.push_str(std::str::from_utf8(&chunk).unwrap());
+        }
+
+        let messages: Vec<String> = received
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .map(|data| {
+                let entry: serde_json::Value = serde_json::from_str(data).unwrap();
+                assert_eq!(entry["worker_id"], "w1");
+                entry["message"].as_str().unwrap().to_string()
+            })
+            .collect();
+        assert_eq!(messages, vec!["before 2", "after 1", "after 2"]);
+
+        drop(body);
+        assert_eq!(log_buffer.subscriber_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_collector_updates_system_metrics() {
+        let state = test_api_state();
+        state
+            .pool_manager
+            .create_pool(crate::pool::PoolConfig {
+                auto_scale: true,
+                min_workers: 1,
+                ..crate::pool::test_pool_config("scalable")
+            })
+            .await
+            .unwrap();
+        state.pool_manager.scale_pool("scalable", 2).await.unwrap();
+
+        let collector = state.spawn_metrics_collector(Duration::from_millis(10));
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while state.system_metrics.read().await.total_workers != 2 {
+            assert!(Instant::now() < deadline, "system metrics were not collected");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        collector.abort();
+
+        let metrics = state.system_metrics.read().await.clone();
+        assert_eq!(metrics.active_workers, 2);
+        assert!(metrics.is_valid(), "{:?}", metrics);
+    }
+
+    #[tokio::test]
+    async fn test_alert_fires_from_collected_system_metrics() {
+        let state = test_api_state();
+        state
+            .alert_system
+            .add_rule(crate::monitoring::alert::AlertRule::parse("cpu_high", "cpu_usage > 90", "critical").unwrap())
+            .await;
+        let evaluator = state
+            .alert_system
+            .clone()
+            .spawn_evaluator(state.system_metrics.clone(), Duration::from_millis(10));
+
+        state.system_metrics.write().await.cpu_usage = 95.0;
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while state.alert_system.active_alerts().await.is_empty() {
+            assert!(Instant::now() < deadline, "alert did not fire");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        evaluator.abort();
+
+        let active = state.alert_system.active_alerts().await;
+        assert_eq!(active[0].rule_id, "cpu_high");
+        assert_eq!(active[0].value, 95.0);
+    }
+
+    #[tokio::test]
+    async fn test_model_handlers_check_named_model_context() {
+        use tower::ServiceExt;
+
+        let state = test_api_state();
+        let mut small = instance::default_model_config("small");
+        small.performance.timeout_seconds = 30;
+        let instance_id = state
+            .instance_manager
+            .create_instance("small".to_string(), Arc::new(instance::DummyModel::new()), small)
+            .await
+            .unwrap();
+        // Маршрутизация выбирает только прогретые экземпляры
+        while state.instance_manager.get_least_loaded_instance("small").await.as_ref() != Some(&instance_id) {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let app = Router::new()
+            .route("/api/v1/models/:name/request", post(api::process_request))
+            .route("/api/v1/models/:name/stream", post(api::stream_request))
+            .with_state(state);
+        let send = |uri: &str, words: usize| {
+            axum::http::Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("Content-Type", "application/json")
+                .body(axum::body::Body::from(
+                    serde_json::json!({ "prompt": vec!["word"; words].join(" ") }).to_string(),
+                ))
+                .unwrap()
+        };
+
+        // Контекст экземпляра модели - 1024 токена
+        for uri in ["/api/v1/models/small/request", "/api/v1/models/small/stream"] {
+            let response = app.clone().oneshot(send(uri, 2000)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+        let response = app.clone().oneshot(send("/api/v1/models/small/request", 10)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Длина контекста неизвестной модели не проверяется
+        let response = app.oneshot(send("/api/v1/models/unknown/request", 2000)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -11,6 +11,7 @@ use url::Url;
 use tokio::sync::Mutex as TokioMutex;
 use chrono::{DateTime, Utc};
 use cursor_codes::core::error::CursorError;
+use cursor_codes::core::idempotency::{request_fingerprint, IdempotencyBegin, IdempotencyStore};
 use cursor_codes::monitoring::logger::LoggerSystem;
 use cursor_codes::monitoring::alert::AlertSystem;
 use cursor_codes::monitoring::metrics::MetricsSystem;
@@ -110,6 +111,7 @@ pub struct BridgeManager {
     configs: Arc<RwLock<HashMap<String, BridgeConfig>>>,
     transactions: Arc<RwLock<HashMap<String, BridgeTransaction>>>,
     bridges: Arc<TokioMutex<Vec<BridgeMetrics>>>,
+    processed_transfers: IdempotencyStore,
 }
 
 impl BridgeManager {
@@ -119,6 +121,7 @@ impl BridgeManager {
             configs: Arc::new(RwLock::new(HashMap::new())),
             transactions: Arc::new(RwLock::new(HashMap::new())),
             bridges: Arc::new(Mutex::new(Vec::new())),
+            processed_transfers: IdempotencyStore::default(),
         }
     }
 
@@ -169,6 +172,34 @@ impl BridgeManager {
         }
     }
 
+    /// Инициирует перевод с ключом идемпотентности: повтор с тем же ключом
+    /// возвращает идентификатор уже созданной транзакции
+    pub fn initiate_transfer_idempotent(
+        &self,
+        source_address: Pubkey,
+        target_address: Pubkey,
+        amount: f64,
+        bridge_id: &str,
+        idempotency_key: &str,
+    ) -> Result<String, BridgeError> {
+        let fingerprint = request_fingerprint(&[
+            &source_address.to_string(),
+            &target_address.to_string(),
+            &amount.to_string(),
+            bridge_id,
+        ]);
+        let guard = match self.processed_transfers.begin(idempotency_key, &fingerprint) {
+            Ok(IdempotencyBegin::Done(transaction_id)) => return Ok(transaction_id),
+            Ok(IdempotencyBegin::Fresh(guard)) => guard,
+            Err(e) => return Err(BridgeError::InternalError(e.to_string())),
+        };
+
+        // При ошибке guard удаляется и освобождает ключ: транзакция не создана
+        let transaction_id = self.initiate_transfer(source_address, target_address, amount, bridge_id)?;
+        guard.complete(transaction_id.clone());
+        Ok(transaction_id)
+    }
+
     /// Обновляет статус транзакции
     pub fn update_transaction_status(
         &self,
@@ -211,6 +242,30 @@ impl BridgeManager {
             .ok_or_else(|| BridgeError::ConfigNotFound(bridge_id.to_string()))
     }
 
+    /// Меняет комиссию и допустимые суммы моста; новые значения действуют
+    /// для следующих переводов
+    pub fn update_fees(
+        &self,
+        bridge_id: &str,
+        fee_percentage: f64,
+        min_amount: f64,
+        max_amount: f64,
+    ) -> Result<(), BridgeError> {
+        let mut configs = self.configs.write();
+        let config = configs
+            .get_mut(bridge_id)
+            .ok_or_else(|| BridgeError::ConfigNotFound(bridge_id.to_string()))?;
+
+        config.fee_percentage = fee_percentage;
+        config.min_amount = min_amount;
+        config.max_amount = max_amount;
+        info!(
+            "Updated bridge {} fees: {}%, amounts {}..={}",
+            bridge_id, fee_percentage, min_amount, max_amount
+        );
+        Ok(())
+    }
+
     /// Получает все транзакции в определенном статусе
     pub fn get_transactions_by_status(&self, status: BridgeStatus) -> Vec<BridgeTransaction> {
         self.transactions
@@ -397,6 +452,38 @@ mod tests {
         assert!(manager.get_bridge_config("test_bridge").is_ok());
     }
 
+    #[test]
+    fn test_update_fees_applies_to_new_transfers() {
+        let manager = BridgeManager::new();
+        let config = BridgeConfig {
+            source_network: "solana".to_string(),
+            target_network: "ethereum".to_string(),
+            fee_percentage: 0.1,
+            min_amount: 1.0,
+            max_amount: 1000.0,
+            source_network_url: "https://solana.com".to_string(),
+            target_network_url: "https://ethereum.com".to_string(),
+            name: "test_bridge".to_string(),
+            url: "https://test.com".to_string(),
+            api_key: "test_api_key".to_string(),
+            timeout: 1000,
+            retry_attempts: 3,
+            active: true,
+        };
+        manager.add_bridge("test_bridge".to_string(), config).unwrap();
+
+        manager.update_fees("test_bridge", 0.2, 10.0, 500.0).unwrap();
+        let updated = manager.get_bridge_config("test_bridge").unwrap();
+        assert_eq!(updated.fee_percentage, 0.2);
+
+        let (source, target) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert!(matches!(
+            manager.initiate_transfer(source, target, 5.0, "test_bridge"),
+            Err(BridgeError::AmountTooLow(_))
+        ));
+        assert!(matches!(manager.update_fees("missing", 0.2, 1.0, 2.0), Err(BridgeError::ConfigNotFound(_))));
+    }
+
     #[test]
     fn test_transaction_flow() {
         let manager = BridgeManager::new();
@@ -458,4 +545,38 @@ mod tests {
         assert!(manager.update_transaction_status(&tx_id, BridgeStatus::Completed).is_ok());
         assert!(manager.update_transaction_status(&tx_id, BridgeStatus::Processing).is_err());
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_repeated_idempotency_key_creates_one_transaction() {
+        let manager = BridgeManager::new();
+        manager.add_bridge("test_bridge".to_string(), BridgeConfig {
+            source_network: "solana".to_string(),
+            target_network: "ethereum".to_string(),
+            fee_percentage: 0.1,
+            min_amount: 1.0,
+            max_amount: 1000.0,
+            source_network_url: "https://solana.com".to_string(),
+            target_network_url: "https://ethereum.com".to_string(),
+            name: "test_bridge".to_string(),
+            url: "https://test.com".to_string(),
+            api_key: "test_api_key".to_string(),
+            timeout: 1000,
+            retry_attempts: 3,
+            active: true,
+        }).unwrap();
+
+        let source = Pubkey::new_unique();
+        let target = Pubkey::new_unique();
+
+        // Отклонённый перевод не занимает ключ
+        assert!(manager.initiate_transfer_idempotent(source, target, 0.5, "test_bridge", "req-0").is_err());
+        let first = manager.initiate_transfer_idempotent(source, target, 100.0, "test_bridge", "req-0").unwrap();
+        let retry = manager.initiate_transfer_idempotent(source, target, 100.0, "test_bridge", "req-0").unwrap();
+        assert_eq!(first, retry);
+        assert_eq!(manager.transactions.read().len(), 1);
+
+        // Тот же ключ с другой суммой отклоняется
+        assert!(manager.initiate_transfer_idempotent(source, target, 200.0, "test_bridge", "req-0").is_err());
+        assert_eq!(manager.transactions.read().len(), 1);
+    }
+}
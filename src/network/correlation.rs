@@ -0,0 +1,291 @@
+//! Correlation - Correlation ID и выборочная трассировка запросов
+//!
+//! Каждый запрос получает correlation ID (из заголовка или новый).
+//! Решение о трассировке принимается в начале запроса (head-based sampling)
+//! с заданной долей; запросы, завершившиеся ошибкой, трассируются всегда.
+//! Решение передаётся дальше через заголовок `X-Trace-Sampled`.
+//!
+//! Отдельно каждому запросу назначается request ID (`X-Request-Id`), который
+//! попадает в тело ответа и во все записи лога, сделанные при его обработке.
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderValue, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use crate::monitoring::logger::with_request_id;
+
+/// Заголовок с correlation ID
+pub const CORRELATION_ID_HEADER: &str = "X-Correlation-Id";
+/// Заголовок с решением о трассировке
+pub const TRACE_SAMPLED_HEADER: &str = "X-Trace-Sampled";
+/// Заголовок с идентификатором запроса
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+/// Максимальная длина принимаемого от клиента request ID
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Идентификатор запроса, доступен обработчикам через extensions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+/// Контекст трассировки запроса, доступен обработчикам через extensions
+#[derive(Debug, Clone)]
+pub struct TraceContext {
+    pub correlation_id: String,
+    pub sampled: bool,
+}
+
+/// Сэмплер трассировки
+#[derive(Debug)]
+pub struct TraceSampler {
+    rate: f64,
+    traced: AtomicU64,
+    total: AtomicU64,
+}
+
+impl TraceSampler {
+    /// `rate` - доля трассируемых успешных запросов (0.0..=1.0)
+    pub fn new(rate: f64) -> Self {
+        Self {
+            rate: rate.clamp(0.0, 1.0),
+            traced: AtomicU64::new(0),
+            total: AtomicU64::new(0),
+        }
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Решение в начале запроса. Решение вышестоящего сервиса имеет приоритет.
+    pub fn sample(&self, correlation_id: &str, upstream: Option<bool>) -> bool {
+        if let Some(sampled) = upstream {
+            return sampled;
+        }
+        if self.rate >= 1.0 {
+            return true;
+        }
+
+        // Детерминированно по correlation ID, чтобы все сервисы решали одинаково.
+        // SHA-256 не зависит от версии Rust, в отличие от DefaultHasher
+        let digest = Sha256::digest(correlation_id.as_bytes());
+        let mut prefix = [0u8; 8];
+        prefix.copy_from_slice(&digest[..8]);
+        (u64::from_be_bytes(prefix) as f64 / u64::MAX as f64) < self.rate
+    }
+
+    /// Итоговое решение: ошибки трассируются всегда
+    pub fn finish(&self, sampled: bool, status: StatusCode) -> bool {
+        let traced = sampled || status.is_server_error();
+        self.total.fetch_add(1, Ordering::Relaxed);
+        if traced {
+            self.traced.fetch_add(1, Ordering::Relaxed);
+        }
+        traced
+    }
+
+    /// (трассировано, всего)
+    pub fn counts(&self) -> (u64, u64) {
+        (self.traced.load(Ordering::Relaxed), self.total.load(Ordering::Relaxed))
+    }
+}
+
+/// Middleware: назначает correlation ID и принимает решение о трассировке
+pub async fn correlation_middleware(
+    State(sampler): State<Arc<TraceSampler>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let correlation_id = request
+        .headers()
+        .get(CORRELATION_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    let upstream = request
+        .headers()
+        .get(TRACE_SAMPLED_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| match value {
+            "1" | "true" => Some(true),
+            "0" | "false" => Some(false),
+            _ => None,
+        });
+
+    let sampled = sampler.sample(&correlation_id, upstream);
+    request.extensions_mut().insert(TraceContext {
+        correlation_id: correlation_id.clone(),
+        sampled,
+    });
+
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let started = Instant::now();
+
+    let mut response = next.run(request).await;
+
+    let status = response.status();
+    let traced = sampler.finish(sampled, status);
+    if traced {
+        log::info!(
+            target: "trace",
+            "correlation_id={} method={} path={} status={} duration_ms={}",
+            correlation_id,
+            method,
+            path,
+            status.as_u16(),
+            started.elapsed().as_millis()
+        );
+    }
+
+    let headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&correlation_id) {
+        headers.insert(CORRELATION_ID_HEADER, value);
+    }
+    headers.insert(
+        TRACE_SAMPLED_HEADER,
+        HeaderValue::from_static(if traced { "1" } else { "0" }),
+    );
+
+    response
+}
+
+/// Middleware: назначает request ID (или принимает переданный клиентом),
+/// делает его доступным логам и возвращает в заголовке `X-Request-Id`
+pub async fn request_id_middleware(mut request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty() && value.len() <= MAX_REQUEST_ID_LEN)
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    request.extensions_mut().insert(RequestId(request_id.clone()));
+
+    let mut response = with_request_id(request_id.clone(), next.run(request)).await;
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app(sampler: Arc<TraceSampler>) -> Router {
+        Router::new()
+            .route("/ok", get(|| async { StatusCode::OK }))
+            .route("/fail", get(|| async { StatusCode::INTERNAL_SERVER_ERROR }))
+            .layer(axum::middleware::from_fn_with_state(sampler, correlation_middleware))
+    }
+
+    async fn request_id_response(header: Option<&str>) -> (String, serde_json::Value) {
+        let app = Router::new()
+            .route(
+                "/data",
+                get(|| async { axum::Json(crate::network::api::ApiResponse::success("ok")) }),
+            )
+            .layer(axum::middleware::from_fn(request_id_middleware));
+
+        let mut request = Request::builder().uri("/data");
+        if let Some(value) = header {
+            request = request.header(REQUEST_ID_HEADER, value);
+        }
+        let response = app.oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+
+        let header = response.headers()[REQUEST_ID_HEADER].to_str().unwrap().to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (header, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_request_id_header_matches_body() {
+        let (header, body) = request_id_response(None).await;
+        assert!(uuid::Uuid::parse_str(&header).is_ok());
+        assert_eq!(body["request_id"], header.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_client_request_id_is_echoed() {
+        let (header, body) = request_id_response(Some("client-req-7")).await;
+        assert_eq!(header, "client-req-7");
+        assert_eq!(body["request_id"], "client-req-7");
+    }
+
+    async fn traced(app: &Router, uri: &str) -> bool {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert!(response.headers().contains_key(CORRELATION_ID_HEADER));
+        response.headers()[TRACE_SAMPLED_HEADER] == "1"
+    }
+
+    #[tokio::test]
+    async fn test_errors_always_traced_successes_sampled() {
+        let sampler = Arc::new(TraceSampler::new(0.1));
+        let app = app(sampler.clone());
+
+        for _ in 0..50 {
+            assert!(traced(&app, "/fail").await);
+        }
+
+        let mut sampled = 0;
+        let requests = 2000;
+        for _ in 0..requests {
+            if traced(&app, "/ok").await {
+                sampled += 1;
+            }
+        }
+        let fraction = sampled as f64 / requests as f64;
+        assert!((0.06..0.14).contains(&fraction), "sampled fraction {}", fraction);
+        assert_eq!(sampler.counts().1, 50 + requests);
+    }
+
+    #[tokio::test]
+    async fn test_upstream_decision_and_correlation_id_propagated() {
+        let app = app(Arc::new(TraceSampler::new(0.0)));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/ok")
+                    .header(CORRELATION_ID_HEADER, "abc-123")
+                    .header(TRACE_SAMPLED_HEADER, "1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()[CORRELATION_ID_HEADER], "abc-123");
+        assert_eq!(response.headers()[TRACE_SAMPLED_HEADER], "1");
+    }
+
+    #[test]
+    fn test_sampling_decision_is_stable() {
+        let sampler = TraceSampler::new(0.5);
+        let decisions: Vec<bool> = (0..64)
+            .map(|i| sampler.sample(&format!("corr-{}", i), None))
+            .collect();
+        let again: Vec<bool> = (0..64)
+            .map(|i| sampler.sample(&format!("corr-{}", i), None))
+            .collect();
+        assert_eq!(decisions, again);
+        assert!(decisions.contains(&true) && decisions.contains(&false));
+    }
+}
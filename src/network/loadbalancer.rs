@@ -5,7 +5,8 @@ use std::collections::HashMap;
 use std::time::{Duration, Instant};
 use tokio::sync::Semaphore;
 use thiserror::Error;
-use crate::lmrouter::{ModelConfig, ModelStats};
+use crate::lmrouter::{ModelConfig, ModelRequirements, ModelStats};
+use crate::admin::maintenance::{Clock, SystemClock};
 use uuid::Uuid;
 use serde::{Serialize, Deserialize};
 use tokio::sync::Mutex;
@@ -28,6 +29,8 @@ pub enum LoadBalancerError {
     InternalError(String),
     #[error("Failed to acquire permit")]
     AcquireError,
+    #[error("All matching models are unhealthy")]
+    AllModelsUnhealthy,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,19 +65,216 @@ pub struct LoadBalancerConfig {
     pub health_check_interval: u64,
     pub max_retries: u32,
     pub timeout: u64,
+    /// Число подряд идущих ошибок, после которого модель исключается из маршрутизации
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    /// Время (в секундах), через которое исключённая модель снова допускается
+    #[serde(default = "default_cooldown_secs")]
+    pub cooldown_secs: u64,
+}
+
+fn default_failure_threshold() -> u32 {
+    3
+}
+
+fn default_cooldown_secs() -> u64 {
+    60
+}
+
+/// Состояние модели в балансировщике
+#[derive(Debug, Clone)]
+struct ModelEntry {
+    config: ModelConfig,
+    stats: ModelStats,
+    consecutive_failures: u32,
+    tripped_until: Option<DateTime<Utc>>,
+}
+
+impl ModelEntry {
+    fn matches(&self, requirements: &ModelRequirements) -> bool {
+        self.config.active
+            && self.config.max_tokens >= requirements.min_tokens
+            && self.config.min_tokens <= requirements.max_tokens
+            && self.config.priority >= requirements.min_priority
+    }
+
+    fn is_tripped(&self, now: DateTime<Utc>) -> bool {
+        self.tripped_until.map_or(false, |until| until > now)
+    }
 }
 
 pub struct LoadBalancer {
     config: Arc<Mutex<LoadBalancerConfig>>,
     nodes: Arc<Mutex<HashMap<String, NodeMetrics>>>,
+    models: Arc<Mutex<HashMap<String, ModelEntry>>>,
+    clock: Arc<dyn Clock>,
 }
 
 impl LoadBalancer {
-    pub fn new(config: LoadBalancerConfig) -> Self {
+    /// Создает балансировщик: порог подряд идущих ошибок, таймаут (мс) и время восстановления (с)
+    pub fn new(failure_threshold: u32, timeout: u64, cooldown_secs: u64) -> Self {
+        Self::with_config(LoadBalancerConfig {
+            algorithm: "least_connections".to_string(),
+            health_check_interval: cooldown_secs,
+            max_retries: failure_threshold,
+            timeout,
+            failure_threshold,
+            cooldown_secs,
+        })
+    }
+
+    pub fn with_config(config: LoadBalancerConfig) -> Self {
+        Self::with_clock(config, Arc::new(SystemClock))
+    }
+
+    pub fn with_clock(config: LoadBalancerConfig, clock: Arc<dyn Clock>) -> Self {
         Self {
             config: Arc::new(Mutex::new(config)),
             nodes: Arc::new(Mutex::new(HashMap::new())),
+            models: Arc::new(Mutex::new(HashMap::new())),
+            clock,
+        }
+    }
+
+    pub async fn register_model(&self, id: String, config: ModelConfig) -> Result<(), LoadBalancerError> {
+        let mut models = self.models.lock().await;
+
+        if models.contains_key(&id) {
+            return Err(LoadBalancerError::InternalError(format!("Model '{}' already registered", id)));
+        }
+
+        models.insert(id.clone(), ModelEntry {
+            config,
+            stats: ModelStats {
+                total_requests: 0,
+                successful_requests: 0,
+                failed_requests: 0,
+                average_response_time: 0.0,
+                last_request_time: None,
+                last_error: None,
+                current_requests: 0,
+            },
+            consecutive_failures: 0,
+            tripped_until: None,
+        });
+        info!("Registered model in load balancer: {}", id);
+        Ok(())
+    }
+
+    /// Выбирает модель, подходящую под требования, пропуская исключённые.
+    /// Модели с истёкшим временем восстановления снова допускаются.
+    pub async fn get_available_model(
+        &self,
+        requirements: &ModelRequirements,
+    ) -> Result<(String, ModelConfig), LoadBalancerError> {
+        let now = self.clock.now();
+        let mut models = self.models.lock().await;
+
+        let mut matching = 0;
+        for (id, entry) in models.iter_mut() {
+            if !entry.matches(requirements) {
+                continue;
+            }
+            matching += 1;
+
+            if entry.tripped_until.is_some() && !entry.is_tripped(now) {
+                entry.tripped_until = None;
+                entry.consecutive_failures = 0;
+                info!("Model '{}' re-admitted after cooldown", id);
+            }
+        }
+
+        let mut candidates: Vec<_> = models
+            .iter()
+            .filter(|(_, entry)| entry.matches(requirements) && !entry.is_tripped(now))
+            .collect();
+        candidates.sort_by(|a, b| a.0.cmp(b.0));
+
+        // Наивысший приоритет, затем наименьшее среднее время ответа
+        let best = candidates.into_iter().fold(None, |best: Option<(&String, &ModelEntry)>, candidate| {
+            match best {
+                Some(current)
+                    if (current.1.config.priority, -current.1.stats.average_response_time)
+                        >= (candidate.1.config.priority, -candidate.1.stats.average_response_time) =>
+                {
+                    Some(current)
+                }
+                _ => Some(candidate),
+            }
+        });
+
+        match best {
+            Some((id, entry)) => Ok((id.clone(), entry.config.clone())),
+            None if matching > 0 => Err(LoadBalancerError::AllModelsUnhealthy),
+            None => Err(LoadBalancerError::ModelNotFound(
+                "No available models matching requirements".to_string(),
+            )),
+        }
+    }
+
+    /// Обновляет статистику модели. Если число подряд идущих ошибок
+    /// достигает порога, модель исключается на время восстановления.
+    pub async fn update_model_stats(
+        &self,
+        id: &str,
+        success: bool,
+        response_time: f64,
+    ) -> Result<(), LoadBalancerError> {
+        let (threshold, cooldown) = {
+            let config = self.config.lock().await;
+            (config.failure_threshold, config.cooldown_secs)
+        };
+        let now = self.clock.now();
+        let mut models = self.models.lock().await;
+
+        let entry = models
+            .get_mut(id)
+            .ok_or_else(|| LoadBalancerError::ModelNotFound(id.to_string()))?;
+
+        entry.stats.total_requests += 1;
+        let total_time = entry.stats.average_response_time * (entry.stats.total_requests - 1) as f64;
+        entry.stats.average_response_time = (total_time + response_time) / entry.stats.total_requests as f64;
+        entry.stats.last_request_time = Some(now);
+
+        if success {
+            entry.stats.successful_requests += 1;
+            entry.consecutive_failures = 0;
+        } else {
+            entry.stats.failed_requests += 1;
+            entry.consecutive_failures += 1;
+            if threshold > 0 && entry.consecutive_failures >= threshold && !entry.is_tripped(now) {
+                entry.tripped_until = Some(now + chrono::Duration::seconds(cooldown as i64));
+                info!(
+                    "Model '{}' tripped after {} consecutive failures",
+                    id, entry.consecutive_failures
+                );
+            }
         }
+
+        Ok(())
+    }
+
+    /// Принудительно исключает модель из маршрутизации на время восстановления
+    pub async fn mark_unhealthy(&self, id: &str) -> Result<(), LoadBalancerError> {
+        let cooldown = self.config.lock().await.cooldown_secs;
+        let now = self.clock.now();
+        let mut models = self.models.lock().await;
+
+        let entry = models
+            .get_mut(id)
+            .ok_or_else(|| LoadBalancerError::ModelNotFound(id.to_string()))?;
+
+        entry.tripped_until = Some(now + chrono::Duration::seconds(cooldown as i64));
+        info!("Model '{}' marked unhealthy", id);
+        Ok(())
+    }
+
+    pub async fn is_model_healthy(&self, id: &str) -> Result<bool, LoadBalancerError> {
+        let models = self.models.lock().await;
+        models
+            .get(id)
+            .map(|entry| !entry.is_tripped(self.clock.now()))
+            .ok_or_else(|| LoadBalancerError::ModelNotFound(id.to_string()))
     }
 
     pub async fn add_node(&self, config: NodeConfig) -> Result<(), String> {
@@ -275,6 +475,7 @@ impl LoadBalancer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::ManualClock;
 
     #[tokio::test]
     async fn test_model_registration() {
@@ -312,4 +513,62 @@ mod tests {
         
         assert!(balancer.get_available_model(&requirements).await.is_ok());
     }
-} 
\ No newline at end of file
+
+    fn model_config(id: &str, priority: u32) -> ModelConfig {
+        ModelConfig {
+            id: id.to_string(),
+            name: id.to_string(),
+            version: "1.0".to_string(),
+            max_tokens: 1000,
+            min_tokens: 1,
+            priority,
+            max_requests_per_minute: 60,
+            active: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consecutive_failures_trip_model_until_cooldown() {
+        let t0 = Utc::now();
+        let clock = Arc::new(ManualClock::new(t0));
+        let mut config = LoadBalancer::new(3, 1000, 60).get_config().await;
+        config.failure_threshold = 2;
+        config.cooldown_secs = 30;
+        let balancer = LoadBalancer::with_clock(config, clock.clone());
+
+        balancer.register_model("primary".to_string(), model_config("primary", 2)).await.unwrap();
+        balancer.register_model("backup".to_string(), model_config("backup", 1)).await.unwrap();
+
+        let requirements = ModelRequirements {
+            min_tokens: 10,
+            max_tokens: 100,
+            min_priority: 1,
+            max_requests_per_minute: 60,
+        };
+        assert_eq!(balancer.get_available_model(&requirements).await.unwrap().0, "primary");
+
+        // Успех между ошибками сбрасывает счётчик
+        balancer.update_model_stats("primary", false, 0.1).await.unwrap();
+        balancer.update_model_stats("primary", true, 0.1).await.unwrap();
+        balancer.update_model_stats("primary", false, 0.1).await.unwrap();
+        assert_eq!(balancer.get_available_model(&requirements).await.unwrap().0, "primary");
+
+        balancer.update_model_stats("primary", false, 0.1).await.unwrap();
+        assert!(!balancer.is_model_healthy("primary").await.unwrap());
+        assert_eq!(balancer.get_available_model(&requirements).await.unwrap().0, "backup");
+
+        balancer.mark_unhealthy("backup").await.unwrap();
+        assert!(matches!(
+            balancer.get_available_model(&requirements).await,
+            Err(LoadBalancerError::AllModelsUnhealthy)
+        ));
+
+        clock.set(t0 + chrono::Duration::seconds(31));
+        assert_eq!(balancer.get_available_model(&requirements).await.unwrap().0, "primary");
+        assert!(balancer.is_model_healthy("backup").await.unwrap());
+
+        // После восстановления для повторного исключения нужен полный порог ошибок
+        balancer.update_model_stats("primary", false, 0.1).await.unwrap();
+        assert!(balancer.is_model_healthy("primary").await.unwrap());
+    }
+}
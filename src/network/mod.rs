@@ -5,6 +5,7 @@ pub mod tls;
 pub mod api;
 pub mod pool_cok;
 pub mod smallworld;
+pub mod correlation;
 
 pub use network::*;
 pub use bridges::*;
@@ -13,6 +14,7 @@ pub use tls::*;
 pub use api::*;
 pub use pool_cok::*;
 pub use smallworld::*;
+pub use correlation::*;
 
 use std::error::Error;
 
@@ -160,12 +160,184 @@ impl TLSManager {
     }
 }
 
+/// TLS-конфигурация HTTPS-сервера: сертификат, ключ, цепочка и,
+/// опционально, обязательная проверка клиентских сертификатов (mTLS)
+pub struct TlsManager {
+    cert_chain: Vec<rustls::pki_types::CertificateDer<'static>>,
+    key: rustls::pki_types::PrivateKeyDer<'static>,
+    alpn_protocols: Vec<Vec<u8>>,
+    client_verifier: Option<Arc<dyn rustls::server::danger::ClientCertVerifier>>,
+    config: Arc<rustls::ServerConfig>,
+}
+
+impl TlsManager {
+    pub fn new(
+        cert_path: &Path,
+        key_path: &Path,
+        chain_path: Option<&Path>,
+        enable_http2: bool,
+        enable_ocsp_stapling: bool,
+    ) -> Result<Self, TlsError> {
+        let mut cert_chain = read_pem_certs(cert_path)?;
+        if cert_chain.is_empty() {
+            return Err(TlsError::CertError(format!(
+                "No certificates found in {}",
+                cert_path.display()
+            )));
+        }
+        if let Some(chain_path) = chain_path {
+            cert_chain.extend(read_pem_certs(chain_path)?);
+        }
+
+        let key = read_pem_key(key_path)?;
+
+        if enable_ocsp_stapling {
+            info!("OCSP stapling requested; no OCSP response configured, skipping");
+        }
+
+        let mut alpn_protocols = Vec::new();
+        if enable_http2 {
+            alpn_protocols.push(b"h2".to_vec());
+        }
+        alpn_protocols.push(b"http/1.1".to_vec());
+
+        let config = build_server_config(&cert_chain, &key, &alpn_protocols, None)?;
+        Ok(Self {
+            cert_chain,
+            key,
+            alpn_protocols,
+            client_verifier: None,
+            config,
+        })
+    }
+
+    /// Требует от клиентов сертификат, подписанный CA из `client_ca_path`.
+    /// Соединения без действительного клиентского сертификата отклоняются.
+    pub fn with_client_auth(mut self, client_ca_path: &Path) -> Result<Self, TlsError> {
+        let mut roots = rustls::RootCertStore::empty();
+        for cert in read_pem_certs(client_ca_path)? {
+            roots
+                .add(cert)
+                .map_err(|e| TlsError::CertError(format!("Invalid client CA certificate: {}", e)))?;
+        }
+        if roots.is_empty() {
+            return Err(TlsError::CertError(format!(
+                "No CA certificates found in {}",
+                client_ca_path.display()
+            )));
+        }
+
+        let verifier = rustls::server::WebPkiClientVerifier::builder(Arc::new(roots))
+            .build()
+            .map_err(|e| TlsError::ConfigError(format!("Client certificate verifier: {}", e)))?;
+
+        self.config = build_server_config(
+            &self.cert_chain,
+            &self.key,
+            &self.alpn_protocols,
+            Some(verifier.clone()),
+        )?;
+        self.client_verifier = Some(verifier);
+        info!("Mutual TLS enabled with client CA {}", client_ca_path.display());
+        Ok(self)
+    }
+
+    /// Включена ли обязательная проверка клиентских сертификатов
+    pub fn client_auth_required(&self) -> bool {
+        self.client_verifier
+            .as_ref()
+            .map_or(false, |verifier| verifier.offer_client_auth() && verifier.client_auth_mandatory())
+    }
+
+    pub fn get_config(&self) -> Arc<rustls::ServerConfig> {
+        self.config.clone()
+    }
+}
+
+fn build_server_config(
+    cert_chain: &[rustls::pki_types::CertificateDer<'static>],
+    key: &rustls::pki_types::PrivateKeyDer<'static>,
+    alpn_protocols: &[Vec<u8>],
+    client_verifier: Option<Arc<dyn rustls::server::danger::ClientCertVerifier>>,
+) -> Result<Arc<rustls::ServerConfig>, TlsError> {
+    let builder = rustls::ServerConfig::builder();
+    let builder = match client_verifier {
+        Some(verifier) => builder.with_client_cert_verifier(verifier),
+        None => builder.with_no_client_auth(),
+    };
+
+    let mut config = builder
+        .with_single_cert(cert_chain.to_vec(), key.clone_key())
+        .map_err(|e| TlsError::TlsError(e.to_string()))?;
+    config.alpn_protocols = alpn_protocols.to_vec();
+    Ok(Arc::new(config))
+}
+
+fn read_pem_certs(path: &Path) -> Result<Vec<rustls::pki_types::CertificateDer<'static>>, TlsError> {
+    let file = File::open(path)
+        .map_err(|e| TlsError::CertError(format!("Failed to read {}: {}", path.display(), e)))?;
+    rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| TlsError::CertError(format!("Failed to parse {}: {}", path.display(), e)))
+}
+
+fn read_pem_key(path: &Path) -> Result<rustls::pki_types::PrivateKeyDer<'static>, TlsError> {
+    let file = File::open(path)
+        .map_err(|e| TlsError::CertError(format!("Failed to read private key: {}", e)))?;
+    rustls_pemfile::private_key(&mut BufReader::new(file))
+        .map_err(|e| TlsError::CertError(format!("Failed to parse private key: {}", e)))?
+        .ok_or_else(|| TlsError::CertError("No private key found".to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::path::PathBuf;
     use std::fs;
 
+    fn write_self_signed(dir: &Path, name: &str) -> (PathBuf, PathBuf) {
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let cert_path = dir.join(format!("{}.pem", name));
+        let key_path = dir.join(format!("{}.key", name));
+        fs::write(&cert_path, cert.serialize_pem().unwrap()).unwrap();
+        fs::write(&key_path, cert.serialize_private_key_pem()).unwrap();
+        (cert_path, key_path)
+    }
+
+    #[test]
+    fn test_server_config_without_client_auth() {
+        let dir = tempfile::tempdir().unwrap();
+        let (cert, key) = write_self_signed(dir.path(), "server");
+
+        let manager = TlsManager::new(&cert, &key, None, true, false).unwrap();
+        assert!(!manager.client_auth_required());
+        assert_eq!(
+            manager.get_config().alpn_protocols,
+            vec![b"h2".to_vec(), b"http/1.1".to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_client_ca_enables_mandatory_client_auth() {
+        let dir = tempfile::tempdir().unwrap();
+        let (cert, key) = write_self_signed(dir.path(), "server");
+        let (ca, _) = write_self_signed(dir.path(), "client-ca");
+
+        let manager = TlsManager::new(&cert, &key, None, false, false)
+            .unwrap()
+            .with_client_auth(&ca)
+            .unwrap();
+        assert!(manager.client_auth_required());
+
+        // CA без сертификатов недопустим
+        let empty = dir.path().join("empty.pem");
+        fs::write(&empty, "").unwrap();
+        let result = TlsManager::new(&cert, &key, None, false, false)
+            .unwrap()
+            .with_client_auth(&empty);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_tls_manager_creation() {
         let cert_path = PathBuf::from("test_cert.pem");
@@ -0,0 +1,588 @@
+//! GPU Management - Управление GPU
+//!
+//! Этот модуль предоставляет:
+//! - Информацию о GPU
+//! - Проверку и применение настроек (лимиты мощности, температуры, частоты, вентилятор)
+//! - Атомарное применение: либо все настройки, либо ни одной
+//! - Работу с NVIDIA GPU через NVML (feature `cuda`) с откатом на заглушку
+
+use crate::core::error::AppError;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
+
+/// Информация о GPU
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GpuInfo {
+    pub model: Option<String>,
+    pub usage: Option<f64>,
+    pub temperature: Option<f64>,
+    pub memory_used: Option<u64>,
+    pub memory_total: Option<u64>,
+    pub power_limit: Option<u32>,
+    pub temperature_limit: Option<f64>,
+    pub memory_clock: Option<u32>,
+    pub gpu_clock: Option<u32>,
+    pub fan_speed: Option<u32>,
+    pub adaptive_power: Option<bool>,
+    pub memory_optimization: Option<bool>,
+}
+
+/// Настройки GPU
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GpuConfig {
+    /// Лимит мощности, Вт
+    pub power_limit: u32,
+    /// Лимит температуры, °C
+    pub temperature_limit: f64,
+    /// Частота памяти, МГц
+    pub memory_clock: u32,
+    /// Частота ядра, МГц
+    pub gpu_clock: u32,
+    /// Скорость вентилятора, %
+    pub fan_speed: u32,
+}
+
+impl Default for GpuConfig {
+    fn default() -> Self {
+        Self {
+            power_limit: 250,
+            temperature_limit: 85.0,
+            memory_clock: 16000,
+            gpu_clock: 2000,
+            fan_speed: 80,
+        }
+    }
+}
+
+/// Безопасные диапазоны настроек
+pub const POWER_LIMIT_RANGE: RangeInclusive<u32> = 50..=450;
+pub const TEMPERATURE_LIMIT_RANGE: RangeInclusive<f64> = 40.0..=90.0;
+pub const MEMORY_CLOCK_RANGE: RangeInclusive<u32> = 100..=24000;
+pub const GPU_CLOCK_RANGE: RangeInclusive<u32> = 100..=3000;
+pub const FAN_SPEED_RANGE: RangeInclusive<u32> = 0..=100;
+
+impl GpuConfig {
+    /// Проверяет, что все значения в безопасных пределах
+    pub fn validate(&self) -> Result<(), AppError> {
+        fn check<T: PartialOrd + std::fmt::Display>(
+            name: &str,
+            value: T,
+            range: &RangeInclusive<T>,
+        ) -> Result<(), AppError> {
+            if range.contains(&value) {
+                Ok(())
+            } else {
+                Err(AppError::InvalidInput(format!(
+                    "{} {} is outside the safe range {}..={}",
+                    name,
+                    value,
+                    range.start(),
+                    range.end()
+                )))
+            }
+        }
+
+        check("power_limit", self.power_limit, &POWER_LIMIT_RANGE)?;
+        check("temperature_limit", self.temperature_limit, &TEMPERATURE_LIMIT_RANGE)?;
+        check("memory_clock", self.memory_clock, &MEMORY_CLOCK_RANGE)?;
+        check("gpu_clock", self.gpu_clock, &GPU_CLOCK_RANGE)?;
+        check("fan_speed", self.fan_speed, &FAN_SPEED_RANGE)
+    }
+}
+
+/// Низкоуровневое управление GPU (драйвер, sysfs, NVML)
+#[async_trait]
+pub trait GpuControl: Send + Sync {
+    async fn read_config(&self) -> Result<GpuConfig, AppError>;
+    async fn set_power_limit(&self, watts: u32) -> Result<(), AppError>;
+    async fn set_temperature_limit(&self, celsius: f64) -> Result<(), AppError>;
+    async fn set_memory_clock(&self, mhz: u32) -> Result<(), AppError>;
+    async fn set_gpu_clock(&self, mhz: u32) -> Result<(), AppError>;
+    async fn set_fan_speed(&self, percent: u32) -> Result<(), AppError>;
+
+    /// Телеметрия устройства (модель, загрузка, температура, память)
+    async fn read_telemetry(&self) -> Result<GpuInfo, AppError> {
+        Ok(GpuInfo::default())
+    }
+
+    /// Управляет ли реализация настоящим устройством
+    fn is_hardware(&self) -> bool {
+        false
+    }
+}
+
+/// Заглушка управления: хранит настройки в памяти
+pub struct StubGpuControl {
+    config: RwLock<GpuConfig>,
+}
+
+impl StubGpuControl {
+    pub fn new() -> Self {
+        Self {
+            config: RwLock::new(GpuConfig::default()),
+        }
+    }
+}
+
+#[async_trait]
+impl GpuControl for StubGpuControl {
+    async fn read_config(&self) -> Result<GpuConfig, AppError> {
+        Ok(self.config.read().await.clone())
+    }
+
+    async fn set_power_limit(&self, watts: u32) -> Result<(), AppError> {
+        self.config.write().await.power_limit = watts;
+        Ok(())
+    }
+
+    async fn set_temperature_limit(&self, celsius: f64) -> Result<(), AppError> {
+        self.config.write().await.temperature_limit = celsius;
+        Ok(())
+    }
+
+    async fn set_memory_clock(&self, mhz: u32) -> Result<(), AppError> {
+        self.config.write().await.memory_clock = mhz;
+        Ok(())
+    }
+
+    async fn set_gpu_clock(&self, mhz: u32) -> Result<(), AppError> {
+        self.config.write().await.gpu_clock = mhz;
+        Ok(())
+    }
+
+    async fn set_fan_speed(&self, percent: u32) -> Result<(), AppError> {
+        self.config.write().await.fan_speed = percent;
+        Ok(())
+    }
+}
+
+/// Управление NVIDIA GPU через NVML
+#[cfg(feature = "cuda")]
+pub struct NvmlGpuControl {
+    nvml: nvml_wrapper::Nvml,
+    index: u32,
+    /// NVML не отдаёт заблокированные частоты и программный лимит температуры,
+    /// поэтому последние применённые значения хранятся здесь
+    applied: RwLock<GpuConfig>,
+}
+
+#[cfg(feature = "cuda")]
+fn nvml_error(e: nvml_wrapper::error::NvmlError) -> AppError {
+    AppError::Unknown(format!("NVML error: {}", e))
+}
+
+#[cfg(feature = "cuda")]
+impl NvmlGpuControl {
+    /// Инициализирует NVML и выбирает устройство с индексом `index`.
+    /// Возвращает ошибку, если драйвер недоступен или устройства нет.
+    pub fn new(index: u32) -> Result<Self, AppError> {
+        use nvml_wrapper::enum_wrappers::device::Clock;
+
+        let nvml = nvml_wrapper::Nvml::init().map_err(nvml_error)?;
+        let (power_limit, memory_clock, gpu_clock, fan_speed) = {
+            let device = nvml.device_by_index(index).map_err(nvml_error)?;
+            (
+                device.power_management_limit().map_err(nvml_error)? / 1000,
+                device.clock_info(Clock::Memory).map_err(nvml_error)?,
+                device.clock_info(Clock::Graphics).map_err(nvml_error)?,
+                device.fan_speed(0).unwrap_or(GpuConfig::default().fan_speed),
+            )
+        };
+
+        Ok(Self {
+            nvml,
+            index,
+            applied: RwLock::new(GpuConfig {
+                power_limit,
+                temperature_limit: GpuConfig::default().temperature_limit,
+                memory_clock,
+                gpu_clock,
+                fan_speed,
+            }),
+        })
+    }
+
+    fn device(&self) -> Result<nvml_wrapper::Device<'_>, AppError> {
+        self.nvml.device_by_index(self.index).map_err(nvml_error)
+    }
+}
+
+#[cfg(feature = "cuda")]
+#[async_trait]
+impl GpuControl for NvmlGpuControl {
+    async fn read_config(&self) -> Result<GpuConfig, AppError> {
+        let device = self.device()?;
+        let mut config = self.applied.read().await.clone();
+        config.power_limit = device.power_management_limit().map_err(nvml_error)? / 1000;
+        if let Ok(fan_speed) = device.fan_speed(0) {
+            config.fan_speed = fan_speed;
+        }
+        Ok(config)
+    }
+
+    async fn set_power_limit(&self, watts: u32) -> Result<(), AppError> {
+        let mut device = self.device()?;
+        device.set_power_management_limit(watts * 1000).map_err(nvml_error)?;
+        self.applied.write().await.power_limit = watts;
+        Ok(())
+    }
+
+    async fn set_temperature_limit(&self, celsius: f64) -> Result<(), AppError> {
+        self.applied.write().await.temperature_limit = celsius;
+        Ok(())
+    }
+
+    async fn set_memory_clock(&self, mhz: u32) -> Result<(), AppError> {
+        let mut device = self.device()?;
+        device.set_mem_locked_clocks(mhz, mhz).map_err(nvml_error)?;
+        self.applied.write().await.memory_clock = mhz;
+        Ok(())
+    }
+
+    async fn set_gpu_clock(&self, mhz: u32) -> Result<(), AppError> {
+        use nvml_wrapper::enums::device::GpuLockedClocksSetting;
+
+        let mut device = self.device()?;
+        device
+            .set_gpu_locked_clocks(GpuLockedClocksSetting::Numeric {
+                min_clock_mhz: mhz,
+                max_clock_mhz: mhz,
+            })
+            .map_err(nvml_error)?;
+        self.applied.write().await.gpu_clock = mhz;
+        Ok(())
+    }
+
+    async fn set_fan_speed(&self, percent: u32) -> Result<(), AppError> {
+        let mut device = self.device()?;
+        device.set_fan_speed(0, percent).map_err(nvml_error)?;
+        self.applied.write().await.fan_speed = percent;
+        Ok(())
+    }
+
+    async fn read_telemetry(&self) -> Result<GpuInfo, AppError> {
+        use nvml_wrapper::enum_wrappers::device::TemperatureSensor;
+
+        let device = self.device()?;
+        let memory = device.memory_info().map_err(nvml_error)?;
+        Ok(GpuInfo {
+            model: device.name().ok(),
+            usage: device.utilization_rates().ok().map(|u| u.gpu as f64),
+            temperature: device
+                .temperature(TemperatureSensor::Gpu)
+                .ok()
+                .map(|t| t as f64),
+            memory_used: Some(memory.used),
+            memory_total: Some(memory.total),
+            ..GpuInfo::default()
+        })
+    }
+
+    fn is_hardware(&self) -> bool {
+        true
+    }
+}
+
+/// Менеджер GPU
+pub struct GpuManager {
+    control: Arc<dyn GpuControl>,
+    gpu_info: Arc<RwLock<GpuInfo>>,
+    /// Сериализует применение настроек
+    apply_lock: Mutex<()>,
+    /// Результат поиска GPU; `None`, если поиск не выполнялся
+    detected: Option<bool>,
+    /// Лимит мощности до первой оптимизации, от него считается профиль
+    baseline_power_limit: tokio::sync::OnceCell<u32>,
+}
+
+impl GpuManager {
+    /// Использует NVML, если включена feature `cuda` и найден GPU,
+    /// иначе заглушку. Без feature `cuda` наличие GPU неизвестно.
+    pub fn new() -> Self {
+        #[cfg(feature = "cuda")]
+        return match NvmlGpuControl::new(0) {
+            Ok(control) => Self::with_control(Arc::new(control)),
+            Err(e) => {
+                log::warn!("NVML unavailable, using stub GPU control: {}", e);
+                Self::with_control(Arc::new(StubGpuControl::new()))
+            }
+        };
+
+        #[cfg(not(feature = "cuda"))]
+        Self {
+            detected: None,
+            ..Self::with_control(Arc::new(StubGpuControl::new()))
+        }
+    }
+
+    /// Менеджер с заданным управлением; наличие GPU определяет `control`
+    pub fn with_control(control: Arc<dyn GpuControl>) -> Self {
+        Self {
+            detected: Some(control.is_hardware()),
+            control,
+            gpu_info: Arc::new(RwLock::new(GpuInfo::default())),
+            apply_lock: Mutex::new(()),
+            baseline_power_limit: tokio::sync::OnceCell::new(),
+        }
+    }
+
+    /// Управляет ли менеджер настоящим GPU
+    pub fn is_available(&self) -> bool {
+        self.control.is_hardware()
+    }
+
+    /// Найден ли GPU; `None`, если поиск не выполнялся (сборка без `cuda`)
+    pub fn gpu_detected(&self) -> Option<bool> {
+        self.detected
+    }
+
+    /// Получает информацию о GPU
+    pub async fn get_gpu_info(&self) -> Result<GpuInfo, AppError> {
+        let mut info = self.gpu_info.read().await.clone();
+        if let Ok(telemetry) = self.control.read_telemetry().await {
+            info.model = telemetry.model.or(info.model);
+            info.usage = telemetry.usage.or(info.usage);
+            info.temperature = telemetry.temperature.or(info.temperature);
+            info.memory_used = telemetry.memory_used.or(info.memory_used);
+            info.memory_total = telemetry.memory_total.or(info.memory_total);
+        }
+        if let Ok(config) = self.control.read_config().await {
+            info.power_limit = Some(config.power_limit);
+            info.temperature_limit = Some(config.temperature_limit);
+            info.memory_clock = Some(config.memory_clock);
+            info.gpu_clock = Some(config.gpu_clock);
+            info.fan_speed = Some(config.fan_speed);
+        }
+        Ok(info)
+    }
+
+    /// Текущие действующие настройки
+    pub async fn get_config(&self) -> Result<GpuConfig, AppError> {
+        self.control.read_config().await
+    }
+
+    /// Проверяет и применяет настройки целиком. При ошибке на любом шаге или
+    /// расхождении считанных значений восстанавливаются прежние настройки.
+    /// Возвращает действующие значения после применения.
+    pub async fn apply_config(&self, config: &GpuConfig) -> Result<GpuConfig, AppError> {
+        config.validate()?;
+
+        let _guard = self.apply_lock.lock().await;
+        let previous = self.control.read_config().await?;
+
+        let applied = match self.write_config(config).await {
+            Ok(()) => self.control.read_config().await,
+            Err(e) => Err(e),
+        };
+
+        match applied {
+            Ok(effective) if effective == *config => {
+                log::info!("Applied GPU config: {:?}", effective);
+                Ok(effective)
+            }
+            result => {
+                let error = match result {
+                    Ok(effective) => AppError::Unknown(format!(
+                        "GPU reported {:?} after applying {:?}",
+                        effective, config
+                    )),
+                    Err(e) => e,
+                };
+                log::warn!("Failed to apply GPU config, rolling back: {}", error);
+                if let Err(e) = self.write_config(&previous).await {
+                    log::error!("Failed to roll back GPU config: {}", e);
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Применяет энергоэффективный профиль: лимит мощности 85% от исходного,
+    /// считанного при первой оптимизации. Повторный вызов не снижает лимит
+    /// дальше; частоты и остальные настройки не меняются.
+    pub async fn optimize(&self) -> Result<GpuConfig, AppError> {
+        let baseline = *self
+            .baseline_power_limit
+            .get_or_try_init(|| async { self.control.read_config().await.map(|c| c.power_limit) })
+            .await?;
+        let target = (baseline * 85 / 100).clamp(*POWER_LIMIT_RANGE.start(), *POWER_LIMIT_RANGE.end());
+
+        let _guard = self.apply_lock.lock().await;
+        let previous = self.control.read_config().await?;
+        let applied = match self.control.set_power_limit(target).await {
+            Ok(()) => self.control.read_config().await,
+            Err(e) => Err(e),
+        };
+
+        match applied {
+            Ok(effective) if effective.power_limit == target => {
+                log::info!("Optimized GPU power limit: {} W (baseline {} W)", target, baseline);
+                Ok(effective)
+            }
+            result => {
+                let error = match result {
+                    Ok(effective) => AppError::Unknown(format!(
+                        "GPU reported power limit {} W after setting {} W",
+                        effective.power_limit, target
+                    )),
+                    Err(e) => e,
+                };
+                log::warn!("Failed to optimize GPU, rolling back: {}", error);
+                if let Err(e) = self.control.set_power_limit(previous.power_limit).await {
+                    log::error!("Failed to roll back GPU power limit: {}", e);
+                }
+                Err(error)
+            }
+        }
+    }
+
+    async fn write_config(&self, config: &GpuConfig) -> Result<(), AppError> {
+        self.control.set_power_limit(config.power_limit).await?;
+        self.control.set_temperature_limit(config.temperature_limit).await?;
+        self.control.set_memory_clock(config.memory_clock).await?;
+        self.control.set_gpu_clock(config.gpu_clock).await?;
+        self.control.set_fan_speed(config.fan_speed).await
+    }
+}
+
+impl Default for GpuManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Драйвер, отказывающий в установке частоты ядра
+    struct FailingClockControl {
+        inner: StubGpuControl,
+    }
+
+    #[async_trait]
+    impl GpuControl for FailingClockControl {
+        async fn read_config(&self) -> Result<GpuConfig, AppError> {
+            self.inner.read_config().await
+        }
+        async fn set_power_limit(&self, watts: u32) -> Result<(), AppError> {
+            self.inner.set_power_limit(watts).await
+        }
+        async fn set_temperature_limit(&self, celsius: f64) -> Result<(), AppError> {
+            self.inner.set_temperature_limit(celsius).await
+        }
+        async fn set_memory_clock(&self, mhz: u32) -> Result<(), AppError> {
+            self.inner.set_memory_clock(mhz).await
+        }
+        async fn set_gpu_clock(&self, mhz: u32) -> Result<(), AppError> {
+            if mhz != GpuConfig::default().gpu_clock {
+                return Err(AppError::Unknown("clock locked by driver".to_string()));
+            }
+            self.inner.set_gpu_clock(mhz).await
+        }
+        async fn set_fan_speed(&self, percent: u32) -> Result<(), AppError> {
+            self.inner.set_fan_speed(percent).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unsafe_values_rejected() {
+        let manager = GpuManager::with_control(Arc::new(StubGpuControl::new()));
+
+        let unsafe_configs = vec![
+            GpuConfig { fan_speed: 120, ..GpuConfig::default() },
+            GpuConfig { temperature_limit: 105.0, ..GpuConfig::default() },
+            GpuConfig { power_limit: 1000, ..GpuConfig::default() },
+            GpuConfig { gpu_clock: 5000, ..GpuConfig::default() },
+        ];
+        for config in unsafe_configs {
+            let result = manager.apply_config(&config).await;
+            assert!(matches!(result, Err(AppError::InvalidInput(_))), "{:?}", config);
+        }
+
+        assert_eq!(manager.get_config().await.unwrap(), GpuConfig::default());
+    }
+
+    #[tokio::test]
+    async fn test_apply_reflected_in_config() {
+        let manager = GpuManager::with_control(Arc::new(StubGpuControl::new()));
+        let config = GpuConfig {
+            power_limit: 200,
+            temperature_limit: 75.0,
+            memory_clock: 15000,
+            gpu_clock: 1800,
+            fan_speed: 65,
+        };
+
+        let effective = manager.apply_config(&config).await.unwrap();
+
+        assert_eq!(effective, config);
+        assert_eq!(manager.get_config().await.unwrap(), config);
+        assert_eq!(manager.get_gpu_info().await.unwrap().fan_speed, Some(65));
+    }
+
+    #[cfg(not(feature = "cuda"))]
+    #[tokio::test]
+    async fn test_stub_used_without_cuda_feature() {
+        let manager = GpuManager::new();
+
+        assert!(!manager.is_available());
+        assert_eq!(manager.gpu_detected(), None);
+        assert_eq!(manager.get_config().await.unwrap(), GpuConfig::default());
+
+        let optimized = manager.optimize().await.unwrap();
+        assert_eq!(optimized.power_limit, GpuConfig::default().power_limit * 85 / 100);
+        assert_eq!(manager.get_gpu_info().await.unwrap().power_limit, Some(optimized.power_limit));
+    }
+
+    #[tokio::test]
+    async fn test_repeated_optimize_keeps_baseline_and_clocks() {
+        let manager = GpuManager::with_control(Arc::new(StubGpuControl::new()));
+        let baseline = GpuConfig::default().power_limit;
+
+        manager.optimize().await.unwrap();
+        let clocked = GpuConfig {
+            gpu_clock: GpuConfig::default().gpu_clock + 100,
+            ..manager.get_config().await.unwrap()
+        };
+        manager.apply_config(&clocked).await.unwrap();
+
+        let optimized = manager.optimize().await.unwrap();
+        assert_eq!(optimized.power_limit, baseline * 85 / 100);
+        assert_eq!(optimized.gpu_clock, clocked.gpu_clock);
+    }
+
+    #[cfg(feature = "cuda")]
+    #[tokio::test]
+    async fn test_nvml_reports_device_when_present() {
+        let manager = GpuManager::new();
+        if !manager.is_available() {
+            // Без GPU менеджер откатывается на заглушку
+            assert_eq!(manager.get_config().await.unwrap(), GpuConfig::default());
+            return;
+        }
+
+        let info = manager.get_gpu_info().await.unwrap();
+        assert!(info.model.is_some());
+        assert!(info.memory_total.unwrap_or(0) > 0);
+        assert!(POWER_LIMIT_RANGE.contains(&info.power_limit.unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_partial_failure_rolls_back() {
+        let manager = GpuManager::with_control(Arc::new(FailingClockControl {
+            inner: StubGpuControl::new(),
+        }));
+        let config = GpuConfig {
+            power_limit: 200,
+            gpu_clock: 1500,
+            ..GpuConfig::default()
+        };
+
+        assert!(manager.apply_config(&config).await.is_err());
+        assert_eq!(manager.get_config().await.unwrap(), GpuConfig::default());
+    }
+}
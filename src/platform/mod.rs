@@ -6,6 +6,7 @@ pub mod soladdr;
 pub mod lmrouter;
 pub mod lib;
 pub mod error;
+pub mod gpu;
 
 pub use linux::*;
 pub use windows::*;
@@ -15,6 +16,7 @@ pub use soladdr::*;
 pub use lmrouter::*;
 pub use lib::*;
 pub use error::*;
+pub use gpu::*;
 
 use std::path::PathBuf;
 use thiserror::Error;
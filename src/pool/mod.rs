@@ -8,10 +8,15 @@ use tokio::sync::Mutex;
 use actix_web::middleware::Logger;
 use actix_files as fs;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use parking_lot::RwLock;
 use std::error::Error;
+use crate::admin::ip_allowlist::IpAllowlist;
+use crate::admin::admin_token::AdminTokenHash;
+use crate::admin::maintenance::{Clock, SystemClock};
+use crate::monitoring::events::{EventBus, EventKind};
 
 pub mod pool;
 pub mod pool_cok;
@@ -21,6 +26,7 @@ pub mod bridges;
 pub mod home;
 pub mod login;
 pub mod playground;
+pub mod rebalance;
 
 pub use pool::*;
 pub use pool_cok::*;
@@ -30,6 +36,7 @@ pub use bridges::*;
 pub use home::*;
 pub use login::*;
 pub use playground::*;
+pub use rebalance::*;
 
 pub fn routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -105,6 +112,22 @@ pub struct PoolConfig {
     pub network_mode: String,
     pub security_groups: Vec<String>,
     pub tags: Vec<String>,
+    /// Минимальный накопленный баланс воркера для выплаты
+    #[serde(default = "default_payout_threshold")]
+    pub payout_threshold: f64,
+    /// Алгоритм майнинга пула
+    #[serde(default)]
+    pub algorithm: String,
+    /// Допустимые модели GPU; пустой список или `*` - любые
+    #[serde(default)]
+    pub allowed_gpu_models: Vec<String>,
+    /// Комиссия пула с каждой начисленной награды, проценты (0-100)
+    #[serde(default)]
+    pub fee_percentage: f64,
+}
+
+fn default_payout_threshold() -> f64 {
+    0.1
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -118,6 +141,69 @@ pub struct PoolStats {
     pub total_tasks: u64,
     pub completed_tasks: u64,
     pub failed_tasks: u64,
+    /// Сумма начисленных наград до вычета комиссии
+    #[serde(default)]
+    pub total_rewards: f64,
+    /// Удержанная комиссия пула
+    #[serde(default)]
+    pub total_fees_collected: f64,
+}
+
+/// Начисление награды в пуле
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RewardCredit {
+    pub gross: f64,
+    pub fee: f64,
+    pub net: f64,
+}
+
+/// Сводка выплат пула
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayoutSummary {
+    pub pool: String,
+    pub fee_percentage: f64,
+    pub gross_rewards: f64,
+    pub fees: f64,
+    pub net_rewards: f64,
+}
+
+/// Ошибки менеджера пулов
+#[derive(Debug, thiserror::Error)]
+pub enum PoolError {
+    #[error("{0}")]
+    AlreadyExists(String),
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    InvalidConfig(String),
+    #[error("{0}")]
+    ScaleRejected(String),
+    #[error("{0}")]
+    Storage(String),
+    #[error("{0}")]
+    WorkerRejected(String),
+}
+
+impl PoolError {
+    /// HTTP-статус, соответствующий ошибке
+    pub fn status_code(&self) -> actix_web::http::StatusCode {
+        use actix_web::http::StatusCode;
+        match self {
+            PoolError::AlreadyExists(_) => StatusCode::CONFLICT,
+            PoolError::NotFound(_) => StatusCode::NOT_FOUND,
+            PoolError::InvalidConfig(_) | PoolError::ScaleRejected(_) => StatusCode::BAD_REQUEST,
+            PoolError::Storage(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            PoolError::WorkerRejected(_) => StatusCode::FORBIDDEN,
+        }
+    }
+
+    fn pool_not_found(name: &str) -> Self {
+        PoolError::NotFound(format!("Pool '{}' not found", name))
+    }
+}
+
+fn pool_error_response(err: &PoolError) -> HttpResponse {
+    HttpResponse::build(err.status_code()).json(err.to_string())
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -127,21 +213,102 @@ pub struct PoolMetrics {
 }
 
 pub struct PoolManager {
-    pools: Arc<Mutex<HashMap<String, PoolMetrics>>>,
+    pools: Arc<parking_lot::Mutex<HashMap<String, PoolMetrics>>>,
+    members: Arc<Mutex<HashMap<String, Vec<PoolWorker>>>>,
+    storage_path: Option<PathBuf>,
+    events: Arc<EventBus>,
 }
 
 impl PoolManager {
     pub fn new() -> Self {
+        Self::with_storage(None)
+    }
+
+    /// Создаёт менеджер, хранящий пулы в JSON-файле. Если путь задан,
+    /// сохранённые пулы загружаются сразу, а изменения сбрасываются на диск.
+    pub fn with_storage(storage_path: Option<PathBuf>) -> Self {
+        let pools = storage_path
+            .as_deref()
+            .map(read_pools_file)
+            .unwrap_or_default();
+
         Self {
-            pools: Arc::new(Mutex::new(HashMap::new())),
+            pools: Arc::new(parking_lot::Mutex::new(pools)),
+            members: Arc::new(Mutex::new(HashMap::new())),
+            storage_path,
+            events: Arc::new(EventBus::default()),
+        }
+    }
+
+    /// Публикует события пулов в общую шину
+    pub fn with_event_bus(mut self, events: Arc<EventBus>) -> Self {
+        self.events = events;
+        self
+    }
+
+    pub fn events(&self) -> Arc<EventBus> {
+        self.events.clone()
+    }
+
+    /// Сохраняет пулы на диск (если путь задан)
+    pub async fn save_to_disk(&self) -> Result<(), PoolError> {
+        let pools = self.pools.lock();
+        self.flush(&pools)
+    }
+
+    /// Перечитывает пулы с диска. Повреждённый файл даёт пустой набор пулов.
+    pub async fn load_from_disk(&self) -> Result<usize, PoolError> {
+        let path = self
+            .storage_path
+            .as_deref()
+            .ok_or_else(|| PoolError::Storage("Pool storage path is not configured".to_string()))?;
+        let loaded = read_pools_file(path);
+        let count = loaded.len();
+        *self.pools.lock() = loaded;
+        info!("Loaded {} pools from {}", count, path.display());
+        Ok(count)
+    }
+
+    fn flush(&self, pools: &HashMap<String, PoolMetrics>) -> Result<(), PoolError> {
+        let path = match &self.storage_path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| PoolError::Storage(format!("Failed to create {}: {}", parent.display(), e)))?;
+        }
+        let data = serde_json::to_string_pretty(pools)
+            .map_err(|e| PoolError::Storage(format!("Failed to serialize pools: {}", e)))?;
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, data)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| PoolError::Storage(format!("Failed to write {}: {}", path.display(), e)))
+    }
+
+    /// Применяет изменение к пулам. При заданном пути изменение сначала
+    /// сохраняется на диск и только затем попадает в память, поэтому ошибка
+    /// записи оставляет память и файл согласованными.
+    fn apply<T>(
+        &self,
+        pools: &mut HashMap<String, PoolMetrics>,
+        change: impl FnOnce(&mut HashMap<String, PoolMetrics>) -> Result<T, PoolError>,
+    ) -> Result<T, PoolError> {
+        if self.storage_path.is_none() {
+            return change(pools);
         }
+        let mut next = pools.clone();
+        let result = change(&mut next)?;
+        self.flush(&next)?;
+        *pools = next;
+        Ok(result)
     }
 
-    pub async fn create_pool(&self, config: PoolConfig) -> Result<(), String> {
-        let mut pools = self.pools.lock().await;
+    pub async fn create_pool(&self, config: PoolConfig) -> Result<(), PoolError> {
+        let mut pools = self.pools.lock();
         
         if pools.contains_key(&config.name) {
-            return Err(format!("Pool '{}' already exists", config.name));
+            return Err(PoolError::AlreadyExists(format!("Pool '{}' already exists", config.name)));
         }
 
         // Validate pool configuration
@@ -159,59 +326,379 @@ impl PoolManager {
                 total_tasks: 0,
                 completed_tasks: 0,
                 failed_tasks: 0,
+                total_rewards: 0.0,
+                total_fees_collected: 0.0,
             },
         };
 
-        pools.insert(metrics.config.name.clone(), metrics);
-        info!("Created new pool: {}", metrics.config.name);
+        let name = metrics.config.name.clone();
+        self.apply(&mut pools, |pools| {
+            pools.insert(name.clone(), metrics);
+            Ok(())
+        })?;
+        info!("Created new pool: {}", name);
+        self.events.publish(EventKind::PoolCreated, serde_json::json!({ "pool": name }));
         Ok(())
     }
 
-    fn validate_pool_config(&self, config: &PoolConfig) -> Result<(), String> {
+    fn validate_pool_config(&self, config: &PoolConfig) -> Result<(), PoolError> {
         if config.max_workers == 0 {
-            return Err("max_workers must be greater than 0".to_string());
+            return Err(PoolError::InvalidConfig("max_workers must be greater than 0".to_string()));
         }
         if config.max_memory_gb == 0 {
-            return Err("max_memory_gb must be greater than 0".to_string());
+            return Err(PoolError::InvalidConfig("max_memory_gb must be greater than 0".to_string()));
         }
         if config.max_cpu_cores == 0 {
-            return Err("max_cpu_cores must be greater than 0".to_string());
+            return Err(PoolError::InvalidConfig("max_cpu_cores must be greater than 0".to_string()));
         }
         if config.auto_scale && config.min_workers >= config.max_workers {
-            return Err("min_workers must be less than max_workers when auto_scale is enabled".to_string());
+            return Err(PoolError::InvalidConfig(
+                "min_workers must be less than max_workers when auto_scale is enabled".to_string(),
+            ));
+        }
+        if !config.payout_threshold.is_finite() || config.payout_threshold < 0.0 {
+            return Err(PoolError::InvalidConfig(
+                "payout_threshold must be a non-negative number".to_string(),
+            ));
+        }
+        if !config.fee_percentage.is_finite() || !(0.0..=100.0).contains(&config.fee_percentage) {
+            return Err(PoolError::InvalidConfig(
+                "fee_percentage must be between 0 and 100".to_string(),
+            ));
         }
         Ok(())
     }
 
     pub async fn get_pool(&self, name: &str) -> Option<PoolMetrics> {
-        self.pools.lock().await.get(name).cloned()
+        self.pools.lock().get(name).cloned()
     }
 
     pub async fn list_pools(&self) -> Vec<PoolMetrics> {
-        self.pools.lock().await.values().cloned().collect()
+        self.pools.lock().values().cloned().collect()
     }
 
-    pub async fn update_pool(&self, name: &str, new_config: PoolConfig) -> Result<(), String> {
-        let mut pools = self.pools.lock().await;
-        
-        if let Some(pool) = pools.get_mut(name) {
-            self.validate_pool_config(&new_config)?;
-            pool.config = new_config;
-            info!("Updated pool: {}", name);
+    pub async fn update_pool(&self, name: &str, new_config: PoolConfig) -> Result<(), PoolError> {
+        let mut pools = self.pools.lock();
+        if !pools.contains_key(name) {
+            return Err(PoolError::pool_not_found(name));
+        }
+        self.validate_pool_config(&new_config)?;
+
+        self.apply(&mut pools, |pools| {
+            if let Some(pool) = pools.get_mut(name) {
+                pool.config = new_config;
+            }
             Ok(())
-        } else {
-            Err(format!("Pool '{}' not found", name))
+        })?;
+        info!("Updated pool: {}", name);
+        Ok(())
+    }
+
+    pub async fn delete_pool(&self, name: &str) -> Result<(), PoolError> {
+        {
+            let mut pools = self.pools.lock();
+            self.apply(&mut pools, |pools| {
+                pools
+                    .remove(name)
+                    .map(|_| ())
+                    .ok_or_else(|| PoolError::pool_not_found(name))
+            })?;
         }
+
+        self.members.lock().await.remove(name);
+        info!("Deleted pool: {}", name);
+        Ok(())
     }
 
-    pub async fn delete_pool(&self, name: &str) -> Result<(), String> {
-        let mut pools = self.pools.lock().await;
-        
-        if pools.remove(name).is_some() {
-            info!("Deleted pool: {}", name);
+    /// Масштабирует пул до заданного числа воркеров
+    pub async fn scale_pool(&self, name: &str, target_workers: u32) -> Result<PoolStats, PoolError> {
+        let mut pools = self.pools.lock();
+        let (previous, stats) = self.apply(&mut pools, |pools| {
+            let pool = pools
+                .get_mut(name)
+                .ok_or_else(|| PoolError::pool_not_found(name))?;
+
+            if !pool.config.auto_scale {
+                return Err(PoolError::ScaleRejected(format!("Auto-scaling is disabled for pool '{}'", name)));
+            }
+            if target_workers < pool.config.min_workers || target_workers > pool.config.max_workers {
+                return Err(PoolError::ScaleRejected(format!(
+                    "Target {} is outside the allowed range {}..={} for pool '{}'",
+                    target_workers, pool.config.min_workers, pool.config.max_workers, name
+                )));
+            }
+
+            let previous = pool.stats.total_workers;
+            pool.stats.total_workers = target_workers;
+            pool.stats.active_workers = target_workers;
+            pool.stats.last_scale_time = Some(Utc::now());
+            Ok((previous, pool.stats.clone()))
+        })?;
+
+        info!("Scaled pool {} from {} to {} workers", name, previous, target_workers);
+        self.events.publish(
+            EventKind::PoolScaled,
+            serde_json::json!({ "pool": name, "from": previous, "to": target_workers }),
+        );
+        Ok(stats)
+    }
+
+    /// Начисляет награду в пуле: удерживает комиссию пула и возвращает
+    /// сумму, причитающуюся воркеру
+    pub fn credit_reward(&self, name: &str, gross: f64) -> Result<RewardCredit, PoolError> {
+        if !gross.is_finite() || gross < 0.0 {
+            return Err(PoolError::InvalidConfig(
+                "reward must be a non-negative number".to_string(),
+            ));
+        }
+
+        let mut pools = self.pools.lock();
+        let fee = self.apply(&mut pools, |pools| {
+            let pool = pools
+                .get_mut(name)
+                .ok_or_else(|| PoolError::pool_not_found(name))?;
+
+            let fee = gross * pool.config.fee_percentage / 100.0;
+            pool.stats.total_rewards += gross;
+            pool.stats.total_fees_collected += fee;
+            Ok(fee)
+        })?;
+        Ok(RewardCredit { gross, fee, net: gross - fee })
+    }
+
+    /// Начисленные награды пула: до комиссии, комиссия и остаток воркерам
+    pub fn payout_summary(&self, name: &str) -> Result<PayoutSummary, PoolError> {
+        let pools = self.pools.lock();
+        let pool = pools.get(name).ok_or_else(|| PoolError::pool_not_found(name))?;
+
+        Ok(PayoutSummary {
+            pool: name.to_string(),
+            fee_percentage: pool.config.fee_percentage,
+            gross_rewards: pool.stats.total_rewards,
+            fees: pool.stats.total_fees_collected,
+            net_rewards: pool.stats.total_rewards - pool.stats.total_fees_collected,
+        })
+    }
+
+    /// Может ли воркер с данной моделью GPU войти в пул.
+    /// Сравнение без учёта регистра; пустой список или `*` разрешают любые модели.
+    pub fn can_join(&self, pool: &str, worker_gpu_model: &str) -> bool {
+        let pools = self.pools.lock();
+        let Some(metrics) = pools.get(pool) else {
+            return false;
+        };
+
+        let allowed: Vec<&str> = metrics
+            .config
+            .allowed_gpu_models
+            .iter()
+            .map(|model| model.trim())
+            .filter(|model| !model.is_empty())
+            .collect();
+
+        allowed.is_empty()
+            || allowed
+                .iter()
+                .any(|model| *model == "*" || model.eq_ignore_ascii_case(worker_gpu_model.trim()))
+    }
+
+    pub async fn add_pool_worker(
+        &self,
+        pool: &str,
+        worker_id: &str,
+        gpu_model: &str,
+        capabilities: Vec<String>,
+    ) -> Result<(), PoolError> {
+        if !self.pools.lock().contains_key(pool) {
+            return Err(PoolError::pool_not_found(pool));
+        }
+        if !self.can_join(pool, gpu_model) {
+            return Err(PoolError::WorkerRejected(format!(
+                "GPU model '{}' of worker '{}' is not allowed in pool '{}'",
+                gpu_model, worker_id, pool
+            )));
+        }
+
+        let mut members = self.members.lock().await;
+        let workers = members.entry(pool.to_string()).or_default();
+        if workers.iter().any(|w| w.worker_id == worker_id) {
+            return Err(PoolError::AlreadyExists(format!("Worker '{}' already in pool '{}'", worker_id, pool)));
+        }
+
+        // Счётчики сохраняются до изменения состава, чтобы ошибка записи ничего не меняла
+        let mut pools = self.pools.lock();
+        self.apply(&mut pools, |pools| {
+            let metrics = pools.get_mut(pool).ok_or_else(|| PoolError::pool_not_found(pool))?;
+            metrics.stats.total_workers += 1;
+            metrics.stats.active_workers += 1;
             Ok(())
-        } else {
-            Err(format!("Pool '{}' not found", name))
+        })?;
+        drop(pools);
+
+        workers.push(PoolWorker {
+            worker_id: worker_id.to_string(),
+            capabilities,
+            tasks: Vec::new(),
+            active: true,
+        });
+        info!("Added worker {} to pool {}", worker_id, pool);
+        Ok(())
+    }
+
+    /// Пул, в котором состоит воркер
+    pub async fn pool_of_worker(&self, worker_id: &str) -> Option<String> {
+        let members = self.members.lock().await;
+        members
+            .iter()
+            .find(|(_, workers)| workers.iter().any(|w| w.worker_id == worker_id))
+            .map(|(pool, _)| pool.clone())
+    }
+
+    /// Отмечает участника пула активным или неактивным и пересчитывает
+    /// `active_workers` пула
+    pub async fn set_pool_worker_active(&self, pool: &str, worker_id: &str, active: bool) -> Result<(), PoolError> {
+        let mut members = self.members.lock().await;
+        let worker = members
+            .get_mut(pool)
+            .and_then(|workers| workers.iter_mut().find(|w| w.worker_id == worker_id))
+            .ok_or_else(|| PoolError::NotFound(format!("Worker '{}' not found in pool '{}'", worker_id, pool)))?;
+        if worker.active == active {
+            return Ok(());
+        }
+
+        let mut pools = self.pools.lock();
+        self.apply(&mut pools, |pools| {
+            let metrics = pools.get_mut(pool).ok_or_else(|| PoolError::pool_not_found(pool))?;
+            metrics.stats.active_workers = if active {
+                metrics.stats.active_workers + 1
+            } else {
+                metrics.stats.active_workers.saturating_sub(1)
+            };
+            Ok(())
+        })?;
+        worker.active = active;
+        Ok(())
+    }
+
+    /// Убирает воркера из всех пулов и уменьшает их счётчики; `active_workers`
+    /// уменьшается, только если воркер был активен. Счётчики сохраняются до
+    /// изменения состава: при ошибке записи воркер остаётся в пулах.
+    /// Возвращает пулы, в которых он состоял; для неизвестного воркера - пустой список.
+    pub async fn remove_worker_from_pools(&self, worker_id: &str) -> Result<Vec<String>, PoolError> {
+        let mut members = self.members.lock().await;
+        let removed: Vec<(String, bool)> = members
+            .iter()
+            .filter_map(|(pool, workers)| {
+                workers
+                    .iter()
+                    .find(|w| w.worker_id == worker_id)
+                    .map(|w| (pool.clone(), w.active))
+            })
+            .collect();
+        if removed.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut pools = self.pools.lock();
+        self.apply(&mut pools, |pools| {
+            for (pool, active) in &removed {
+                if let Some(metrics) = pools.get_mut(pool) {
+                    metrics.stats.total_workers = metrics.stats.total_workers.saturating_sub(1);
+                    if *active {
+                        metrics.stats.active_workers = metrics.stats.active_workers.saturating_sub(1);
+                    }
+                }
+            }
+            Ok(())
+        })?;
+        drop(pools);
+
+        let mut owners = Vec::with_capacity(removed.len());
+        for (pool, _) in removed {
+            if let Some(workers) = members.get_mut(&pool) {
+                workers.retain(|w| w.worker_id != worker_id);
+            }
+            info!("Removed worker {} from pool {}", worker_id, pool);
+            owners.push(pool);
+        }
+        Ok(owners)
+    }
+
+    pub async fn assign_task(
+        &self,
+        pool: &str,
+        worker_id: &str,
+        task: AssignedTask,
+    ) -> Result<(), PoolError> {
+        let mut members = self.members.lock().await;
+        let worker = members
+            .get_mut(pool)
+            .and_then(|workers| workers.iter_mut().find(|w| w.worker_id == worker_id))
+            .ok_or_else(|| PoolError::NotFound(format!("Worker '{}' not found in pool '{}'", worker_id, pool)))?;
+        worker.tasks.push(task);
+        Ok(())
+    }
+
+    pub async fn pool_workers(&self, pool: &str) -> Vec<PoolWorker> {
+        self.members.lock().await.get(pool).cloned().unwrap_or_default()
+    }
+
+    /// Воркеры пула по возрастанию ID; `NotFound`, если пула нет
+    pub async fn get_pool_workers(&self, name: &str) -> Result<Vec<PoolWorker>, PoolError> {
+        if !self.pools.lock().contains_key(name) {
+            return Err(PoolError::pool_not_found(name));
+        }
+        let mut workers = self.pool_workers(name).await;
+        workers.sort_by(|a, b| a.worker_id.cmp(&b.worker_id));
+        Ok(workers)
+    }
+
+    /// Перераспределяет задачи между воркерами пула
+    pub async fn rebalance_pool(
+        &self,
+        pool: &str,
+        max_moves: usize,
+    ) -> Result<RebalanceSummary, PoolError> {
+        if !self.pools.lock().contains_key(pool) {
+            return Err(PoolError::pool_not_found(pool));
+        }
+
+        let mut members = self.members.lock().await;
+        let workers = members.entry(pool.to_string()).or_default();
+
+        let load_variance_before = load_variance(workers);
+        let moves = rebalance(workers, max_moves);
+        let load_variance_after = load_variance(workers);
+
+        info!(
+            "Rebalanced pool {}: {} moves, variance {:.3} -> {:.3}",
+            pool, moves.len(), load_variance_before, load_variance_after
+        );
+
+        Ok(RebalanceSummary {
+            pool: pool.to_string(),
+            moves,
+            load_variance_before,
+            load_variance_after,
+        })
+    }
+}
+
+/// Читает файл пулов; отсутствующий или повреждённый файл даёт пустой набор
+fn read_pools_file(path: &std::path::Path) -> HashMap<String, PoolMetrics> {
+    let data = match std::fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return HashMap::new(),
+        Err(e) => {
+            log::warn!("Failed to read pools from {}: {}", path.display(), e);
+            return HashMap::new();
+        }
+    };
+    match serde_json::from_str(&data) {
+        Ok(pools) => pools,
+        Err(e) => {
+            log::warn!("Pool storage {} is corrupt, starting empty: {}", path.display(), e);
+            HashMap::new()
         }
     }
 }
@@ -231,16 +718,14 @@ pub fn config(cfg: &mut web::ServiceConfig) {
                     .route("/pools/{name}/scale", web::post().to(scale_pool))
                     .route("/pools/{name}/stats", web::get().to(get_pool_stats))
             )
+            .route("/{name}/rebalance", web::post().to(rebalance_pool))
     );
 }
 
 async fn get_pools(
     pool_manager: web::Data<PoolManager>,
 ) -> impl Responder {
-    match pool_manager.list_pools().await {
-        Ok(pools) => HttpResponse::Ok().json(pools),
-        Err(e) => HttpResponse::InternalServerError().json(e),
-    }
+    HttpResponse::Ok().json(pool_manager.list_pools().await)
 }
 
 async fn create_pool(
@@ -249,7 +734,7 @@ async fn create_pool(
 ) -> impl Responder {
     match pool_manager.create_pool(config.into_inner()).await {
         Ok(_) => HttpResponse::Created().finish(),
-        Err(e) => HttpResponse::BadRequest().json(e),
+        Err(e) => pool_error_response(&e),
     }
 }
 
@@ -270,7 +755,7 @@ async fn update_pool(
 ) -> impl Responder {
     match pool_manager.update_pool(&name, config.into_inner()).await {
         Ok(_) => HttpResponse::Ok().finish(),
-        Err(e) => HttpResponse::BadRequest().json(e),
+        Err(e) => pool_error_response(&e),
     }
 }
 
@@ -280,7 +765,7 @@ async fn delete_pool(
 ) -> impl Responder {
     match pool_manager.delete_pool(&name).await {
         Ok(_) => HttpResponse::Ok().finish(),
-        Err(e) => HttpResponse::BadRequest().json(e),
+        Err(e) => pool_error_response(&e),
     }
 }
 
@@ -289,8 +774,32 @@ async fn scale_pool(
     name: web::Path<String>,
     scale: web::Json<u32>,
 ) -> impl Responder {
-    // Implement pool scaling logic
-    HttpResponse::Ok().finish()
+    match pool_manager.scale_pool(&name, scale.into_inner()).await {
+        Ok(stats) => HttpResponse::Ok().json(stats),
+        Err(e) => pool_error_response(&e),
+    }
+}
+
+async fn rebalance_pool(
+    pool_manager: web::Data<PoolManager>,
+    name: web::Path<String>,
+    body: web::Bytes,
+) -> impl Responder {
+    // Тело необязательно, но если передано, оно должно быть корректным
+    let request = if body.iter().all(u8::is_ascii_whitespace) {
+        RebalanceRequest::default()
+    } else {
+        match serde_json::from_slice::<RebalanceRequest>(&body) {
+            Ok(request) => request,
+            Err(e) => return HttpResponse::BadRequest().json(format!("Invalid rebalance request: {}", e)),
+        }
+    };
+    let max_moves = request.max_moves.unwrap_or(DEFAULT_REBALANCE_CHURN_LIMIT);
+
+    match pool_manager.rebalance_pool(&name, max_moves).await {
+        Ok(summary) => HttpResponse::Ok().json(summary),
+        Err(e) => pool_error_response(&e),
+    }
 }
 
 async fn get_pool_stats(
@@ -305,17 +814,41 @@ async fn get_pool_stats(
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdminConfig {
-    pub admin_token: String,
-    pub allowed_ips: Vec<String>,
+    /// SHA-256 токена администратора в hex
+    pub admin_token_hash: AdminTokenHash,
+    /// Разбирается и проверяется при загрузке конфигурации
+    pub allowed_ips: IpAllowlist,
     pub rate_limit: u32,
     pub session_timeout_minutes: u32,
 }
 
+impl AdminConfig {
+    /// Создаёт конфигурацию из токена в открытом виде; сохраняется только хэш
+    pub fn with_token(
+        token: &str,
+        allowed_ips: IpAllowlist,
+        rate_limit: u32,
+        session_timeout_minutes: u32,
+    ) -> Self {
+        Self {
+            admin_token_hash: AdminTokenHash::from_plaintext(token),
+            allowed_ips,
+            rate_limit,
+            session_timeout_minutes,
+        }
+    }
+
+    /// Проверяет токен за постоянное время
+    pub fn verify_token(&self, candidate: &str) -> bool {
+        self.admin_token_hash.verify(candidate)
+    }
+}
+
 pub struct PoolAdminPanel {
     bridge_manager: Arc<BridgeManager>,
     pool_manager: Arc<PoolManager>,
     config: AdminConfig,
-    sessions: Arc<RwLock<HashMap<String, DateTime<Utc>>>>,
+    sessions: SessionMap,
 }
 
 impl PoolAdminPanel {
@@ -368,13 +901,85 @@ struct LoginRequest {
     token: String,
 }
 
+/// Заголовок с идентификатором сессии администратора
+pub const SESSION_HEADER: &str = "X-Session-Id";
+
+/// Сессии администратора: id -> время входа
+pub type SessionMap = Arc<RwLock<HashMap<String, DateTime<Utc>>>>;
+
+/// Проверяет сессию. Сессия, открытая раньше чем `timeout` назад,
+/// удаляется и считается недействительной.
+pub fn validate_session(
+    session_id: &str,
+    sessions: &RwLock<HashMap<String, DateTime<Utc>>>,
+    timeout: chrono::Duration,
+    now: DateTime<Utc>,
+) -> bool {
+    let mut sessions = sessions.write();
+    match sessions.get(session_id) {
+        Some(created) if now - *created < timeout => true,
+        Some(_) => {
+            sessions.remove(session_id);
+            info!("Admin session {} expired", session_id);
+            false
+        }
+        None => false,
+    }
+}
+
+/// Часы панели: `web::Data<Arc<dyn Clock>>`, если задан, иначе системные
+fn session_clock(req: &actix_web::HttpRequest) -> Arc<dyn Clock> {
+    req.app_data::<web::Data<Arc<dyn Clock>>>()
+        .map(|clock| clock.get_ref().clone())
+        .unwrap_or_else(|| Arc::new(SystemClock))
+}
+
+/// Действующая сессия администратора. Извлекается из `X-Session-Id`;
+/// отсутствующая или истёкшая сессия даёт 401.
+pub struct AdminSession {
+    pub session_id: String,
+}
+
+impl actix_web::FromRequest for AdminSession {
+    type Error = actix_web::Error;
+    type Future = std::future::Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &actix_web::HttpRequest, _payload: &mut actix_web::dev::Payload) -> Self::Future {
+        std::future::ready(authorize_session(req))
+    }
+}
+
+fn authorize_session(req: &actix_web::HttpRequest) -> Result<AdminSession, actix_web::Error> {
+    let (config, sessions) = match (
+        req.app_data::<web::Data<AdminConfig>>(),
+        req.app_data::<web::Data<SessionMap>>(),
+    ) {
+        (Some(config), Some(sessions)) => (config, sessions),
+        _ => return Err(error::ErrorInternalServerError("Admin sessions are not configured")),
+    };
+
+    let session_id = req
+        .headers()
+        .get(SESSION_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| error::ErrorUnauthorized("Missing session"))?;
+
+    let timeout = chrono::Duration::minutes(config.session_timeout_minutes as i64);
+    if validate_session(session_id, sessions, timeout, session_clock(req).now()) {
+        Ok(AdminSession { session_id: session_id.to_string() })
+    } else {
+        Err(error::ErrorUnauthorized("Session expired or invalid"))
+    }
+}
+
 #[post("/login")]
 async fn login(
+    http_req: actix_web::HttpRequest,
     req: web::Json<LoginRequest>,
     config: web::Data<AdminConfig>,
-    sessions: web::Data<Arc<RwLock<HashMap<String, DateTime<Utc>>>>>,
+    sessions: web::Data<SessionMap>,
 ) -> impl Responder {
-    if req.token != config.admin_token {
+    if !config.verify_token(&req.token) {
         return HttpResponse::Unauthorized().json(serde_json::json!({
             "error": "Invalid token"
         }));
@@ -382,7 +987,7 @@ async fn login(
 
     let session_id = Uuid::new_v4().to_string();
     let mut sessions = sessions.write();
-    sessions.insert(session_id.clone(), Utc::now());
+    sessions.insert(session_id.clone(), session_clock(&http_req).now());
 
     HttpResponse::Ok().json(serde_json::json!({
         "session_id": session_id
@@ -391,11 +996,11 @@ async fn login(
 
 #[post("/logout")]
 async fn logout(
-    session_id: web::Header<String>,
-    sessions: web::Data<Arc<RwLock<HashMap<String, DateTime<Utc>>>>>,
+    session: AdminSession,
+    sessions: web::Data<SessionMap>,
 ) -> impl Responder {
     let mut sessions = sessions.write();
-    sessions.remove(&session_id.to_string());
+    sessions.remove(&session.session_id);
     HttpResponse::Ok().json(serde_json::json!({
         "status": "logged out"
     }))
@@ -403,6 +1008,7 @@ async fn logout(
 
 #[get("/bridges")]
 async fn get_bridges(
+    _session: AdminSession,
     bridge_manager: web::Data<Arc<BridgeManager>>,
 ) -> impl Responder {
     let bridges = bridge_manager.get_all_bridges().await;
@@ -411,6 +1017,7 @@ async fn get_bridges(
 
 #[post("/bridges")]
 async fn add_bridge(
+    _session: AdminSession,
     config: web::Json<BridgeConfig>,
     bridge_manager: web::Data<Arc<BridgeManager>>,
 ) -> impl Responder {
@@ -426,6 +1033,7 @@ async fn add_bridge(
 
 #[delete("/bridges/{bridge_id}")]
 async fn remove_bridge(
+    _session: AdminSession,
     bridge_id: web::Path<String>,
     bridge_manager: web::Data<Arc<BridgeManager>>,
 ) -> impl Responder {
@@ -441,6 +1049,7 @@ async fn remove_bridge(
 
 #[get("/bridges/{bridge_id}/transactions")]
 async fn get_bridge_transactions(
+    _session: AdminSession,
     bridge_id: web::Path<String>,
     bridge_manager: web::Data<Arc<BridgeManager>>,
 ) -> impl Responder {
@@ -454,6 +1063,7 @@ async fn get_bridge_transactions(
 
 #[get("/pools")]
 async fn get_pools(
+    _session: AdminSession,
     pool_manager: web::Data<Arc<PoolManager>>,
 ) -> impl Responder {
     let pools = pool_manager.get_all_pools().await;
@@ -462,6 +1072,7 @@ async fn get_pools(
 
 #[post("/pools")]
 async fn add_pool(
+    _session: AdminSession,
     config: web::Json<PoolConfig>,
     pool_manager: web::Data<Arc<PoolManager>>,
 ) -> impl Responder {
@@ -477,6 +1088,7 @@ async fn add_pool(
 
 #[delete("/pools/{pool_id}")]
 async fn remove_pool(
+    _session: AdminSession,
     pool_id: web::Path<String>,
     pool_manager: web::Data<Arc<PoolManager>>,
 ) -> impl Responder {
@@ -492,6 +1104,7 @@ async fn remove_pool(
 
 #[get("/pools/{pool_id}/stats")]
 async fn get_pool_stats(
+    _session: AdminSession,
     pool_id: web::Path<String>,
     pool_manager: web::Data<Arc<PoolManager>>,
 ) -> impl Responder {
@@ -505,6 +1118,7 @@ async fn get_pool_stats(
 
 #[get("/pools/{pool_id}/workers/{worker_id}/stats")]
 async fn get_worker_stats(
+    _session: AdminSession,
     path: web::Path<(String, String)>,
     pool_manager: web::Data<Arc<PoolManager>>,
 ) -> impl Responder {
@@ -562,6 +1176,7 @@ async fn serve_index() -> impl Responder {
                                 <th>Workers</th>
                                 <th>Hashrate</th>
                                 <th>Shares</th>
+                                <th>Payout Threshold</th>
                                 <th>Status</th>
                                 <th>Actions</th>
                             </tr>
@@ -648,6 +1263,7 @@ async fn serve_index() -> impl Responder {
                         <td>${pool.stats.active_workers}/${pool.stats.total_workers}</td>
                         <td>${pool.stats.total_hashrate.toFixed(2)} H/s</td>
                         <td>${pool.stats.total_shares}</td>
+                        <td>${pool.config.payout_threshold}</td>
                         <td>${pool.config.maintenance_mode ? 'Maintenance' : 'Active'}</td>
                         <td>
                             <button class="btn" onclick="showPoolStats('${pool.config.name}')">Stats</button>
@@ -834,19 +1450,38 @@ async fn serve_index() -> impl Responder {
         .body(html)
 }
 
+/// Минимальная конфигурация пула для тестов
+#[cfg(test)]
+pub(crate) fn test_pool_config(name: &str) -> PoolConfig {
+    PoolConfig {
+        name: name.to_string(),
+        description: String::new(),
+        max_workers: 4,
+        max_memory_gb: 8,
+        max_cpu_cores: 4,
+        auto_scale: false,
+        min_workers: 0,
+        max_workers_per_vm: 1,
+        vm_template: "none".to_string(),
+        network_mode: "none".to_string(),
+        security_groups: vec![],
+        tags: vec![],
+        payout_threshold: 0.1,
+        algorithm: String::new(),
+        allowed_gpu_models: vec![],
+        fee_percentage: 0.0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::ManualClock;
     use actix_web::test;
 
     #[actix_rt::test]
     async fn test_login() {
-        let config = AdminConfig {
-            admin_token: "test_token".to_string(),
-            allowed_ips: vec![],
-            rate_limit: 100,
-            session_timeout_minutes: 30,
-        };
+        let config = AdminConfig::with_token("test_token", IpAllowlist::default(), 100, 30);
         let bridge_manager = Arc::new(BridgeManager::new());
         let pool_manager = Arc::new(PoolManager::new());
         let panel = PoolAdminPanel::new(bridge_manager, pool_manager, config);
@@ -870,4 +1505,379 @@ mod tests {
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_success());
     }
-} 
\ No newline at end of file
+
+    #[actix_rt::test]
+    async fn test_rebalance_pool_endpoint() {
+        let pool_manager = web::Data::new(PoolManager::new());
+        pool_manager.create_pool(test_pool_config("p1")).await.unwrap();
+        pool_manager.add_pool_worker("p1", "a", "", vec![]).await.unwrap();
+        pool_manager.add_pool_worker("p1", "b", "", vec![]).await.unwrap();
+        for i in 0..4 {
+            pool_manager.assign_task("p1", "a", AssignedTask {
+                task_id: format!("t{}", i),
+                load: 1.0,
+                capabilities: vec![],
+            }).await.unwrap();
+        }
+
+        let app = test::init_service(
+            App::new()
+                .app_data(pool_manager.clone())
+                .route("/pool/{name}/rebalance", web::post().to(rebalance_pool))
+        ).await;
+
+        let req = test::TestRequest::post().uri("/pool/p1/rebalance").to_request();
+        let summary: RebalanceSummary = test::call_and_read_body_json(&app, req).await;
+
+        assert_eq!(summary.moves.len(), 2);
+        assert!(summary.load_variance_after < summary.load_variance_before);
+        let loads: Vec<f64> = pool_manager.pool_workers("p1").await.iter().map(|w| w.load()).collect();
+        assert_eq!(loads, vec![2.0, 2.0]);
+
+        let req = test::TestRequest::post().uri("/pool/missing/rebalance").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 404);
+
+        let req = test::TestRequest::post()
+            .uri("/pool/p1/rebalance")
+            .insert_header(("content-type", "application/json"))
+            .set_payload("{ max_moves: ")
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 400);
+    }
+
+    #[actix_rt::test]
+    async fn test_pools_survive_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state").join("pools.json");
+
+        let manager = PoolManager::with_storage(Some(path.clone()));
+        manager.create_pool(test_pool_config("persistent")).await.unwrap();
+        manager.create_pool(test_pool_config("temporary")).await.unwrap();
+        manager.delete_pool("temporary").await.unwrap();
+        drop(manager);
+
+        let reloaded = PoolManager::with_storage(Some(path));
+        assert!(reloaded.get_pool("persistent").await.is_some());
+        assert!(reloaded.get_pool("temporary").await.is_none());
+    }
+
+    #[actix_rt::test]
+    async fn test_failed_write_leaves_pools_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pools.json");
+        let manager = PoolManager::with_storage(Some(path.clone()));
+        manager.create_pool(test_pool_config("kept")).await.unwrap();
+
+        // Каталог на месте временного файла: запись не удастся
+        std::fs::create_dir(path.with_extension("json.tmp")).unwrap();
+
+        let err = manager.create_pool(test_pool_config("lost")).await.unwrap_err();
+        assert!(matches!(err, PoolError::Storage(_)));
+        assert!(manager.get_pool("lost").await.is_none());
+        assert!(matches!(manager.delete_pool("kept").await, Err(PoolError::Storage(_))));
+        assert!(manager.get_pool("kept").await.is_some());
+    }
+
+    #[actix_rt::test]
+    async fn test_worker_counts_persisted_and_inactive_removal_keeps_active_count() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pools.json");
+        let manager = PoolManager::with_storage(Some(path.clone()));
+        manager.create_pool(test_pool_config("counted")).await.unwrap();
+        for worker in ["w1", "w2", "w3"] {
+            manager.add_pool_worker("counted", worker, "RTX 4090", vec![]).await.unwrap();
+        }
+        manager.set_pool_worker_active("counted", "w2", false).await.unwrap();
+
+        assert_eq!(manager.remove_worker_from_pools("w2").await.unwrap(), vec!["counted"]);
+        assert!(manager.remove_worker_from_pools("missing").await.unwrap().is_empty());
+
+        let restored = PoolManager::with_storage(Some(path));
+        let stats = restored.get_pool("counted").await.unwrap().stats;
+        assert_eq!(stats.total_workers, 2);
+        assert_eq!(stats.active_workers, 2);
+    }
+
+    #[actix_rt::test]
+    async fn test_failed_write_keeps_worker_in_pool() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pools.json");
+        let manager = PoolManager::with_storage(Some(path.clone()));
+        manager.create_pool(test_pool_config("kept")).await.unwrap();
+        manager.add_pool_worker("kept", "w1", "RTX 4090", vec![]).await.unwrap();
+
+        std::fs::create_dir(path.with_extension("json.tmp")).unwrap();
+
+        assert!(matches!(
+            manager.add_pool_worker("kept", "w2", "RTX 4090", vec![]).await,
+            Err(PoolError::Storage(_))
+        ));
+        assert!(matches!(manager.remove_worker_from_pools("w1").await, Err(PoolError::Storage(_))));
+        let workers = manager.get_pool_workers("kept").await.unwrap();
+        assert_eq!(workers.iter().map(|w| w.worker_id.as_str()).collect::<Vec<_>>(), vec!["w1"]);
+        assert_eq!(manager.get_pool("kept").await.unwrap().stats.total_workers, 1);
+    }
+
+    #[actix_rt::test]
+    async fn test_corrupt_pool_storage_starts_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pools.json");
+        std::fs::write(&path, "{ not json").unwrap();
+
+        let manager = PoolManager::with_storage(Some(path.clone()));
+        assert!(manager.list_pools().await.is_empty());
+
+        manager.create_pool(test_pool_config("fresh")).await.unwrap();
+        assert_eq!(manager.load_from_disk().await.unwrap(), 1);
+    }
+
+    fn gpu_pool(name: &str, allowed: &[&str]) -> PoolConfig {
+        PoolConfig {
+            algorithm: "ethash".to_string(),
+            allowed_gpu_models: allowed.iter().map(|m| m.to_string()).collect(),
+            ..test_pool_config(name)
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_whitelisted_gpu_can_join() {
+        let manager = PoolManager::new();
+        manager.create_pool(gpu_pool("rtx", &["RTX 4090", "RTX 3090"])).await.unwrap();
+
+        assert!(manager.can_join("rtx", "rtx 4090"));
+        manager.add_pool_worker("rtx", "w1", "RTX 3090", vec![]).await.unwrap();
+        assert_eq!(manager.get_pool_workers("rtx").await.unwrap().len(), 1);
+    }
+
+    #[actix_rt::test]
+    async fn test_non_whitelisted_gpu_rejected() {
+        let manager = PoolManager::new();
+        manager.create_pool(gpu_pool("rtx", &["RTX 4090"])).await.unwrap();
+
+        assert!(!manager.can_join("rtx", "GTX 1060"));
+        assert!(!manager.can_join("missing", "RTX 4090"));
+        let err = manager.add_pool_worker("rtx", "w1", "GTX 1060", vec![]).await.unwrap_err();
+        assert!(matches!(err, PoolError::WorkerRejected(_)));
+        assert_eq!(err.status_code(), actix_web::http::StatusCode::FORBIDDEN);
+        assert!(manager.get_pool_workers("rtx").await.unwrap().is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn test_empty_or_wildcard_whitelist_allows_all() {
+        let manager = PoolManager::new();
+        manager.create_pool(gpu_pool("open", &[])).await.unwrap();
+        manager.create_pool(gpu_pool("star", &["*"])).await.unwrap();
+        // Пустые элементы из формы ("a,,b") не считаются ограничением
+        manager.create_pool(gpu_pool("blank", &["", " "])).await.unwrap();
+
+        for pool in ["open", "star", "blank"] {
+            assert!(manager.can_join(pool, "Any GPU"));
+            manager.add_pool_worker(pool, "w1", "Any GPU", vec![]).await.unwrap();
+        }
+    }
+
+    fn auto_scaled_pool(name: &str) -> PoolConfig {
+        PoolConfig {
+            auto_scale: true,
+            min_workers: 1,
+            ..test_pool_config(name)
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_credited_rewards_accrue_pool_fee() {
+        let manager = PoolManager::new();
+        manager
+            .create_pool(PoolConfig { fee_percentage: 2.5, ..test_pool_config("fees") })
+            .await
+            .unwrap();
+
+        let credit = manager.credit_reward("fees", 40.0).unwrap();
+        assert_eq!(credit, RewardCredit { gross: 40.0, fee: 1.0, net: 39.0 });
+        manager.credit_reward("fees", 60.0).unwrap();
+
+        let summary = manager.payout_summary("fees").unwrap();
+        assert_eq!(summary.fee_percentage, 2.5);
+        assert!((summary.gross_rewards - 100.0).abs() < 1e-9);
+        assert!((summary.fees - 2.5).abs() < 1e-9);
+        assert!((summary.net_rewards - 97.5).abs() < 1e-9);
+        assert!((manager.get_pool("fees").await.unwrap().stats.total_fees_collected - 2.5).abs() < 1e-9);
+
+        assert!(matches!(manager.credit_reward("fees", -1.0), Err(PoolError::InvalidConfig(_))));
+        assert!(matches!(manager.payout_summary("missing"), Err(PoolError::NotFound(_))));
+        assert!(matches!(
+            manager.create_pool(PoolConfig { fee_percentage: 150.0, ..test_pool_config("greedy") }).await,
+            Err(PoolError::InvalidConfig(_))
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn test_worker_rewards_pay_pool_fee_and_persist() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pools.json");
+        let manager = Arc::new(PoolManager::with_storage(Some(path.clone())));
+        manager
+            .create_pool(PoolConfig { fee_percentage: 2.5, ..test_pool_config("fees") })
+            .await
+            .unwrap();
+        manager.add_pool_worker("fees", "w1", "", vec![]).await.unwrap();
+
+        // Воркеру пула зачисляется награда за вычетом комиссии
+        let rewards = RewardSystem::with_base_rate(10.0).with_pool_manager(manager.clone());
+        let hour = std::time::Duration::from_secs(3600);
+        let net = rewards.record_reward("w1", ActivityType::Mining, 1.0, hour).await.unwrap();
+        let gross = rewards.calculate_reward(ActivityType::Mining, 1.0, hour);
+        assert!((net - gross * 0.975).abs() < 1e-9);
+        assert!((rewards.get_worker_total("w1") - net).abs() < 1e-9);
+
+        // Воркер вне пулов получает награду целиком
+        let solo = rewards.record_reward("solo", ActivityType::Mining, 1.0, hour).await.unwrap();
+        assert!((solo - gross).abs() < 1e-9);
+
+        // Комиссия сохранена на диск сразу после начисления
+        let reloaded = PoolManager::with_storage(Some(path));
+        reloaded.load_from_disk().await.unwrap();
+        let summary = reloaded.payout_summary("fees").unwrap();
+        assert!((summary.gross_rewards - gross).abs() < 1e-9);
+        assert!((summary.fees - gross * 0.025).abs() < 1e-9);
+    }
+
+    #[actix_rt::test]
+    async fn test_create_pool_publishes_event() {
+        let events = Arc::new(EventBus::default());
+        let manager = PoolManager::new().with_event_bus(events.clone());
+        manager.create_pool(test_pool_config("evented")).await.unwrap();
+
+        let published = events.recent(None, 10);
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].kind, EventKind::PoolCreated);
+        assert_eq!(published[0].data["pool"], "evented");
+
+        // Отклонённое создание событий не порождает
+        assert!(manager.create_pool(test_pool_config("evented")).await.is_err());
+        assert_eq!(events.len(), 1);
+    }
+
+    #[actix_rt::test]
+    async fn test_scale_pool_up() {
+        let pool_manager = web::Data::new(PoolManager::new());
+        pool_manager.create_pool(auto_scaled_pool("scalable")).await.unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(pool_manager.clone())
+                .route("/pools/{name}/scale", web::post().to(scale_pool))
+        ).await;
+
+        let req = test::TestRequest::post().uri("/pools/scalable/scale").set_json(3u32).to_request();
+        let stats: PoolStats = test::call_and_read_body_json(&app, req).await;
+
+        assert_eq!(stats.total_workers, 3);
+        assert_eq!(stats.active_workers, 3);
+        assert!(stats.last_scale_time.is_some());
+        assert_eq!(pool_manager.get_pool("scalable").await.unwrap().stats.total_workers, 3);
+    }
+
+    #[actix_rt::test]
+    async fn test_scale_pool_past_max_rejected() {
+        let manager = PoolManager::new();
+        manager.create_pool(auto_scaled_pool("bounded")).await.unwrap();
+
+        assert!(matches!(manager.scale_pool("bounded", 5).await, Err(PoolError::ScaleRejected(_))));
+        assert!(matches!(manager.scale_pool("bounded", 0).await, Err(PoolError::ScaleRejected(_))));
+        assert_eq!(manager.get_pool("bounded").await.unwrap().stats.total_workers, 0);
+    }
+
+    #[actix_rt::test]
+    async fn test_scale_pool_without_auto_scale_rejected() {
+        let manager = PoolManager::new();
+        manager.create_pool(test_pool_config("fixed")).await.unwrap();
+
+        let err = manager.scale_pool("fixed", 2).await.unwrap_err();
+        assert!(matches!(err, PoolError::ScaleRejected(_)));
+        assert!(err.to_string().contains("Auto-scaling is disabled"));
+        assert!(manager.get_pool("fixed").await.unwrap().stats.last_scale_time.is_none());
+    }
+
+    #[actix_rt::test]
+    async fn test_pool_errors_are_typed() {
+        let manager = PoolManager::new();
+        manager.create_pool(test_pool_config("dup")).await.unwrap();
+
+        assert!(matches!(
+            manager.create_pool(test_pool_config("dup")).await,
+            Err(PoolError::AlreadyExists(_))
+        ));
+        assert!(matches!(
+            manager.create_pool(PoolConfig { max_workers: 0, ..test_pool_config("bad") }).await,
+            Err(PoolError::InvalidConfig(_))
+        ));
+        assert!(matches!(manager.delete_pool("missing").await, Err(PoolError::NotFound(_))));
+        assert!(matches!(manager.scale_pool("missing", 1).await, Err(PoolError::NotFound(_))));
+    }
+
+    #[actix_rt::test]
+    async fn test_pool_error_status_codes() {
+        let pool_manager = web::Data::new(PoolManager::new());
+        pool_manager.create_pool(test_pool_config("taken")).await.unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(pool_manager.clone())
+                .route("/pools", web::post().to(create_pool))
+                .route("/pools/{name}", web::put().to(update_pool))
+                .route("/pools/{name}", web::delete().to(delete_pool))
+        ).await;
+
+        let req = test::TestRequest::post().uri("/pools").set_json(test_pool_config("taken")).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 409);
+
+        let invalid = PoolConfig { max_cpu_cores: 0, ..test_pool_config("taken") };
+        let req = test::TestRequest::put().uri("/pools/taken").set_json(invalid).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 400);
+
+        let req = test::TestRequest::delete().uri("/pools/missing").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 404);
+    }
+
+    #[actix_rt::test]
+    async fn test_expired_session_rejected() {
+        let config = AdminConfig::with_token("test_token", IpAllowlist::default(), 100, 30);
+        let sessions: SessionMap = Arc::new(RwLock::new(HashMap::new()));
+        let clock = Arc::new(ManualClock::new(Utc::now()));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(BridgeManager::new())))
+                .app_data(web::Data::new(config))
+                .app_data(web::Data::new(sessions.clone()))
+                .app_data(web::Data::new(clock.clone() as Arc<dyn Clock>))
+                .service(login)
+                .service(get_bridges)
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri("/login")
+            .set_json(&LoginRequest { token: "test_token".to_string() })
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        let session_id = body["session_id"].as_str().unwrap().to_string();
+
+        let req = test::TestRequest::get()
+            .uri("/bridges")
+            .insert_header((SESSION_HEADER, session_id.as_str()))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+        clock.advance(chrono::Duration::minutes(31));
+        let req = test::TestRequest::get()
+            .uri("/bridges")
+            .insert_header((SESSION_HEADER, session_id.as_str()))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 401);
+        assert!(sessions.read().is_empty());
+
+        let req = test::TestRequest::get().uri("/bridges").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 401);
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Serialize, Deserialize};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use parking_lot::Mutex;
 use log::{info, warn, error};
 use chrono::{DateTime, Utc};
 use thiserror::Error;
@@ -71,6 +72,20 @@ pub struct PoolStats {
     pub network_difficulty: u64,
     pub block_reward: f64,
     pub estimated_daily_reward: f64,
+    /// Задачи в очереди
+    #[serde(default)]
+    pub queued_tasks: u64,
+    /// Выполняющиеся задачи
+    #[serde(default)]
+    pub active_tasks: u64,
+    #[serde(default)]
+    pub completed_tasks: u64,
+    #[serde(default)]
+    pub failed_tasks: u64,
+    #[serde(default)]
+    pub last_block_hash: Option<String>,
+    #[serde(default)]
+    pub last_block_time: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,17 +96,54 @@ pub struct PoolMetrics {
 
 pub struct PoolManager {
     pools: Arc<Mutex<Vec<PoolMetrics>>>,
+    /// Пулы, переведённые в обслуживание вызовом `stop`
+    stopped: Arc<Mutex<Vec<String>>>,
+    running: AtomicBool,
 }
 
 impl PoolManager {
     pub fn new() -> Self {
         Self {
             pools: Arc::new(Mutex::new(Vec::new())),
+            stopped: Arc::new(Mutex::new(Vec::new())),
+            running: AtomicBool::new(true),
         }
     }
 
+    /// Прекращает приём работы: активные пулы переходят в режим обслуживания.
+    /// Пулы, уже бывшие в обслуживании, `start` не трогает.
+    pub async fn stop(&self) -> Result<(), PoolError> {
+        let mut pools = self.pools.lock();
+        let mut stopped = self.stopped.lock();
+        for pool in pools.iter_mut().filter(|p| !p.config.maintenance_mode) {
+            pool.config.maintenance_mode = true;
+            stopped.push(pool.config.name.clone());
+        }
+        self.running.store(false, Ordering::SeqCst);
+        info!("Stopped {} pools", stopped.len());
+        Ok(())
+    }
+
+    /// Возвращает в работу пулы, остановленные `stop`
+    pub async fn start(&self) -> Result<(), PoolError> {
+        let mut pools = self.pools.lock();
+        let stopped: Vec<String> = self.stopped.lock().drain(..).collect();
+        for pool in pools.iter_mut().filter(|p| stopped.contains(&p.config.name)) {
+            pool.config.maintenance_mode = false;
+        }
+        self.running.store(true, Ordering::SeqCst);
+        info!("Started {} pools", stopped.len());
+        Ok(())
+    }
+
+    /// Активные воркеры во всех пулах
+    pub async fn active_worker_count(&self) -> u32 {
+        let pools = self.pools.lock();
+        pools.iter().map(|p| p.stats.active_workers).sum()
+    }
+
     pub async fn add_pool(&self, config: PoolConfig) -> Result<(), PoolError> {
-        let mut pools = self.pools.lock().await;
+        let mut pools = self.pools.lock();
         
         if pools.iter().any(|p| p.config.name == config.name) {
             return Err(PoolError::InvalidConfig(format!("Pool '{}' already exists", config.name)));
@@ -118,6 +170,12 @@ impl PoolManager {
                 network_difficulty: 0,
                 block_reward: 0.0,
                 estimated_daily_reward: 0.0,
+                queued_tasks: 0,
+                active_tasks: 0,
+                completed_tasks: 0,
+                failed_tasks: 0,
+                last_block_hash: None,
+                last_block_time: None,
             },
         };
 
@@ -127,7 +185,7 @@ impl PoolManager {
     }
 
     pub async fn remove_pool(&self, name: &str) -> Result<(), PoolError> {
-        let mut pools = self.pools.lock().await;
+        let mut pools = self.pools.lock();
         
         let initial_len = pools.len();
         pools.retain(|p| p.config.name != name);
@@ -152,7 +210,7 @@ impl PoolManager {
         temperature: f64,
         power_usage: f64,
     ) -> Result<(), PoolError> {
-        let mut pools = self.pools.lock().await;
+        let mut pools = self.pools.lock();
         
         let pool = pools
             .iter_mut()
@@ -203,7 +261,7 @@ impl PoolManager {
     }
 
     pub async fn get_pool(&self, name: &str) -> Result<PoolMetrics, PoolError> {
-        let pools = self.pools.lock().await;
+        let pools = self.pools.lock();
         
         pools
             .iter()
@@ -213,12 +271,12 @@ impl PoolManager {
     }
 
     pub async fn get_all_pools(&self) -> Vec<PoolMetrics> {
-        let pools = self.pools.lock().await;
+        let pools = self.pools.lock();
         pools.clone()
     }
 
     pub async fn get_active_pools(&self) -> Vec<PoolMetrics> {
-        let pools = self.pools.lock().await;
+        let pools = self.pools.lock();
         pools
             .iter()
             .filter(|p| !p.config.maintenance_mode)
@@ -227,7 +285,7 @@ impl PoolManager {
     }
 
     pub async fn set_pool_maintenance(&self, name: &str, maintenance: bool) -> Result<(), PoolError> {
-        let mut pools = self.pools.lock().await;
+        let mut pools = self.pools.lock();
         
         let pool = pools
             .iter_mut()
@@ -244,7 +302,7 @@ impl PoolManager {
     }
 
     pub async fn get_worker_stats(&self, pool_name: &str, worker_id: &str) -> Result<WorkerStats, PoolError> {
-        let pools = self.pools.lock().await;
+        let pools = self.pools.lock();
         
         let pool = pools
             .iter()
@@ -260,7 +318,7 @@ impl PoolManager {
     }
 
     pub async fn get_pool_stats(&self, name: &str) -> Result<PoolStats, PoolError> {
-        let pools = self.pools.lock().await;
+        let pools = self.pools.lock();
         
         let pool = pools
             .iter()
@@ -276,7 +334,7 @@ impl PoolManager {
         network_difficulty: u64,
         block_reward: f64,
     ) -> Result<(), PoolError> {
-        let mut pools = self.pools.lock().await;
+        let mut pools = self.pools.lock();
         
         let pool = pools
             .iter_mut()
@@ -294,6 +352,102 @@ impl PoolManager {
 
         Ok(())
     }
+
+    /// Пулы принимают работу (не остановлены через `stop`)
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// Общее число воркеров во всех пулах
+    pub fn get_worker_count(&self) -> u32 {
+        self.pools.lock().iter().map(|p| p.stats.total_workers).sum()
+    }
+
+    /// Число активных воркеров во всех пулах
+    pub fn get_active_worker_count(&self) -> u32 {
+        self.pools.lock().iter().map(|p| p.stats.active_workers).sum()
+    }
+
+    /// Суммарный хешрейт всех пулов
+    pub fn get_total_hashrate(&self) -> f64 {
+        self.pools.lock().iter().map(|p| p.stats.total_hashrate).sum()
+    }
+
+    /// Число выполняющихся задач
+    pub fn get_active_task_count(&self) -> u64 {
+        self.pools.lock().iter().map(|p| p.stats.active_tasks).sum()
+    }
+
+    /// Число задач в очереди
+    pub fn get_queue_size(&self) -> u64 {
+        self.pools.lock().iter().map(|p| p.stats.queued_tasks).sum()
+    }
+
+    /// Хэш последнего найденного блока среди всех пулов
+    pub fn get_last_block_hash(&self) -> Option<String> {
+        self.pools
+            .lock()
+            .iter()
+            .filter(|p| p.stats.last_block_time.is_some())
+            .max_by_key(|p| p.stats.last_block_time)
+            .and_then(|p| p.stats.last_block_hash.clone())
+    }
+
+    fn with_pool_stats<T>(
+        &self,
+        name: &str,
+        update: impl FnOnce(&mut PoolMetrics) -> Result<T, PoolError>,
+    ) -> Result<T, PoolError> {
+        let mut pools = self.pools.lock();
+        let pool = pools
+            .iter_mut()
+            .find(|p| p.config.name == name)
+            .ok_or_else(|| PoolError::PoolNotFound(name.to_string()))?;
+        update(pool)
+    }
+
+    /// Принимает задачу в очередь пула. Пул в обслуживании задачу отклоняет,
+    /// и она не попадает в счётчики.
+    pub fn record_task_queued(&self, name: &str) -> Result<(), PoolError> {
+        self.with_pool_stats(name, |pool| {
+            if pool.config.maintenance_mode {
+                return Err(PoolError::MaintenanceMode(name.to_string()));
+            }
+            pool.stats.queued_tasks += 1;
+            Ok(())
+        })
+    }
+
+    /// Задача взята из очереди в работу
+    pub fn record_task_started(&self, name: &str) -> Result<(), PoolError> {
+        self.with_pool_stats(name, |pool| {
+            pool.stats.queued_tasks = pool.stats.queued_tasks.saturating_sub(1);
+            pool.stats.active_tasks += 1;
+            Ok(())
+        })
+    }
+
+    /// Задача завершена: принята (`accepted`) или отклонена
+    pub fn record_task_finished(&self, name: &str, accepted: bool) -> Result<(), PoolError> {
+        self.with_pool_stats(name, |pool| {
+            pool.stats.active_tasks = pool.stats.active_tasks.saturating_sub(1);
+            if accepted {
+                pool.stats.completed_tasks += 1;
+            } else {
+                pool.stats.failed_tasks += 1;
+            }
+            Ok(())
+        })
+    }
+
+    /// Пул нашёл блок
+    pub fn record_block(&self, name: &str, block_hash: &str) -> Result<(), PoolError> {
+        self.with_pool_stats(name, |pool| {
+            pool.stats.last_block_hash = Some(block_hash.to_string());
+            pool.stats.last_block_time = Some(Utc::now());
+            Ok(())
+        })
+    }
 }
 
 #[cfg(test)]
@@ -374,4 +528,90 @@ mod tests {
         assert_eq!(stats.hashrate, 100.0);
         assert_eq!(stats.shares, 1000);
     }
-} 
\ No newline at end of file
+
+    fn pool_config(name: &str) -> PoolConfig {
+        PoolConfig {
+            name: name.to_string(),
+            url: "http://test.com".to_string(),
+            api_key: "test_key".to_string(),
+            min_workers: 1,
+            max_workers: 10,
+            min_memory_gb: 4,
+            max_memory_gb: 16,
+            allowed_gpu_models: vec!["RTX 3080".to_string()],
+            maintenance_mode: false,
+            algorithm: "ethash".to_string(),
+            difficulty: 1,
+            payout_threshold: 0.1,
+            fee_percentage: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_counters_on_empty_manager() {
+        let manager = PoolManager::new();
+        assert!(manager.is_running());
+        assert_eq!(manager.get_worker_count(), 0);
+        assert_eq!(manager.get_active_worker_count(), 0);
+        assert_eq!(manager.get_total_hashrate(), 0.0);
+        assert_eq!(manager.get_active_task_count(), 0);
+        assert_eq!(manager.get_queue_size(), 0);
+        assert!(manager.get_last_block_hash().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_counters_aggregate_across_pools() {
+        let manager = PoolManager::new();
+        manager.add_pool(pool_config("a")).await.unwrap();
+        manager.add_pool(pool_config("b")).await.unwrap();
+        manager
+            .update_worker_stats("a", "w1".to_string(), 100.0, 10, 1, 0, 0.0, 0.0, 200.0)
+            .await
+            .unwrap();
+        manager
+            .update_worker_stats("b", "w2".to_string(), 50.5, 5, 0, 0, 0.0, 0.0, 100.0)
+            .await
+            .unwrap();
+        manager
+            .update_worker_stats("b", "w3".to_string(), 0.0, 0, 0, 0, 0.0, 0.0, 0.0)
+            .await
+            .unwrap();
+
+        for _ in 0..3 {
+            manager.record_task_queued("a").unwrap();
+        }
+        manager.record_task_queued("b").unwrap();
+        manager.record_task_started("a").unwrap();
+        manager.record_task_started("b").unwrap();
+        manager.record_task_finished("b", true).unwrap();
+
+        manager.record_block("a", "0000aaaa").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        manager.record_block("b", "0000bbbb").unwrap();
+
+        assert_eq!(manager.get_worker_count(), 3);
+        assert_eq!(manager.get_active_worker_count(), 2);
+        assert_eq!(manager.get_total_hashrate(), 150.5);
+        assert_eq!(manager.get_queue_size(), 2);
+        assert_eq!(manager.get_active_task_count(), 1);
+        assert_eq!(manager.get_pool_stats("b").await.unwrap().completed_tasks, 1);
+        assert_eq!(manager.get_last_block_hash().as_deref(), Some("0000bbbb"));
+        assert!(matches!(manager.record_task_queued("missing"), Err(PoolError::PoolNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_stopped_pool_rejects_tasks() {
+        let manager = PoolManager::new();
+        manager.add_pool(pool_config("a")).await.unwrap();
+
+        manager.stop().await.unwrap();
+        assert!(!manager.is_running());
+        assert!(matches!(manager.record_task_queued("a"), Err(PoolError::MaintenanceMode(_))));
+        assert_eq!(manager.get_queue_size(), 0);
+
+        manager.start().await.unwrap();
+        assert!(manager.is_running());
+        manager.record_task_queued("a").unwrap();
+        assert_eq!(manager.get_queue_size(), 1);
+    }
+}
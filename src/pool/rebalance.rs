@@ -0,0 +1,177 @@
+//! Rebalance - Выравнивание нагрузки между воркерами пула
+//!
+//! Задачи переносятся с самых загруженных воркеров на наименее загруженные
+//! с учётом требуемых возможностей. Количество переносов ограничено,
+//! чтобы не прерывать слишком много выполняющихся задач за один проход.
+
+use serde::{Deserialize, Serialize};
+
+/// Лимит переносов за один проход по умолчанию
+pub const DEFAULT_REBALANCE_CHURN_LIMIT: usize = 10;
+
+/// Задача, назначенная воркеру пула
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssignedTask {
+    pub task_id: String,
+    pub load: f64,
+    pub capabilities: Vec<String>,
+}
+
+/// Воркер пула и его задачи
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolWorker {
+    pub worker_id: String,
+    pub capabilities: Vec<String>,
+    pub tasks: Vec<AssignedTask>,
+    /// Учитывается в `active_workers` пула
+    #[serde(default = "default_active")]
+    pub active: bool,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+impl PoolWorker {
+    pub fn load(&self) -> f64 {
+        self.tasks.iter().map(|task| task.load).sum()
+    }
+
+    fn can_run(&self, task: &AssignedTask) -> bool {
+        task.capabilities.iter().all(|cap| self.capabilities.contains(cap))
+    }
+}
+
+/// Параметры ребалансировки
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RebalanceRequest {
+    /// Максимум переносов задач за проход
+    pub max_moves: Option<usize>,
+}
+
+/// Перенос задачи
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskMove {
+    pub task_id: String,
+    pub from_worker: String,
+    pub to_worker: String,
+}
+
+/// Итог ребалансировки
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebalanceSummary {
+    pub pool: String,
+    pub moves: Vec<TaskMove>,
+    pub load_variance_before: f64,
+    pub load_variance_after: f64,
+}
+
+/// Дисперсия нагрузки воркеров
+pub fn load_variance(workers: &[PoolWorker]) -> f64 {
+    if workers.is_empty() {
+        return 0.0;
+    }
+    let loads: Vec<f64> = workers.iter().map(PoolWorker::load).collect();
+    let mean = loads.iter().sum::<f64>() / loads.len() as f64;
+    loads.iter().map(|load| (load - mean).powi(2)).sum::<f64>() / loads.len() as f64
+}
+
+/// Переносит задачи, пока это уменьшает разброс нагрузки и не исчерпан лимит
+pub fn rebalance(workers: &mut [PoolWorker], max_moves: usize) -> Vec<TaskMove> {
+    let mut moves = Vec::new();
+
+    while moves.len() < max_moves {
+        // Ищем перенос с наибольшим уменьшением разницы нагрузок
+        let mut best: Option<(usize, usize, usize, f64)> = None;
+
+        for (from, source) in workers.iter().enumerate() {
+            let source_load = source.load();
+            for (task_index, task) in source.tasks.iter().enumerate() {
+                for (to, target) in workers.iter().enumerate() {
+                    if from == to || !target.can_run(task) {
+                        continue;
+                    }
+                    let target_load = target.load();
+                    // Перенос полезен, только если после него цель легче источника
+                    if target_load + task.load >= source_load {
+                        continue;
+                    }
+                    let gain = task.load * (source_load - target_load - task.load);
+                    if best.map_or(true, |(_, _, _, best_gain)| gain > best_gain) {
+                        best = Some((from, task_index, to, gain));
+                    }
+                }
+            }
+        }
+
+        let (from, task_index, to, _) = match best {
+            Some(best) => best,
+            None => break,
+        };
+
+        let task = workers[from].tasks.remove(task_index);
+        moves.push(TaskMove {
+            task_id: task.task_id.clone(),
+            from_worker: workers[from].worker_id.clone(),
+            to_worker: workers[to].worker_id.clone(),
+        });
+        workers[to].tasks.push(task);
+    }
+
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, capabilities: &[&str]) -> AssignedTask {
+        AssignedTask {
+            task_id: id.to_string(),
+            load: 1.0,
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    fn unbalanced_pool() -> Vec<PoolWorker> {
+        vec![
+            PoolWorker {
+                worker_id: "busy".to_string(),
+                capabilities: vec!["gpu".to_string()],
+                tasks: vec![
+                    task("t1", &["gpu"]),
+                    task("t2", &[]),
+                    task("t3", &[]),
+                    task("t4", &[]),
+                    task("t5", &[]),
+                    task("t6", &["gpu"]),
+                ],
+                active: true,
+            },
+            PoolWorker { worker_id: "idle1".to_string(), capabilities: vec![], tasks: vec![], active: true },
+            PoolWorker { worker_id: "idle2".to_string(), capabilities: vec![], tasks: vec![], active: true },
+        ]
+    }
+
+    #[test]
+    fn test_rebalance_reduces_variance_and_respects_capabilities() {
+        let mut workers = unbalanced_pool();
+        let before = load_variance(&workers);
+
+        let moves = rebalance(&mut workers, DEFAULT_REBALANCE_CHURN_LIMIT);
+
+        assert_eq!(moves.len(), 4);
+        assert!(load_variance(&workers) < before);
+        // GPU-задачи остаются на единственном воркере с GPU
+        assert!(moves.iter().all(|m| m.task_id != "t1" && m.task_id != "t6"));
+        assert!(workers.iter().all(|w| w.load() == 2.0));
+    }
+
+    #[test]
+    fn test_rebalance_respects_churn_limit() {
+        let mut workers = unbalanced_pool();
+        let moves = rebalance(&mut workers, 1);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(workers[0].load(), 5.0);
+    }
+}
@@ -1,12 +1,15 @@
 use std::sync::Arc;
 use parking_lot::RwLock;
 use serde::{Serialize, Deserialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::Path;
 use log::{info, warn, error};
 use chrono::{DateTime, Utc};
 use thiserror::Error;
 use tokio::sync::Mutex;
 use uuid;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
 use crate::core::error::CursorError;
 use crate::monitoring::logger::LoggerSystem;
 use crate::monitoring::alert::AlertSystem;
@@ -20,10 +23,37 @@ pub enum RewardError {
     WorkerNotFound(String),
     #[error("Invalid activity type")]
     InvalidActivityType,
+    #[error("Invalid payout address '{address}': {reason}")]
+    InvalidPayoutAddress { address: String, reason: String },
+    #[error("Payout of {amount} exceeds balance {balance} of worker {worker_id}")]
+    InsufficientBalance { worker_id: String, balance: f64, amount: f64 },
+    #[error("Invalid payout amount: {0}")]
+    InvalidAmount(f64),
+    #[error("Balance storage error: {0}")]
+    Storage(String),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Правила выплат: воркеры с балансом не ниже порога получают выплату в токене `token_label`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayoutPolicy {
+    pub threshold: f64,
+    pub token_label: String,
+}
+
+impl Default for PayoutPolicy {
+    fn default() -> Self {
+        Self {
+            threshold: 0.1,
+            token_label: "SOL".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ActivityType {
+    Mining,
+    ModelInference,
+    Uptime,
     TextGeneration,
     ImageGeneration,
     CodeGeneration,
@@ -32,6 +62,23 @@ pub enum ActivityType {
     SystemMaintenance,
 }
 
+impl ActivityType {
+    /// Множитель награды по умолчанию
+    pub fn default_multiplier(&self) -> f64 {
+        match self {
+            ActivityType::Mining => 1.0,
+            ActivityType::ModelInference => 1.5,
+            ActivityType::Uptime => 0.2,
+            ActivityType::TextGeneration => 1.2,
+            ActivityType::ImageGeneration => 1.8,
+            ActivityType::CodeGeneration => 1.4,
+            ActivityType::ModelTraining => 2.0,
+            ActivityType::DataProcessing => 0.8,
+            ActivityType::SystemMaintenance => 0.5,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GenerationMetrics {
     pub tokens_per_second: f64,
@@ -86,21 +133,272 @@ pub struct Contribution {
     pub amount: u64,
     pub timestamp: DateTime<Utc>,
     pub status: String,
+    #[serde(default)]
+    pub event_id: Option<String>,
+}
+
+/// Максимальное количество запоминаемых идентификаторов событий
+const PROCESSED_EVENTS_CAPACITY: usize = 10_000;
+/// Время жизни идентификатора события (в секундах)
+const PROCESSED_EVENTS_TTL_SECS: i64 = 3600;
+
+/// Ограниченное по размеру и времени множество обработанных событий
+struct ProcessedEvents {
+    order: VecDeque<(String, DateTime<Utc>)>,
+    ids: HashSet<String>,
+    capacity: usize,
+    ttl: chrono::Duration,
+}
+
+impl ProcessedEvents {
+    fn new(capacity: usize, ttl: chrono::Duration) -> Self {
+        Self {
+            order: VecDeque::new(),
+            ids: HashSet::new(),
+            capacity,
+            ttl,
+        }
+    }
+
+    /// Проверяет, было ли событие уже обработано
+    fn contains(&mut self, event_id: &str, now: DateTime<Utc>) -> bool {
+        self.evict(now);
+        self.ids.contains(event_id)
+    }
+
+    /// Возвращает `false`, если событие уже было обработано
+    fn insert(&mut self, event_id: &str, now: DateTime<Utc>) -> bool {
+        if self.contains(event_id, now) {
+            return false;
+        }
+
+        if self.order.len() >= self.capacity {
+            if let Some((oldest, _)) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+
+        self.ids.insert(event_id.to_string());
+        self.order.push_back((event_id.to_string(), now));
+        true
+    }
+
+    fn evict(&mut self, now: DateTime<Utc>) {
+        while let Some((id, seen_at)) = self.order.front() {
+            if now - *seen_at < self.ttl {
+                break;
+            }
+            self.ids.remove(id);
+            self.order.pop_front();
+        }
+    }
 }
 
 pub struct RewardSystem {
     rewards: Arc<Mutex<HashMap<String, RewardMetrics>>>,
     contributions: Arc<Mutex<HashMap<String, Contribution>>>,
+    processed_events: Arc<Mutex<ProcessedEvents>>,
+    payout_addresses: Arc<Mutex<HashMap<String, Pubkey>>>,
+    /// Базовая ставка награды за час активности
+    base_rate: f64,
+    activity_multipliers: Arc<RwLock<HashMap<ActivityType, f64>>>,
+    worker_totals: Arc<RwLock<HashMap<String, f64>>>,
+    payout_policy: Arc<RwLock<PayoutPolicy>>,
+    /// Пулы, удерживающие комиссию с наград своих воркеров
+    pools: Option<Arc<super::PoolManager>>,
 }
 
 impl RewardSystem {
     pub fn new() -> Self {
+        Self::with_base_rate(1.0)
+    }
+
+    pub fn with_base_rate(base_rate: f64) -> Self {
         Self {
+            base_rate: base_rate.max(0.0),
+            activity_multipliers: Arc::new(RwLock::new(HashMap::new())),
+            worker_totals: Arc::new(RwLock::new(HashMap::new())),
             rewards: Arc::new(Mutex::new(HashMap::new())),
             contributions: Arc::new(Mutex::new(HashMap::new())),
+            processed_events: Arc::new(Mutex::new(ProcessedEvents::new(
+                PROCESSED_EVENTS_CAPACITY,
+                chrono::Duration::seconds(PROCESSED_EVENTS_TTL_SECS),
+            ))),
+            payout_addresses: Arc::new(Mutex::new(HashMap::new())),
+            payout_policy: Arc::new(RwLock::new(PayoutPolicy::default())),
+            pools: None,
         }
     }
 
+    /// С награды воркера, состоящего в пуле, удерживается комиссия пула
+    pub fn with_pool_manager(mut self, pools: Arc<super::PoolManager>) -> Self {
+        self.pools = Some(pools);
+        self
+    }
+
+    /// Регистрирует адрес выплат воркера. Адрес проверяется и хранится
+    /// в разобранном виде, поэтому при выплате строки не разбираются.
+    pub async fn register_payout_address(
+        &self,
+        user_id: &str,
+        address: &str,
+    ) -> Result<Pubkey, RewardError> {
+        let trimmed = address.trim();
+        let pubkey = Pubkey::from_str(trimmed).map_err(|e| RewardError::InvalidPayoutAddress {
+            address: trimmed.to_string(),
+            reason: e.to_string(),
+        })?;
+
+        self.payout_addresses.lock().await.insert(user_id.to_string(), pubkey);
+        info!("Registered payout address {} for user {}", pubkey, user_id);
+        Ok(pubkey)
+    }
+
+    pub async fn get_payout_address(&self, user_id: &str) -> Option<Pubkey> {
+        self.payout_addresses.lock().await.get(user_id).copied()
+    }
+
+    /// Переопределяет множитель награды для типа активности
+    pub fn set_activity_multiplier(&self, activity: ActivityType, multiplier: f64) {
+        self.activity_multipliers.write().insert(activity, multiplier.max(0.0));
+    }
+
+    pub fn activity_multiplier(&self, activity: &ActivityType) -> f64 {
+        self.activity_multipliers
+            .read()
+            .get(activity)
+            .copied()
+            .unwrap_or_else(|| activity.default_multiplier())
+    }
+
+    /// Награда = базовая ставка × множитель активности × производительность × часы.
+    /// Отрицательная (или нечисловая) производительность даёт нулевую награду.
+    pub fn calculate_reward(
+        &self,
+        activity: ActivityType,
+        performance: f64,
+        duration: std::time::Duration,
+    ) -> f64 {
+        let performance = if performance.is_finite() { performance.max(0.0) } else { 0.0 };
+        let hours = duration.as_secs_f64() / 3600.0;
+        self.base_rate * self.activity_multiplier(&activity) * performance * hours
+    }
+
+    /// Начисляет воркеру награду за активность и возвращает зачисленную сумму.
+    /// Если воркер состоит в пуле, пул удерживает свою комиссию, а воркеру
+    /// зачисляется остаток
+    pub async fn record_reward(
+        &self,
+        worker_id: &str,
+        activity: ActivityType,
+        performance: f64,
+        duration: std::time::Duration,
+    ) -> Result<f64, super::PoolError> {
+        let gross = self.calculate_reward(activity, performance, duration);
+        let pool = match &self.pools {
+            Some(pools) => pools.pool_of_worker(worker_id).await.map(|pool| (pools, pool)),
+            None => None,
+        };
+        let net = match pool {
+            Some((pools, pool)) => pools.credit_reward(&pool, gross)?.net,
+            None => gross,
+        };
+        *self.worker_totals.write().entry(worker_id.to_string()).or_insert(0.0) += net;
+        Ok(net)
+    }
+
+    /// Сумма начисленных воркеру наград
+    pub fn get_worker_total(&self, worker_id: &str) -> f64 {
+        self.worker_totals.read().get(worker_id).copied().unwrap_or(0.0)
+    }
+
+    /// Сумма начисленных, но ещё не выплаченных наград всех воркеров
+    pub fn total_accrued(&self) -> f64 {
+        self.worker_totals.read().values().sum()
+    }
+
+    pub fn set_payout_policy(&self, policy: PayoutPolicy) {
+        *self.payout_policy.write() = policy;
+    }
+
+    pub fn payout_policy(&self) -> PayoutPolicy {
+        self.payout_policy.read().clone()
+    }
+
+    /// Воркеры, чей накопленный баланс достиг порога выплаты, по возрастанию ID
+    pub fn eligible_for_payout(&self) -> Vec<(String, f64)> {
+        let threshold = self.payout_policy.read().threshold;
+        let mut eligible: Vec<_> = self
+            .worker_totals
+            .read()
+            .iter()
+            .filter(|(_, balance)| **balance > 0.0 && **balance >= threshold)
+            .map(|(worker_id, balance)| (worker_id.clone(), *balance))
+            .collect();
+        eligible.sort_by(|a, b| a.0.cmp(&b.0));
+        eligible
+    }
+
+    /// Уменьшает баланс воркера на выплаченную сумму и возвращает остаток
+    pub fn mark_paid(&self, worker_id: &str, amount: f64) -> Result<f64, RewardError> {
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(RewardError::InvalidAmount(amount));
+        }
+
+        let mut totals = self.worker_totals.write();
+        let balance = totals
+            .get_mut(worker_id)
+            .ok_or_else(|| RewardError::WorkerNotFound(worker_id.to_string()))?;
+
+        // Допуск на погрешность сложения f64
+        if amount > *balance + 1e-9 {
+            return Err(RewardError::InsufficientBalance {
+                worker_id: worker_id.to_string(),
+                balance: *balance,
+                amount,
+            });
+        }
+
+        *balance = (*balance - amount).max(0.0);
+        info!("Marked {} paid to worker {}, remaining balance {}", amount, worker_id, balance);
+        Ok(*balance)
+    }
+
+    /// Сохраняет балансы воркеров в JSON (через временный файл)
+    pub fn save_balances(&self, path: &Path) -> Result<(), RewardError> {
+        let balances: BTreeMap<String, f64> = self
+            .worker_totals
+            .read()
+            .iter()
+            .map(|(worker_id, balance)| (worker_id.clone(), *balance))
+            .collect();
+        let json = serde_json::to_string_pretty(&balances)
+            .map_err(|e| RewardError::Storage(e.to_string()))?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| RewardError::Storage(e.to_string()))?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json).map_err(|e| RewardError::Storage(e.to_string()))?;
+        std::fs::rename(&tmp, path).map_err(|e| RewardError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Загружает балансы, сохранённые `save_balances`, заменяя текущие.
+    /// Отсутствующий файл означает пустые балансы. Возвращает число воркеров.
+    pub fn load_balances(&self, path: &Path) -> Result<usize, RewardError> {
+        let balances: HashMap<String, f64> = match std::fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| RewardError::Storage(e.to_string()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(RewardError::Storage(e.to_string())),
+        };
+
+        let count = balances.len();
+        *self.worker_totals.write() = balances;
+        info!("Loaded reward balances for {} workers from {}", count, path.display());
+        Ok(count)
+    }
+
     pub async fn add_reward(&self, config: RewardConfig) -> Result<(), String> {
         let mut rewards = self.rewards.lock().await;
         
@@ -170,6 +468,7 @@ impl RewardSystem {
             amount,
             timestamp: Utc::now(),
             status: "pending".to_string(),
+            event_id: None,
         };
 
         contributions.insert(contribution.id.clone(), contribution);
@@ -183,6 +482,75 @@ impl RewardSystem {
         Ok(())
     }
 
+    /// Начисление вклада по идентификатору события (share/event id).
+    /// Повторное событие игнорируется и записывается в журнал как no-op.
+    /// Возвращает `true`, если вклад был начислен.
+    pub async fn add_contribution_for_event(
+        &self,
+        event_id: &str,
+        user_id: &str,
+        reward_id: &str,
+        amount: u64,
+    ) -> Result<bool, String> {
+        let mut rewards = self.rewards.lock().await;
+        let mut contributions = self.contributions.lock().await;
+
+        let reward = rewards
+            .get_mut(reward_id)
+            .ok_or_else(|| format!("Reward '{}' not found", reward_id))?;
+
+        if !reward.config.active {
+            return Err("Reward is not active".to_string());
+        }
+
+        let now = Utc::now();
+        let mut processed_events = self.processed_events.lock().await;
+
+        if processed_events.contains(event_id, now) {
+            let contribution = Contribution {
+                id: uuid::Uuid::new_v4().to_string(),
+                user_id: user_id.to_string(),
+                reward_id: reward_id.to_string(),
+                amount: 0,
+                timestamp: now,
+                status: "duplicate".to_string(),
+                event_id: Some(event_id.to_string()),
+            };
+            contributions.insert(contribution.id.clone(), contribution);
+
+            warn!("Duplicate reward event ignored: {} (reward: {})", event_id, reward_id);
+            return Ok(false);
+        }
+
+        if reward.stats.current_contributions >= reward.config.max_contributions {
+            return Err("Maximum contributions reached".to_string());
+        }
+
+        // Событие считается обработанным только после всех проверок,
+        // иначе отклонённое событие нельзя было бы повторить
+        processed_events.insert(event_id, now);
+
+        let contribution = Contribution {
+            id: uuid::Uuid::new_v4().to_string(),
+            user_id: user_id.to_string(),
+            reward_id: reward_id.to_string(),
+            amount,
+            timestamp: now,
+            status: "pending".to_string(),
+            event_id: Some(event_id.to_string()),
+        };
+
+        info!(
+            "Added contribution: {} for reward: {} (event: {}, amount: {})",
+            contribution.id, reward_id, event_id, amount
+        );
+        contributions.insert(contribution.id.clone(), contribution);
+        reward.stats.current_contributions += 1;
+        reward.stats.total_contributions += 1;
+
+        Ok(true)
+    }
+
     pub async fn process_reward(&self, reward_id: &str) -> Result<(), String> {
         let mut rewards = self.rewards.lock().await;
         let mut contributions = self.contributions.lock().await;
@@ -239,6 +607,11 @@ impl RewardSystem {
         contribution: &Contribution,
         config: &RewardConfig,
     ) -> Result<(), String> {
+        let payout_address = self
+            .get_payout_address(&contribution.user_id)
+            .await
+            .ok_or_else(|| format!("No payout address registered for user '{}'", contribution.user_id))?;
+
         // Simulate reward distribution
         let reward_amount = (contribution.amount as f64 * config.reward_amount as f64 / 100.0) as u64;
         
@@ -246,8 +619,8 @@ impl RewardSystem {
         tokio::time::sleep(std::time::Duration::from_millis(100)).await;
         
         info!(
-            "Distributed reward: {} to user: {} (amount: {})",
-            config.id, contribution.user_id, reward_amount
+            "Distributed reward: {} to user: {} at {} (amount: {})",
+            config.id, contribution.user_id, payout_address, reward_amount
         );
         Ok(())
     }
@@ -320,10 +693,92 @@ mod tests {
     #[test]
     fn test_reward_calculation() {
         let system = RewardSystem::new();
-        let reward = system.calculate_reward(ActivityType::Mining, 0.8);
+        let reward = system.calculate_reward(ActivityType::Mining, 0.8, std::time::Duration::from_secs(3600));
         assert!(reward > 0.0);
     }
 
+    #[test]
+    fn test_activity_types_weighted_differently() {
+        let system = RewardSystem::with_base_rate(2.0);
+        let hour = std::time::Duration::from_secs(3600);
+
+        let mining = system.calculate_reward(ActivityType::Mining, 0.8, hour);
+        let inference = system.calculate_reward(ActivityType::ModelInference, 0.8, hour);
+        let uptime = system.calculate_reward(ActivityType::Uptime, 0.8, hour);
+
+        assert!((mining - 1.6).abs() < 1e-9);
+        assert_ne!(mining, inference);
+        assert!(inference > mining && mining > uptime);
+        assert_eq!(system.calculate_reward(ActivityType::Mining, -3.0, hour), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_worker_totals_accumulate() {
+        let system = RewardSystem::new();
+        let half_hour = std::time::Duration::from_secs(1800);
+
+        let first = system.record_reward("w1", ActivityType::Mining, 1.0, half_hour).await.unwrap();
+        let second = system.record_reward("w1", ActivityType::ModelInference, 1.0, half_hour).await.unwrap();
+        system.record_reward("w2", ActivityType::Uptime, 1.0, half_hour).await.unwrap();
+
+        assert!((system.get_worker_total("w1") - (first + second)).abs() < 1e-9);
+        assert!(system.get_worker_total("w2") > 0.0);
+        assert_eq!(system.get_worker_total("unknown"), 0.0);
+
+        system.set_activity_multiplier(ActivityType::Mining, 3.0);
+        assert!((system.calculate_reward(ActivityType::Mining, 1.0, half_hour) - 1.5).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_payout_eligibility_and_mark_paid() {
+        let system = RewardSystem::with_base_rate(1.0);
+        let hour = std::time::Duration::from_secs(3600);
+        system.set_payout_policy(PayoutPolicy { threshold: 1.0, token_label: "POOL".to_string() });
+
+        system.record_reward("w1", ActivityType::Mining, 1.5, hour).await.unwrap();
+        system.record_reward("w2", ActivityType::Mining, 0.5, hour).await.unwrap();
+        system.record_reward("w3", ActivityType::Mining, 1.0, hour).await.unwrap();
+        system.record_reward("w2", ActivityType::Mining, 0.25, hour).await.unwrap();
+
+        let eligible = system.eligible_for_payout();
+        let ids: Vec<_> = eligible.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["w1", "w3"]);
+        assert!((eligible[0].1 - 1.5).abs() < 1e-9);
+
+        let remaining = system.mark_paid("w1", 1.0).unwrap();
+        assert!((remaining - 0.5).abs() < 1e-9);
+        assert!((system.get_worker_total("w1") - 0.5).abs() < 1e-9);
+        assert_eq!(system.eligible_for_payout().len(), 1);
+
+        assert!(matches!(
+            system.mark_paid("w3", 2.0),
+            Err(RewardError::InsufficientBalance { .. })
+        ));
+        assert!(matches!(system.mark_paid("w3", -1.0), Err(RewardError::InvalidAmount(_))));
+        assert!(matches!(system.mark_paid("nobody", 1.0), Err(RewardError::WorkerNotFound(_))));
+        assert_eq!(system.mark_paid("w3", 1.0).unwrap(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_balances_survive_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rewards").join("balances.json");
+        let hour = std::time::Duration::from_secs(3600);
+
+        let system = RewardSystem::new();
+        system.record_reward("w1", ActivityType::ModelInference, 1.0, hour).await.unwrap();
+        system.record_reward("w2", ActivityType::Uptime, 1.0, hour).await.unwrap();
+        system.save_balances(&path).unwrap();
+
+        let reloaded = RewardSystem::new();
+        assert_eq!(reloaded.load_balances(&path).unwrap(), 2);
+        assert_eq!(reloaded.get_worker_total("w1"), system.get_worker_total("w1"));
+        assert_eq!(reloaded.get_worker_total("w2"), system.get_worker_total("w2"));
+
+        let empty = RewardSystem::new();
+        assert_eq!(empty.load_balances(&dir.path().join("missing.json")).unwrap(), 0);
+    }
+
     #[test]
     fn test_reward_distribution() {
         let system = RewardSystem::new();
@@ -338,4 +793,103 @@ mod tests {
         let metrics = system.get_user_metrics("test_user");
         assert!(metrics.is_ok());
     }
+
+    fn test_reward_config() -> RewardConfig {
+        RewardConfig {
+            id: "mining".to_string(),
+            name: "Mining".to_string(),
+            description: "Mining reward".to_string(),
+            reward_amount: 10,
+            min_contributions: 1,
+            max_contributions: 100,
+            cooldown_period: 0,
+            active: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_event_counted_once() {
+        let system = RewardSystem::new();
+        system.add_reward(test_reward_config()).await.unwrap();
+
+        let first = system
+            .add_contribution_for_event("share-1", "worker-1", "mining", 50)
+            .await
+            .unwrap();
+        let second = system
+            .add_contribution_for_event("share-1", "worker-1", "mining", 50)
+            .await
+            .unwrap();
+
+        assert!(first);
+        assert!(!second);
+
+        let reward = system.get_reward("mining").await.unwrap();
+        assert_eq!(reward.stats.total_contributions, 1);
+
+        let contributions = system.get_contributions("mining").await;
+        let credited: u64 = contributions
+            .iter()
+            .filter(|c| c.status != "duplicate")
+            .map(|c| c.amount)
+            .sum();
+        assert_eq!(credited, 50);
+        assert_eq!(contributions.iter().filter(|c| c.status == "duplicate").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_event_can_be_retried() {
+        let system = RewardSystem::new();
+        let config = RewardConfig { max_contributions: 1, ..test_reward_config() };
+        system.add_reward(config.clone()).await.unwrap();
+
+        assert!(system.add_contribution_for_event("share-1", "worker-1", "mining", 50).await.unwrap());
+        assert!(system.add_contribution_for_event("share-2", "worker-1", "mining", 50).await.is_err());
+
+        // Отклонённое по лимиту событие не помечено обработанным
+        system
+            .update_reward_config("mining", RewardConfig { max_contributions: 2, ..config })
+            .await
+            .unwrap();
+        assert!(system.add_contribution_for_event("share-2", "worker-1", "mining", 50).await.unwrap());
+        assert_eq!(system.get_reward("mining").await.unwrap().stats.total_contributions, 2);
+    }
+
+    #[tokio::test]
+    async fn test_register_valid_payout_address() {
+        let system = RewardSystem::new();
+        let address = Pubkey::new_unique().to_string();
+
+        let pubkey = system.register_payout_address("worker-1", &address).await.unwrap();
+
+        assert_eq!(pubkey.to_string(), address);
+        assert_eq!(system.get_payout_address("worker-1").await, Some(pubkey));
+    }
+
+    #[tokio::test]
+    async fn test_register_invalid_payout_address_rejected() {
+        let system = RewardSystem::new();
+
+        for address in ["", "not-a-pubkey", "0OIl0OIl0OIl0OIl0OIl0OIl0OIl0OIl0OIl0OIl0OI"] {
+            let err = system.register_payout_address("worker-1", address).await.unwrap_err();
+            assert!(matches!(err, RewardError::InvalidPayoutAddress { .. }));
+            assert!(err.to_string().contains("Invalid payout address"));
+        }
+        assert!(system.get_payout_address("worker-1").await.is_none());
+    }
+
+    #[test]
+    fn test_processed_events_bounded_and_expiring() {
+        let mut events = ProcessedEvents::new(2, chrono::Duration::seconds(60));
+        let now = Utc::now();
+
+        assert!(events.insert("a", now));
+        assert!(events.insert("b", now));
+        assert!(events.insert("c", now));
+        // "a" вытеснено по ёмкости
+        assert!(events.insert("a", now));
+
+        let later = now + chrono::Duration::seconds(61);
+        assert!(events.insert("c", later));
+    }
 } 
\ No newline at end of file
@@ -11,12 +11,15 @@ use std::io;
 use thiserror::Error;
 use tokio::sync::mpsc;
 use tokio::fs as tokio_fs;
+use tokio::io::AsyncReadExt;
 use std::io::Write;
 use sha2::{Sha256, Digest};
 use serde::{Serialize, Deserialize};
 use tokio::sync::Mutex;
 use chrono::{DateTime, Utc};
 use reqwest;
+use crate::monitoring::events::{EventBus, EventKind};
+use super::capacity::{DiskCapacity, StatvfsDiskCapacity};
 use cursor_codes::core::error::CursorError;
 use cursor_codes::monitoring::logger::LoggerSystem;
 use cursor_codes::monitoring::alert::AlertSystem;
@@ -29,6 +32,10 @@ use cursor_codes::network::network::NetworkSystem;
 
 const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
 const NODE_TIMEOUT: Duration = Duration::from_secs(30);
+/// Диск или seed, не обновлявшийся дольше этого времени, считается устаревшим
+const STALE_THRESHOLD: Duration = Duration::from_secs(300);
+/// Запас свободного места сверх части модели на диске (доля от неё)
+pub const FREE_SPACE_MARGIN: f64 = 0.1;
 
 #[derive(Error, Debug)]
 pub enum BurstRaidError {
@@ -40,10 +47,46 @@ pub enum BurstRaidError {
     WorkerError(String),
     #[error("Seed error: {0}")]
     SeedError(String),
+    #[error("Disk I/O error at {path}: {source}")]
+    DiskIo {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Download error from {url}: {source}")]
+    Download {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("RAID array {0} already exists")]
+    ArrayExists(String),
+    #[error("RAID array {0} not found")]
+    ArrayNotFound(String),
+    #[error("Not enough free space on disk {disk}: required {required} bytes, available {available}")]
+    InsufficientSpace {
+        disk: String,
+        required: u64,
+        available: u64,
+    },
     #[error("IO error: {0}")]
     IoError(#[from] io::Error),
 }
 
+impl BurstRaidError {
+    /// Оборачивает ошибку ввода-вывода с указанием пути
+    pub fn disk_io(path: impl Into<String>) -> impl FnOnce(io::Error) -> Self {
+        let path = path.into();
+        move |source| BurstRaidError::DiskIo { path, source }
+    }
+
+    /// Оборачивает ошибку загрузки с указанием URL
+    pub fn download(url: impl Into<String>) -> impl FnOnce(reqwest::Error) -> Self {
+        let url = url.into();
+        move |source| BurstRaidError::Download { url, source }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RaidConfig {
     pub raid_level: u8,
@@ -52,6 +95,62 @@ pub struct RaidConfig {
     pub redundancy: usize,
 }
 
+/// Раскладка модели в RAID 5: страйпы данных по N-1 дискам, чётность на N-м
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParityLayout {
+    /// Каталоги модели на дисках данных, в порядке столбцов
+    pub data_dirs: Vec<String>,
+    /// Каталог модели на диске чётности
+    pub parity_dir: String,
+    pub stripe_size: usize,
+    pub model_size: u64,
+    pub source_checksum: String,
+}
+
+impl ParityLayout {
+    const FILE_NAME: &'static str = "layout.json";
+
+    fn stripe_count(&self) -> usize {
+        (self.model_size as f64 / self.stripe_size as f64).ceil() as usize
+    }
+
+    fn row_count(&self) -> usize {
+        (self.stripe_count() as f64 / self.data_dirs.len() as f64).ceil() as usize
+    }
+
+    /// Длина страйпа с номером `index` (последний может быть короче)
+    fn stripe_len(&self, index: usize) -> usize {
+        let offset = (index * self.stripe_size) as u64;
+        std::cmp::min(self.stripe_size as u64, self.model_size - offset) as usize
+    }
+
+    fn stripe_path(&self, index: usize) -> String {
+        let row = index / self.data_dirs.len();
+        let column = index % self.data_dirs.len();
+        format!("{}/stripe_{}", self.data_dirs[column], row)
+    }
+
+    fn parity_path(&self, row: usize) -> String {
+        format!("{}/parity_{}", self.parity_dir, row)
+    }
+
+    /// Номера страйпов, входящих в строку `row`
+    fn row_stripes(&self, row: usize) -> std::ops::Range<usize> {
+        let start = row * self.data_dirs.len();
+        start..std::cmp::min(start + self.data_dirs.len(), self.stripe_count())
+    }
+}
+
+/// XOR `data` в `parity`; более короткий буфер считается дополненным нулями
+fn xor_into(parity: &mut Vec<u8>, data: &[u8]) {
+    if parity.len() < data.len() {
+        parity.resize(data.len(), 0);
+    }
+    for (p, d) in parity.iter_mut().zip(data) {
+        *p ^= d;
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DiskInfo {
     pub path: String,
@@ -60,7 +159,8 @@ pub struct DiskInfo {
     pub last_seen: Instant,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DiskStatus {
     Active,
     Degraded,
@@ -77,19 +177,83 @@ pub struct SeedInfo {
     pub status: SeedStatus,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SeedStatus {
     Available,
     Unavailable,
     Migrating,
 }
 
+/// Снимок состояния массива
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RaidHealth {
+    pub raid_level: u8,
+    pub total_disks: usize,
+    pub active_disks: usize,
+    pub failed_disks: Vec<String>,
+    pub stale_disks: Vec<String>,
+    pub stale_seeds: Vec<String>,
+    /// Достаточно рабочих дисков и отказов не больше, чем позволяет избыточность
+    pub healthy: bool,
+}
+
+/// Общее состояние массива в отчёте
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RaidArrayStatus {
+    Healthy,
+    /// Есть диск в состоянии `Degraded` или `Failed`
+    Degraded,
+}
+
+/// Диск в отчёте о состоянии
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskStatusEntry {
+    pub disk_id: String,
+    pub path: String,
+    pub size: u64,
+    pub status: DiskStatus,
+    /// Секунд с последнего отклика диска
+    pub last_seen_secs: u64,
+}
+
+/// Seed в отчёте о состоянии
+#[derive(Debug, Clone, Serialize)]
+pub struct SeedStatusEntry {
+    pub worker_id: String,
+    pub path: String,
+    pub size: u64,
+    pub status: SeedStatus,
+    /// Секунд с последнего обращения к seed'у
+    pub last_accessed_secs: u64,
+}
+
+/// Сводка по моделям, загруженным в массив
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelPoolSummary {
+    pub model_count: usize,
+    pub models: Vec<String>,
+}
+
+/// Отчёт о состоянии массива для `GET /api/v1/raid/status`
+#[derive(Debug, Clone, Serialize)]
+pub struct RaidStatusReport {
+    pub status: RaidArrayStatus,
+    pub raid_level: u8,
+    pub disks: Vec<DiskStatusEntry>,
+    pub seeds: Vec<SeedStatusEntry>,
+    pub model_pool: ModelPoolSummary,
+}
+
 pub struct BurstRaidManager {
     config: RaidConfig,
     disks: Arc<RwLock<HashMap<String, DiskInfo>>>,
     seeds: Arc<RwLock<HashMap<String, SeedInfo>>>,
     model_pool: Arc<RwLock<HashMap<String, String>>>, // model_id -> raid_path
     health_check_tx: mpsc::Sender<()>,
+    events: Arc<EventBus>,
+    capacity: Arc<dyn DiskCapacity>,
 }
 
 impl BurstRaidManager {
@@ -102,6 +266,8 @@ impl BurstRaidManager {
             seeds: Arc::new(RwLock::new(HashMap::new())),
             model_pool: Arc::new(RwLock::new(HashMap::new())),
             health_check_tx,
+            events: Arc::new(EventBus::default()),
+            capacity: Arc::new(StatvfsDiskCapacity),
         };
 
         // Create data directory if it doesn't exist
@@ -110,6 +276,18 @@ impl BurstRaidManager {
         Ok(manager)
     }
 
+    /// Публикует события RAID в общую шину
+    pub fn with_event_bus(mut self, events: Arc<EventBus>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Источник сведений о свободном месте на дисках
+    pub fn with_disk_capacity(mut self, capacity: Arc<dyn DiskCapacity>) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
     pub async fn initialize_raid(&self) -> Result<(), BurstRaidError> {
         info!("Initializing RAID array with level {}", self.config.raid_level);
         
@@ -125,7 +303,7 @@ impl BurstRaidManager {
         // Create RAID structure
         for (disk_id, disk) in disks.iter() {
             let raid_path = format!("data/raid/{}", disk_id);
-            fs::create_dir_all(&raid_path)?;
+            fs::create_dir_all(&raid_path).map_err(BurstRaidError::disk_io(&raid_path))?;
             
             info!("Initialized disk {} at {}", disk_id, raid_path);
         }
@@ -147,6 +325,24 @@ impl BurstRaidManager {
         Ok(())
     }
 
+    /// Помечает диск как отказавший
+    pub async fn mark_disk_failed(&self, disk_id: &str) -> Result<(), BurstRaidError> {
+        let mut disks = self.disks.write();
+        let disk = disks
+            .get_mut(disk_id)
+            .ok_or_else(|| BurstRaidError::DiskError(format!("Disk {} not found", disk_id)))?;
+
+        if disk.status != DiskStatus::Failed {
+            disk.status = DiskStatus::Failed;
+            error!("Disk {} at {} failed", disk_id, disk.path);
+            self.events.publish(
+                EventKind::RaidDiskFailed,
+                serde_json::json!({ "disk": disk_id, "path": disk.path }),
+            );
+        }
+        Ok(())
+    }
+
     pub async fn register_seed(&self, worker_id: String, seed_path: String, size: u64) -> Result<(), BurstRaidError> {
         let mut seeds = self.seeds.write();
         
@@ -166,8 +362,14 @@ impl BurstRaidManager {
         let mut model_pool = self.model_pool.write();
         
         // Calculate required space based on model size
-        let model_size = fs::metadata(&model_path)?.len();
-        let required_disks = (model_size as f64 / self.config.stripe_size as f64).ceil() as usize;
+        let model_size = fs::metadata(&model_path)
+            .map_err(BurstRaidError::disk_io(&model_path))?
+            .len();
+        let required_disks = match self.config.raid_level {
+            // N-1 дисков данных и один диск чётности
+            5 => 3,
+            _ => (model_size as f64 / self.config.stripe_size as f64).ceil() as usize,
+        };
         
         // Check if we have enough disks
         let disks = self.disks.read();
@@ -177,16 +379,21 @@ impl BurstRaidManager {
                         required_disks, disks.len())
             ));
         }
+        drop(disks);
+
+        // Проверяем место заранее, чтобы не упасть посреди копирования
+        self.check_free_space(model_size)?;
 
         // Distribute model across RAID
         let raid_path = format!("data/raid/models/{}", model_id);
-        fs::create_dir_all(&raid_path)?;
+        fs::create_dir_all(&raid_path).map_err(BurstRaidError::disk_io(&raid_path))?;
         
         // Copy model to RAID with striping
         // Implementation depends on specific RAID level
         match self.config.raid_level {
             0 => self.strip_model(&model_path, &raid_path, model_size).await?,
             1 => self.mirror_model(&model_path, &raid_path, model_size).await?,
+            5 => self.parity_model(&model_id, &model_path, &raid_path, model_size).await?,
             _ => return Err(BurstRaidError::RaidInitError(
                 format!("Unsupported RAID level: {}", self.config.raid_level)
             )),
@@ -197,6 +404,67 @@ impl BurstRaidManager {
         Ok(())
     }
 
+    /// Сколько байт модели размером `model_size` попадёт на каждый диск
+    /// при раскладке текущего уровня RAID: диски, которые использует копирование,
+    /// и размер их части
+    fn placement(&self, model_size: u64) -> Vec<(String, String, u64)> {
+        let disks = self.disks.read();
+        let mut targets: Vec<(String, String)> = disks
+            .iter()
+            // RAID 0 раскладывает страйпы по всем дискам, остальные уровни - по активным
+            .filter(|(_, disk)| self.config.raid_level == 0 || disk.status == DiskStatus::Active)
+            .map(|(disk_id, disk)| (disk_id.clone(), disk.path.clone()))
+            .collect();
+        targets.sort();
+        if targets.is_empty() {
+            return Vec::new();
+        }
+
+        let stripe_size = self.config.stripe_size.max(1) as u64;
+        let stripes = model_size.div_ceil(stripe_size);
+        let per_disk = match self.config.raid_level {
+            1 => model_size,
+            // Строки по N-1 страйпов данных плюс страйп чётности на каждой строке
+            5 if targets.len() > 1 => stripes.div_ceil(targets.len() as u64 - 1) * stripe_size,
+            _ => (stripes.div_ceil(targets.len() as u64) * stripe_size).min(model_size),
+        };
+
+        targets
+            .into_iter()
+            .map(|(disk_id, path)| (disk_id, path, per_disk))
+            .collect()
+    }
+
+    /// Проверяет, что на каждой файловой системе хватит места для частей
+    /// модели на её дисках с запасом `FREE_SPACE_MARGIN`. Диски на одном
+    /// устройстве делят его свободное место, поэтому их части суммируются.
+    fn check_free_space(&self, model_size: u64) -> Result<(), BurstRaidError> {
+        // устройство -> (первый диск, требуется, доступно)
+        let mut devices: Vec<(u64, String, u64, u64)> = Vec::new();
+        for (disk_id, path, share) in self.placement(model_size) {
+            let required = share + (share as f64 * FREE_SPACE_MARGIN).ceil() as u64;
+            let space = self
+                .capacity
+                .disk_space(Path::new(&path))
+                .map_err(BurstRaidError::disk_io(&path))?;
+            match devices.iter_mut().find(|(device, ..)| *device == space.device) {
+                Some((_, _, total_required, _)) => *total_required += required,
+                None => devices.push((space.device, disk_id, required, space.free)),
+            }
+        }
+
+        for (_, disk_id, required, available) in devices {
+            if available < required {
+                warn!(
+                    "Rejecting model of {} bytes: disk {} has {} bytes free, needs {}",
+                    model_size, disk_id, available, required
+                );
+                return Err(BurstRaidError::InsufficientSpace { disk: disk_id, required, available });
+            }
+        }
+        Ok(())
+    }
+
     async fn strip_model(&self, source: &str, target: &str, size: u64) -> Result<(), BurstRaidError> {
         let stripe_size = self.config.stripe_size as u64;
         let mut offset = 0;
@@ -220,15 +488,25 @@ impl BurstRaidManager {
             
             // Create stripe file
             let stripe_path = format!("{}/stripe_{}", disk.path, offset);
-            let mut stripe_file = tokio_fs::File::create(&stripe_path).await?;
+            let mut stripe_file = tokio_fs::File::create(&stripe_path)
+                .await
+                .map_err(BurstRaidError::disk_io(&stripe_path))?;
             
             // Read and write stripe
-            let mut source_file = tokio_fs::File::open(source).await?;
-            source_file.seek(io::SeekFrom::Start(offset)).await?;
+            let mut source_file = tokio_fs::File::open(source)
+                .await
+                .map_err(BurstRaidError::disk_io(source))?;
+            source_file.seek(io::SeekFrom::Start(offset))
+                .await
+                .map_err(BurstRaidError::disk_io(source))?;
             
             let mut buffer = vec![0; current_stripe as usize];
-            source_file.read_exact(&mut buffer).await?;
-            stripe_file.write_all(&buffer).await?;
+            source_file.read_exact(&mut buffer)
+                .await
+                .map_err(BurstRaidError::disk_io(source))?;
+            stripe_file.write_all(&buffer)
+                .await
+                .map_err(BurstRaidError::disk_io(&stripe_path))?;
             
             // Verify stripe checksum
             let stripe_checksum = self.calculate_checksum(&stripe_path).await?;
@@ -262,10 +540,14 @@ impl BurstRaidManager {
         // Copy to each disk
         for (disk_id, disk) in active_disks {
             let mirror_path = format!("{}/{}", target, disk_id);
-            tokio_fs::create_dir_all(&mirror_path).await?;
+            tokio_fs::create_dir_all(&mirror_path)
+                .await
+                .map_err(BurstRaidError::disk_io(&mirror_path))?;
             
             // Copy file
-            tokio_fs::copy(source, &mirror_path).await?;
+            tokio_fs::copy(source, &mirror_path)
+                .await
+                .map_err(BurstRaidError::disk_io(&mirror_path))?;
             
             // Verify checksum
             let mirror_checksum = self.calculate_checksum(&mirror_path).await?;
@@ -279,13 +561,204 @@ impl BurstRaidManager {
         Ok(())
     }
 
+    /// RAID 5: страйпы данных распределяются по N-1 дискам, XOR каждой строки
+    /// страйпов записывается на N-й диск. Раскладка сохраняется в `target`.
+    async fn parity_model(&self, model_id: &str, source: &str, target: &str, size: u64) -> Result<(), BurstRaidError> {
+        let mut active_disks: Vec<(String, String)> = self.disks.read()
+            .iter()
+            .filter(|(_, disk)| disk.status == DiskStatus::Active)
+            .map(|(disk_id, disk)| (disk_id.clone(), disk.path.clone()))
+            .collect();
+        if active_disks.len() < 3 {
+            return Err(BurstRaidError::DiskError(format!(
+                "RAID 5 requires at least 3 active disks, available: {}",
+                active_disks.len()
+            )));
+        }
+        active_disks.sort();
+
+        let (_, parity_disk) = active_disks.pop().unwrap();
+        let mut layout = ParityLayout {
+            data_dirs: active_disks.iter()
+                .map(|(_, path)| format!("{}/{}", path, model_id))
+                .collect(),
+            parity_dir: format!("{}/{}", parity_disk, model_id),
+            stripe_size: self.config.stripe_size,
+            model_size: size,
+            // Заполняется по ходу чтения модели
+            source_checksum: String::new(),
+        };
+
+        for dir in layout.data_dirs.iter().chain(std::iter::once(&layout.parity_dir)) {
+            tokio_fs::create_dir_all(dir)
+                .await
+                .map_err(BurstRaidError::disk_io(dir))?;
+        }
+
+        // Модель читается по страйпам и хешируется на лету, целиком в память не попадает
+        let mut file = tokio_fs::File::open(source)
+            .await
+            .map_err(BurstRaidError::disk_io(source))?;
+        let mut hasher = Sha256::new();
+        let mut stripe = vec![0u8; layout.stripe_size];
+        let size_changed = || BurstRaidError::DiskError(format!(
+            "Model size changed while loading: expected {} bytes",
+            size
+        ));
+
+        for row in 0..layout.row_count() {
+            let mut parity = Vec::with_capacity(layout.stripe_size);
+            for index in layout.row_stripes(row) {
+                let stripe = &mut stripe[..layout.stripe_len(index)];
+                file.read_exact(stripe).await.map_err(|e| match e.kind() {
+                    io::ErrorKind::UnexpectedEof => size_changed(),
+                    _ => BurstRaidError::disk_io(source)(e),
+                })?;
+                hasher.update(&*stripe);
+
+                let stripe_path = layout.stripe_path(index);
+                tokio_fs::write(&stripe_path, &*stripe)
+                    .await
+                    .map_err(BurstRaidError::disk_io(&stripe_path))?;
+                xor_into(&mut parity, stripe);
+            }
+
+            let parity_path = layout.parity_path(row);
+            tokio_fs::write(&parity_path, &parity)
+                .await
+                .map_err(BurstRaidError::disk_io(&parity_path))?;
+        }
+
+        if file.read(&mut [0u8; 1]).await.map_err(BurstRaidError::disk_io(source))? != 0 {
+            return Err(size_changed());
+        }
+        layout.source_checksum = format!("{:x}", hasher.finalize());
+
+        let layout_path = format!("{}/{}", target, ParityLayout::FILE_NAME);
+        let layout_json = serde_json::to_vec_pretty(&layout)
+            .map_err(|e| BurstRaidError::RaidInitError(format!("Failed to serialize RAID 5 layout: {}", e)))?;
+        tokio_fs::write(&layout_path, layout_json)
+            .await
+            .map_err(BurstRaidError::disk_io(&layout_path))?;
+
+        info!(
+            "Wrote model {} as {} stripes across {} data disks with parity",
+            model_id,
+            layout.stripe_count(),
+            layout.data_dirs.len()
+        );
+        Ok(())
+    }
+
+    /// Проверяет страйпы модели RAID 5 и восстанавливает один потерянный
+    /// страйп в строке по чётности. Возвращает число восстановленных страйпов.
+    async fn verify_parity(&self, raid_path: &str) -> Result<usize, BurstRaidError> {
+        let layout_path = format!("{}/{}", raid_path, ParityLayout::FILE_NAME);
+        let layout_json = tokio_fs::read(&layout_path)
+            .await
+            .map_err(BurstRaidError::disk_io(&layout_path))?;
+        let layout: ParityLayout = serde_json::from_slice(&layout_json)
+            .map_err(|e| BurstRaidError::DiskError(format!("Corrupt RAID 5 layout {}: {}", layout_path, e)))?;
+
+        let mut recovered = 0;
+        let mut model_hasher = Sha256::new();
+
+        for row in 0..layout.row_count() {
+            let parity_path = layout.parity_path(row);
+            let mut parity = match tokio_fs::read(&parity_path).await {
+                Ok(parity) => Some(parity),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+                Err(e) => return Err(BurstRaidError::disk_io(&parity_path)(e)),
+            };
+
+            let mut stripes = Vec::new();
+            let mut missing = Vec::new();
+            for index in layout.row_stripes(row) {
+                let stripe_path = layout.stripe_path(index);
+                match tokio_fs::read(&stripe_path).await {
+                    Ok(stripe) if stripe.len() == layout.stripe_len(index) => stripes.push((index, Some(stripe))),
+                    Ok(_) => {
+                        warn!("Stripe {} has unexpected length, rebuilding", stripe_path);
+                        missing.push(index);
+                        stripes.push((index, None));
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                        missing.push(index);
+                        stripes.push((index, None));
+                    }
+                    Err(e) => return Err(BurstRaidError::disk_io(&stripe_path)(e)),
+                }
+            }
+
+            let parity_present = parity.is_some();
+            match (missing.as_slice(), parity.as_mut()) {
+                ([], _) => {}
+                ([index], Some(parity)) => {
+                    let index = *index;
+                    for (_, stripe) in stripes.iter().filter(|(i, _)| *i != index) {
+                        xor_into(parity, stripe.as_ref().unwrap());
+                    }
+                    let mut rebuilt = parity.clone();
+                    rebuilt.resize(layout.stripe_len(index), 0);
+
+                    let stripe_path = layout.stripe_path(index);
+                    tokio_fs::write(&stripe_path, &rebuilt)
+                        .await
+                        .map_err(BurstRaidError::disk_io(&stripe_path))?;
+                    warn!("Rebuilt stripe {} from parity", stripe_path);
+
+                    if let Some(slot) = stripes.iter_mut().find(|(i, _)| *i == index) {
+                        slot.1 = Some(rebuilt);
+                    }
+                    recovered += 1;
+                }
+                _ => {
+                    return Err(BurstRaidError::DiskError(format!(
+                        "Cannot rebuild row {} of {}: {} stripes missing, parity {}",
+                        row,
+                        raid_path,
+                        missing.len(),
+                        if parity_present { "present" } else { "missing" }
+                    )));
+                }
+            }
+
+            if parity.is_none() {
+                let mut rebuilt = Vec::with_capacity(layout.stripe_size);
+                for (_, stripe) in &stripes {
+                    xor_into(&mut rebuilt, stripe.as_ref().unwrap());
+                }
+                tokio_fs::write(&parity_path, &rebuilt)
+                    .await
+                    .map_err(BurstRaidError::disk_io(&parity_path))?;
+                warn!("Rebuilt parity {}", parity_path);
+            }
+
+            for (_, stripe) in &stripes {
+                model_hasher.update(stripe.as_ref().unwrap());
+            }
+        }
+
+        let checksum = format!("{:x}", model_hasher.finalize());
+        if checksum != layout.source_checksum {
+            return Err(BurstRaidError::DiskError(format!(
+                "Checksum mismatch for RAID 5 model at {}",
+                raid_path
+            )));
+        }
+
+        Ok(recovered)
+    }
+
     async fn calculate_checksum(&self, path: &str) -> Result<String, BurstRaidError> {
-        let mut file = tokio_fs::File::open(path).await?;
+        let mut file = tokio_fs::File::open(path)
+            .await
+            .map_err(BurstRaidError::disk_io(path))?;
         let mut hasher = Sha256::new();
         let mut buffer = vec![0; 1024 * 1024]; // 1MB buffer
         
         loop {
-            let n = file.read(&mut buffer).await?;
+            let n = file.read(&mut buffer).await.map_err(BurstRaidError::disk_io(path))?;
             if n == 0 {
                 break;
             }
@@ -337,7 +810,9 @@ impl BurstRaidManager {
                 
             // Copy seed data
             let target_path = format!("{}/migrated_{}", target_worker.1.path, worker_id);
-            tokio_fs::copy(&seed.path, &target_path).await?;
+            tokio_fs::copy(&seed.path, &target_path)
+                .await
+                .map_err(BurstRaidError::disk_io(&target_path))?;
             
             // Verify checksum
             let source_checksum = self.calculate_checksum(&seed.path).await?;
@@ -358,25 +833,127 @@ impl BurstRaidManager {
         Ok(())
     }
 
+    pub fn config(&self) -> &RaidConfig {
+        &self.config
+    }
+
+    /// Идентификаторы дисков массива в порядке сортировки
+    pub fn disk_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.disks.read().keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Однократная проверка дисков и seed'ов массива
+    pub fn check_health(&self) -> RaidHealth {
+        let disks = self.disks.read();
+        let seeds = self.seeds.read();
+
+        let sorted = |mut ids: Vec<String>| {
+            ids.sort();
+            ids
+        };
+        let failed_disks = sorted(
+            disks
+                .iter()
+                .filter(|(_, disk)| disk.status == DiskStatus::Failed)
+                .map(|(id, _)| id.clone())
+                .collect(),
+        );
+        let stale_disks = sorted(
+            disks
+                .iter()
+                .filter(|(_, disk)| disk.last_seen.elapsed() > STALE_THRESHOLD)
+                .map(|(id, _)| id.clone())
+                .collect(),
+        );
+        let stale_seeds = sorted(
+            seeds
+                .iter()
+                .filter(|(_, seed)| seed.last_accessed.elapsed() > STALE_THRESHOLD)
+                .map(|(id, _)| id.clone())
+                .collect(),
+        );
+        let active_disks = disks
+            .values()
+            .filter(|disk| disk.status == DiskStatus::Active)
+            .count();
+
+        RaidHealth {
+            raid_level: self.config.raid_level,
+            total_disks: disks.len(),
+            active_disks,
+            healthy: active_disks >= self.config.min_disks
+                && failed_disks.len() <= self.config.redundancy,
+            failed_disks,
+            stale_disks,
+            stale_seeds,
+        }
+    }
+
+    /// Диски, seed'ы и модели массива; диски и seed'ы отсортированы по идентификатору
+    pub fn status_report(&self) -> RaidStatusReport {
+        let mut disks: Vec<DiskStatusEntry> = self
+            .disks
+            .read()
+            .iter()
+            .map(|(disk_id, disk)| DiskStatusEntry {
+                disk_id: disk_id.clone(),
+                path: disk.path.clone(),
+                size: disk.size,
+                status: disk.status.clone(),
+                last_seen_secs: disk.last_seen.elapsed().as_secs(),
+            })
+            .collect();
+        disks.sort_by(|a, b| a.disk_id.cmp(&b.disk_id));
+
+        let mut seeds: Vec<SeedStatusEntry> = self
+            .seeds
+            .read()
+            .values()
+            .map(|seed| SeedStatusEntry {
+                worker_id: seed.worker_id.clone(),
+                path: seed.path.clone(),
+                size: seed.size,
+                status: seed.status.clone(),
+                last_accessed_secs: seed.last_accessed.elapsed().as_secs(),
+            })
+            .collect();
+        seeds.sort_by(|a, b| a.worker_id.cmp(&b.worker_id));
+
+        let mut models: Vec<String> = self.model_pool.read().keys().cloned().collect();
+        models.sort();
+
+        let degraded = disks
+            .iter()
+            .any(|disk| matches!(disk.status, DiskStatus::Degraded | DiskStatus::Failed));
+
+        RaidStatusReport {
+            status: if degraded { RaidArrayStatus::Degraded } else { RaidArrayStatus::Healthy },
+            raid_level: self.config.raid_level,
+            disks,
+            seeds,
+            model_pool: ModelPoolSummary {
+                model_count: models.len(),
+                models,
+            },
+        }
+    }
+
     pub async fn monitor_health(&self) {
         loop {
             tokio::time::sleep(Duration::from_secs(60)).await;
             
-            let disks = self.disks.read();
-            let seeds = self.seeds.read();
+            let health = self.check_health();
             
             // Check disk health
-            for (disk_id, disk) in disks.iter() {
-                if disk.last_seen.elapsed() > Duration::from_secs(300) {
-                    warn!("Disk {} has not been seen for 5 minutes", disk_id);
-                }
+            for disk_id in &health.stale_disks {
+                warn!("Disk {} has not been seen for 5 minutes", disk_id);
             }
             
             // Check seed health
-            for (worker_id, seed) in seeds.iter() {
-                if seed.last_accessed.elapsed() > Duration::from_secs(300) {
-                    warn!("Seed from worker {} has not been accessed for 5 minutes", worker_id);
-                }
+            for worker_id in &health.stale_seeds {
+                warn!("Seed from worker {} has not been accessed for 5 minutes", worker_id);
             }
         }
     }
@@ -411,6 +988,13 @@ impl BurstRaidManager {
                         // Implementation depends on how checksums are stored
                     }
                 },
+                5 => {
+                    // Verify stripes and rebuild a single lost stripe per row
+                    let recovered = self.verify_parity(raid_path).await?;
+                    if recovered > 0 {
+                        info!("Recovered {} stripes of model {} from parity", recovered, model_id);
+                    }
+                },
                 _ => return Err(BurstRaidError::RaidInitError(
                     format!("Unsupported RAID level: {}", self.config.raid_level)
                 )),
@@ -526,6 +1110,304 @@ mod tests {
             1024 * 1024
         ).await.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_io_error_exposes_source() {
+        use std::error::Error as _;
+
+        let config = RaidConfig {
+            raid_level: 1,
+            min_disks: 1,
+            stripe_size: 1024 * 1024,
+            redundancy: 1,
+        };
+        
+        let manager = BurstRaidManager::new(config).unwrap();
+        let err = manager
+            .calculate_checksum("data/missing/model.bin")
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().starts_with("Disk I/O error at data/missing/model.bin"));
+
+        let source = err.source().expect("IO error should be chained");
+        let io_err = source.downcast_ref::<io::Error>().expect("source should be io::Error");
+        assert_eq!(io_err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_raid5_recovers_lost_stripe_from_parity() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = RaidConfig {
+            raid_level: 5,
+            min_disks: 3,
+            stripe_size: 16,
+            redundancy: 1,
+        };
+
+        let manager = BurstRaidManager::new(config).unwrap();
+        for disk_id in ["disk1", "disk2", "disk3", "disk4"] {
+            let path = dir.path().join(disk_id).to_str().unwrap().to_string();
+            manager.add_disk(disk_id.to_string(), path, 1024 * 1024).await.unwrap();
+        }
+
+        // 7 stripes: two full rows of three and a short tail stripe
+        let model: Vec<u8> = (0..100u8).collect();
+        let model_path = dir.path().join("model.bin");
+        std::fs::write(&model_path, &model).unwrap();
+
+        let model_id = format!("raid5-{}", uuid::Uuid::new_v4());
+        manager
+            .load_model(model_id.clone(), model_path.to_str().unwrap().to_string())
+            .await
+            .unwrap();
+
+        // Parity lives on the last disk, data stripes on the first three
+        let parity = std::fs::read(dir.path().join("disk4").join(&model_id).join("parity_0")).unwrap();
+        assert_eq!(parity.len(), 16);
+
+        let lost = dir.path().join("disk2").join(&model_id).join("stripe_1");
+        let original = std::fs::read(&lost).unwrap();
+        assert_eq!(original, model[64..80].to_vec());
+        std::fs::remove_file(&lost).unwrap();
+
+        manager.verify_data_integrity().await.unwrap();
+
+        assert_eq!(std::fs::read(&lost).unwrap(), original);
+        let _ = std::fs::remove_dir_all(format!("data/raid/models/{}", model_id));
+    }
+
+    #[tokio::test]
+    async fn test_status_report_degraded_by_failed_disk() {
+        let config = RaidConfig {
+            raid_level: 1,
+            min_disks: 2,
+            stripe_size: 1024 * 1024,
+            redundancy: 1,
+        };
+
+        let manager = BurstRaidManager::new(config).unwrap();
+        manager.add_disk("disk1".to_string(), "data/disk1".to_string(), 1024).await.unwrap();
+        manager.add_disk("disk2".to_string(), "data/disk2".to_string(), 1024).await.unwrap();
+        manager.register_seed("worker1".to_string(), "data/seeds/worker1".to_string(), 512).await.unwrap();
+
+        let report = manager.status_report();
+        assert_eq!(report.status, RaidArrayStatus::Healthy);
+
+        manager.mark_disk_failed("disk2").await.unwrap();
+
+        let report = manager.status_report();
+        assert_eq!(report.status, RaidArrayStatus::Degraded);
+        assert_eq!(report.raid_level, 1);
+        let statuses: Vec<(&str, DiskStatus)> = report
+            .disks
+            .iter()
+            .map(|disk| (disk.disk_id.as_str(), disk.status.clone()))
+            .collect();
+        assert_eq!(statuses, vec![("disk1", DiskStatus::Active), ("disk2", DiskStatus::Failed)]);
+        assert_eq!(report.seeds.len(), 1);
+        assert_eq!(report.seeds[0].status, SeedStatus::Available);
+        assert_eq!(report.model_pool.model_count, 0);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["status"], "degraded");
+        assert_eq!(json["disks"][1]["status"], "failed");
+    }
+
+    use std::path::PathBuf;
+    use super::super::capacity::DiskSpace;
+
+    /// Свободное место по путям дисков: (устройство, свободно).
+    /// Неизвестные пути лежат на отдельном безразмерном устройстве
+    struct FixedCapacity(HashMap<PathBuf, (u64, u64)>);
+
+    impl DiskCapacity for FixedCapacity {
+        fn disk_space(&self, path: &Path) -> io::Result<DiskSpace> {
+            let (device, free) = self.0.get(path).copied().unwrap_or((u64::MAX, u64::MAX));
+            Ok(DiskSpace { device, total: free, free })
+        }
+    }
+
+    async fn mirror_manager(dir: &Path, devices: [u64; 2]) -> BurstRaidManager {
+        let config = RaidConfig {
+            raid_level: 1,
+            min_disks: 2,
+            stripe_size: 16,
+            redundancy: 1,
+        };
+        let free = ["disk1", "disk2"]
+            .iter()
+            .zip(devices)
+            .map(|(disk_id, device)| (dir.join(disk_id), (device, 64)))
+            .collect();
+        let manager = BurstRaidManager::new(config)
+            .unwrap()
+            .with_disk_capacity(Arc::new(FixedCapacity(free)));
+        for disk_id in ["disk1", "disk2"] {
+            let path = dir.join(disk_id).to_str().unwrap().to_string();
+            manager.add_disk(disk_id.to_string(), path, 64).await.unwrap();
+        }
+        manager
+    }
+
+    #[tokio::test]
+    async fn test_disks_on_one_device_share_free_space() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // Зеркало на разных устройствах: по 44 байта на каждом из 64
+        let separate = mirror_manager(dir.path(), [1, 2]).await;
+        assert!(separate.check_free_space(40).is_ok());
+
+        // Оба зеркала на одном устройстве: 88 байт из тех же 64
+        let shared = mirror_manager(dir.path(), [1, 1]).await;
+        assert!(matches!(
+            shared.check_free_space(40),
+            Err(BurstRaidError::InsufficientSpace { required: 88, available: 64, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_load_model_checks_free_space_per_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = RaidConfig {
+            raid_level: 5,
+            min_disks: 3,
+            stripe_size: 16,
+            redundancy: 1,
+        };
+        let free: HashMap<PathBuf, (u64, u64)> = ["disk1", "disk2", "disk3", "disk4"]
+            .iter()
+            .enumerate()
+            .map(|(device, disk_id)| (dir.path().join(disk_id), (device as u64, 64)))
+            .collect();
+        let manager = BurstRaidManager::new(config)
+            .unwrap()
+            .with_disk_capacity(Arc::new(FixedCapacity(free)));
+        for disk_id in ["disk1", "disk2", "disk3", "disk4"] {
+            let path = dir.path().join(disk_id).to_str().unwrap().to_string();
+            manager.add_disk(disk_id.to_string(), path, 64).await.unwrap();
+        }
+
+        // 100 байт: 7 страйпов, 3 строки по 16 байт на диск, с запасом 53 <= 64
+        let fitting = dir.path().join("fitting.bin");
+        std::fs::write(&fitting, vec![7u8; 100]).unwrap();
+        let fitting_id = format!("fits-{}", uuid::Uuid::new_v4());
+        manager
+            .load_model(fitting_id.clone(), fitting.to_str().unwrap().to_string())
+            .await
+            .unwrap();
+
+        // 200 байт: 13 страйпов, 5 строк - 80 байт на диск, с запасом 88 > 64
+        let oversized = dir.path().join("oversized.bin");
+        std::fs::write(&oversized, vec![7u8; 200]).unwrap();
+        let oversized_id = format!("oversized-{}", uuid::Uuid::new_v4());
+        let err = manager
+            .load_model(oversized_id.clone(), oversized.to_str().unwrap().to_string())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            BurstRaidError::InsufficientSpace { required: 88, available: 64, .. }
+        ));
+        assert!(!Path::new(&format!("data/raid/models/{}", oversized_id)).exists());
+        assert!(!dir.path().join("disk1").join(&oversized_id).exists());
+        let _ = std::fs::remove_dir_all(format!("data/raid/models/{}", fitting_id));
+    }
+
+    fn burst_config(target_url: String) -> BurstConfig {
+        BurstConfig {
+            id: "burst".to_string(),
+            target_url,
+            concurrent_requests: 1,
+            request_timeout: 1000,
+            max_retries: 5,
+            retry_delay: 50,
+            max_retry_delay: 120,
+            active: true,
+        }
+    }
+
+    #[test]
+    fn test_retry_backoff_grows_and_is_capped() {
+        let config = burst_config(String::new());
+
+        assert_eq!(retry_backoff(&config, 0, 1.0), Duration::from_millis(50));
+        assert_eq!(retry_backoff(&config, 1, 1.0), Duration::from_millis(100));
+        assert_eq!(retry_backoff(&config, 2, 1.0), Duration::from_millis(120));
+        assert_eq!(retry_backoff(&config, 40, 1.0), Duration::from_millis(120));
+        assert_eq!(retry_backoff(&config, 1, 0.5), Duration::from_millis(50));
+        assert_eq!(retry_backoff(&config, 1, 0.0), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_retries_back_off_until_server_recovers() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Сервер обрывает первые два соединения, затем отвечает 200
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let attempts = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let seen = attempts.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let count = {
+                    let mut seen = seen.lock();
+                    seen.push(Instant::now());
+                    seen.len()
+                };
+                if count <= 2 {
+                    drop(socket);
+                    continue;
+                }
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let _ = socket
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                    .await;
+            }
+        });
+
+        let raid = BurstRaid::with_jitter(Arc::new(|| 1.0));
+        raid.execute_request(&burst_config(url)).await.unwrap();
+
+        let attempts = attempts.lock();
+        assert_eq!(attempts.len(), 3);
+        let first_gap = attempts[1] - attempts[0];
+        let second_gap = attempts[2] - attempts[1];
+        assert!(first_gap >= Duration::from_millis(50), "{:?}", first_gap);
+        assert!(second_gap >= Duration::from_millis(100), "{:?}", second_gap);
+        assert!(second_gap > first_gap);
+        assert!(second_gap < Duration::from_millis(120 + 500), "{:?}", second_gap);
+    }
+
+    #[tokio::test]
+    async fn test_failed_burst_request_is_typed_download_error() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf).await;
+            let _ = socket
+                .write_all(b"HTTP/1.1 500 Internal Server Error\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                .await;
+        });
+
+        let raid = BurstRaid::with_jitter(Arc::new(|| 0.0));
+        let err = raid.execute_request(&burst_config(url.clone())).await.unwrap_err();
+
+        match err {
+            BurstRaidError::Download { url: failed, source } => {
+                assert_eq!(failed, url);
+                assert_eq!(source.status(), Some(reqwest::StatusCode::INTERNAL_SERVER_ERROR));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -535,10 +1417,29 @@ pub struct BurstConfig {
     pub concurrent_requests: u32,
     pub request_timeout: u64,
     pub max_retries: u32,
+    /// Базовая задержка между повторами, мс
     pub retry_delay: u64,
+    /// Потолок задержки между повторами, мс
+    #[serde(default = "default_max_retry_delay")]
+    pub max_retry_delay: u64,
     pub active: bool,
 }
 
+fn default_max_retry_delay() -> u64 {
+    30_000
+}
+
+/// Задержка перед повтором `attempt` (с нуля): экспоненциальный рост
+/// `retry_delay * 2^attempt`, ограниченный `max_retry_delay`, с полным jitter.
+/// `jitter` - доля из диапазона `0.0..=1.0`.
+pub fn retry_backoff(config: &BurstConfig, attempt: u32, jitter: f64) -> Duration {
+    let ceiling = config
+        .retry_delay
+        .saturating_mul(1u64 << attempt.min(32))
+        .min(config.max_retry_delay);
+    Duration::from_millis((ceiling as f64 * jitter.clamp(0.0, 1.0)) as u64)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BurstStats {
     pub total_requests: u64,
@@ -558,12 +1459,19 @@ pub struct BurstMetrics {
 
 pub struct BurstRaid {
     bursts: Arc<Mutex<HashMap<String, BurstMetrics>>>,
+    /// Источник jitter для задержек между повторами
+    jitter: Arc<dyn Fn() -> f64 + Send + Sync>,
 }
 
 impl BurstRaid {
     pub fn new() -> Self {
+        Self::with_jitter(Arc::new(|| rand::random::<f64>()))
+    }
+
+    pub fn with_jitter(jitter: Arc<dyn Fn() -> f64 + Send + Sync>) -> Self {
         Self {
             bursts: Arc::new(Mutex::new(HashMap::new())),
+            jitter,
         }
     }
 
@@ -635,7 +1543,7 @@ impl BurstRaid {
             }
             Err(e) => {
                 burst.stats.failed_requests += 1;
-                burst.stats.last_error = Some(e);
+                burst.stats.last_error = Some(e.to_string());
             }
         }
 
@@ -649,7 +1557,7 @@ impl BurstRaid {
         Ok(())
     }
 
-    async fn execute_request(&self, config: &BurstConfig) -> Result<(), String> {
+    async fn execute_request(&self, config: &BurstConfig) -> Result<(), BurstRaidError> {
         // Simulate HTTP request
         let client = reqwest::Client::new();
         let mut retries = 0;
@@ -662,24 +1570,29 @@ impl BurstRaid {
                 .await
             {
                 Ok(response) => {
-                    if response.status().is_success() {
-                        return Ok(());
-                    } else {
-                        return Err(format!("Request failed with status: {}", response.status()));
-                    }
+                    return response
+                        .error_for_status()
+                        .map(|_| ())
+                        .map_err(BurstRaidError::download(&config.target_url));
                 }
                 Err(e) => {
                     retries += 1;
                     if retries < config.max_retries {
-                        tokio::time::sleep(std::time::Duration::from_millis(config.retry_delay)).await;
+                        let delay = retry_backoff(config, retries - 1, (self.jitter)());
+                        warn!("Burst {} request failed ({}), retrying in {:?}", config.id, e, delay);
+                        tokio::time::sleep(delay).await;
                         continue;
                     }
-                    return Err(format!("Request failed after {} retries: {}", retries, e));
+                    warn!("Burst {} request failed after {} retries", config.id, retries);
+                    return Err(BurstRaidError::download(&config.target_url)(e));
                 }
             }
         }
 
-        Err("Maximum retries exceeded".to_string())
+        Err(BurstRaidError::WorkerError(format!(
+            "Burst {} has no request attempts configured",
+            config.id
+        )))
     }
 
     pub async fn get_burst(&self, id: &str) -> Result<BurstMetrics, String> {
@@ -0,0 +1,90 @@
+//! Свободное место на дисках RAID-массива
+//!
+//! Перед загрузкой модели массив проверяет, хватит ли места на каждом диске
+//! для его части раскладки. Диски на одной файловой системе делят её
+//! свободное место, поэтому проверка группирует их по устройству.
+//! Источник данных о дисках подменяется в тестах.
+
+use std::io;
+use std::path::Path;
+
+/// Место на файловой системе, на которой лежит путь
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskSpace {
+    /// Идентификатор устройства: у путей одной файловой системы он совпадает
+    pub device: u64,
+    pub total: u64,
+    /// Доступно непривилегированному процессу
+    pub free: u64,
+}
+
+/// Источник сведений о файловой системе, на которой лежит путь
+pub trait DiskCapacity: Send + Sync {
+    fn disk_space(&self, path: &Path) -> io::Result<DiskSpace>;
+}
+
+/// Сведения из `statvfs` для файловой системы пути. Каталог диска может быть
+/// ещё не создан, поэтому опрашивается ближайший существующий предок.
+#[derive(Debug, Default)]
+pub struct StatvfsDiskCapacity;
+
+impl DiskCapacity for StatvfsDiskCapacity {
+    fn disk_space(&self, path: &Path) -> io::Result<DiskSpace> {
+        let existing = path
+            .ancestors()
+            .find(|ancestor| ancestor.exists())
+            .filter(|ancestor| !ancestor.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        statvfs(existing)
+    }
+}
+
+#[cfg(unix)]
+fn statvfs(path: &Path) -> io::Result<DiskSpace> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::fs::MetadataExt;
+
+    let device = std::fs::metadata(path)?.dev();
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    // SAFETY: statvfs заполняет переданную структуру, путь - валидная C-строка
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let fragment = stat.f_frsize as u64;
+    Ok(DiskSpace {
+        device,
+        total: stat.f_blocks as u64 * fragment,
+        free: stat.f_bavail as u64 * fragment,
+    })
+}
+
+#[cfg(not(unix))]
+fn statvfs(path: &Path) -> io::Result<DiskSpace> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("Free space check is not supported for {}", path.display()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_statvfs_reports_missing_directory_through_ancestor() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let space = StatvfsDiskCapacity.disk_space(&dir.path().join("not/created/yet")).unwrap();
+        let root = StatvfsDiskCapacity.disk_space(dir.path()).unwrap();
+
+        assert!(space.total > 0);
+        assert!(space.free <= space.total);
+        assert_eq!(space.device, root.device);
+    }
+}
@@ -9,6 +9,8 @@ pub mod network;
 pub mod storage;
 pub mod worker;
 pub mod mount;
+pub mod registry;
+pub mod capacity;
 
 pub use burstraid::*;
 pub use smallworld::*;
@@ -21,6 +23,8 @@ pub use network::*;
 pub use storage::*;
 pub use worker::*;
 pub use mount::*;
+pub use registry::*;
+pub use capacity::*;
 
 use std::error::Error;
 
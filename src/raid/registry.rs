@@ -0,0 +1,143 @@
+//! Реестр RAID-массивов
+//!
+//! Каждый массив - отдельный `BurstRaidManager` со своей конфигурацией и
+//! набором дисков (например, массивы для моделей и для seed'ов).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use log::info;
+use parking_lot::RwLock;
+
+use super::burstraid::{BurstRaidError, BurstRaidManager, RaidConfig, RaidHealth};
+
+/// Именованные RAID-массивы
+#[derive(Default)]
+pub struct RaidArrayRegistry {
+    arrays: RwLock<HashMap<String, Arc<BurstRaidManager>>>,
+}
+
+impl RaidArrayRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Создаёт массив; имя должно быть уникальным
+    pub fn create_array(
+        &self,
+        name: &str,
+        config: RaidConfig,
+    ) -> Result<Arc<BurstRaidManager>, BurstRaidError> {
+        let mut arrays = self.arrays.write();
+        if arrays.contains_key(name) {
+            return Err(BurstRaidError::ArrayExists(name.to_string()));
+        }
+
+        let raid_level = config.raid_level;
+        let manager = Arc::new(BurstRaidManager::new(config)?);
+        arrays.insert(name.to_string(), manager.clone());
+
+        info!("Created RAID array {} with level {}", name, raid_level);
+        Ok(manager)
+    }
+
+    pub fn get_array(&self, name: &str) -> Option<Arc<BurstRaidManager>> {
+        self.arrays.read().get(name).cloned()
+    }
+
+    pub fn remove_array(&self, name: &str) -> Result<Arc<BurstRaidManager>, BurstRaidError> {
+        let manager = self
+            .arrays
+            .write()
+            .remove(name)
+            .ok_or_else(|| BurstRaidError::ArrayNotFound(name.to_string()))?;
+
+        info!("Removed RAID array {}", name);
+        Ok(manager)
+    }
+
+    /// Имена массивов в порядке сортировки
+    pub fn array_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.arrays.read().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Состояние всех массивов
+    pub fn monitor_health_all(&self) -> HashMap<String, RaidHealth> {
+        self.arrays
+            .read()
+            .iter()
+            .map(|(name, manager)| (name.clone(), manager.check_health()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(raid_level: u8, min_disks: usize) -> RaidConfig {
+        RaidConfig {
+            raid_level,
+            min_disks,
+            stripe_size: 1024 * 1024,
+            redundancy: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_arrays_have_independent_disks_and_health() {
+        let registry = RaidArrayRegistry::new();
+        let models = registry.create_array("models", config(1, 2)).unwrap();
+        let seeds = registry.create_array("seeds", config(5, 3)).unwrap();
+
+        for disk_id in ["m1", "m2"] {
+            models
+                .add_disk(disk_id.to_string(), format!("data/{}", disk_id), 1024)
+                .await
+                .unwrap();
+        }
+        for disk_id in ["s1", "s2", "s3"] {
+            seeds
+                .add_disk(disk_id.to_string(), format!("data/{}", disk_id), 1024)
+                .await
+                .unwrap();
+        }
+
+        let models = registry.get_array("models").unwrap();
+        let seeds = registry.get_array("seeds").unwrap();
+        assert_eq!(models.config().raid_level, 1);
+        assert_eq!(seeds.config().raid_level, 5);
+        assert_eq!(models.disk_ids(), vec!["m1", "m2"]);
+        assert_eq!(seeds.disk_ids(), vec!["s1", "s2", "s3"]);
+
+        seeds.mark_disk_failed("s2").await.unwrap();
+
+        let health = registry.monitor_health_all();
+        assert_eq!(health.len(), 2);
+        assert!(health["models"].healthy);
+        assert!(health["models"].failed_disks.is_empty());
+        assert_eq!(health["seeds"].raid_level, 5);
+        assert_eq!(health["seeds"].failed_disks, vec!["s2"]);
+        assert_eq!(health["seeds"].active_disks, 2);
+        assert!(!health["seeds"].healthy);
+    }
+
+    #[test]
+    fn test_array_names_are_unique() {
+        let registry = RaidArrayRegistry::new();
+        registry.create_array("models", config(1, 2)).unwrap();
+
+        let result = registry.create_array("models", config(0, 1));
+        assert!(matches!(result, Err(BurstRaidError::ArrayExists(name)) if name == "models"));
+        assert_eq!(registry.get_array("models").unwrap().config().raid_level, 1);
+
+        registry.remove_array("models").unwrap();
+        assert!(registry.get_array("models").is_none());
+        assert!(matches!(
+            registry.remove_array("models"),
+            Err(BurstRaidError::ArrayNotFound(_))
+        ));
+        assert!(registry.array_names().is_empty());
+    }
+}
@@ -23,6 +23,28 @@ pub enum Error {
     NetworkError(String),
     #[error("Message error: {0}")]
     MessageError(String),
+    #[error("Node '{to}' is unreachable from '{from}'")]
+    Unreachable { from: NodeId, to: NodeId },
+    #[error("Message from '{from}' to '{to}' timed out after {timeout:?}")]
+    Timeout { from: NodeId, to: NodeId, timeout: std::time::Duration },
+}
+
+pub type SmallWorldError = Error;
+
+/// Идентификатор узла сети
+pub type NodeId = String;
+
+/// Результат доставки сообщения
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryReport {
+    pub from: NodeId,
+    pub to: NodeId,
+    /// Пройденный путь, включая отправителя и получателя
+    pub path: Vec<NodeId>,
+    pub hops: usize,
+    /// Номер попытки, на которой сообщение было доставлено (с 1)
+    pub attempts: u32,
+    pub payload_size: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -208,6 +230,82 @@ impl SmallWorldManager {
         Ok(None)
     }
 
+    /// Доставляет сообщение жадной маршрутизацией: на каждом шаге сообщение
+    /// уходит соседу, ближайшему к получателю. Связи длиннее `max_distance`
+    /// не используются. Если маршрут зашёл в тупик, тупиковый узел
+    /// исключается и попытка повторяется (не больше `max_retries` раз).
+    pub async fn route_message(
+        &self,
+        from: NodeId,
+        to: NodeId,
+        payload: Vec<u8>,
+    ) -> Result<DeliveryReport, SmallWorldError> {
+        let config = self.config.lock().await.clone();
+        let timeout = std::time::Duration::from_secs(config.message_timeout);
+
+        let result = tokio::time::timeout(timeout, async {
+            let nodes = self.nodes.lock().await;
+            for id in [&from, &to] {
+                match nodes.get(id) {
+                    Some(node) if node.config.active => {}
+                    Some(_) => return Err(Error::NodeError(format!("Node '{}' is inactive", id))),
+                    None => return Err(Error::NodeError(format!("Node '{}' not found", id))),
+                }
+            }
+
+            if !is_reachable(&nodes, &from, &to, config.max_distance) {
+                return Err(Error::Unreachable { from: from.clone(), to: to.clone() });
+            }
+
+            let mut excluded = HashSet::new();
+            for attempt in 1..=config.max_retries.saturating_add(1) {
+                match greedy_route(&nodes, &from, &to, config.max_distance, &excluded) {
+                    Ok(path) => return Ok((path, attempt)),
+                    Err(dead_end) => {
+                        log::debug!(
+                            "Greedy route {} -> {} stuck at {} (attempt {})",
+                            from, to, dead_end, attempt
+                        );
+                        excluded.insert(dead_end);
+                    }
+                }
+            }
+
+            Err(Error::MessageError(format!(
+                "No greedy route from '{}' to '{}' after {} attempts",
+                from,
+                to,
+                config.max_retries.saturating_add(1)
+            )))
+        })
+        .await
+        .unwrap_or_else(|_| Err(Error::Timeout { from: from.clone(), to: to.clone(), timeout }));
+
+        let mut nodes = self.nodes.lock().await;
+        if let Some(source) = nodes.get_mut(&from) {
+            source.stats.total_messages += 1;
+            source.stats.last_message_time = Some(Utc::now());
+            match &result {
+                Ok(_) => source.stats.successful_messages += 1,
+                Err(e) => {
+                    source.stats.failed_messages += 1;
+                    source.stats.last_error = Some(e.to_string());
+                }
+            }
+        }
+
+        let (path, attempts) = result?;
+        info!("Delivered message {} -> {} in {} hops", from, to, path.len() - 1);
+        Ok(DeliveryReport {
+            hops: path.len() - 1,
+            from,
+            to,
+            path,
+            attempts,
+            payload_size: payload.len(),
+        })
+    }
+
     pub async fn shutdown(&mut self) -> Result<(), Error> {
         // Cleanup and close all connections
         let mut nodes = self.nodes.lock().await;
@@ -216,4 +314,201 @@ impl SmallWorldManager {
         neurons.clear();
         Ok(())
     }
-} 
\ No newline at end of file
+} 
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+/// Соседи узла, до которых можно передать сообщение
+fn usable_links<'a>(
+    nodes: &'a HashMap<String, NodeMetrics>,
+    node: &'a NodeMetrics,
+    max_distance: f64,
+) -> impl Iterator<Item = &'a NodeMetrics> + 'a {
+    node.config
+        .connections
+        .iter()
+        .filter_map(move |id| nodes.get(id))
+        .filter(move |next| {
+            next.config.active && distance(node.config.position, next.config.position) <= max_distance
+        })
+}
+
+fn is_reachable(nodes: &HashMap<String, NodeMetrics>, from: &str, to: &str, max_distance: f64) -> bool {
+    let mut visited = HashSet::new();
+    let mut queue = std::collections::VecDeque::from([from.to_string()]);
+
+    while let Some(current) = queue.pop_front() {
+        if current == to {
+            return true;
+        }
+        if !visited.insert(current.clone()) {
+            continue;
+        }
+        if let Some(node) = nodes.get(&current) {
+            queue.extend(usable_links(nodes, node, max_distance).map(|next| next.config.id.clone()));
+        }
+    }
+    false
+}
+
+/// Жадный маршрут от `from` к `to`; при тупике возвращает тупиковый узел
+fn greedy_route(
+    nodes: &HashMap<String, NodeMetrics>,
+    from: &str,
+    to: &str,
+    max_distance: f64,
+    excluded: &HashSet<String>,
+) -> Result<Vec<NodeId>, NodeId> {
+    let target = nodes[to].config.position;
+    let mut path = vec![from.to_string()];
+    let mut visited: HashSet<String> = HashSet::from([from.to_string()]);
+    let mut current = &nodes[from];
+
+    while current.config.id != to {
+        let next = usable_links(nodes, current, max_distance)
+            .filter(|next| !visited.contains(&next.config.id) && !excluded.contains(&next.config.id))
+            .min_by(|a, b| {
+                distance(a.config.position, target)
+                    .partial_cmp(&distance(b.config.position, target))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+
+        match next {
+            Some(next) => {
+                visited.insert(next.config.id.clone());
+                path.push(next.config.id.clone());
+                current = next;
+            }
+            None => return Err(current.config.id.clone()),
+        }
+    }
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network(max_retries: u32) -> SmallWorldManager {
+        SmallWorldManager::new(
+            NetworkConfig {
+                rewiring_probability: 0.1,
+                max_distance: 10.0,
+                message_timeout: 5,
+                max_retries,
+            },
+            4,
+            0.1,
+        )
+    }
+
+    fn node(id: &str, position: (f64, f64), connections: &[&str]) -> NodeConfig {
+        NodeConfig {
+            id: id.to_string(),
+            position,
+            connections: connections.iter().map(|c| c.to_string()).collect(),
+            max_connections: 8,
+            active: true,
+        }
+    }
+
+    /// Кольцо из 8 узлов с соседями на расстоянии 1 и 2 и одной дальней связью
+    async fn ring() -> SmallWorldManager {
+        let manager = network(2);
+        let n = 8;
+        for i in 0..n {
+            let angle = i as f64 * std::f64::consts::TAU / n as f64;
+            let mut links: Vec<String> = [1, 2, n - 1, n - 2]
+                .iter()
+                .map(|d| format!("n{}", (i + d) % n))
+                .collect();
+            if i == 0 {
+                links.push("n4".to_string());
+            }
+            let links: Vec<&str> = links.iter().map(String::as_str).collect();
+            manager
+                .add_node(node(&format!("n{}", i), (angle.cos() * 3.0, angle.sin() * 3.0), &links))
+                .await
+                .unwrap();
+        }
+        manager
+    }
+
+    #[tokio::test]
+    async fn test_delivery_within_hop_bound() {
+        let manager = ring().await;
+
+        let report = manager
+            .route_message("n0".to_string(), "n4".to_string(), b"ping".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(report.hops, 1, "shortcut should be used: {:?}", report.path);
+
+        for target in 1..8 {
+            let report = manager
+                .route_message("n1".to_string(), format!("n{}", target), vec![0; 16])
+                .await
+                .unwrap();
+            assert!(report.hops <= 3, "{:?}", report.path);
+            assert_eq!(report.path.first().unwrap(), "n1");
+            assert_eq!(report.path.last().unwrap(), &format!("n{}", target));
+            assert_eq!(report.payload_size, 16);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_node() {
+        let manager = ring().await;
+        manager.add_node(node("island", (1.0, 1.0), &["n0"])).await.unwrap();
+
+        match manager.route_message("n0".to_string(), "island".to_string(), vec![]).await {
+            Err(Error::Unreachable { from, to }) => {
+                assert_eq!(from, "n0");
+                assert_eq!(to, "island");
+            }
+            other => panic!("expected Unreachable, got {:?}", other),
+        }
+
+        // Ссылка длиннее max_distance не используется
+        manager.add_node(node("far", (100.0, 0.0), &[])).await.unwrap();
+        manager.add_node(node("hub", (0.0, 0.0), &["far"])).await.unwrap();
+        assert!(matches!(
+            manager.route_message("hub".to_string(), "far".to_string(), vec![]).await,
+            Err(Error::Unreachable { .. })
+        ));
+
+        let nodes = manager.nodes.lock().await;
+        assert_eq!(nodes["n0"].stats.failed_messages, 1);
+    }
+
+    #[tokio::test]
+    async fn test_dead_end_is_retried() {
+        // a -> b (ближе к d, но тупик), a -> c -> d
+        let manager = network(1);
+        manager.add_node(node("a", (0.0, 0.0), &["b", "c"])).await.unwrap();
+        manager.add_node(node("b", (3.0, 0.0), &[])).await.unwrap();
+        manager.add_node(node("c", (0.0, 3.0), &["d"])).await.unwrap();
+        manager.add_node(node("d", (4.0, 0.0), &[])).await.unwrap();
+
+        let report = manager
+            .route_message("a".to_string(), "d".to_string(), vec![])
+            .await
+            .unwrap();
+        assert_eq!(report.path, vec!["a", "c", "d"]);
+        assert_eq!(report.attempts, 2);
+
+        // Без повторов тупик приводит к ошибке маршрутизации
+        let strict = network(0);
+        strict.add_node(node("a", (0.0, 0.0), &["b", "c"])).await.unwrap();
+        strict.add_node(node("b", (3.0, 0.0), &[])).await.unwrap();
+        strict.add_node(node("c", (0.0, 3.0), &["d"])).await.unwrap();
+        strict.add_node(node("d", (4.0, 0.0), &[])).await.unwrap();
+        assert!(matches!(
+            strict.route_message("a".to_string(), "d".to_string(), vec![]).await,
+            Err(Error::MessageError(_))
+        ));
+    }
+}
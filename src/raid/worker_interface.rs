@@ -5,6 +5,8 @@ use thiserror::Error;
 use teloxide::{prelude::*, utils::command::BotCommands};
 use std::collections::HashMap;
 use chrono::{DateTime, Utc};
+use async_trait::async_trait;
+use std::time::Duration;
 use cursor_codes::core::error::CursorError;
 use cursor_codes::monitoring::logger::LoggerSystem;
 use cursor_codes::monitoring::alert::AlertSystem;
@@ -15,13 +17,64 @@ use cursor_codes::runtime::cache::CacheSystem;
 use cursor_codes::runtime::storage::StorageSystem;
 
 #[derive(Error, Debug)]
-pub enum Error {
+pub enum WorkerInterfaceError {
     #[error("Telegram error: {0}")]
     TelegramError(String),
     #[error("Worker error: {0}")]
     WorkerError(String),
     #[error("Hardware error: {0}")]
     HardwareError(String),
+    #[error("Worker {worker_id} did not respond within {timeout:?}")]
+    Timeout { worker_id: String, timeout: Duration },
+}
+
+pub type Error = WorkerInterfaceError;
+
+/// Команда, отправляемая воркеру
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum WorkerCommand {
+    Status,
+    Configure { cores: u32, memory: u64, storage: u64 },
+    Start,
+    Stop,
+}
+
+/// Ответ воркера на команду
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerReply {
+    pub worker_id: String,
+    pub payload: serde_json::Value,
+}
+
+/// Канал связи с воркером
+#[async_trait]
+pub trait WorkerConnection: Send + Sync {
+    async fn send(&self, command: WorkerCommand) -> Result<WorkerReply, Error>;
+}
+
+/// Настройки таймаута команд
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandTimeoutConfig {
+    pub timeout: Duration,
+    /// Число таймаутов подряд, после которого соединение считается подозрительным
+    pub suspect_after: u32,
+}
+
+impl Default for CommandTimeoutConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            suspect_after: 3,
+        }
+    }
+}
+
+/// Состояние соединения с воркером
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConnectionHealth {
+    pub consecutive_timeouts: u32,
+    pub suspect: bool,
+    pub last_timeout: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,6 +135,9 @@ pub enum Command {
 pub struct WorkerInterfaceManager {
     interfaces: Arc<Mutex<HashMap<String, WorkerInterface>>>,
     bot: Bot,
+    connections: Arc<Mutex<HashMap<String, Arc<dyn WorkerConnection>>>>,
+    connection_health: Arc<Mutex<HashMap<String, ConnectionHealth>>>,
+    command_config: CommandTimeoutConfig,
 }
 
 impl WorkerInterfaceManager {
@@ -89,9 +145,64 @@ impl WorkerInterfaceManager {
         Self {
             interfaces: Arc::new(Mutex::new(HashMap::new())),
             bot: Bot::new(bot_token),
+            connections: Arc::new(Mutex::new(HashMap::new())),
+            connection_health: Arc::new(Mutex::new(HashMap::new())),
+            command_config: CommandTimeoutConfig::default(),
+        }
+    }
+
+    pub fn with_command_config(mut self, config: CommandTimeoutConfig) -> Self {
+        self.command_config = config;
+        self
+    }
+
+    pub async fn attach_connection(&self, worker_id: &str, connection: Arc<dyn WorkerConnection>) {
+        self.connections.lock().await.insert(worker_id.to_string(), connection);
+        self.connection_health.lock().await.insert(worker_id.to_string(), ConnectionHealth::default());
+    }
+
+    /// Отправляет команду воркеру. Если воркер не ответил за отведённое время,
+    /// команда завершается ошибкой `Timeout`, а после нескольких таймаутов
+    /// подряд соединение помечается подозрительным.
+    pub async fn send_command(&self, worker_id: &str, command: WorkerCommand) -> Result<WorkerReply, Error> {
+        let connection = self.connections.lock().await.get(worker_id).cloned()
+            .ok_or_else(|| Error::WorkerError(format!("Worker {} is not connected", worker_id)))?;
+
+        let timeout = self.command_config.timeout;
+        let result = tokio::time::timeout(timeout, connection.send(command)).await;
+
+        let mut health = self.connection_health.lock().await;
+        let entry = health.entry(worker_id.to_string()).or_default();
+        match result {
+            Ok(reply) => {
+                entry.consecutive_timeouts = 0;
+                entry.suspect = false;
+                reply
+            }
+            Err(_) => {
+                entry.consecutive_timeouts += 1;
+                entry.last_timeout = Some(Utc::now());
+                if entry.consecutive_timeouts >= self.command_config.suspect_after && !entry.suspect {
+                    entry.suspect = true;
+                    log::warn!(
+                        "Worker {} connection marked suspect after {} timeouts",
+                        worker_id,
+                        entry.consecutive_timeouts
+                    );
+                }
+                Err(Error::Timeout { worker_id: worker_id.to_string(), timeout })
+            }
         }
     }
 
+    pub async fn connection_health(&self, worker_id: &str) -> Option<ConnectionHealth> {
+        self.connection_health.lock().await.get(worker_id).cloned()
+    }
+
+    pub async fn is_connection_suspect(&self, worker_id: &str) -> bool {
+        self.connection_health(worker_id).await.map_or(false, |health| health.suspect)
+    }
+
     pub async fn init(&mut self) -> Result<(), Error> {
         // Initialize bot commands
         self.bot.set_my_commands(Command::bot_commands()).await
@@ -255,4 +366,38 @@ impl WorkerInterfaceManager {
         // Cleanup and close all connections
         Ok(())
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Воркер, который никогда не отвечает
+    struct StalledConnection;
+
+    #[async_trait]
+    impl WorkerConnection for StalledConnection {
+        async fn send(&self, _command: WorkerCommand) -> Result<WorkerReply, Error> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stalled_worker_times_out_and_is_flagged() {
+        let manager = WorkerInterfaceManager::new("test_token".to_string())
+            .with_command_config(CommandTimeoutConfig {
+                timeout: Duration::from_millis(50),
+                suspect_after: 2,
+            });
+        manager.attach_connection("worker-1", Arc::new(StalledConnection)).await;
+
+        let result = manager.send_command("worker-1", WorkerCommand::Status).await;
+        assert!(matches!(result, Err(WorkerInterfaceError::Timeout { .. })));
+        assert!(!manager.is_connection_suspect("worker-1").await);
+
+        let result = manager.send_command("worker-1", WorkerCommand::Status).await;
+        assert!(matches!(result, Err(WorkerInterfaceError::Timeout { .. })));
+        assert!(manager.is_connection_suspect("worker-1").await);
+        assert_eq!(manager.connection_health("worker-1").await.unwrap().consecutive_timeouts, 2);
+    }
+}
@@ -0,0 +1,106 @@
+//! Micro-batching - Объединение одновременных запросов к экземпляру
+//!
+//! Запросы, пришедшие к экземпляру в пределах короткого окна, собираются
+//! в пакет размером до `batch_size` и отправляются в модель одним вызовом
+//! `process_batch`. Ответы раздаются вызывающим в порядке запросов.
+//! Очередь ограничена: при её заполнении `submit` ждёт, пока модель
+//! разберёт уже принятые пакеты.
+
+use crate::core::error::AppError;
+use crate::core::model_interface::{ModelInterface, ModelRequest, ModelResponse, PerformanceConfig};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+
+type Reply = oneshot::Sender<Result<ModelResponse, AppError>>;
+
+/// Сколько полных пакетов может ждать в очереди экземпляра
+pub const QUEUED_BATCHES: usize = 4;
+
+/// Очередь запросов экземпляра. Пакеты собирает фоновая задача, которая
+/// завершается вместе с последним клоном батчера.
+#[derive(Clone)]
+pub struct MicroBatcher {
+    sender: mpsc::Sender<(ModelRequest, Reply)>,
+}
+
+impl MicroBatcher {
+    pub fn spawn(
+        model: Arc<dyn ModelInterface + Send + Sync>,
+        max_batch_size: usize,
+        window: Duration,
+    ) -> Self {
+        let max_batch_size = max_batch_size.max(1);
+        let (sender, receiver) = mpsc::channel(max_batch_size.saturating_mul(QUEUED_BATCHES));
+        tokio::spawn(collect_batches(model, receiver, max_batch_size, window));
+        Self { sender }
+    }
+
+    /// Батчер по настройкам производительности; `None`, если пакет из одного
+    /// запроса или окно нулевое
+    pub fn from_config(
+        model: Arc<dyn ModelInterface + Send + Sync>,
+        performance: &PerformanceConfig,
+    ) -> Option<Self> {
+        if performance.batch_size <= 1 || performance.batch_window_ms == 0 {
+            return None;
+        }
+        Some(Self::spawn(
+            model,
+            performance.batch_size as usize,
+            Duration::from_millis(performance.batch_window_ms),
+        ))
+    }
+
+    /// Добавляет запрос в текущий пакет и ждёт ответа. Таймаут ожидания
+    /// задаёт вызывающий: по его истечении запрос выпадает из пакета.
+    pub async fn submit(&self, request: ModelRequest) -> Result<ModelResponse, AppError> {
+        let (reply, response) = oneshot::channel();
+        self.sender
+            .send((request, reply))
+            .await
+            .map_err(|_| AppError::Unknown("Request batcher stopped".to_string()))?;
+        response
+            .await
+            .map_err(|_| AppError::Unknown("Batch dropped the request".to_string()))?
+    }
+}
+
+async fn collect_batches(
+    model: Arc<dyn ModelInterface + Send + Sync>,
+    mut receiver: mpsc::Receiver<(ModelRequest, Reply)>,
+    max_batch_size: usize,
+    window: Duration,
+) {
+    while let Some(first) = receiver.recv().await {
+        let mut batch = vec![first];
+        let window_end = tokio::time::Instant::now() + window;
+        while batch.len() < max_batch_size {
+            match tokio::time::timeout_at(window_end, receiver.recv()).await {
+                Ok(Some(entry)) => batch.push(entry),
+                Ok(None) | Err(_) => break,
+            }
+        }
+        tokio::spawn(dispatch_batch(model.clone(), batch));
+    }
+}
+
+async fn dispatch_batch(model: Arc<dyn ModelInterface + Send + Sync>, batch: Vec<(ModelRequest, Reply)>) {
+    // Запросы, чьи вызывающие уже не ждут ответа, в модель не отправляются
+    let (requests, replies): (Vec<_>, Vec<_>) = batch
+        .into_iter()
+        .filter(|(_, reply)| !reply.is_closed())
+        .unzip();
+    if requests.is_empty() {
+        return;
+    }
+
+    let expected = requests.len();
+    let mut results = model.process_batch(requests).await.into_iter();
+    for reply in replies {
+        let result = results.next().unwrap_or_else(|| {
+            Err(AppError::Unknown(format!("Model returned fewer than {} batch responses", expected)))
+        });
+        let _ = reply.send(result);
+    }
+}
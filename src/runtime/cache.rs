@@ -2,9 +2,9 @@ use serde::{Serialize, Deserialize};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use log::{info, warn, error};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use chrono::{DateTime, Utc};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use cursor_codes::core::error::CursorError;
 use cursor_codes::monitoring::logger::LoggerSystem;
 use cursor_codes::monitoring::alert::AlertSystem;
@@ -51,16 +51,191 @@ pub struct CacheItem {
     pub hits: u32,
 }
 
+/// Ограничения общего кэша узла
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheLimits {
+    /// Максимум записей; при превышении вытесняется давно не использованная
+    pub max_entries: usize,
+    /// Время жизни записи, если не задано явно
+    pub default_ttl: Duration,
+}
+
+impl Default for CacheLimits {
+    fn default() -> Self {
+        Self {
+            max_entries: 10_000,
+            default_ttl: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Счётчики общего кэша
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CacheCounters {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub entries: usize,
+}
+
+struct LruEntry {
+    value: String,
+    expires_at: Instant,
+    last_access: u64,
+}
+
+/// Записи общего кэша с порядком использования для LRU-вытеснения
+#[derive(Default)]
+struct LruStore {
+    entries: HashMap<String, LruEntry>,
+    /// Метка последнего обращения -> ключ; первый элемент — кандидат на вытеснение
+    order: BTreeMap<u64, String>,
+    tick: u64,
+    counters: CacheCounters,
+}
+
+impl LruStore {
+    fn touch(&mut self, key: &str) {
+        self.tick += 1;
+        let tick = self.tick;
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.order.remove(&entry.last_access);
+            entry.last_access = tick;
+            self.order.insert(tick, key.to_string());
+        }
+    }
+
+    fn remove(&mut self, key: &str) -> Option<LruEntry> {
+        let entry = self.entries.remove(key)?;
+        self.order.remove(&entry.last_access);
+        Some(entry)
+    }
+
+    /// Удаляет устаревшие записи; они не считаются вытесненными
+    fn purge_expired(&mut self, now: Instant) {
+        let expired: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.expires_at <= now)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in expired {
+            self.remove(&key);
+        }
+    }
+
+    fn evict_lru(&mut self) {
+        if let Some((_, key)) = self.order.pop_first() {
+            self.entries.remove(&key);
+            self.counters.evictions += 1;
+        }
+    }
+}
+
 pub struct CacheSystem {
     caches: Arc<Mutex<HashMap<String, CacheMetrics>>>,
     items: Arc<Mutex<HashMap<String, CacheItem>>>,
+    limits: CacheLimits,
+    store: Arc<Mutex<LruStore>>,
 }
 
 impl CacheSystem {
     pub fn new() -> Self {
+        Self::with_limits(CacheLimits::default())
+    }
+
+    pub fn with_limits(limits: CacheLimits) -> Self {
         Self {
             caches: Arc::new(Mutex::new(HashMap::new())),
             items: Arc::new(Mutex::new(HashMap::new())),
+            limits,
+            store: Arc::new(Mutex::new(LruStore::default())),
+        }
+    }
+
+    pub async fn initialize(&self) -> Result<(), String> {
+        info!(
+            "Cache initialized (max entries: {}, default ttl: {:?})",
+            self.limits.max_entries, self.limits.default_ttl
+        );
+        Ok(())
+    }
+
+    pub async fn shutdown(&self) -> Result<(), String> {
+        let mut store = self.store.lock().await;
+        store.entries.clear();
+        store.order.clear();
+        info!("Cache shut down");
+        Ok(())
+    }
+
+    pub fn limits(&self) -> &CacheLimits {
+        &self.limits
+    }
+
+    /// Сохраняет значение со временем жизни по умолчанию
+    pub async fn insert(&self, key: &str, value: &str) {
+        self.insert_with_ttl(key, value, self.limits.default_ttl).await
+    }
+
+    /// Сохраняет значение с заданным временем жизни. Если кэш заполнен,
+    /// сначала удаляются устаревшие записи, и только если места всё ещё нет,
+    /// вытесняется запись, к которой дольше всего не обращались.
+    pub async fn insert_with_ttl(&self, key: &str, value: &str, ttl: Duration) {
+        let mut store = self.store.lock().await;
+        store.remove(key);
+
+        if store.entries.len() >= self.limits.max_entries.max(1) {
+            store.purge_expired(Instant::now());
+        }
+        while store.entries.len() >= self.limits.max_entries.max(1) {
+            store.evict_lru();
+        }
+
+        store.entries.insert(
+            key.to_string(),
+            LruEntry {
+                value: value.to_string(),
+                expires_at: Instant::now() + ttl,
+                last_access: 0,
+            },
+        );
+        store.touch(key);
+    }
+
+    /// Возвращает значение, если оно есть и не устарело
+    pub async fn get(&self, key: &str) -> Option<String> {
+        let mut store = self.store.lock().await;
+
+        let expired = match store.entries.get(key) {
+            Some(entry) => entry.expires_at <= Instant::now(),
+            None => {
+                store.counters.misses += 1;
+                return None;
+            }
+        };
+
+        if expired {
+            store.remove(key);
+            store.counters.misses += 1;
+            return None;
+        }
+
+        store.counters.hits += 1;
+        store.touch(key);
+        store.entries.get(key).map(|entry| entry.value.clone())
+    }
+
+    /// Удаляет значение из общего кэша
+    pub async fn invalidate(&self, key: &str) -> bool {
+        self.store.lock().await.remove(key).is_some()
+    }
+
+    pub async fn stats(&self) -> CacheCounters {
+        let store = self.store.lock().await;
+        CacheCounters {
+            entries: store.entries.len(),
+            ..store.counters.clone()
         }
     }
 
@@ -300,4 +475,73 @@ impl CacheSystem {
         info!("Updated cache configuration: {}", id);
         Ok(())
     }
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(max_entries: usize) -> CacheSystem {
+        CacheSystem::with_limits(CacheLimits {
+            max_entries,
+            default_ttl: Duration::from_secs(60),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_lru_eviction_under_pressure() {
+        let cache = cache(2);
+        cache.insert("a", "1").await;
+        cache.insert("b", "2").await;
+
+        // Обращение к "a" делает "b" самой старой записью
+        assert_eq!(cache.get("a").await.as_deref(), Some("1"));
+        cache.insert("c", "3").await;
+
+        assert_eq!(cache.get("b").await, None);
+        assert_eq!(cache.get("a").await.as_deref(), Some("1"));
+        assert_eq!(cache.get("c").await.as_deref(), Some("3"));
+
+        let stats = cache.stats().await;
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.hits, 3);
+        assert_eq!(stats.misses, 1);
+    }
+
+    #[tokio::test]
+    async fn test_stale_entry_expires() {
+        let cache = cache(10);
+        cache.insert_with_ttl("short", "x", Duration::from_millis(20)).await;
+        cache.insert("long", "y").await;
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(cache.get("short").await, None);
+        assert_eq!(cache.get("long").await.as_deref(), Some("y"));
+
+        let stats = cache.stats().await;
+        assert_eq!(stats.entries, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.evictions, 0);
+    }
+
+    #[tokio::test]
+    async fn test_expired_entry_purged_before_eviction() {
+        let cache = cache(2);
+        cache.insert("live", "1").await;
+        cache.insert_with_ttl("stale", "2", Duration::from_millis(20)).await;
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // "live" — самая старая по обращению, но место освобождает устаревшая "stale"
+        cache.insert("new", "3").await;
+
+        assert_eq!(cache.get("live").await.as_deref(), Some("1"));
+        assert_eq!(cache.get("new").await.as_deref(), Some("3"));
+
+        let stats = cache.stats().await;
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.evictions, 0);
+    }
+}
@@ -7,33 +7,72 @@
 //! - Метрики
 
 use crate::core::model_interface::{
-    ModelInterface, ModelRequest, ModelResponse, ModelInfo, ModelConfig, ModelMetrics, ModelHealth
+    ModelInterface, ModelRequest, ModelResponse, ModelInfo, ModelConfig, ModelMetrics, ModelHealth,
+    InferenceDefaults, TokenChunk,
 };
 use crate::core::error::AppError;
+use crate::monitoring::events::{EventBus, EventKind};
 use crate::monitoring::metrics::InstanceMetrics;
+use crate::platform::gpu::GpuManager;
+use crate::runtime::batcher::MicroBatcher;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, Semaphore};
 use std::time::{Instant, Duration};
 
+/// Промпт синтетического запроса прогрева
+const WARM_UP_PROMPT: &str = "ping";
+/// Задержка перед повтором неудачного прогрева; удваивается до `WARM_UP_MAX_RETRY_DELAY`
+const WARM_UP_RETRY_DELAY: Duration = Duration::from_secs(1);
+const WARM_UP_MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
 /// Менеджер экземпляров моделей
 pub struct InstanceManager {
     instances: Arc<RwLock<HashMap<String, ModelInstance>>>,
     config: InstanceManagerConfig,
     metrics: Arc<RwLock<InstanceMetrics>>,
+    /// Ограничивает число одновременных загрузок моделей
+    load_semaphore: Arc<Semaphore>,
+    /// Определяет, есть ли GPU для экземпляров
+    gpu_manager: Arc<GpuManager>,
+    /// Сохранённые конфигурации моделей; новые экземпляры создаются с ними
+    stored_configs: Arc<parking_lot::RwLock<HashMap<String, ModelConfig>>>,
+    /// Завершённые загрузки моделей публикуются как `ModelLoaded`
+    events: Arc<EventBus>,
 }
 
 impl InstanceManager {
     /// Создает новый менеджер экземпляров
     pub fn new(config: InstanceManagerConfig) -> Self {
+        let load_semaphore = Arc::new(Semaphore::new(config.max_concurrent_loads.max(1)));
+        let stored_configs = config
+            .config_store
+            .as_deref()
+            .map(read_model_configs)
+            .unwrap_or_default();
         Self {
             instances: Arc::new(RwLock::new(HashMap::new())),
+            stored_configs: Arc::new(parking_lot::RwLock::new(stored_configs)),
             config,
             metrics: Arc::new(RwLock::new(InstanceMetrics::default())),
+            load_semaphore,
+            gpu_manager: Arc::new(GpuManager::new()),
+            events: Arc::new(EventBus::default()),
         }
     }
 
+    /// Загрузки моделей публикуются в общую шину
+    pub fn with_event_bus(mut self, events: Arc<EventBus>) -> Self {
+        self.events = events;
+        self
+    }
+
+    pub fn with_gpu_manager(mut self, gpu_manager: Arc<GpuManager>) -> Self {
+        self.gpu_manager = gpu_manager;
+        self
+    }
+
     /// Инициализирует менеджер экземпляров
     pub async fn initialize(&self) -> Result<(), AppError> {
         log::info!("Initializing instance manager");
@@ -67,13 +106,18 @@ impl InstanceManager {
         &self,
         model_name: String,
         model: Arc<dyn ModelInterface + Send + Sync>,
-        config: ModelConfig,
+        mut config: ModelConfig,
     ) -> Result<String, AppError> {
         let instance_id = self.generate_instance_id(&model_name);
+        // На CPU откатываемся, только если GPU искали и не нашли
+        if let Some(gpu_available) = self.gpu_manager.gpu_detected() {
+            config.device = config.device.resolve(gpu_available);
+        }
         
         let instance = ModelInstance {
             id: instance_id.clone(),
             model_name,
+            batcher: MicroBatcher::from_config(model.clone(), &config.performance),
             model,
             config,
             status: InstanceStatus::Starting,
@@ -82,12 +126,12 @@ impl InstanceManager {
             metrics: Arc::new(RwLock::new(InstanceMetrics::default())),
         };
         
-        // Инициализируем экземпляр
-        instance.initialize().await?;
+        // Инициализируем экземпляр, дожидаясь свободного слота загрузки
+        self.load_instance(&instance).await?;
         
-        // Добавляем в менеджер
-        let mut instances = self.instances.write().await;
-        instances.insert(instance_id.clone(), instance);
+        // Добавляем в менеджер; `Running` экземпляр станет после прогрева
+        self.instances.write().await.insert(instance_id.clone(), instance.clone());
+        self.spawn_warm_up(instance);
         
         log::info!("Created model instance: {}", instance_id);
         Ok(instance_id)
@@ -124,19 +168,156 @@ impl InstanceManager {
         &self,
         instance_id: &str,
         request: ModelRequest,
+    ) -> Result<ModelResponse, AppError> {
+        self.process_request_with_deadline(instance_id, request, None).await
+    }
+
+    /// Обрабатывает запрос через экземпляр с ограничением по времени клиента
+    pub async fn process_request_with_deadline(
+        &self,
+        instance_id: &str,
+        request: ModelRequest,
+        deadline: Option<Instant>,
     ) -> Result<ModelResponse, AppError> {
         let instance = self.get_instance(instance_id).await
             .ok_or_else(|| AppError::NotFound(format!("Instance {} not found", instance_id)))?;
         
-        instance.process_request(request).await
+        instance.process_request_with_deadline(request, deadline).await
+    }
+
+    /// Потоковый запрос к экземпляру; см. `ModelInstance::stream_request_with_deadline`
+    pub async fn stream_request_with_deadline(
+        &self,
+        instance_id: &str,
+        request: ModelRequest,
+        deadline: Option<Instant>,
+        tx: futures::channel::mpsc::Sender<Result<TokenChunk, AppError>>,
+    ) -> Result<(), AppError> {
+        let instance = self.get_instance(instance_id).await
+            .ok_or_else(|| AppError::NotFound(format!("Instance {} not found", instance_id)))?;
+
+        instance.stream_request_with_deadline(request, deadline, tx).await;
+        Ok(())
+    }
+
+    /// Получает параметры генерации по умолчанию для модели
+    pub async fn get_inference_defaults(&self, model_name: &str) -> Option<InferenceDefaults> {
+        let instances = self.instances.read().await;
+        instances.values()
+            .find(|instance| instance.model_name == model_name)
+            .map(|instance| instance.config.inference.defaults())
+    }
+
+    /// Обновляет параметры генерации по умолчанию во всех экземплярах модели
+    pub async fn set_inference_defaults(
+        &self,
+        model_name: &str,
+        defaults: InferenceDefaults,
+    ) -> Result<InferenceDefaults, AppError> {
+        defaults.validate()?;
+
+        let mut instances = self.instances.write().await;
+        let mut config = instances.values()
+            .find(|instance| instance.model_name == model_name)
+            .map(|instance| instance.config.clone())
+            .ok_or_else(|| AppError::NotFound(format!("Model '{}' has no instances", model_name)))?;
+        config.inference.apply_defaults(&defaults);
+        self.persist_model_config(model_name, &config)?;
+
+        let mut updated = 0;
+        for instance in instances.values_mut().filter(|i| i.model_name == model_name) {
+            instance.config.inference.apply_defaults(&defaults);
+            updated += 1;
+        }
+
+        log::info!(
+            "Updated inference defaults for model {} ({} instances): {:?}",
+            model_name, updated, defaults
+        );
+        Ok(defaults)
+    }
+
+    /// Сведения о модели от любого её экземпляра; `None`, если экземпляров нет
+    /// или модель не ответила
+    pub async fn get_model_info(&self, model_name: &str) -> Option<ModelInfo> {
+        let model = self.instances.read().await.values()
+            .find(|instance| instance.model_name == model_name)
+            .map(|instance| instance.model.clone())?;
+        match model.get_model_info().await {
+            Ok(info) => Some(info),
+            Err(e) => {
+                log::warn!("Failed to read info of model {}: {}", model_name, e);
+                None
+            }
+        }
+    }
+
+    /// Текущая конфигурация модели (берётся у любого её экземпляра)
+    pub async fn get_model_config(&self, model_name: &str) -> Option<ModelConfig> {
+        let instances = self.instances.read().await;
+        instances.values()
+            .find(|instance| instance.model_name == model_name)
+            .map(|instance| instance.config.clone())
+    }
+
+    /// Применяет JSON merge patch к конфигурации модели во всех её экземплярах.
+    /// Некорректный результат отклоняется, экземпляры остаются без изменений.
+    pub async fn patch_model_config(
+        &self,
+        model_name: &str,
+        patch: &serde_json::Value,
+    ) -> Result<ModelConfig, AppError> {
+        let mut instances = self.instances.write().await;
+        let current = instances.values()
+            .find(|instance| instance.model_name == model_name)
+            .map(|instance| instance.config.clone())
+            .ok_or_else(|| AppError::NotFound(format!("Model '{}' has no instances", model_name)))?;
+
+        let patched = current.merge_patch(patch)?;
+        self.persist_model_config(model_name, &patched)?;
+        let mut updated = 0;
+        for instance in instances.values_mut().filter(|i| i.model_name == model_name) {
+            instance.set_config(patched.clone());
+            updated += 1;
+        }
+
+        log::info!("Patched config for model {} ({} instances)", model_name, updated);
+        Ok(patched)
+    }
+
+    /// Заменяет конфигурацию модели во всех её экземплярах и сохраняет её.
+    /// Некорректная конфигурация отклоняется, экземпляры остаются без изменений.
+    pub async fn set_model_config(
+        &self,
+        model_name: &str,
+        config: ModelConfig,
+    ) -> Result<ModelConfig, AppError> {
+        config.validate()?;
+
+        let mut instances = self.instances.write().await;
+        if !instances.values().any(|instance| instance.model_name == model_name) {
+            return Err(AppError::NotFound(format!("Model '{}' has no instances", model_name)));
+        }
+        self.persist_model_config(model_name, &config)?;
+
+        let mut updated = 0;
+        for instance in instances.values_mut().filter(|i| i.model_name == model_name) {
+            instance.set_config(config.clone());
+            updated += 1;
+        }
+
+        log::info!("Replaced config for model {} ({} instances)", model_name, updated);
+        Ok(config)
     }
 
     /// Получает экземпляр с наименьшей нагрузкой
     pub async fn get_least_loaded_instance(&self, model_name: &str) -> Option<String> {
         let instances = self.instances.read().await;
         
+        // Экземпляры, ещё не прошедшие прогрев или остановленные, трафик не получают
         let model_instances: Vec<_> = instances.values()
             .filter(|instance| instance.model_name == model_name)
+            .filter(|instance| instance.status == InstanceStatus::Running)
             .collect();
         
         if model_instances.is_empty() {
@@ -153,10 +334,10 @@ impl InstanceManager {
         Some(least_loaded.id.clone())
     }
 
-    /// Масштабирует экземпляры
+    /// Масштабирует экземпляры. Увеличение сверх `max_instances_per_model`
+    /// или `max_instances` отклоняется с `AppError::InvalidInput`.
     pub async fn scale_instances(&self, model_name: &str, target_count: u32) -> Result<(), AppError> {
-        let instances = self.instances.read().await;
-        let current_count = instances.values()
+        let current_count = self.instances.read().await.values()
             .filter(|instance| instance.model_name == model_name)
             .count() as u32;
         
@@ -186,7 +367,8 @@ impl InstanceManager {
         metrics
     }
 
-    /// Проверяет здоровье всех экземпляров
+    /// Проверяет здоровье всех экземпляров. Экземпляры, не завершившие
+    /// прогрев, отмечаются как `starting` с `ready: false`.
     pub async fn health_check_all(&self) -> HashMap<String, InstanceHealth> {
         let instances = self.instances.read().await;
         let mut health = HashMap::new();
@@ -200,6 +382,7 @@ impl InstanceManager {
                         .duration_since(std::time::UNIX_EPOCH)
                         .unwrap()
                         .as_secs(),
+                    ready: false,
                 }
             });
             health.insert(id.clone(), instance_health);
@@ -210,6 +393,83 @@ impl InstanceManager {
 
     // Приватные методы
 
+    /// Сохраняет конфигурацию модели в `config_store` до её применения,
+    /// чтобы экземпляры не разошлись с тем, что записано на диск
+    fn persist_model_config(&self, model_name: &str, config: &ModelConfig) -> Result<(), AppError> {
+        let mut stored = self.stored_configs.write();
+        let previous = stored.insert(model_name.to_string(), config.clone());
+        let Some(path) = self.config.config_store.as_deref() else {
+            return Ok(());
+        };
+        if let Err(e) = write_model_configs(path, &stored) {
+            match previous {
+                Some(previous) => stored.insert(model_name.to_string(), previous),
+                None => stored.remove(model_name),
+            };
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Загружает модель экземпляра. Одновременно выполняется не больше
+    /// `max_concurrent_loads` загрузок, остальные ждут в очереди.
+    async fn load_instance(&self, instance: &ModelInstance) -> Result<(), AppError> {
+        let _permit = self.load_semaphore.acquire().await
+            .map_err(|_| AppError::Unknown("Model load semaphore closed".to_string()))?;
+        instance.initialize().await
+    }
+
+    /// Прогревает экземпляр в фоне и переводит его в `Running` после успеха.
+    /// Пока прогрев не удался, экземпляр остаётся `Starting`, а попытки
+    /// повторяются, пока экземпляр не удалён из менеджера. Прогрев занимает
+    /// слот загрузки, как и сама загрузка модели.
+    fn spawn_warm_up(&self, instance: ModelInstance) -> tokio::task::JoinHandle<()> {
+        let instances = self.instances.clone();
+        let load_semaphore = self.load_semaphore.clone();
+        let events = self.events.clone();
+        tokio::spawn(async move {
+            let mut delay = WARM_UP_RETRY_DELAY;
+            loop {
+                let warmed_up = match load_semaphore.acquire().await {
+                    Ok(_permit) => instance.warm_up().await,
+                    Err(_) => {
+                        log::warn!("Model load semaphore closed, warm-up of {} abandoned", instance.id);
+                        return;
+                    }
+                };
+                match warmed_up {
+                    Ok(()) => {
+                        let loaded = match instances.write().await.get_mut(&instance.id) {
+                            Some(entry) if entry.status == InstanceStatus::Starting => {
+                                entry.status = InstanceStatus::Running;
+                                true
+                            }
+                            _ => false,
+                        };
+                        if loaded {
+                            events.publish(
+                                EventKind::ModelLoaded,
+                                serde_json::json!({ "model": instance.model_name, "instance": instance.id }),
+                            );
+                        }
+                        log::info!("Model instance warmed up: {}", instance.id);
+                        return;
+                    }
+                    Err(e) => log::warn!(
+                        "Warm-up of instance {} failed: {}; retrying in {:?}",
+                        instance.id, e, delay
+                    ),
+                }
+
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(WARM_UP_MAX_RETRY_DELAY);
+                if !instances.read().await.contains_key(&instance.id) {
+                    return;
+                }
+            }
+        })
+    }
+
     async fn create_instance_pool(&self) -> Result<(), AppError> {
         log::info!("Creating instance pool");
         
@@ -221,70 +481,100 @@ impl InstanceManager {
         Ok(())
     }
 
+    /// Проверяет, что добавление `count` экземпляров модели не превысит
+    /// ограничения на модель и общее число экземпляров
+    fn check_capacity(
+        &self,
+        instances: &HashMap<String, ModelInstance>,
+        model_name: &str,
+        count: u32,
+    ) -> Result<(), AppError> {
+        let total = instances.len() as u32;
+        let per_model = instances.values()
+            .filter(|instance| instance.model_name == model_name)
+            .count() as u32;
+
+        if per_model.saturating_add(count) > self.config.max_instances_per_model {
+            return Err(AppError::InvalidInput(format!(
+                "Model {} would have {} instances, limit is {}",
+                model_name, per_model + count, self.config.max_instances_per_model
+            )));
+        }
+
+        if total.saturating_add(count) > self.config.max_instances {
+            return Err(AppError::InvalidInput(format!(
+                "Total instances would be {}, limit is {}",
+                total + count, self.config.max_instances
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Создает `count` экземпляров модели. Экземпляры добавляются в менеджер
+    /// только все вместе: при ошибке загрузки уже созданные останавливаются.
     async fn create_instances_for_model(&self, model_name: &str, count: u32) -> Result<(), AppError> {
         log::info!("Creating {} instances for model {}", count, model_name);
+
+        self.check_capacity(&*self.instances.read().await, model_name, count)?;
         
         // В реальной реализации здесь должна быть логика создания моделей
-        for i in 0..count {
-            let instance_id = format!("{}_{}", model_name, i);
-            
-            // Создаем заглушку экземпляра
+        let mut created: Vec<ModelInstance> = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let model: Arc<dyn ModelInterface + Send + Sync> = Arc::new(DummyModel::new());
+            let config = self.stored_configs.read()
+                .get(model_name)
+                .cloned()
+                .unwrap_or_else(|| default_model_config(model_name));
             let instance = ModelInstance {
-                id: instance_id.clone(),
+                id: self.generate_instance_id(model_name),
                 model_name: model_name.to_string(),
-                model: Arc::new(DummyModel::new()),
-                config: ModelConfig {
-                    model_path: Some(format!("/models/{}", model_name)),
-                    device: crate::core::model_interface::DeviceConfig {
-                        device_type: crate::core::model_interface::DeviceType::GPU,
-                        device_id: Some(0),
-                        memory_fraction: 0.8,
-                        allow_growth: true,
-                    },
-                    performance: crate::core::model_interface::PerformanceConfig {
-                        batch_size: 16,
-                        max_concurrent_requests: 32,
-                        timeout_seconds: 30,
-                        retry_attempts: 3,
-                        enable_caching: true,
-                        cache_size: 1024 * 1024 * 1024,
-                    },
-                    memory: crate::core::model_interface::MemoryConfig {
-                        max_memory_usage: 16384,
-                        memory_pool_size: 8192,
-                        enable_memory_optimization: true,
-                        garbage_collection_threshold: 0.8,
-                    },
-                    inference: crate::core::model_interface::InferenceConfig {
-                        default_temperature: 0.7,
-                        default_max_tokens: 100,
-                        default_top_p: 0.9,
-                        enable_sampling: true,
-                        enable_beam_search: false,
-                        beam_width: 5,
-                    },
-                    optimization: crate::core::model_interface::OptimizationConfig {
-                        enable_quantization: true,
-                        quantization_type: Some(crate::core::model_interface::Precision::FP16),
-                        enable_pruning: false,
-                        enable_distillation: false,
-                        enable_compilation: true,
-                        optimization_level: crate::core::model_interface::OptimizationLevel::Advanced,
-                    },
-                },
-                status: InstanceStatus::Running,
+                batcher: MicroBatcher::from_config(model.clone(), &config.performance),
+                model,
+                config,
+                status: InstanceStatus::Starting,
                 created_at: Instant::now(),
                 last_used: Instant::now(),
                 metrics: Arc::new(RwLock::new(InstanceMetrics::default())),
             };
             
-            let mut instances = self.instances.write().await;
-            instances.insert(instance_id, instance);
+            if let Err(e) = self.load_instance(&instance).await {
+                log::warn!(
+                    "Failed to create instance for model {}: {}; rolling back {} instances",
+                    model_name, e, created.len()
+                );
+                Self::rollback(&created).await;
+                return Err(e);
+            }
+            created.push(instance);
+        }
+
+        // Пока шла загрузка, лимиты могли быть заняты другими вызовами
+        let mut instances = self.instances.write().await;
+        if let Err(e) = self.check_capacity(&instances, model_name, count) {
+            drop(instances);
+            Self::rollback(&created).await;
+            return Err(e);
+        }
+        for instance in &created {
+            instances.insert(instance.id.clone(), instance.clone());
+        }
+        drop(instances);
+        for instance in created {
+            self.spawn_warm_up(instance);
         }
         
         Ok(())
     }
 
+    async fn rollback(created: &[ModelInstance]) {
+        for instance in created {
+            if let Err(e) = instance.shutdown().await {
+                log::warn!("Failed to shut down instance {} during rollback: {}", instance.id, e);
+            }
+        }
+    }
+
     async fn remove_instances_for_model(&self, model_name: &str, count: u32) -> Result<(), AppError> {
         log::info!("Removing {} instances for model {}", count, model_name);
         
@@ -322,7 +612,8 @@ impl InstanceManager {
             .unwrap()
             .as_millis();
         
-        format!("{}_{}", model_name, timestamp)
+        let suffix = uuid::Uuid::new_v4().simple().to_string();
+        format!("{}_{}_{}", model_name, timestamp, &suffix[..8])
     }
 
     async fn start_monitoring(&self) -> Result<(), AppError> {
@@ -342,6 +633,8 @@ pub struct ModelInstance {
     pub id: String,
     pub model_name: String,
     pub model: Arc<dyn ModelInterface + Send + Sync>,
+    /// Собирает одновременные запросы в пакеты; `None` - запросы идут по одному
+    pub batcher: Option<MicroBatcher>,
     pub config: ModelConfig,
     pub status: InstanceStatus,
     pub created_at: Instant,
@@ -350,6 +643,13 @@ pub struct ModelInstance {
 }
 
 impl ModelInstance {
+    /// Заменяет конфигурацию; батчер пересоздаётся по новым настройкам
+    /// производительности. Запросы, уже ожидающие в старом пакете, дорабатывают.
+    pub fn set_config(&mut self, config: ModelConfig) {
+        self.batcher = MicroBatcher::from_config(self.model.clone(), &config.performance);
+        self.config = config;
+    }
+
     /// Инициализирует экземпляр
     pub async fn initialize(&self) -> Result<(), AppError> {
         log::info!("Initializing model instance: {}", self.id);
@@ -357,14 +657,32 @@ impl ModelInstance {
         // Инициализируем модель
         self.model.initialize().await?;
         
-        // Обновляем статус
-        let mut status = self.status.clone();
-        status = InstanceStatus::Running;
-        
         log::info!("Model instance initialized: {}", self.id);
         Ok(())
     }
 
+    /// Прогревает модель коротким синтетическим запросом, чтобы первый
+    /// настоящий запрос не платил за холодный старт
+    pub async fn warm_up(&self) -> Result<(), AppError> {
+        let request = ModelRequest {
+            prompt: WARM_UP_PROMPT.to_string(),
+            max_tokens: Some(1),
+            temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop_sequences: None,
+            stream: None,
+            user_id: None,
+            session_id: None,
+            metadata: None,
+            required_features: vec![],
+        };
+        let deadline = Instant::now() + Duration::from_secs(self.config.performance.timeout_seconds);
+        with_deadline(self.model.process_request(request), deadline, None).await?;
+        Ok(())
+    }
+
     /// Останавливает экземпляр
     pub async fn shutdown(&self) -> Result<(), AppError> {
         log::info!("Shutting down model instance: {}", self.id);
@@ -378,25 +696,48 @@ impl ModelInstance {
 
     /// Обрабатывает запрос
     pub async fn process_request(&self, request: ModelRequest) -> Result<ModelResponse, AppError> {
-        let start_time = Instant::now();
-        
-        // Обновляем метрики
-        {
-            let mut metrics = self.metrics.write().await;
-            metrics.active_requests += 1;
-            metrics.total_requests += 1;
+        self.process_request_with_deadline(request, None).await
+    }
+
+    /// Обрабатывает запрос, прерывая его по истечении таймаута модели
+    /// или дедлайна клиента (что наступит раньше)
+    pub async fn process_request_with_deadline(
+        &self,
+        request: ModelRequest,
+        deadline: Option<Instant>,
+    ) -> Result<ModelResponse, AppError> {
+        // Проверяем поддержку требуемых возможностей до отправки в модель
+        if !request.required_features.is_empty() {
+            let info = self.model.get_model_info().await?;
+            request.check_feature_support(&info)?;
         }
+
+        let start_time = Instant::now();
         
-        // Обрабатываем запрос
-        let response = self.model.process_request(request).await?;
+        // Обновляем метрики; счётчик снимается и при отмене запроса
+        let active = ActiveRequestGuard::enter(self.metrics.clone(), start_time).await;
         
-        // Обновляем метрики
-        {
-            let mut metrics = self.metrics.write().await;
-            metrics.active_requests -= 1;
-            metrics.total_processing_time += start_time.elapsed().as_secs_f64();
-            metrics.average_response_time = metrics.total_processing_time / metrics.total_requests as f64;
-        }
+        // Обрабатываем запрос; таймаут ограничивает и ожидание в пакете
+        let model_timeout = Duration::from_secs(self.config.performance.timeout_seconds);
+        let response = async {
+            match &self.batcher {
+                Some(batcher) => batcher.submit(request).await,
+                None => self.model.process_request(request).await,
+            }
+        };
+        let result = with_deadline(
+            response,
+            start_time + model_timeout,
+            deadline,
+        )
+        .await
+        .map_err(|e| match e {
+            AppError::Timeout(msg) => AppError::Timeout(format!("instance {}: {}", self.id, msg)),
+            other => other,
+        });
+        drop(active);
+
+        let response = result?;
         
         // Обновляем время последнего использования
         let mut last_used = self.last_used;
@@ -405,6 +746,50 @@ impl ModelInstance {
         Ok(response)
     }
 
+    /// Потоковая обработка запроса: фрагменты передаются в `tx`. Таймаут
+    /// модели и дедлайн клиента ограничивают весь поток; по их истечении
+    /// последним элементом отправляется `AppError::Timeout`.
+    pub async fn stream_request_with_deadline(
+        &self,
+        request: ModelRequest,
+        deadline: Option<Instant>,
+        mut tx: futures::channel::mpsc::Sender<Result<TokenChunk, AppError>>,
+    ) {
+        use futures::{SinkExt, StreamExt};
+
+        let start_time = Instant::now();
+        let _active = ActiveRequestGuard::enter(self.metrics.clone(), start_time).await;
+
+        let model_timeout = Duration::from_secs(self.config.performance.timeout_seconds);
+        let streamed = with_deadline(
+            async {
+                if !request.required_features.is_empty() {
+                    let info = self.model.get_model_info().await?;
+                    request.check_feature_support(&info)?;
+                }
+                let mut stream = self.model.process_request_stream(request).await?;
+                while let Some(item) = stream.next().await {
+                    // Клиент отключился - дальше генерировать незачем
+                    if tx.send(item).await.is_err() {
+                        break;
+                    }
+                }
+                Ok(())
+            },
+            start_time + model_timeout,
+            deadline,
+        )
+        .await;
+
+        if let Err(e) = streamed {
+            let e = match e {
+                AppError::Timeout(msg) => AppError::Timeout(format!("instance {}: {}", self.id, msg)),
+                other => other,
+            };
+            let _ = tx.send(Err(e)).await;
+        }
+    }
+
     /// Получает информацию об экземпляре
     pub fn get_info(&self) -> InstanceInfo {
         InstanceInfo {
@@ -418,6 +803,19 @@ impl ModelInstance {
 
     /// Проверяет здоровье экземпляра
     pub async fn health_check(&self) -> Result<InstanceHealth, AppError> {
+        let last_check = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        if self.status == InstanceStatus::Starting {
+            return Ok(InstanceHealth {
+                status: "starting".to_string(),
+                message: "Warming up".to_string(),
+                last_check,
+                ready: false,
+            });
+        }
+
         let model_health = self.model.health_check().await?;
         
         let status = match model_health.status {
@@ -430,16 +828,104 @@ impl ModelInstance {
         Ok(InstanceHealth {
             status: status.to_string(),
             message: model_health.message,
-            last_check: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            last_check,
+            ready: self.status == InstanceStatus::Running,
         })
     }
 }
 
+/// Учитывает запрос в `active_requests` экземпляра, пока жив. Счётчик
+/// снимается при сбросе, в том числе когда вызывающий отменил future запроса.
+struct ActiveRequestGuard {
+    metrics: Arc<RwLock<InstanceMetrics>>,
+    start_time: Instant,
+}
+
+impl ActiveRequestGuard {
+    async fn enter(metrics: Arc<RwLock<InstanceMetrics>>, start_time: Instant) -> Self {
+        {
+            let mut metrics = metrics.write().await;
+            metrics.active_requests += 1;
+            metrics.total_requests += 1;
+        }
+        Self { metrics, start_time }
+    }
+
+    fn finish(metrics: &mut InstanceMetrics, elapsed: Duration) {
+        metrics.active_requests -= 1;
+        metrics.total_processing_time += elapsed.as_secs_f64();
+        metrics.average_response_time = metrics.total_processing_time / metrics.total_requests as f64;
+    }
+}
+
+impl Drop for ActiveRequestGuard {
+    fn drop(&mut self) {
+        let elapsed = self.start_time.elapsed();
+        match self.metrics.try_write() {
+            Ok(mut metrics) => Self::finish(&mut metrics, elapsed),
+            // Метрики сейчас читают - снимаем счётчик, как только блокировка освободится
+            Err(_) => {
+                let metrics = self.metrics.clone();
+                if let Ok(handle) = tokio::runtime::Handle::try_current() {
+                    handle.spawn(async move {
+                        Self::finish(&mut *metrics.write().await, elapsed);
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Читает сохранённые конфигурации моделей. Отсутствующий или повреждённый
+/// файл даёт пустой набор: модели стартуют с конфигурацией по умолчанию.
+fn read_model_configs(path: &std::path::Path) -> HashMap<String, ModelConfig> {
+    let data = match std::fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return HashMap::new(),
+        Err(e) => {
+            log::warn!("Failed to read model configs from {}: {}", path.display(), e);
+            return HashMap::new();
+        }
+    };
+    serde_json::from_str(&data).unwrap_or_else(|e| {
+        log::warn!("Ignoring corrupted model configs in {}: {}", path.display(), e);
+        HashMap::new()
+    })
+}
+
+fn write_model_configs(path: &std::path::Path, configs: &HashMap<String, ModelConfig>) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string_pretty(configs)?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Выполняет future до истечения таймаута модели или дедлайна клиента.
+/// Дедлайн клиента, наступающий раньше таймаута модели, имеет приоритет.
+pub async fn with_deadline<F, T>(
+    future: F,
+    model_deadline: Instant,
+    client_deadline: Option<Instant>,
+) -> Result<T, AppError>
+where
+    F: std::future::Future<Output = Result<T, AppError>>,
+{
+    let (effective, reason) = match client_deadline {
+        Some(client) if client < model_deadline => (client, "request deadline exceeded"),
+        _ => (model_deadline, "model timeout exceeded"),
+    };
+
+    match tokio::time::timeout_at(tokio::time::Instant::from_std(effective), future).await {
+        Ok(result) => result,
+        Err(_) => Err(AppError::Timeout(reason.to_string())),
+    }
+}
+
 /// Статус экземпляра
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum InstanceStatus {
     Starting,
     Running,
@@ -464,6 +950,9 @@ pub struct InstanceHealth {
     pub status: String,
     pub message: String,
     pub last_check: u64,
+    /// Экземпляр прогрет и принимает запросы
+    #[serde(default)]
+    pub ready: bool,
 }
 
 /// Конфигурация менеджера экземпляров
@@ -477,6 +966,16 @@ pub struct InstanceManagerConfig {
     pub health_check_interval: u64,
     pub instance_timeout: u64,
     pub initial_models: Vec<InitialModelConfig>,
+    /// Максимум одновременных загрузок моделей (создание, прогрев, автомасштабирование)
+    #[serde(default = "default_max_concurrent_loads")]
+    pub max_concurrent_loads: usize,
+    /// Файл, в котором сохраняются изменённые через API конфигурации моделей
+    #[serde(default)]
+    pub config_store: Option<std::path::PathBuf>,
+}
+
+fn default_max_concurrent_loads() -> usize {
+    2
 }
 
 /// Конфигурация начальной модели
@@ -502,15 +1001,61 @@ impl Default for InstanceManagerConfig {
                     count: 2,
                 }
             ],
+            max_concurrent_loads: default_max_concurrent_loads(),
+            config_store: None,
         }
     }
 }
 
+/// Конфигурация экземпляра модели по умолчанию
+pub(crate) fn default_model_config(model_name: &str) -> ModelConfig {
+    ModelConfig {
+        model_path: Some(format!("/models/{}", model_name)),
+        device: crate::core::model_interface::DeviceConfig {
+            device_type: crate::core::model_interface::DeviceType::GPU,
+            device_id: Some(0),
+            memory_fraction: 0.8,
+            allow_growth: true,
+        },
+        performance: crate::core::model_interface::PerformanceConfig {
+            batch_size: 16,
+            max_concurrent_requests: 32,
+            timeout_seconds: 30,
+            retry_attempts: 3,
+            enable_caching: true,
+            cache_size: 1024 * 1024 * 1024,
+            batch_window_ms: 0,
+        },
+        memory: crate::core::model_interface::MemoryConfig {
+            max_memory_usage: 16384,
+            memory_pool_size: 8192,
+            enable_memory_optimization: true,
+            garbage_collection_threshold: 0.8,
+        },
+        inference: crate::core::model_interface::InferenceConfig {
+            default_temperature: 0.7,
+            default_max_tokens: 100,
+            default_top_p: 0.9,
+            enable_sampling: true,
+            enable_beam_search: false,
+            beam_width: 5,
+        },
+        optimization: crate::core::model_interface::OptimizationConfig {
+            enable_quantization: true,
+            quantization_type: Some(crate::core::model_interface::Precision::FP16),
+            enable_pruning: false,
+            enable_distillation: false,
+            enable_compilation: true,
+            optimization_level: crate::core::model_interface::OptimizationLevel::Advanced,
+        },
+    }
+}
+
 /// Заглушка модели для тестирования
-struct DummyModel;
+pub(crate) struct DummyModel;
 
 impl DummyModel {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self
     }
 }
@@ -598,4 +1143,622 @@ impl ModelInterface for DummyModel {
             warning_count: 0,
         })
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_default_stream_yields_single_chunk() {
+        use futures::StreamExt;
+
+        let model = DummyModel::new();
+        let request = test_request();
+
+        let response = model.process_request(request.clone()).await.unwrap();
+        let chunks: Vec<_> = model
+            .process_request_stream(request)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(chunks.len(), 1);
+        let chunk = chunks.into_iter().next().unwrap().unwrap();
+        assert_eq!(chunk.index, 0);
+        assert_eq!(chunk.text, response.text);
+        assert_eq!(chunk.finish_reason, response.finish_reason);
+    }
+
+    /// Обёртка над `DummyModel` с управляемым поведением: задержкой ответа,
+    /// шлюзом, счётчиком одновременных загрузок и журналом размеров пакетов
+    #[derive(Default)]
+    struct ProbeModel {
+        delay: Option<Duration>,
+        gate: Option<Arc<Semaphore>>,
+        loads: Option<(Arc<std::sync::atomic::AtomicUsize>, Arc<std::sync::atomic::AtomicUsize>)>,
+        batch_sizes: Option<Arc<parking_lot::Mutex<Vec<usize>>>>,
+    }
+
+    impl ProbeModel {
+        /// Отвечает с заданной задержкой
+        fn with_delay(mut self, delay: Duration) -> Self {
+            self.delay = Some(delay);
+            self
+        }
+
+        /// Отвечает только после выдачи разрешения в `gate`
+        fn with_gate(mut self, gate: Arc<Semaphore>) -> Self {
+            self.gate = Some(gate);
+            self
+        }
+
+        /// Фиксирует текущее и пиковое число одновременных загрузок
+        fn with_load_probe(
+            mut self,
+            current: Arc<std::sync::atomic::AtomicUsize>,
+            peak: Arc<std::sync::atomic::AtomicUsize>,
+        ) -> Self {
+            self.loads = Some((current, peak));
+            self
+        }
+
+        /// Записывает размеры полученных пакетов
+        fn with_batch_sizes(mut self, batch_sizes: Arc<parking_lot::Mutex<Vec<usize>>>) -> Self {
+            self.batch_sizes = Some(batch_sizes);
+            self
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ModelInterface for ProbeModel {
+        async fn process_request(&self, request: ModelRequest) -> Result<ModelResponse, AppError> {
+            let _open = match &self.gate {
+                Some(gate) => Some(gate.acquire().await.unwrap()),
+                None => None,
+            };
+            if let Some(delay) = self.delay {
+                tokio::time::sleep(delay).await;
+            }
+            DummyModel::new().process_request(request).await
+        }
+
+        async fn process_batch(&self, requests: Vec<ModelRequest>) -> Vec<Result<ModelResponse, AppError>> {
+            if let Some(batch_sizes) = &self.batch_sizes {
+                batch_sizes.lock().push(requests.len());
+            }
+            futures::future::join_all(requests.into_iter().map(|request| self.process_request(request))).await
+        }
+
+        async fn get_model_info(&self) -> Result<ModelInfo, AppError> {
+            DummyModel::new().get_model_info().await
+        }
+
+        async fn update_config(&self, _config: ModelConfig) -> Result<(), AppError> {
+            Ok(())
+        }
+
+        async fn get_metrics(&self) -> Result<ModelMetrics, AppError> {
+            DummyModel::new().get_metrics().await
+        }
+
+        async fn initialize(&self) -> Result<(), AppError> {
+            if let Some((current, peak)) = &self.loads {
+                use std::sync::atomic::Ordering;
+                let now = current.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                current.fetch_sub(1, Ordering::SeqCst);
+            }
+            Ok(())
+        }
+
+        async fn shutdown(&self) -> Result<(), AppError> {
+            Ok(())
+        }
+
+        async fn health_check(&self) -> Result<ModelHealth, AppError> {
+            DummyModel::new().health_check().await
+        }
+    }
+
+    fn test_request() -> ModelRequest {
+        ModelRequest {
+            prompt: "hello".to_string(),
+            max_tokens: None,
+            temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop_sequences: None,
+            stream: None,
+            user_id: None,
+            session_id: None,
+            metadata: None,
+            required_features: vec![],
+        }
+    }
+
+    fn test_config(timeout_seconds: u64) -> ModelConfig {
+        let mut config = default_model_config("test");
+        config.performance.timeout_seconds = timeout_seconds;
+        config
+    }
+
+    fn manager() -> InstanceManager {
+        InstanceManager::new(InstanceManagerConfig {
+            initial_models: vec![],
+            ..InstanceManagerConfig::default()
+        })
+    }
+
+    fn capped_manager(max_instances: u32, max_instances_per_model: u32) -> InstanceManager {
+        InstanceManager::new(InstanceManagerConfig {
+            max_instances,
+            max_instances_per_model,
+            initial_models: vec![],
+            ..InstanceManagerConfig::default()
+        })
+    }
+
+    async fn count_for(manager: &InstanceManager, model_name: &str) -> usize {
+        manager.list_instances().await.iter().filter(|i| i.model_name == model_name).count()
+    }
+
+    #[tokio::test]
+    async fn test_gpu_config_falls_back_to_cpu_without_gpu() {
+        use crate::core::model_interface::{DeviceType, CPU_MEMORY_FRACTION};
+        use crate::platform::gpu::StubGpuControl;
+
+        let gpu_manager = Arc::new(GpuManager::with_control(Arc::new(StubGpuControl::new())));
+        assert!(!gpu_manager.is_available());
+        let manager = manager().with_gpu_manager(gpu_manager);
+        let config = test_config(30);
+        assert_eq!(config.device.device_type, DeviceType::GPU);
+
+        let instance_id = manager
+            .create_instance("llama".to_string(), Arc::new(DummyModel::new()), config)
+            .await
+            .unwrap();
+
+        let device = &manager.get_instance(&instance_id).await.unwrap().config.device;
+        assert_eq!(device.device_type, DeviceType::CPU);
+        assert_eq!(device.device_id, None);
+        assert_eq!(device.memory_fraction, CPU_MEMORY_FRACTION);
+    }
+
+    #[cfg(not(feature = "cuda"))]
+    #[tokio::test]
+    async fn test_gpu_config_kept_when_gpu_not_probed() {
+        use crate::core::model_interface::DeviceType;
+
+        let manager = manager().with_gpu_manager(Arc::new(GpuManager::new()));
+        let instance_id = manager
+            .create_instance("llama".to_string(), Arc::new(DummyModel::new()), test_config(30))
+            .await
+            .unwrap();
+
+        let device = &manager.get_instance(&instance_id).await.unwrap().config.device;
+        assert_eq!(device.device_type, DeviceType::GPU);
+    }
+
+    #[tokio::test]
+    async fn test_scale_within_limits() {
+        let manager = capped_manager(10, 4);
+        manager.scale_instances("llama", 3).await.unwrap();
+        assert_eq!(count_for(&manager, "llama").await, 3);
+
+        manager.scale_instances("llama", 4).await.unwrap();
+        assert_eq!(count_for(&manager, "llama").await, 4);
+
+        manager.scale_instances("llama", 1).await.unwrap();
+        assert_eq!(count_for(&manager, "llama").await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_model_config_persisted_and_reused() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = dir.path().join("models").join("configs.json");
+        let persistent = || InstanceManager::new(InstanceManagerConfig {
+            initial_models: vec![],
+            config_store: Some(store.clone()),
+            ..InstanceManagerConfig::default()
+        });
+
+        let manager = persistent();
+        manager.scale_instances("llama", 2).await.unwrap();
+        let mut config = manager.get_model_config("llama").await.unwrap();
+        config.performance.timeout_seconds = 45;
+        manager.set_model_config("llama", config).await.unwrap();
+        manager
+            .patch_model_config("llama", &serde_json::json!({ "inference": { "default_max_tokens": 64 } }))
+            .await
+            .unwrap();
+        for info in manager.list_instances().await {
+            let instance = manager.get_instance(&info.id).await.unwrap();
+            assert_eq!(instance.config.performance.timeout_seconds, 45);
+            assert_eq!(instance.config.inference.default_max_tokens, 64);
+        }
+
+        // После перезапуска новые экземпляры получают сохранённую конфигурацию
+        let restarted = persistent();
+        restarted.scale_instances("llama", 1).await.unwrap();
+        let config = restarted.get_model_config("llama").await.unwrap();
+        assert_eq!(config.performance.timeout_seconds, 45);
+        assert_eq!(config.inference.default_max_tokens, 64);
+
+        let mut invalid = config.clone();
+        invalid.inference.default_top_p = 1.5;
+        assert!(matches!(restarted.set_model_config("llama", invalid).await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(restarted.set_model_config("missing", config).await, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_scale_past_per_model_cap_fails() {
+        let manager = capped_manager(10, 4);
+        manager.scale_instances("llama", 2).await.unwrap();
+
+        let result = manager.scale_instances("llama", 5).await;
+        assert!(matches!(result, Err(AppError::InvalidInput(_))));
+        assert_eq!(count_for(&manager, "llama").await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_scale_past_global_cap_fails() {
+        let manager = capped_manager(5, 4);
+        manager.scale_instances("llama", 3).await.unwrap();
+
+        let result = manager.scale_instances("mistral", 3).await;
+        match result {
+            Err(AppError::InvalidInput(msg)) => assert!(msg.contains("Total instances"), "{}", msg),
+            other => panic!("expected InvalidInput, got {:?}", other),
+        }
+        assert_eq!(count_for(&manager, "mistral").await, 0);
+        assert_eq!(manager.list_instances().await.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_request_deadline_fires_before_model_timeout() {
+        let manager = manager();
+        let model = Arc::new(ProbeModel::default().with_delay(Duration::from_secs(5)));
+        let id = manager
+            .create_instance("slow".to_string(), model, test_config(30))
+            .await
+            .unwrap();
+
+        let started = Instant::now();
+        let deadline = Some(started + Duration::from_millis(50));
+        let result = manager
+            .process_request_with_deadline(&id, test_request(), deadline)
+            .await;
+
+        match result {
+            Err(AppError::Timeout(msg)) => assert!(msg.contains("request deadline")),
+            other => panic!("expected deadline timeout, got {:?}", other.map(|r| r.text)),
+        }
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_stream_request_bounded_by_deadline() {
+        use futures::StreamExt;
+
+        let manager = manager();
+        let slow_model = Arc::new(ProbeModel::default().with_delay(Duration::from_secs(5)));
+        let slow = manager
+            .create_instance("slow".to_string(), slow_model, test_config(30))
+            .await
+            .unwrap();
+        let fast = manager
+            .create_instance("fast".to_string(), Arc::new(DummyModel::new()), test_config(30))
+            .await
+            .unwrap();
+
+        let started = Instant::now();
+        let (tx, rx) = futures::channel::mpsc::channel(4);
+        let deadline = Some(started + Duration::from_millis(50));
+        manager.stream_request_with_deadline(&slow, test_request(), deadline, tx).await.unwrap();
+        let items: Vec<_> = rx.collect().await;
+        assert!(matches!(items.as_slice(), [Err(AppError::Timeout(msg))] if msg.contains("request deadline")));
+        assert!(started.elapsed() < Duration::from_secs(1));
+        let instance = manager.get_instance(&slow).await.unwrap();
+        assert_eq!(instance.metrics.read().await.active_requests, 0);
+
+        let (tx, rx) = futures::channel::mpsc::channel(4);
+        manager.stream_request_with_deadline(&fast, test_request(), None, tx).await.unwrap();
+        let items: Vec<_> = rx.collect().await;
+        assert!(matches!(items.as_slice(), [Ok(chunk)] if chunk.index == 0));
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_feature_rejected_before_dispatch() {
+        use crate::core::model_interface::ModelFeature;
+
+        let manager = manager();
+        let id = manager
+            .create_instance("text-only".to_string(), Arc::new(DummyModel::new()), test_config(30))
+            .await
+            .unwrap();
+
+        let mut request = test_request();
+        request.required_features = vec![ModelFeature::Embeddings];
+        match manager.process_request_with_deadline(&id, request, None).await {
+            Err(AppError::InvalidInput(msg)) => assert!(msg.contains("Embeddings"), "{}", msg),
+            other => panic!("expected InvalidInput, got {:?}", other),
+        }
+
+        let mut request = test_request();
+        request.required_features = vec![ModelFeature::TextGeneration];
+        assert!(manager.process_request_with_deadline(&id, request, None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_inference_defaults_read_and_update() {
+        let manager = manager();
+        manager
+            .create_instance("tuned".to_string(), Arc::new(DummyModel::new()), test_config(30))
+            .await
+            .unwrap();
+
+        let current = manager.get_inference_defaults("tuned").await.unwrap();
+        assert_eq!(current.max_tokens, 100);
+
+        let updated = InferenceDefaults { temperature: 1.2, max_tokens: 256, top_p: 0.5 };
+        manager.set_inference_defaults("tuned", updated.clone()).await.unwrap();
+        assert_eq!(manager.get_inference_defaults("tuned").await.unwrap(), updated);
+
+        assert!(manager.get_inference_defaults("missing").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_inference_defaults_range_rejection() {
+        let manager = manager();
+        manager
+            .create_instance("tuned".to_string(), Arc::new(DummyModel::new()), test_config(30))
+            .await
+            .unwrap();
+
+        let invalid = [
+            InferenceDefaults { temperature: 2.5, max_tokens: 10, top_p: 0.5 },
+            InferenceDefaults { temperature: 0.5, max_tokens: 10, top_p: 1.5 },
+            InferenceDefaults { temperature: 0.5, max_tokens: 0, top_p: 0.5 },
+        ];
+        for defaults in invalid {
+            let result = manager.set_inference_defaults("tuned", defaults).await;
+            assert!(matches!(result, Err(AppError::InvalidInput(_))));
+        }
+
+        // Исходные значения не изменились
+        assert_eq!(manager.get_inference_defaults("tuned").await.unwrap().max_tokens, 100);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_request_releases_active_request() {
+        let manager = manager();
+        let model = Arc::new(ProbeModel::default().with_delay(Duration::from_secs(30)));
+        let id = manager
+            .create_instance("hung".to_string(), model, test_config(60))
+            .await
+            .unwrap();
+
+        // Клиент отключился: future запроса сброшен до ответа модели
+        let cancelled = tokio::time::timeout(Duration::from_millis(50), manager.process_request(&id, test_request())).await;
+        assert!(cancelled.is_err());
+
+        let instance = manager.get_instance(&id).await.unwrap();
+        let metrics = instance.metrics.read().await;
+        assert_eq!(metrics.active_requests, 0);
+        assert_eq!(metrics.total_requests, 1);
+    }
+
+    #[tokio::test]
+    async fn test_model_timeout_releases_active_request() {
+        let manager = manager();
+        let model = Arc::new(ProbeModel::default().with_delay(Duration::from_secs(30)));
+        let id = manager
+            .create_instance("hung".to_string(), model, test_config(1))
+            .await
+            .unwrap();
+
+        let started = Instant::now();
+        match manager.process_request(&id, test_request()).await {
+            Err(AppError::Timeout(msg)) => assert!(msg.contains("model timeout"), "{}", msg),
+            other => panic!("expected model timeout, got {:?}", other.map(|r| r.text)),
+        }
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let instance = manager.get_instance(&id).await.unwrap();
+        let metrics = instance.metrics.read().await;
+        assert_eq!(metrics.active_requests, 0);
+        assert_eq!(metrics.total_requests, 1);
+    }
+
+    #[tokio::test]
+    async fn test_request_within_deadline_succeeds() {
+        let manager = manager();
+        let model = Arc::new(ProbeModel::default().with_delay(Duration::from_millis(10)));
+        let id = manager
+            .create_instance("fast".to_string(), model, test_config(30))
+            .await
+            .unwrap();
+
+        let deadline = Some(Instant::now() + Duration::from_secs(2));
+        let response = manager
+            .process_request_with_deadline(&id, test_request(), deadline)
+            .await
+            .unwrap();
+        assert!(response.text.contains("hello"));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_loads_limited_by_semaphore() {
+        let manager = InstanceManager::new(InstanceManagerConfig {
+            initial_models: vec![],
+            max_concurrent_loads: 2,
+            ..InstanceManagerConfig::default()
+        });
+        let current = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let peak = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+        let loads = (0..8).map(|_| {
+            let model = Arc::new(ProbeModel::default().with_load_probe(current.clone(), peak.clone()));
+            manager.create_instance("large".to_string(), model, test_config(30))
+        });
+        let results = futures::future::join_all(loads).await;
+
+        assert!(results.iter().all(|r| r.is_ok()));
+        assert_eq!(manager.list_instances().await.len(), 8);
+        let peak = peak.load(std::sync::atomic::Ordering::SeqCst);
+        assert!(peak <= 2, "observed {} concurrent loads", peak);
+        assert_eq!(peak, 2);
+    }
+
+    #[tokio::test]
+    async fn test_instance_starting_until_warm_up_completes() {
+        let manager = manager();
+        let gate = Arc::new(Semaphore::new(0));
+        let model = Arc::new(ProbeModel::default().with_gate(gate.clone()));
+
+        let instance_id = manager
+            .create_instance("llama".to_string(), model, test_config(30))
+            .await
+            .unwrap();
+
+        let instance = manager.get_instance(&instance_id).await.unwrap();
+        assert_eq!(instance.status, InstanceStatus::Starting);
+        let health = manager.health_check_all().await;
+        assert_eq!(health[&instance_id].status, "starting");
+        assert!(!health[&instance_id].ready);
+
+        gate.add_permits(1);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while manager.get_instance(&instance_id).await.unwrap().status != InstanceStatus::Running {
+            assert!(Instant::now() < deadline, "instance did not finish warm-up");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let health = manager.health_check_all().await;
+        assert_eq!(health[&instance_id].status, "healthy");
+        assert!(health[&instance_id].ready);
+    }
+
+    #[tokio::test]
+    async fn test_model_loaded_published_after_warm_up() {
+        let events = Arc::new(EventBus::new(16));
+        let manager = manager().with_event_bus(events.clone());
+        let gate = Arc::new(Semaphore::new(0));
+        let model = Arc::new(ProbeModel::default().with_gate(gate.clone()));
+
+        let instance_id = manager
+            .create_instance("llama".to_string(), model, test_config(30))
+            .await
+            .unwrap();
+        assert!(events.is_empty());
+
+        gate.add_permits(1);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while events.is_empty() {
+            assert!(Instant::now() < deadline, "ModelLoaded was not published");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let published = events.recent(None, 10);
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].kind, EventKind::ModelLoaded);
+        assert_eq!(published[0].data["model"], "llama");
+        assert_eq!(published[0].data["instance"], instance_id);
+    }
+
+    #[tokio::test]
+    async fn test_starting_instance_not_routed() {
+        let manager = manager();
+        let gate = Arc::new(Semaphore::new(0));
+        let model = Arc::new(ProbeModel::default().with_gate(gate.clone()));
+
+        let instance_id = manager
+            .create_instance("llama".to_string(), model, test_config(30))
+            .await
+            .unwrap();
+        assert_eq!(manager.get_least_loaded_instance("llama").await, None);
+
+        gate.add_permits(1);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while manager.get_instance(&instance_id).await.unwrap().status != InstanceStatus::Running {
+            assert!(Instant::now() < deadline, "instance did not finish warm-up");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(manager.get_least_loaded_instance("llama").await, Some(instance_id));
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_holds_load_slot() {
+        let manager = InstanceManager::new(InstanceManagerConfig {
+            initial_models: vec![],
+            max_concurrent_loads: 1,
+            ..InstanceManagerConfig::default()
+        });
+        let gate = Arc::new(Semaphore::new(0));
+        let model = Arc::new(ProbeModel::default().with_gate(gate.clone()));
+        manager
+            .create_instance("llama".to_string(), model, test_config(30))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // Слот занят прогревом первого экземпляра, вторая загрузка ждёт
+        let second = manager.create_instance("llama".to_string(), Arc::new(DummyModel::new()), test_config(30));
+        tokio::pin!(second);
+        assert!(tokio::time::timeout(Duration::from_millis(50), &mut second).await.is_err());
+
+        gate.add_permits(1);
+        assert!(tokio::time::timeout(Duration::from_secs(5), second).await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_grouped_into_batches() {
+        let manager = manager();
+        let batch_sizes = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let model = Arc::new(ProbeModel::default().with_batch_sizes(batch_sizes.clone()));
+        let mut config = test_config(30);
+        config.performance.batch_size = 4;
+        config.performance.batch_window_ms = 100;
+        let id = manager.create_instance("batched".to_string(), model, config).await.unwrap();
+
+        let requests = (0..6).map(|i| {
+            let mut request = test_request();
+            request.prompt = format!("prompt {}", i);
+            manager.process_request(&id, request)
+        });
+        let responses = futures::future::join_all(requests).await;
+
+        for (i, response) in responses.into_iter().enumerate() {
+            assert_eq!(response.unwrap().text, format!("Dummy response to: prompt {}", i));
+        }
+        // Первый пакет заполнен до batch_size, остаток ушёл по окончании окна
+        assert_eq!(*batch_sizes.lock(), vec![4, 2]);
+
+        let metrics = manager.get_instance(&id).await.unwrap().metrics.read().await.clone();
+        assert_eq!(metrics.total_requests, 6);
+        assert_eq!(metrics.active_requests, 0);
+    }
+
+    #[tokio::test]
+    async fn test_patched_batch_window_rebuilds_batcher() {
+        let manager = manager();
+        let mut config = test_config(30);
+        config.performance.batch_size = 4;
+        config.performance.batch_window_ms = 0;
+        let id = manager.create_instance("llama".to_string(), Arc::new(DummyModel::new()), config).await.unwrap();
+        assert!(manager.get_instance(&id).await.unwrap().batcher.is_none());
+
+        let patch = serde_json::json!({ "performance": { "batch_window_ms": 10 } });
+        manager.patch_model_config("llama", &patch).await.unwrap();
+        assert!(manager.get_instance(&id).await.unwrap().batcher.is_some());
+
+        let patch = serde_json::json!({ "performance": { "batch_window_ms": 0 } });
+        manager.patch_model_config("llama", &patch).await.unwrap();
+        let instance = manager.get_instance(&id).await.unwrap();
+        assert!(instance.batcher.is_none());
+        assert!(instance.process_request(test_request()).await.is_ok());
+    }
+}
@@ -3,14 +3,18 @@ pub mod scheduler;
 pub mod queue;
 pub mod cache;
 pub mod storage;
+pub mod storage_backend;
 pub mod instance;
+pub mod batcher;
 
 pub use worker::*;
 pub use scheduler::*;
 pub use queue::*;
 pub use cache::*;
 pub use storage::*;
+pub use storage_backend::*;
 pub use instance::*;
+pub use batcher::*;
 
 use std::error::Error;
 
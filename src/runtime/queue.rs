@@ -2,13 +2,32 @@ use serde::{Serialize, Deserialize};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use log::{info, warn, error};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 use chrono::{DateTime, Utc};
 use std::time::Duration;
 use cursor_codes::core::error::CursorError;
 use cursor_codes::monitoring::logger::LoggerSystem;
 use cursor_codes::monitoring::alert::AlertSystem;
 use cursor_codes::monitoring::metrics::MetricsSystem;
+use crate::workers::{Task, TaskPriority};
+
+/// Приоритеты в порядке выдачи из очереди
+const PRIORITY_ORDER: [TaskPriority; 4] = [
+    TaskPriority::Critical,
+    TaskPriority::High,
+    TaskPriority::Normal,
+    TaskPriority::Low,
+];
+
+fn priority_slot(priority: TaskPriority) -> usize {
+    match priority {
+        TaskPriority::Critical => 0,
+        TaskPriority::High => 1,
+        TaskPriority::Normal => 2,
+        TaskPriority::Low => 3,
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueueConfig {
@@ -54,6 +73,9 @@ pub struct QueueItem {
 pub struct QueueSystem {
     queues: Arc<Mutex<HashMap<String, QueueMetrics>>>,
     items: Arc<Mutex<HashMap<String, QueueItem>>>,
+    /// Очередь задач: по одной FIFO на приоритет, в порядке `PRIORITY_ORDER`
+    tasks: Arc<Mutex<[VecDeque<Task>; 4]>>,
+    accepting: AtomicBool,
 }
 
 impl QueueSystem {
@@ -61,9 +83,62 @@ impl QueueSystem {
         Self {
             queues: Arc::new(Mutex::new(HashMap::new())),
             items: Arc::new(Mutex::new(HashMap::new())),
+            tasks: Arc::new(Mutex::new(Default::default())),
+            accepting: AtomicBool::new(true),
         }
     }
 
+    pub async fn initialize(&self) -> Result<(), String> {
+        self.accepting.store(true, Ordering::SeqCst);
+        info!("Task queue initialized");
+        Ok(())
+    }
+
+    /// Перестаёт принимать новые задачи; уже поставленные остаются в очереди
+    pub async fn stop_accepting(&self) -> Result<(), String> {
+        self.accepting.store(false, Ordering::SeqCst);
+        info!("Task queue stopped accepting new tasks");
+        Ok(())
+    }
+
+    pub async fn shutdown(&self) -> Result<(), String> {
+        self.accepting.store(false, Ordering::SeqCst);
+        let mut tasks = self.tasks.lock().await;
+        let dropped: usize = tasks.iter().map(|q| q.len()).sum();
+        if dropped > 0 {
+            warn!("Dropping {} queued tasks on shutdown", dropped);
+        }
+        tasks.iter_mut().for_each(|q| q.clear());
+        Ok(())
+    }
+
+    /// Ставит задачу в очередь её приоритета
+    pub async fn enqueue(&self, task: Task) -> Result<(), String> {
+        if !self.accepting.load(Ordering::SeqCst) {
+            return Err("Queue is not accepting new tasks".to_string());
+        }
+
+        let mut tasks = self.tasks.lock().await;
+        info!("Enqueued task: {} (priority: {:?})", task.id, task.priority);
+        tasks[priority_slot(task.priority)].push_back(task);
+        Ok(())
+    }
+
+    /// Выдаёт самую приоритетную задачу; внутри приоритета — в порядке поступления
+    pub async fn dequeue(&self) -> Option<Task> {
+        let mut tasks = self.tasks.lock().await;
+        tasks.iter_mut().find_map(|queue| queue.pop_front())
+    }
+
+    /// Число ожидающих задач по приоритетам
+    pub async fn len_by_priority(&self) -> HashMap<TaskPriority, usize> {
+        let tasks = self.tasks.lock().await;
+        PRIORITY_ORDER
+            .iter()
+            .map(|&priority| (priority, tasks[priority_slot(priority)].len()))
+            .collect()
+    }
+
     pub async fn add_queue(&self, config: QueueConfig) -> Result<(), String> {
         let mut queues = self.queues.lock().await;
         
@@ -304,4 +379,80 @@ impl QueueSystem {
         info!("Updated queue configuration: {}", id);
         Ok(())
     }
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workers::TaskRequirements;
+
+    fn task(id: &str, priority: TaskPriority) -> Task {
+        Task {
+            id: id.to_string(),
+            name: id.to_string(),
+            priority,
+            requirements: TaskRequirements {
+                min_cpu: 0.0,
+                min_memory: 0.0,
+                min_gpu: 0.0,
+                capabilities: vec![],
+            },
+            data: serde_json::Value::Null,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dequeue_by_priority_then_fifo() {
+        let queue = QueueSystem::new();
+        let order = [
+            ("low-1", TaskPriority::Low),
+            ("normal-1", TaskPriority::Normal),
+            ("critical-1", TaskPriority::Critical),
+            ("high-1", TaskPriority::High),
+            ("normal-2", TaskPriority::Normal),
+            ("critical-2", TaskPriority::Critical),
+            ("low-2", TaskPriority::Low),
+            ("high-2", TaskPriority::High),
+        ];
+        for (id, priority) in order {
+            queue.enqueue(task(id, priority)).await.unwrap();
+        }
+
+        let counts = queue.len_by_priority().await;
+        assert_eq!(counts[&TaskPriority::Critical], 2);
+        assert_eq!(counts[&TaskPriority::Low], 2);
+
+        let mut dequeued = Vec::new();
+        while let Some(task) = queue.dequeue().await {
+            dequeued.push(task.id);
+        }
+        assert_eq!(
+            dequeued,
+            ["critical-1", "critical-2", "high-1", "high-2", "normal-1", "normal-2", "low-1", "low-2"]
+        );
+        assert!(queue.len_by_priority().await.values().all(|&n| n == 0));
+    }
+
+    #[tokio::test]
+    async fn test_late_critical_jumps_ahead() {
+        let queue = QueueSystem::new();
+        queue.enqueue(task("normal", TaskPriority::Normal)).await.unwrap();
+        queue.enqueue(task("low", TaskPriority::Low)).await.unwrap();
+        assert_eq!(queue.dequeue().await.unwrap().id, "normal");
+
+        queue.enqueue(task("critical", TaskPriority::Critical)).await.unwrap();
+        assert_eq!(queue.dequeue().await.unwrap().id, "critical");
+        assert_eq!(queue.dequeue().await.unwrap().id, "low");
+        assert!(queue.dequeue().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stop_accepting_rejects_new_tasks() {
+        let queue = QueueSystem::new();
+        queue.enqueue(task("queued", TaskPriority::High)).await.unwrap();
+        queue.stop_accepting().await.unwrap();
+
+        assert!(queue.enqueue(task("late", TaskPriority::Critical)).await.is_err());
+        assert_eq!(queue.dequeue().await.unwrap().id, "queued");
+    }
+}
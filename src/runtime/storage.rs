@@ -8,6 +8,7 @@ use cursor_codes::core::error::CursorError;
 use cursor_codes::monitoring::logger::LoggerSystem;
 use cursor_codes::monitoring::alert::AlertSystem;
 use cursor_codes::monitoring::metrics::MetricsSystem;
+use super::storage_backend::{StorageBackend, StorageBackendConfig};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageConfig {
@@ -53,16 +54,51 @@ pub struct File {
 pub struct StorageSystem {
     storages: Arc<Mutex<HashMap<String, StorageMetrics>>>,
     files: Arc<Mutex<HashMap<String, File>>>,
+    backend: Arc<dyn StorageBackend>,
 }
 
 impl StorageSystem {
     pub fn new() -> Self {
+        Self::with_backend(
+            StorageBackendConfig::default()
+                .build()
+                .expect("default local storage backend"),
+        )
+    }
+
+    /// Создание системы хранения с бэкендом, выбранным в конфигурации
+    pub fn from_config(config: &StorageBackendConfig) -> Result<Self, String> {
+        Ok(Self::with_backend(config.build()?))
+    }
+
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Self {
         Self {
             storages: Arc::new(Mutex::new(HashMap::new())),
             files: Arc::new(Mutex::new(HashMap::new())),
+            backend,
         }
     }
 
+    pub async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        self.backend.get(key).await
+    }
+
+    pub async fn put(&self, key: &str, data: Vec<u8>) -> Result<(), String> {
+        self.backend.put(key, data).await
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<(), String> {
+        self.backend.delete(key).await
+    }
+
+    pub async fn list(&self, prefix: &str) -> Result<Vec<String>, String> {
+        self.backend.list(prefix).await
+    }
+
+    fn object_key(storage_id: &str, name: &str) -> String {
+        format!("{}/{}", storage_id, name)
+    }
+
     pub async fn add_storage(&self, config: StorageConfig) -> Result<(), String> {
         let mut storages = self.storages.lock().await;
         
@@ -99,6 +135,13 @@ impl StorageSystem {
 
         // Remove associated files
         files.retain(|_, f| f.storage_id != id);
+
+        let prefix = format!("{}/", id);
+        for key in self.backend.list(&prefix).await? {
+            if let Err(e) = self.backend.delete(&key).await {
+                warn!("Failed to delete object '{}' of storage '{}': {}", key, id, e);
+            }
+        }
         
         storages.remove(id);
         info!("Removed storage: {}", id);
@@ -276,4 +319,104 @@ impl StorageSystem {
         info!("Updated storage configuration: {}", id);
         Ok(())
     }
-} 
\ No newline at end of file
+
+    /// Запись содержимого файла в бэкенд хранения
+    pub async fn write_file(
+        &self,
+        storage_id: &str,
+        name: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> Result<(), String> {
+        self.store_file(storage_id, name, data.len() as u64, content_type).await?;
+        self.backend.put(&Self::object_key(storage_id, name), data).await
+    }
+
+    /// Чтение содержимого файла из бэкенда хранения
+    pub async fn read_file(&self, storage_id: &str, name: &str) -> Result<Option<Vec<u8>>, String> {
+        self.backend.get(&Self::object_key(storage_id, name)).await
+    }
+
+    pub async fn delete_file(&self, storage_id: &str, name: &str) -> Result<(), String> {
+        let mut storages = self.storages.lock().await;
+        let mut files = self.files.lock().await;
+
+        self.backend.delete(&Self::object_key(storage_id, name)).await?;
+
+        let removed: Vec<File> = files
+            .values()
+            .filter(|f| f.storage_id == storage_id && f.name == name)
+            .cloned()
+            .collect();
+        for file in &removed {
+            files.remove(&file.id);
+        }
+
+        if let Some(storage) = storages.get_mut(storage_id) {
+            for file in &removed {
+                storage.stats.current_files = storage.stats.current_files.saturating_sub(1);
+                storage.stats.current_size = storage.stats.current_size.saturating_sub(file.size);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Список имён файлов хранилища в бэкенде
+    pub async fn list_stored(&self, storage_id: &str) -> Result<Vec<String>, String> {
+        let prefix = format!("{}/", storage_id);
+        Ok(self
+            .backend
+            .list(&prefix)
+            .await?
+            .into_iter()
+            .map(|key| key[prefix.len()..].to_string())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn round_trip(storage: &StorageSystem) {
+        let blob: Vec<u8> = (0..=255u8).cycle().take(4096).collect();
+        storage.put("blobs/model.bin", blob.clone()).await.unwrap();
+
+        assert_eq!(storage.get("blobs/model.bin").await.unwrap(), Some(blob));
+        assert_eq!(storage.list("blobs/").await.unwrap(), vec!["blobs/model.bin".to_string()]);
+
+        storage.delete("blobs/model.bin").await.unwrap();
+        assert_eq!(storage.get("blobs/model.bin").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_memory() {
+        let storage = StorageSystem::from_config(&StorageBackendConfig::Memory).unwrap();
+        round_trip(&storage).await;
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_filesystem() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = StorageBackendConfig::Local { root: dir.path().to_path_buf() };
+        round_trip(&StorageSystem::from_config(&config).unwrap()).await;
+    }
+
+    #[tokio::test]
+    async fn test_filesystem_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = StorageBackendConfig::Local { root: dir.path().to_path_buf() };
+
+        {
+            let storage = StorageSystem::from_config(&config).unwrap();
+            storage.put("state/checkpoint", b"epoch-7".to_vec()).await.unwrap();
+        }
+
+        let restarted = StorageSystem::from_config(&config).unwrap();
+        assert_eq!(
+            restarted.get("state/checkpoint").await.unwrap(),
+            Some(b"epoch-7".to_vec())
+        );
+    }
+}
@@ -0,0 +1,601 @@
+use serde::{Serialize, Deserialize};
+use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use async_trait::async_trait;
+use log::{info, warn};
+use chrono::Utc;
+use ring::{digest, hmac};
+
+/// Бэкенд хранения объектов для `StorageSystem`
+#[async_trait]
+pub trait StorageBackend: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String>;
+    async fn put(&self, key: &str, data: Vec<u8>) -> Result<(), String>;
+    async fn delete(&self, key: &str) -> Result<(), String>;
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, String>;
+}
+
+/// Выбор бэкенда хранения в конфигурации
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StorageBackendConfig {
+    /// Хранение в памяти процесса; данные теряются при перезапуске
+    Memory,
+    Local {
+        root: PathBuf,
+    },
+    S3 {
+        endpoint: String,
+        bucket: String,
+        region: String,
+        access_key: String,
+        secret_key: String,
+    },
+}
+
+impl Default for StorageBackendConfig {
+    fn default() -> Self {
+        StorageBackendConfig::Local {
+            root: PathBuf::from("./storage"),
+        }
+    }
+}
+
+impl StorageBackendConfig {
+    pub fn build(&self) -> Result<Arc<dyn StorageBackend>, String> {
+        match self {
+            StorageBackendConfig::Memory => {
+                info!("Using in-memory storage backend");
+                Ok(Arc::new(MemoryStorageBackend::default()))
+            }
+            StorageBackendConfig::Local { root } => {
+                info!("Using local storage backend at {}", root.display());
+                Ok(Arc::new(LocalStorageBackend::new(root.clone())))
+            }
+            StorageBackendConfig::S3 { endpoint, bucket, region, access_key, secret_key } => {
+                info!("Using S3 storage backend: {}/{}", endpoint, bucket);
+                let client = HttpObjectStoreClient::new(
+                    endpoint.clone(),
+                    bucket.clone(),
+                    region.clone(),
+                    access_key.clone(),
+                    secret_key.clone(),
+                )?;
+                Ok(Arc::new(S3StorageBackend::new(Arc::new(client))))
+            }
+        }
+    }
+}
+
+fn validate_key(key: &str) -> Result<(), String> {
+    if key.is_empty() {
+        return Err("Storage key must not be empty".to_string());
+    }
+    if key.starts_with('/') || key.split('/').any(|part| part == ".." || part == ".") {
+        return Err(format!("Invalid storage key: {}", key));
+    }
+    Ok(())
+}
+
+const TEMP_SUFFIX: &str = ".tmp";
+
+/// Временный файл для атомарной записи: скрытый, в том же каталоге
+fn temp_path_for(path: &Path) -> PathBuf {
+    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    path.with_file_name(format!(".{}.{}{}", name, &suffix[..8], TEMP_SUFFIX))
+}
+
+fn is_temp_file(path: &Path) -> bool {
+    path.file_name()
+        .map(|n| n.to_string_lossy())
+        .map_or(false, |n| n.starts_with('.') && n.ends_with(TEMP_SUFFIX))
+}
+
+async fn write_and_sync(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut file = tokio::fs::File::create(path).await?;
+    file.write_all(data).await?;
+    file.sync_all().await
+}
+
+/// Хранение объектов в памяти процесса
+#[derive(Default)]
+pub struct MemoryStorageBackend {
+    objects: tokio::sync::RwLock<std::collections::BTreeMap<String, Vec<u8>>>,
+}
+
+#[async_trait]
+impl StorageBackend for MemoryStorageBackend {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        validate_key(key)?;
+        Ok(self.objects.read().await.get(key).cloned())
+    }
+
+    async fn put(&self, key: &str, data: Vec<u8>) -> Result<(), String> {
+        validate_key(key)?;
+        self.objects.write().await.insert(key.to_string(), data);
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        validate_key(key)?;
+        self.objects.write().await.remove(key);
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, String> {
+        Ok(self
+            .objects
+            .read()
+            .await
+            .keys()
+            .filter(|k| k.starts_with(prefix))
+            .cloned()
+            .collect())
+    }
+}
+
+/// Хранение объектов в локальной файловой системе
+pub struct LocalStorageBackend {
+    root: PathBuf,
+}
+
+impl LocalStorageBackend {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn path_for(&self, key: &str) -> Result<PathBuf, String> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+
+    async fn collect_keys(&self, dir: &Path, keys: &mut Vec<String>) -> Result<(), String> {
+        let mut stack = vec![dir.to_path_buf()];
+
+        while let Some(current) = stack.pop() {
+            let mut entries = match tokio::fs::read_dir(&current).await {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(format!("Failed to read {}: {}", current.display(), e)),
+            };
+
+            while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+                let path = entry.path();
+                let file_type = entry.file_type().await.map_err(|e| e.to_string())?;
+                if file_type.is_dir() {
+                    stack.push(path);
+                } else if is_temp_file(&path) {
+                    continue;
+                } else if let Ok(relative) = path.strip_prefix(&self.root) {
+                    keys.push(relative.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StorageBackend for LocalStorageBackend {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        let path = self.path_for(key)?;
+        match tokio::fs::read(&path).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("Failed to read '{}': {}", key, e)),
+        }
+    }
+
+    async fn put(&self, key: &str, data: Vec<u8>) -> Result<(), String> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+
+        // Пишем во временный файл рядом с целевым и переименовываем:
+        // при сбое посреди записи старое содержимое остаётся целым
+        let tmp_path = temp_path_for(&path);
+        if let Err(e) = write_and_sync(&tmp_path, &data).await {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(format!("Failed to write '{}': {}", key, e));
+        }
+        tokio::fs::rename(&tmp_path, &path)
+            .await
+            .map_err(|e| format!("Failed to write '{}': {}", key, e))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        let path = self.path_for(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(_) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("Failed to delete '{}': {}", key, e)),
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, String> {
+        let mut keys = Vec::new();
+        self.collect_keys(&self.root, &mut keys).await?;
+        keys.retain(|k| k.starts_with(prefix));
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+/// Низкоуровневый клиент S3-совместимого хранилища
+#[async_trait]
+pub trait ObjectStoreClient: Send + Sync {
+    async fn get_object(&self, key: &str) -> Result<Option<Vec<u8>>, String>;
+    async fn put_object(&self, key: &str, data: Vec<u8>) -> Result<(), String>;
+    async fn delete_object(&self, key: &str) -> Result<(), String>;
+    async fn list_objects(&self, prefix: &str) -> Result<Vec<String>, String>;
+}
+
+/// Хранение объектов в S3-совместимом хранилище
+pub struct S3StorageBackend {
+    client: Arc<dyn ObjectStoreClient>,
+}
+
+impl S3StorageBackend {
+    pub fn new(client: Arc<dyn ObjectStoreClient>) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl StorageBackend for S3StorageBackend {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        validate_key(key)?;
+        self.client.get_object(key).await
+    }
+
+    async fn put(&self, key: &str, data: Vec<u8>) -> Result<(), String> {
+        validate_key(key)?;
+        self.client.put_object(key, data).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        validate_key(key)?;
+        self.client.delete_object(key).await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, String> {
+        let mut keys = self.client.list_objects(prefix).await?;
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+/// HTTP-клиент S3 (path-style адресация, подпись AWS SigV4)
+pub struct HttpObjectStoreClient {
+    client: reqwest::Client,
+    endpoint: String,
+    bucket: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+}
+
+impl HttpObjectStoreClient {
+    pub fn new(
+        endpoint: String,
+        bucket: String,
+        region: String,
+        access_key: String,
+        secret_key: String,
+    ) -> Result<Self, String> {
+        let parsed = url::Url::parse(&endpoint).map_err(|e| format!("Invalid S3 endpoint: {}", e))?;
+        if parsed.host_str().is_none() {
+            return Err(format!("Invalid S3 endpoint: {}", endpoint));
+        }
+
+        Ok(Self {
+            client: reqwest::Client::new(),
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            bucket,
+            region,
+            access_key,
+            secret_key,
+        })
+    }
+
+    fn host(&self) -> String {
+        let parsed = url::Url::parse(&self.endpoint).expect("endpoint validated in new()");
+        match parsed.port() {
+            Some(port) => format!("{}:{}", parsed.host_str().unwrap_or_default(), port),
+            None => parsed.host_str().unwrap_or_default().to_string(),
+        }
+    }
+
+    fn signed_request(
+        &self,
+        method: reqwest::Method,
+        key: &str,
+        query: &[(&str, &str)],
+        body: &[u8],
+    ) -> reqwest::RequestBuilder {
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+
+        let canonical_uri = if key.is_empty() {
+            format!("/{}", self.bucket)
+        } else {
+            format!("/{}/{}", self.bucket, uri_encode(key, false))
+        };
+
+        let mut sorted_query: Vec<_> = query.to_vec();
+        sorted_query.sort();
+        let canonical_query = sorted_query
+            .iter()
+            .map(|(k, v)| format!("{}={}", uri_encode(k, true), uri_encode(v, true)))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let payload_hash = hex::encode(digest::digest(&digest::SHA256, body));
+        let host = self.host();
+        let canonical_headers = format!(
+            "host:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n",
+            host, payload_hash, amz_date
+        );
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            method.as_str(), canonical_uri, canonical_query, canonical_headers, signed_headers, payload_hash
+        );
+
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(digest::digest(&digest::SHA256, canonical_request.as_bytes()))
+        );
+
+        let sign = |key: &[u8], msg: &str| -> Vec<u8> {
+            hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key), msg.as_bytes())
+                .as_ref()
+                .to_vec()
+        };
+        let k_date = sign(format!("AWS4{}", self.secret_key).as_bytes(), &date);
+        let k_region = sign(&k_date, &self.region);
+        let k_service = sign(&k_region, "s3");
+        let k_signing = sign(&k_service, "aws4_request");
+        let signature = hex::encode(sign(&k_signing, &string_to_sign));
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key, scope, signed_headers, signature
+        );
+
+        let mut url = format!("{}{}", self.endpoint, canonical_uri);
+        if !canonical_query.is_empty() {
+            url = format!("{}?{}", url, canonical_query);
+        }
+
+        self.client
+            .request(method, url)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("Authorization", authorization)
+    }
+}
+
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[async_trait]
+impl ObjectStoreClient for HttpObjectStoreClient {
+    async fn get_object(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        let response = self
+            .signed_request(reqwest::Method::GET, key, &[], b"")
+            .send()
+            .await
+            .map_err(|e| format!("S3 GET '{}' failed: {}", key, e))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(format!("S3 GET '{}' returned {}", key, response.status()));
+        }
+
+        let bytes = response.bytes().await.map_err(|e| e.to_string())?;
+        Ok(Some(bytes.to_vec()))
+    }
+
+    async fn put_object(&self, key: &str, data: Vec<u8>) -> Result<(), String> {
+        let response = self
+            .signed_request(reqwest::Method::PUT, key, &[], &data)
+            .body(data)
+            .send()
+            .await
+            .map_err(|e| format!("S3 PUT '{}' failed: {}", key, e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("S3 PUT '{}' returned {}", key, response.status()));
+        }
+        Ok(())
+    }
+
+    async fn delete_object(&self, key: &str) -> Result<(), String> {
+        let response = self
+            .signed_request(reqwest::Method::DELETE, key, &[], b"")
+            .send()
+            .await
+            .map_err(|e| format!("S3 DELETE '{}' failed: {}", key, e))?;
+
+        if !response.status().is_success() && response.status() != reqwest::StatusCode::NOT_FOUND {
+            return Err(format!("S3 DELETE '{}' returned {}", key, response.status()));
+        }
+        Ok(())
+    }
+
+    async fn list_objects(&self, prefix: &str) -> Result<Vec<String>, String> {
+        let mut keys = Vec::new();
+        let mut continuation: Option<String> = None;
+
+        loop {
+            let mut query = vec![("list-type", "2"), ("prefix", prefix)];
+            if let Some(token) = continuation.as_deref() {
+                query.push(("continuation-token", token));
+            }
+
+            let response = self
+                .signed_request(reqwest::Method::GET, "", &query, b"")
+                .send()
+                .await
+                .map_err(|e| format!("S3 LIST '{}' failed: {}", prefix, e))?;
+
+            if !response.status().is_success() {
+                return Err(format!("S3 LIST '{}' returned {}", prefix, response.status()));
+            }
+
+            let body = response.text().await.map_err(|e| e.to_string())?;
+            keys.extend(xml_values(&body, "Key"));
+
+            continuation = xml_values(&body, "NextContinuationToken").into_iter().next();
+            if continuation.is_none() {
+                break;
+            }
+        }
+
+        Ok(keys)
+    }
+}
+
+fn xml_values(body: &str, tag: &str) -> Vec<String> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let mut values = Vec::new();
+    let mut rest = body;
+
+    while let Some(start) = rest.find(&open) {
+        let after = &rest[start + open.len()..];
+        match after.find(&close) {
+            Some(end) => {
+                values.push(after[..end].to_string());
+                rest = &after[end + close.len()..];
+            }
+            None => {
+                warn!("Malformed S3 list response: unterminated <{}>", tag);
+                break;
+            }
+        }
+    }
+
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tokio::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockObjectStore {
+        objects: Mutex<HashMap<String, Vec<u8>>>,
+    }
+
+    #[async_trait]
+    impl ObjectStoreClient for MockObjectStore {
+        async fn get_object(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+            Ok(self.objects.lock().await.get(key).cloned())
+        }
+
+        async fn put_object(&self, key: &str, data: Vec<u8>) -> Result<(), String> {
+            self.objects.lock().await.insert(key.to_string(), data);
+            Ok(())
+        }
+
+        async fn delete_object(&self, key: &str) -> Result<(), String> {
+            self.objects.lock().await.remove(key);
+            Ok(())
+        }
+
+        async fn list_objects(&self, prefix: &str) -> Result<Vec<String>, String> {
+            Ok(self
+                .objects
+                .lock()
+                .await
+                .keys()
+                .filter(|k| k.starts_with(prefix))
+                .cloned()
+                .collect())
+        }
+    }
+
+    async fn exercise_backend(backend: &dyn StorageBackend) {
+        backend.put("models/a.bin", b"alpha".to_vec()).await.unwrap();
+        backend.put("models/b.bin", b"beta".to_vec()).await.unwrap();
+        backend.put("seeds/c.bin", b"gamma".to_vec()).await.unwrap();
+
+        assert_eq!(backend.get("models/a.bin").await.unwrap(), Some(b"alpha".to_vec()));
+        assert_eq!(
+            backend.list("models/").await.unwrap(),
+            vec!["models/a.bin".to_string(), "models/b.bin".to_string()]
+        );
+
+        backend.delete("models/a.bin").await.unwrap();
+        assert_eq!(backend.get("models/a.bin").await.unwrap(), None);
+        assert_eq!(backend.list("models/").await.unwrap(), vec!["models/b.bin".to_string()]);
+
+        assert!(backend.put("../escape", vec![]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_local_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LocalStorageBackend::new(dir.path().to_path_buf());
+        exercise_backend(&backend).await;
+    }
+
+    #[tokio::test]
+    async fn test_memory_backend() {
+        exercise_backend(&MemoryStorageBackend::default()).await;
+    }
+
+    #[tokio::test]
+    async fn test_local_put_leaves_no_temp_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LocalStorageBackend::new(dir.path().to_path_buf());
+        backend.put("models/a.bin", b"old".to_vec()).await.unwrap();
+        backend.put("models/a.bin", b"new".to_vec()).await.unwrap();
+
+        // Недописанный временный файл не виден как объект
+        std::fs::write(dir.path().join("models/.a.bin.deadbeef.tmp"), b"partial").unwrap();
+
+        assert_eq!(backend.get("models/a.bin").await.unwrap(), Some(b"new".to_vec()));
+        assert_eq!(backend.list("").await.unwrap(), vec!["models/a.bin".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_s3_backend_with_mock_store() {
+        let backend = S3StorageBackend::new(Arc::new(MockObjectStore::default()));
+        exercise_backend(&backend).await;
+    }
+
+    #[test]
+    fn test_xml_values() {
+        let body = "<ListBucketResult><Contents><Key>a</Key></Contents><Contents><Key>b/c</Key></Contents></ListBucketResult>";
+        assert_eq!(xml_values(body, "Key"), vec!["a".to_string(), "b/c".to_string()]);
+    }
+}
@@ -185,7 +185,19 @@ impl WorkerSystem {
         Ok(())
     }
 
+    /// Выполняет задачу; записи лога помечаются воркером, которому она назначена
     pub async fn process_task(&self, task_id: &str) -> Result<(), String> {
+        let worker_id = self
+            .tasks
+            .lock()
+            .await
+            .get(task_id)
+            .map(|task| task.worker_id.clone())
+            .ok_or_else(|| format!("Task '{}' not found", task_id))?;
+        crate::monitoring::logger::with_worker_id(worker_id, self.run_task(task_id)).await
+    }
+
+    async fn run_task(&self, task_id: &str) -> Result<(), String> {
         let mut workers = self.workers.lock().await;
         let mut tasks = self.tasks.lock().await;
         
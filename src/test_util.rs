@@ -0,0 +1,27 @@
+//! Общие тестовые заглушки
+
+use crate::admin::maintenance::Clock;
+use chrono::{DateTime, Utc};
+
+/// Часы, которые двигает сам тест
+pub struct ManualClock(parking_lot::Mutex<DateTime<Utc>>);
+
+impl ManualClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self(parking_lot::Mutex::new(now))
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.0.lock() = now;
+    }
+
+    pub fn advance(&self, by: chrono::Duration) {
+        *self.0.lock() += by;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.0.lock()
+    }
+}
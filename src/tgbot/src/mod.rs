@@ -16,15 +16,17 @@ use teloxide::{
     prelude::*,
     types::{InlineKeyboardButton, InlineKeyboardMarkup, Message},
     utils::command::BotCommands,
+    ApiError, RequestError,
 };
 use serde::{Serialize, Deserialize};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use log::{info, warn, error};
 use std::error::Error;
+use std::time::Duration;
 
 use crate::{
-    workers::WorkerManager,
+    workers::{WorkerManager, WorkerStats},
     vm::VMManager,
     reward_system::RewardSystem,
 };
@@ -52,6 +54,13 @@ pub struct BotConfig {
     pub admin_chat_id: i64,
     pub allowed_users: Vec<i64>,
     pub vm_manager: Box<dyn VmManager>,
+    /// Попыток переподключения к Telegram подряд, после чего бот останавливается
+    #[serde(default = "default_max_reconnect_attempts")]
+    pub max_reconnect_attempts: u32,
+}
+
+fn default_max_reconnect_attempts() -> u32 {
+    10
 }
 
 impl BotConfig {
@@ -61,14 +70,70 @@ impl BotConfig {
             admin_chat_id: 0,
             allowed_users: Vec::new(),
             vm_manager,
+            max_reconnect_attempts: default_max_reconnect_attempts(),
         }
     }
+
+    /// Пользователь может выполнять управляющие команды
+    pub fn is_authorized(&self, user_id: i64) -> bool {
+        is_authorized_user(self.admin_chat_id, &self.allowed_users, user_id)
+    }
+}
+
+impl Command {
+    /// Команды, доступные любому пользователю
+    pub fn is_public(&self) -> bool {
+        matches!(self, Command::Start | Command::Help)
+    }
+}
+
+/// Администратор (если задан) и пользователи из `allowed_users`
+fn is_authorized_user(admin_chat_id: i64, allowed_users: &[i64], user_id: i64) -> bool {
+    (admin_chat_id != 0 && user_id == admin_chat_id) || allowed_users.contains(&user_id)
+}
+
+/// Публичные команды доступны всем, остальные - только авторизованным пользователям
+fn command_permitted(cmd: &Command, user_id: Option<i64>, is_authorized: impl Fn(i64) -> bool) -> bool {
+    cmd.is_public() || user_id.map_or(false, is_authorized)
 }
 
 pub async fn run_bot(config: BotConfig) {
+/// Начальная задержка переподключения; удваивается с каждой попыткой
+const RECONNECT_BASE_DELAY: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Что делать после ошибки связи с Telegram
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReconnectDecision {
+    /// Переподключиться через указанное время
+    Retry(Duration),
+    /// Ошибка не устранится повтором (неверный токен) или попытки исчерпаны
+    Fatal,
+}
+
+/// Решает, переподключаться ли после ошибки; `attempt` считается с нуля
+pub fn reconnect_decision(error: &RequestError, attempt: u32, max_attempts: u32) -> ReconnectDecision {
+    if attempt >= max_attempts {
+        return ReconnectDecision::Fatal;
+    }
+
+    match error {
+        RequestError::Api(ApiError::InvalidToken) => ReconnectDecision::Fatal,
+        RequestError::RetryAfter(seconds) => ReconnectDecision::Retry(seconds.duration()),
+        _ => ReconnectDecision::Retry(backoff_delay(attempt)),
+    }
+}
+
+/// Задержка перед попыткой `attempt` (с нуля): удваивается до `RECONNECT_MAX_DELAY`
+pub fn backoff_delay(attempt: u32) -> Duration {
+    RECONNECT_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(RECONNECT_MAX_DELAY)
+}
+
 pub struct MiningBot {
     bot: Bot,
-    config: BotConfig,
+    config: Arc<BotConfig>,
     worker_manager: Arc<WorkerManager>,
     vm_manager: Arc<Mutex<VMManager>>,
     reward_system: Arc<RewardSystem>,
@@ -83,27 +148,98 @@ impl MiningBot {
     ) -> Self {
         Self {
             bot: Bot::new(&config.token),
-            config,
+            config: Arc::new(config),
             worker_manager,
             vm_manager,
             reward_system,
         }
     }
 
-    pub async fn run(&self) {
-        let handler = Update::filter_message()
-            .filter_command::<Command>()
-            .endpoint(answer);
+    /// Обрабатывает обновления, переподключаясь при сетевых ошибках.
+    /// Возвращает ошибку, только если токен отклонён или попытки исчерпаны.
+    pub async fn run(&self) -> Result<(), RequestError> {
+        let mut attempt = 0;
+        // Перезапуски диспетчера подряд; сбрасываются, если он проработал дольше
+        // максимальной задержки
+        let mut restarts = 0;
+        loop {
+            // Проверяем связь и токен перед каждым запуском long polling
+            if let Err(e) = self.bot.get_me().await {
+                match reconnect_decision(&e, attempt, self.config.max_reconnect_attempts) {
+                    ReconnectDecision::Retry(delay) => {
+                        attempt += 1;
+                        warn!(
+                            "Telegram connection failed ({}), reconnect attempt {}/{} in {:?}",
+                            e, attempt, self.config.max_reconnect_attempts, delay
+                        );
+                        tokio::time::sleep(delay).await;
+                        continue;
+                    }
+                    ReconnectDecision::Fatal => {
+                        error!("Telegram bot stopped: {}", e);
+                        return Err(e);
+                    }
+                }
+            }
+            attempt = 0;
+
+            let handler = Update::filter_message()
+                .filter_command::<Command>()
+                .endpoint(answer);
+
+            let mut dispatcher = Dispatcher::builder(self.bot.clone(), handler)
+                .dependencies(dptree::deps![
+                    self.config.clone(),
+                    self.worker_manager.clone(),
+                    self.reward_system.clone()
+                ])
+                .build();
+
+            let started = tokio::time::Instant::now();
+            tokio::select! {
+                _ = dispatcher.dispatch() => {}
+                _ = tokio::signal::ctrl_c() => {
+                    info!("Telegram bot shutting down");
+                    return Ok(());
+                }
+            }
 
-        Dispatcher::builder(self.bot.clone(), handler)
-            .enable_ctrlc_handler()
-            .build()
-            .dispatch()
-            .await;
+            if started.elapsed() >= RECONNECT_MAX_DELAY {
+                restarts = 0;
+            }
+            let delay = backoff_delay(restarts);
+            restarts += 1;
+            warn!("Telegram dispatcher stopped unexpectedly, restarting in {:?}", delay);
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = tokio::signal::ctrl_c() => {
+                    info!("Telegram bot shutting down");
+                    return Ok(());
+                }
+            }
+        }
     }
 }
 
-async fn answer(bot: Bot, msg: Message, cmd: Command) -> ResponseResult<()> {
+async fn answer(
+    bot: Bot,
+    msg: Message,
+    cmd: Command,
+    config: Arc<BotConfig>,
+    worker_manager: Arc<WorkerManager>,
+    reward_system: Arc<RewardSystem>,
+) -> ResponseResult<()> {
+    let user_id = msg.from().map(|user| user.id.0 as i64);
+    if !command_permitted(&cmd, user_id, |id| config.is_authorized(id)) {
+        warn!("Rejected command from unauthorized user {:?}", user_id);
+        bot.send_message(
+            msg.chat.id,
+            "Sorry, you are not authorized to use this command. Please contact the pool administrator.",
+        )
+        .await?;
+        return Ok(());
+    }
+
     match cmd {
         Command::Start => {
             bot.send_message(msg.chat.id, "Welcome to the Mining Bot! Use /help to see available commands.")
@@ -115,8 +251,7 @@ async fn answer(bot: Bot, msg: Message, cmd: Command) -> ResponseResult<()> {
                 .await?;
         }
         Command::Status => {
-            // Get current mining status
-            let status = "Mining Status:\nActive: Yes\nWorkers: 2\nHashrate: 100 MH/s";
+            let status = status_message(&worker_manager.get_worker_stats().await);
             bot.send_message(msg.chat.id, status).await?;
         }
         Command::Config => {
@@ -127,8 +262,7 @@ async fn answer(bot: Bot, msg: Message, cmd: Command) -> ResponseResult<()> {
                 .await?;
         }
         Command::Stats => {
-            // Show mining statistics
-            let stats = "Mining Statistics:\nTotal Rewards: 100 SOL\nUptime: 24h\nSuccess Rate: 99%";
+            let stats = stats_message(&worker_manager.get_worker_stats().await, &reward_system);
             bot.send_message(msg.chat.id, stats).await?;
         }
         Command::Help => {
@@ -158,6 +292,42 @@ fn make_config_keyboard() -> InlineKeyboardMarkup {
     InlineKeyboardMarkup::new(keyboard)
 }
 
+/// Текст ответа на /status по статистике воркеров
+fn status_message(stats: &WorkerStats) -> String {
+    if stats.total_workers == 0 {
+        return format_status("No workers connected yet.");
+    }
+    format_status(&format!(
+        "Active: {}\nWorkers: {}/{}\nHashrate: {:.2} H/s",
+        if stats.active_workers > 0 { "Yes" } else { "No" },
+        stats.active_workers,
+        stats.total_workers,
+        stats.total_hashrate,
+    ))
+}
+
+/// Текст ответа на /stats: статистика воркеров и начисленные награды
+fn stats_message(stats: &WorkerStats, reward_system: &RewardSystem) -> String {
+    let policy = reward_system.payout_policy();
+    let rewards = format!(
+        "Accrued Rewards: {:.4} {}\nWorkers Eligible for Payout: {}",
+        reward_system.total_accrued(),
+        policy.token_label,
+        reward_system.eligible_for_payout().len(),
+    );
+    if stats.total_workers == 0 {
+        return format_stats(&format!("No workers connected yet.\n{}", rewards));
+    }
+    format_stats(&format!(
+        "Active Workers: {}/{}\nTotal Hashrate: {:.2} H/s\nAverage Load: {:.1}%\n{}",
+        stats.active_workers,
+        stats.total_workers,
+        stats.total_hashrate,
+        stats.average_load,
+        rewards,
+    ))
+}
+
 fn format_status(status: &str) -> String {
     format!("📊 Mining Status:\n{}", status)
 }
@@ -166,6 +336,113 @@ fn format_stats(stats: &str) -> String {
     format!("📈 Mining Statistics:\n{}", stats)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADMIN: i64 = 1000;
+    const ALLOWED: i64 = 42;
+    const STRANGER: i64 = 7;
+
+    fn permitted(cmd: Command, user_id: Option<i64>) -> bool {
+        command_permitted(&cmd, user_id, |id| is_authorized_user(ADMIN, &[ALLOWED], id))
+    }
+
+    #[test]
+    fn test_command_authorization_guard() {
+        for user in [ADMIN, ALLOWED] {
+            assert!(permitted(Command::Stop, Some(user)));
+            assert!(permitted(Command::Config, Some(user)));
+            assert!(permitted(Command::Stats, Some(user)));
+        }
+
+        assert!(!permitted(Command::Stop, Some(STRANGER)));
+        assert!(!permitted(Command::Config, Some(STRANGER)));
+        assert!(!permitted(Command::Status, None));
+
+        // /start и /help доступны всем
+        assert!(permitted(Command::Start, Some(STRANGER)));
+        assert!(permitted(Command::Help, None));
+    }
+
+    fn worker(id: &str, hashrate: f64, status: crate::workers::WorkerStatus) -> crate::workers::Worker {
+        crate::workers::Worker {
+            id: id.to_string(),
+            name: id.to_string(),
+            status,
+            hashrate,
+            cpu_usage: 0.0,
+            memory_usage: 0.0,
+            gpu_usage: 0.0,
+            uptime: std::time::Duration::from_secs(60),
+            last_seen: chrono::Utc::now(),
+            capabilities: vec![],
+            calibrated_hashrate: None,
+            endpoint: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_status_and_stats_use_real_numbers() {
+        use crate::workers::WorkerStatus;
+
+        let worker_manager = WorkerManager::new();
+        let reward_system = RewardSystem::new();
+
+        let empty = worker_manager.get_worker_stats().await;
+        assert!(status_message(&empty).contains("No workers connected"));
+        assert!(stats_message(&empty, &reward_system).contains("Accrued Rewards: 0.0000"));
+
+        worker_manager.add_worker(worker("a", 120.5, WorkerStatus::Active)).await.unwrap();
+        worker_manager.add_worker(worker("b", 30.0, WorkerStatus::Active)).await.unwrap();
+        worker_manager.add_worker(worker("c", 0.0, WorkerStatus::Inactive)).await.unwrap();
+        reward_system.record_reward(
+            "a",
+            crate::reward_system::ActivityType::Mining,
+            2.5,
+            std::time::Duration::from_secs(3600),
+        ).await.unwrap();
+
+        let stats = worker_manager.get_worker_stats().await;
+        let status = status_message(&stats);
+        assert!(status.contains("Workers: 2/3"), "{}", status);
+        assert!(status.contains("Hashrate: 150.50 H/s"), "{}", status);
+
+        let message = stats_message(&stats, &reward_system);
+        assert!(message.contains("Active Workers: 2/3"), "{}", message);
+        assert!(message.contains("Accrued Rewards: 2.5000 SOL"), "{}", message);
+        assert!(message.contains("Workers Eligible for Payout: 1"), "{}", message);
+    }
+
+    #[test]
+    fn test_reconnect_decision_classifies_errors() {
+        let network = RequestError::Io(std::io::Error::from(std::io::ErrorKind::ConnectionReset));
+        assert_eq!(reconnect_decision(&network, 0, 5), ReconnectDecision::Retry(Duration::from_secs(1)));
+        assert_eq!(reconnect_decision(&network, 3, 5), ReconnectDecision::Retry(Duration::from_secs(8)));
+        assert_eq!(reconnect_decision(&network, 5, 5), ReconnectDecision::Fatal);
+
+        // Задержка ограничена сверху
+        assert_eq!(reconnect_decision(&network, 20, 100), ReconnectDecision::Retry(RECONNECT_MAX_DELAY));
+        assert_eq!(backoff_delay(0), Duration::from_secs(1));
+        assert_eq!(backoff_delay(2), Duration::from_secs(4));
+        assert_eq!(backoff_delay(u32::MAX), RECONNECT_MAX_DELAY);
+
+        // Telegram сам сообщает, когда повторить
+        let throttled = RequestError::RetryAfter(teloxide::types::Seconds::from_seconds(7));
+        assert_eq!(reconnect_decision(&throttled, 0, 5), ReconnectDecision::Retry(Duration::from_secs(7)));
+
+        // Неверный токен повтором не исправить
+        let unauthorized = RequestError::Api(ApiError::InvalidToken);
+        assert_eq!(reconnect_decision(&unauthorized, 0, 5), ReconnectDecision::Fatal);
+    }
+
+    #[test]
+    fn test_unset_admin_does_not_authorize_zero() {
+        assert!(!is_authorized_user(0, &[], 0));
+        assert!(is_authorized_user(0, &[ALLOWED], ALLOWED));
+    }
+}
+
 /// Инициализация tgbot модуля
 pub async fn initialize() -> Result<(), Box<dyn Error>> {
     log::info!("Initializing tgbot module");
@@ -0,0 +1,254 @@
+//! Events - Рассылка событий подписчикам WebSocket
+//!
+//! У каждого подписчика ограниченный буфер. Если клиент не успевает читать,
+//! самые старые события вытесняются, а клиент получает уведомление
+//! о количестве пропущенных событий. Источник событий никогда не блокируется.
+
+use crate::monitoring::events::EventBus;
+use crate::network::api::Event;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Arc, Weak};
+use tokio::sync::Notify;
+
+/// Размер буфера подписчика по умолчанию
+pub const DEFAULT_SUBSCRIBER_BUFFER: usize = 256;
+
+/// Сообщение для клиента
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamMessage {
+    Event { event: Event },
+    Dropped { count: u64 },
+}
+
+struct SubscriberQueue {
+    events: VecDeque<Event>,
+    dropped: u64,
+    closed: bool,
+}
+
+/// Подписчик на события
+pub struct EventSubscriber {
+    queue: Mutex<SubscriberQueue>,
+    capacity: usize,
+    notify: Notify,
+}
+
+impl EventSubscriber {
+    fn push(&self, event: Event) {
+        {
+            let mut queue = self.queue.lock();
+            if queue.events.len() >= self.capacity {
+                queue.events.pop_front();
+                queue.dropped += 1;
+            }
+            queue.events.push_back(event);
+        }
+        self.notify.notify_one();
+    }
+
+    /// Ожидает следующее сообщение. Сначала сообщает о пропущенных событиях.
+    /// Возвращает `None`, когда рассылка закрыта и буфер пуст.
+    pub async fn recv(&self) -> Option<StreamMessage> {
+        loop {
+            {
+                let mut queue = self.queue.lock();
+                if queue.dropped > 0 {
+                    let count = std::mem::take(&mut queue.dropped);
+                    return Some(StreamMessage::Dropped { count });
+                }
+                if let Some(event) = queue.events.pop_front() {
+                    return Some(StreamMessage::Event { event });
+                }
+                if queue.closed {
+                    return None;
+                }
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    fn close(&self) {
+        self.queue.lock().closed = true;
+        self.notify.notify_one();
+    }
+}
+
+/// Рассылка событий
+pub struct EventBroadcaster {
+    subscribers: Mutex<Vec<Weak<EventSubscriber>>>,
+    buffer_size: usize,
+}
+
+impl EventBroadcaster {
+    pub fn new(buffer_size: usize) -> Self {
+        Self {
+            subscribers: Mutex::new(Vec::new()),
+            buffer_size: buffer_size.max(1),
+        }
+    }
+
+    /// Регистрирует подписчика. Подписка снимается, когда подписчик удалён.
+    pub fn subscribe(&self) -> Arc<EventSubscriber> {
+        let subscriber = Arc::new(EventSubscriber {
+            queue: Mutex::new(SubscriberQueue {
+                events: VecDeque::with_capacity(self.buffer_size),
+                dropped: 0,
+                closed: false,
+            }),
+            capacity: self.buffer_size,
+            notify: Notify::new(),
+        });
+        self.subscribers.lock().push(Arc::downgrade(&subscriber));
+        subscriber
+    }
+
+    /// Публикует событие без ожидания подписчиков
+    pub fn publish(&self, event: Event) {
+        let mut subscribers = self.subscribers.lock();
+        subscribers.retain(|weak| match weak.upgrade() {
+            Some(subscriber) => {
+                subscriber.push(event.clone());
+                true
+            }
+            None => false,
+        });
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        let mut subscribers = self.subscribers.lock();
+        subscribers.retain(|weak| weak.strong_count() > 0);
+        subscribers.len()
+    }
+
+    /// Закрывает все подписки
+    pub fn close(&self) {
+        for subscriber in self.subscribers.lock().drain(..) {
+            if let Some(subscriber) = subscriber.upgrade() {
+                subscriber.close();
+            }
+        }
+    }
+}
+
+impl EventBroadcaster {
+    /// Пересылает события шины подписчикам, пока шина существует.
+    /// События, пропущенные из-за отставания, досылаются из истории шины
+    pub fn spawn_forwarder(self: Arc<Self>, bus: &Arc<EventBus>) -> tokio::task::JoinHandle<()> {
+        use tokio::sync::broadcast::error::RecvError;
+
+        let mut last_seen = bus.recent(None, 1).last().map_or(0, |event| event.id);
+        let mut events = bus.subscribe();
+        let bus = Arc::downgrade(bus);
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => {
+                        last_seen = event.id;
+                        self.publish(Event::from(event));
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        log::warn!("UI event forwarder lagging, skipped {} events", skipped);
+                        let Some(bus) = bus.upgrade() else {
+                            continue;
+                        };
+                        for event in bus.after(last_seen, skipped as usize) {
+                            last_seen = event.id;
+                            self.publish(Event::from(event));
+                        }
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+            self.close();
+        })
+    }
+}
+
+impl Default for EventBroadcaster {
+    fn default() -> Self {
+        Self::new(DEFAULT_SUBSCRIBER_BUFFER)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn event(n: u64) -> Event {
+        Event {
+            id: n.to_string(),
+            type_: "test".to_string(),
+            data: serde_json::json!({ "n": n }),
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_consumer_does_not_stall_producer() {
+        let broadcaster = Arc::new(EventBroadcaster::new(4));
+        let slow = broadcaster.subscribe();
+        let fast = broadcaster.subscribe();
+
+        // Производитель публикует 100 событий, пока медленный клиент не читает
+        let producer = {
+            let broadcaster = broadcaster.clone();
+            tokio::spawn(async move {
+                for n in 0..100 {
+                    broadcaster.publish(event(n));
+                }
+            })
+        };
+        tokio::time::timeout(Duration::from_secs(1), producer)
+            .await
+            .expect("producer stalled by slow consumer")
+            .unwrap();
+
+        // Медленный клиент узнаёт о пропусках и получает самые свежие события
+        match slow.recv().await {
+            Some(StreamMessage::Dropped { count }) => assert_eq!(count, 96),
+            other => panic!("expected drop notice, got {:?}", other),
+        }
+        let mut ids = Vec::new();
+        for _ in 0..4 {
+            match slow.recv().await {
+                Some(StreamMessage::Event { event }) => ids.push(event.id),
+                other => panic!("expected event, got {:?}", other),
+            }
+        }
+        assert_eq!(ids, vec!["96", "97", "98", "99"]);
+
+        drop(fast);
+        assert_eq!(broadcaster.subscriber_count(), 1);
+
+        broadcaster.close();
+        assert!(slow.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_forwarder_publishes_bus_events() {
+        use crate::monitoring::events::EventKind;
+
+        let bus = Arc::new(EventBus::default());
+        let broadcaster = Arc::new(EventBroadcaster::default());
+        let subscriber = broadcaster.subscribe();
+        let forwarder = broadcaster.clone().spawn_forwarder(&bus);
+
+        bus.publish(EventKind::WorkerAdded, serde_json::json!({ "worker": "w1" }));
+        match tokio::time::timeout(Duration::from_secs(1), subscriber.recv()).await.unwrap() {
+            Some(StreamMessage::Event { event }) => {
+                assert_eq!(event.type_, "worker_added");
+                assert_eq!(event.data["worker"], "w1");
+            }
+            other => panic!("expected event, got {:?}", other),
+        }
+
+        // Шина удалена - подписчики закрываются
+        drop(bus);
+        forwarder.await.unwrap();
+        assert!(subscriber.recv().await.is_none());
+    }
+}
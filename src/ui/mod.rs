@@ -12,13 +12,15 @@ pub mod dashboard;
 pub mod components;
 pub mod styles;
 pub mod utils;
+pub mod events;
 
 use crate::core::model_interface::ModelInterface;
-use crate::monitoring::metrics::ModelMetrics;
+use crate::core::model_interface::ModelMetrics;
 use crate::pool::worker::WorkerStatus;
 use crate::runtime::instance::InstanceManager;
 use crate::network::api::ApiServer;
 use crate::platform::gpu::GpuManager;
+use crate::monitoring::events::EventBus;
 
 use axum::{
     routing::{get, post},
@@ -39,6 +41,9 @@ pub struct UiState {
     pub api_server: Arc<ApiServer>,
     pub gpu_manager: Arc<GpuManager>,
     pub metrics: Arc<RwLock<ModelMetrics>>,
+    pub events: Arc<events::EventBroadcaster>,
+    /// Активные WebSocket-соединения
+    pub connections: ConnectionCounter,
 }
 
 /// Конфигурация UI
@@ -57,6 +62,19 @@ pub struct UiConfig {
     pub session_timeout: u64,
     pub theme: UiTheme,
     pub language: String,
+    /// Период отправки метрик в `/ws/metrics`, секунды
+    #[serde(default = "default_metrics_interval_secs")]
+    pub metrics_interval_secs: u64,
+}
+
+fn default_metrics_interval_secs() -> u64 {
+    2
+}
+
+impl UiConfig {
+    pub fn metrics_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.metrics_interval_secs.max(1))
+    }
 }
 
 /// Тема UI
@@ -72,22 +90,42 @@ pub struct UiServer {
     config: UiConfig,
     state: UiState,
     router: Router,
+    started_at: std::time::Instant,
+    event_bus: Option<Arc<EventBus>>,
 }
 
 impl UiServer {
     /// Создает новый UI сервер
     pub fn new(config: UiConfig, state: UiState) -> Self {
-        let router = Self::create_router(state.clone());
+        let router = Self::create_router(state.clone(), &config);
         
         Self {
             config,
             state,
             router,
+            started_at: std::time::Instant::now(),
+            event_bus: None,
         }
     }
 
+    /// События шины пересылаются клиентам `/ws/events`
+    pub fn with_event_bus(mut self, bus: Arc<EventBus>) -> Self {
+        self.event_bus = Some(bus);
+        self
+    }
+
     /// Создает роутер с маршрутами
-    fn create_router(state: UiState) -> Router {
+    fn create_router(state: UiState, config: &UiConfig) -> Router {
+        let metrics_stream = websocket::MetricsStream {
+            metrics: state.metrics.clone(),
+            interval: config.metrics_interval(),
+            connections: state.connections.clone(),
+        };
+        let events_stream = websocket::EventsStream {
+            events: state.events.clone(),
+            connections: state.connections.clone(),
+        };
+
         Router::new()
             // Основные страницы
             .route("/", get(dashboard::index))
@@ -106,8 +144,8 @@ impl UiServer {
             .route("/api/memory", get(api::get_memory_info))
             
             // WebSocket для real-time обновлений
-            .route("/ws/metrics", get(websocket::metrics_stream))
-            .route("/ws/events", get(websocket::events_stream))
+            .route("/ws/metrics", get(websocket::metrics_stream).with_state(metrics_stream))
+            .route("/ws/events", get(websocket::events_stream).with_state(events_stream))
             
             // Статические файлы
             .nest_service("/static", get(static_files::serve))
@@ -121,6 +159,10 @@ impl UiServer {
         let listener = tokio::net::TcpListener::bind(&addr).await?;
         
         log::info!("UI Server starting on {}", addr);
+
+        if let Some(bus) = &self.event_bus {
+            self.state.events.clone().spawn_forwarder(bus);
+        }
         
         axum::serve(listener, self.router.clone()).await?;
         
@@ -139,8 +181,8 @@ impl UiServer {
             running: true,
             host: self.config.host.clone(),
             port: self.config.port,
-            uptime: std::time::Duration::from_secs(0), // TODO: реализовать
-            connections: 0, // TODO: реализовать
+            uptime: self.started_at.elapsed(),
+            connections: self.state.connections.get(),
         }
     }
 }
@@ -202,4 +244,5 @@ mod static_files;
 
 pub use dashboard::*;
 pub use components::*;
-pub use styles::*; 
\ No newline at end of file
+pub use styles::*;
+pub use websocket::ConnectionCounter; 
\ No newline at end of file
@@ -0,0 +1,283 @@
+//! WebSocket - Потоки real-time обновлений для UI
+//!
+//! `/ws/metrics` периодически отправляет снимок метрик модели. Между
+//! таймером и отправкой стоит `watch`-канал: если клиент не успевает читать,
+//! промежуточные кадры заменяются последним, а не копятся в памяти.
+
+use super::events::{EventBroadcaster, StreamMessage};
+use crate::core::model_interface::ModelMetrics;
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::Response,
+};
+use futures::{SinkExt, StreamExt};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{watch, RwLock};
+use tokio::time::MissedTickBehavior;
+
+/// Счётчик активных WebSocket-соединений
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionCounter(Arc<AtomicU32>);
+
+impl ConnectionCounter {
+    /// Текущее число соединений
+    pub fn get(&self) -> u32 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Учитывает соединение до тех пор, пока жив возвращённый guard
+    fn track(&self) -> ConnectionGuard {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        ConnectionGuard(self.0.clone())
+    }
+}
+
+struct ConnectionGuard(Arc<AtomicU32>);
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Источник данных для `/ws/metrics`
+#[derive(Clone)]
+pub struct MetricsStream {
+    pub metrics: Arc<RwLock<ModelMetrics>>,
+    pub interval: Duration,
+    pub connections: ConnectionCounter,
+}
+
+/// Поток метрик `/ws/metrics`
+pub async fn metrics_stream(ws: WebSocketUpgrade, State(stream): State<MetricsStream>) -> Response {
+    ws.on_upgrade(move |socket| handle_metrics(socket, stream))
+}
+
+async fn handle_metrics(socket: WebSocket, stream: MetricsStream) {
+    let _connection = stream.connections.track();
+    let (mut sink, mut incoming) = socket.split();
+    let (frames_tx, mut frames_rx) = watch::channel(None::<String>);
+    log::debug!("Metrics WebSocket client connected");
+
+    // Отправка идёт отдельно от таймера: медленный клиент получает
+    // только последний кадр
+    let sender = tokio::spawn(async move {
+        while frames_rx.changed().await.is_ok() {
+            let frame = frames_rx.borrow_and_update().clone();
+            if let Some(payload) = frame {
+                if sink.send(Message::Text(payload)).await.is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    let mut interval = tokio::time::interval(stream.interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let snapshot = stream.metrics.read().await.clone();
+                match serde_json::to_string(&snapshot) {
+                    Ok(payload) => {
+                        if frames_tx.send(Some(payload)).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => log::error!("Failed to serialize metrics: {}", e),
+                }
+            }
+            message = incoming.next() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+
+    sender.abort();
+    log::debug!("Metrics WebSocket client disconnected");
+}
+
+/// Источник данных для `/ws/events`
+#[derive(Clone)]
+pub struct EventsStream {
+    pub events: Arc<EventBroadcaster>,
+    pub connections: ConnectionCounter,
+}
+
+/// Поток событий `/ws/events`
+pub async fn events_stream(ws: WebSocketUpgrade, State(stream): State<EventsStream>) -> Response {
+    ws.on_upgrade(move |socket| handle_events(socket, stream))
+}
+
+async fn handle_events(socket: WebSocket, stream: EventsStream) {
+    let _connection = stream.connections.track();
+    let subscriber = stream.events.subscribe();
+    let (mut sink, mut incoming) = socket.split();
+    log::debug!("Events WebSocket client connected");
+
+    // Входящие сообщения читаются, чтобы закрытое соединение освобождалось
+    // сразу, а не при следующем событии
+    loop {
+        let message = tokio::select! {
+            message = subscriber.recv() => match message {
+                Some(message) => message,
+                None => break,
+            },
+            incoming_message = incoming.next() => match incoming_message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+        };
+
+        if let StreamMessage::Dropped { count } = &message {
+            log::warn!("Events WebSocket client lagging, dropped {} events", count);
+        }
+
+        let payload = match serde_json::to_string(&message) {
+            Ok(payload) => payload,
+            Err(e) => {
+                log::error!("Failed to serialize event: {}", e);
+                continue;
+            }
+        };
+
+        if sink.send(Message::Text(payload)).await.is_err() {
+            break;
+        }
+    }
+
+    log::debug!("Events WebSocket client disconnected");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+
+    fn metrics() -> ModelMetrics {
+        ModelMetrics {
+            requests_processed: 42,
+            requests_per_second: 1.5,
+            average_response_time: 20.0,
+            tokens_generated: 1000,
+            tokens_per_second: 50.0,
+            memory_usage: 2048,
+            gpu_usage: 75.0,
+            cpu_usage: 30.0,
+            error_rate: 0.0,
+            cache_hit_rate: 0.5,
+            active_sessions: 3,
+            queue_length: 0,
+            last_updated: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_stream_pushes_frames() {
+        let stream = MetricsStream {
+            metrics: Arc::new(RwLock::new(metrics())),
+            interval: Duration::from_millis(50),
+            connections: ConnectionCounter::default(),
+        };
+        let app = Router::new().route("/ws/metrics", get(metrics_stream).with_state(stream));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let (mut client, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws/metrics", addr))
+            .await
+            .unwrap();
+
+        let mut frames = 0;
+        while frames < 2 {
+            let message = tokio::time::timeout(Duration::from_secs(2), client.next())
+                .await
+                .expect("no metrics frame received")
+                .unwrap()
+                .unwrap();
+            if let tokio_tungstenite::tungstenite::Message::Text(payload) = message {
+                let received: ModelMetrics = serde_json::from_str(&payload).unwrap();
+                assert_eq!(received.requests_processed, 42);
+                frames += 1;
+            }
+        }
+
+        client.close(None).await.unwrap();
+    }
+
+    async fn wait_for_connections(counter: &ConnectionCounter, expected: u32) {
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(2);
+        while counter.get() != expected {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "expected {} connections, got {}",
+                expected,
+                counter.get()
+            );
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connection_counter_tracks_open_sockets() {
+        let connections = ConnectionCounter::default();
+        let stream = MetricsStream {
+            metrics: Arc::new(RwLock::new(metrics())),
+            interval: Duration::from_millis(50),
+            connections: connections.clone(),
+        };
+        let app = Router::new().route("/ws/metrics", get(metrics_stream).with_state(stream));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let url = format!("ws://{}/ws/metrics", addr);
+        let (mut first, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        wait_for_connections(&connections, 1).await;
+        let (mut second, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        wait_for_connections(&connections, 2).await;
+
+        first.close(None).await.unwrap();
+        second.close(None).await.unwrap();
+        wait_for_connections(&connections, 0).await;
+    }
+
+    #[tokio::test]
+    async fn test_events_stream_releases_closed_connections() {
+        let connections = ConnectionCounter::default();
+        let stream = EventsStream {
+            events: Arc::new(EventBroadcaster::default()),
+            connections: connections.clone(),
+        };
+        let app = Router::new().route("/ws/events", get(events_stream).with_state(stream.clone()));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let (mut client, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws/events", addr))
+            .await
+            .unwrap();
+        wait_for_connections(&connections, 1).await;
+
+        // Без новых событий закрытие всё равно замечается
+        client.close(None).await.unwrap();
+        wait_for_connections(&connections, 0).await;
+        assert_eq!(stream.events.subscriber_count(), 0);
+    }
+}
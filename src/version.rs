@@ -1,26 +1,33 @@
 //! Version information for PoolAI
 //! Auto-generated build information
 
+/// Build-time variable emitted by vergen, or `UNKNOWN` when it is missing
+macro_rules! vergen_var {
+    ($name:literal) => {
+        match option_env!($name) {
+            Some(value) => value,
+            None => UNKNOWN,
+        }
+    };
+}
+
 /// Current version of PoolAI
 pub const VERSION: &str = "Beta_bolvanka_v1";
 
-/// Build date and time
-pub const BUILD_DATE: &str = env!("VERGEN_BUILD_TIMESTAMP");
-
 /// Git commit hash
-pub const GIT_COMMIT: &str = env!("VERGEN_GIT_SHA");
+pub const GIT_COMMIT: &str = vergen_var!("VERGEN_GIT_SHA");
 
 /// Git branch name
-pub const GIT_BRANCH: &str = env!("VERGEN_GIT_BRANCH");
+pub const GIT_BRANCH: &str = vergen_var!("VERGEN_GIT_BRANCH");
 
 /// Rust compiler version
-pub const RUST_VERSION: &str = env!("VERGEN_RUSTC_SEMVER");
+pub const RUST_VERSION: &str = vergen_var!("VERGEN_RUSTC_SEMVER");
 
 /// Build target triple
-pub const BUILD_TARGET: &str = env!("VERGEN_TARGET_TRIPLE");
+pub const BUILD_TARGET: &str = env!("POOLAI_TARGET_TRIPLE");
 
 /// Build profile (debug/release)
-pub const BUILD_PROFILE: &str = env!("VERGEN_CARGO_PROFILE");
+pub const BUILD_PROFILE: &str = env!("POOLAI_BUILD_PROFILE");
 
 /// Cargo package name
 pub const PACKAGE_NAME: &str = env!("CARGO_PKG_NAME");
@@ -47,10 +54,10 @@ pub const PACKAGE_KEYWORDS: &str = env!("CARGO_PKG_KEYWORDS");
 pub const PACKAGE_CATEGORIES: &str = env!("CARGO_PKG_CATEGORIES");
 
 /// System information
-pub const OS: &str = env!("VERGEN_SYSINFO_OS");
-pub const ARCH: &str = env!("VERGEN_SYSINFO_ARCH");
-pub const POINTER_WIDTH: &str = env!("VERGEN_SYSINFO_POINTER_WIDTH");
-pub const ENDIAN: &str = env!("VERGEN_SYSINFO_ENDIAN");
+pub const OS: &str = env!("POOLAI_TARGET_OS");
+pub const ARCH: &str = env!("POOLAI_TARGET_ARCH");
+pub const POINTER_WIDTH: &str = env!("POOLAI_TARGET_POINTER_WIDTH");
+pub const ENDIAN: &str = env!("POOLAI_TARGET_ENDIAN");
 
 /// Version information structure
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -78,7 +85,7 @@ impl Default for VersionInfo {
     fn default() -> Self {
         Self {
             version: VERSION.to_string(),
-            build_date: BUILD_DATE.to_string(),
+            build_date: build_timestamp().to_string(),
             git_commit: GIT_COMMIT.to_string(),
             git_branch: GIT_BRANCH.to_string(),
             rust_version: RUST_VERSION.to_string(),
@@ -159,6 +166,52 @@ impl VersionInfo {
     }
 }
 
+/// Placeholder for build metadata that is unavailable (e.g. no git in CI)
+pub const UNKNOWN: &str = "unknown";
+
+/// Build metadata exposed via the API
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct BuildInfo {
+    pub version: String,
+    pub git_sha: String,
+    pub git_branch: String,
+    pub rustc_version: String,
+    /// "debug" or "release"
+    pub profile: String,
+    pub build_timestamp: String,
+}
+
+impl BuildInfo {
+    /// Build metadata of the running binary
+    pub fn current() -> Self {
+        let profile = if cfg!(debug_assertions) { "debug" } else { "release" };
+        Self {
+            version: PACKAGE_VERSION.to_string(),
+            git_sha: build_var(option_env!("VERGEN_GIT_SHA")),
+            git_branch: build_var(option_env!("VERGEN_GIT_BRANCH")),
+            rustc_version: build_var(option_env!("VERGEN_RUSTC_SEMVER")),
+            profile: profile.to_string(),
+            build_timestamp: build_timestamp().to_string(),
+        }
+    }
+}
+
+/// Build date and time, or `UNKNOWN` when the build script did not emit it
+pub fn build_timestamp() -> &'static str {
+    match option_env!("VERGEN_BUILD_TIMESTAMP") {
+        Some(value) if !value.trim().is_empty() => value,
+        _ => UNKNOWN,
+    }
+}
+
+fn build_var(value: Option<&str>) -> String {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(UNKNOWN)
+        .to_string()
+}
+
 /// Get current version info
 pub fn get_version_info() -> VersionInfo {
     VersionInfo::default()
@@ -228,6 +281,42 @@ mod tests {
         assert!(!get_system_info().is_empty());
     }
     
+    #[test]
+    fn test_build_info_reflects_build_environment() {
+        let info = BuildInfo::current();
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(info.git_sha, build_var(option_env!("VERGEN_GIT_SHA")));
+        assert_eq!(info.rustc_version, build_var(option_env!("VERGEN_RUSTC_SEMVER")));
+        let expected_profile = if cfg!(debug_assertions) { "debug" } else { "release" };
+        assert_eq!(info.profile, expected_profile);
+    }
+
+    #[test]
+    fn test_build_var_trims_and_falls_back() {
+        assert_eq!(build_var(Some(" abc123 ")), "abc123");
+        assert_eq!(build_var(Some("  ")), UNKNOWN);
+        assert_eq!(build_var(None), UNKNOWN);
+    }
+
+    #[test]
+    fn test_build_timestamp_not_empty() {
+        let timestamp = build_timestamp();
+        assert!(!timestamp.is_empty());
+        match option_env!("VERGEN_BUILD_TIMESTAMP") {
+            Some(value) if !value.trim().is_empty() => assert_eq!(timestamp, value),
+            _ => assert_eq!(timestamp, UNKNOWN),
+        }
+        assert_eq!(BuildInfo::current().build_timestamp, timestamp);
+    }
+
+    #[test]
+    fn test_target_metadata_matches_compilation_target() {
+        assert_eq!(OS, std::env::consts::OS);
+        assert_eq!(ARCH, std::env::consts::ARCH);
+        assert_eq!(POINTER_WIDTH, (std::mem::size_of::<usize>() * 8).to_string());
+        assert_eq!(is_development(), BUILD_PROFILE == "debug");
+    }
+
     #[test]
     fn test_version_checks() {
         // These should not panic
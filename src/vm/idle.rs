@@ -0,0 +1,380 @@
+//! Idle Shutdown - Автоматическая остановка простаивающих VM
+//!
+//! VM считается простаивающей, если загрузка CPU ниже порога и к ней не
+//! подключены активные устройства, а запросы к ней через `tracked()`
+//! не поступали. После заданного периода простоя VM останавливается,
+//! и поднимается алерт.
+
+use super::{
+    Device, DeviceStatus, PciePassthrough, UsbPassthrough, VmConfig, VmError, VmManager, VmState,
+    VmStatus,
+};
+use async_trait::async_trait;
+use crate::monitoring::alert::{AlertConfig, AlertSystem};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// ID алерта об остановке простаивающей VM
+pub const VM_IDLE_SHUTDOWN_ALERT: &str = "vm-idle-shutdown";
+
+/// Конфигурация автоостановки
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IdleShutdownConfig {
+    pub enabled: bool,
+    /// Загрузка CPU (0.0..1.0), ниже которой VM считается простаивающей
+    pub cpu_threshold: f32,
+    pub idle_period: Duration,
+    pub check_interval: Duration,
+}
+
+impl Default for IdleShutdownConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cpu_threshold: 0.05,
+            idle_period: Duration::from_secs(30 * 60),
+            check_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+impl VmStatus {
+    /// VM активна, если загружен CPU или используется подключённое устройство
+    pub fn is_active(&self, cpu_threshold: f32) -> bool {
+        self.cpu_usage >= cpu_threshold
+            || self.attached_devices.iter().any(|d| d.status == DeviceStatus::InUse)
+    }
+}
+
+/// Монитор простоя VM
+pub struct IdleMonitor {
+    manager: Arc<dyn VmManager>,
+    alert_system: Arc<AlertSystem>,
+    config: IdleShutdownConfig,
+    last_activity: parking_lot::Mutex<HashMap<String, DateTime<Utc>>>,
+}
+
+impl IdleMonitor {
+    pub async fn new(
+        manager: Arc<dyn VmManager>,
+        alert_system: Arc<AlertSystem>,
+        config: IdleShutdownConfig,
+    ) -> Self {
+        let alert = AlertConfig {
+            id: VM_IDLE_SHUTDOWN_ALERT.to_string(),
+            name: "Idle VM stopped".to_string(),
+            description: "VM was stopped after a period of inactivity".to_string(),
+            alert_type: "vm".to_string(),
+            severity: "warning".to_string(),
+            condition: ">=".to_string(),
+            threshold: config.idle_period.as_secs_f64(),
+            cooldown: Duration::from_secs(0),
+            channels: vec!["log".to_string()],
+            active: true,
+        };
+        if let Err(e) = alert_system.add_alert(alert).await {
+            log::debug!("Idle shutdown alert already registered: {}", e);
+        }
+
+        Self {
+            manager,
+            alert_system,
+            config,
+            last_activity: parking_lot::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Время последней активности VM
+    pub fn last_activity(&self, name: &str) -> Option<DateTime<Utc>> {
+        self.last_activity.lock().get(name).copied()
+    }
+
+    /// Отмечает обращение к VM: простой отсчитывается заново
+    pub fn record_activity(&self, name: &str) {
+        self.last_activity.lock().insert(name.to_string(), Utc::now());
+    }
+
+    /// Менеджер VM, обращения через который считаются активностью
+    pub fn tracked(self: &Arc<Self>) -> Arc<dyn VmManager> {
+        Arc::new(TrackedVmManager { monitor: self.clone() })
+    }
+
+    /// Запускает периодическую проверку
+    pub async fn run(self: Arc<Self>) {
+        if !self.config.enabled {
+            log::info!("VM idle shutdown disabled");
+            return;
+        }
+
+        let mut interval = tokio::time::interval(self.config.check_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.check_once(Utc::now()).await {
+                log::error!("VM idle check failed: {}", e);
+            }
+        }
+    }
+
+    /// Один проход проверки. Возвращает имена остановленных VM.
+    /// Ошибка по одной VM не прерывает проверку остальных
+    pub async fn check_once(&self, now: DateTime<Utc>) -> Result<Vec<String>, VmError> {
+        let idle_period = chrono::Duration::from_std(self.config.idle_period)
+            .unwrap_or(chrono::TimeDelta::MAX);
+        let mut stopped = Vec::new();
+
+        for name in self.manager.list_vms().await? {
+            let status = match self.manager.get_vm_status(&name).await {
+                Ok(status) => status,
+                Err(e) => {
+                    log::error!("Failed to get status of VM {}: {}", name, e);
+                    continue;
+                }
+            };
+            if status.state != VmState::Running {
+                self.last_activity.lock().remove(&name);
+                continue;
+            }
+
+            let last_activity = {
+                let mut tracked = self.last_activity.lock();
+                let last = tracked.entry(name.clone()).or_insert(now);
+                if status.is_active(self.config.cpu_threshold) {
+                    *last = now;
+                }
+                if let Some(reported) = status.last_activity {
+                    *last = (*last).max(reported);
+                }
+                *last
+            };
+
+            let idle_for = now - last_activity;
+            if idle_for < idle_period {
+                continue;
+            }
+
+            log::warn!("Stopping VM {} after {}s of inactivity", name, idle_for.num_seconds());
+            if let Err(e) = self.manager.stop_vm(&name).await {
+                log::error!("Failed to stop idle VM {}: {}", name, e);
+                continue;
+            }
+            self.last_activity.lock().remove(&name);
+
+            let mut metadata = HashMap::new();
+            metadata.insert("vm".to_string(), name.clone());
+            if let Err(e) = self
+                .alert_system
+                .check_alert(VM_IDLE_SHUTDOWN_ALERT, idle_for.num_seconds() as f64, metadata)
+                .await
+            {
+                log::error!("Failed to raise idle shutdown alert for VM {}: {}", name, e);
+            }
+            stopped.push(name);
+        }
+
+        Ok(stopped)
+    }
+}
+
+/// Обёртка над менеджером VM: обращения к VM продлевают её активность
+struct TrackedVmManager {
+    monitor: Arc<IdleMonitor>,
+}
+
+impl TrackedVmManager {
+    fn inner(&self) -> &dyn VmManager {
+        self.monitor.manager.as_ref()
+    }
+}
+
+#[async_trait]
+impl VmManager for TrackedVmManager {
+    async fn create_vm(&self, config: VmConfig) -> Result<(), VmError> {
+        let name = config.name.clone();
+        self.inner().create_vm(config).await?;
+        self.monitor.record_activity(&name);
+        Ok(())
+    }
+    async fn start_vm(&self, name: &str) -> Result<(), VmError> {
+        self.inner().start_vm(name).await?;
+        self.monitor.record_activity(name);
+        Ok(())
+    }
+    async fn stop_vm(&self, name: &str) -> Result<(), VmError> {
+        self.inner().stop_vm(name).await
+    }
+    async fn delete_vm(&self, name: &str) -> Result<(), VmError> {
+        self.inner().delete_vm(name).await
+    }
+    async fn list_vms(&self) -> Result<Vec<String>, VmError> {
+        self.inner().list_vms().await
+    }
+    async fn get_vm_status(&self, name: &str) -> Result<VmStatus, VmError> {
+        self.inner().get_vm_status(name).await
+    }
+    async fn attach_device(&self, name: &str, device: Device) -> Result<(), VmError> {
+        self.monitor.record_activity(name);
+        self.inner().attach_device(name, device).await
+    }
+    async fn detach_device(&self, name: &str, device_id: &str) -> Result<(), VmError> {
+        self.monitor.record_activity(name);
+        self.inner().detach_device(name, device_id).await
+    }
+    async fn attach_usb(&self, name: &str, usb: UsbPassthrough) -> Result<(), VmError> {
+        self.monitor.record_activity(name);
+        self.inner().attach_usb(name, usb).await
+    }
+    async fn detach_usb(&self, name: &str, usb_id: &str) -> Result<(), VmError> {
+        self.monitor.record_activity(name);
+        self.inner().detach_usb(name, usb_id).await
+    }
+    async fn attach_pcie(&self, name: &str, pcie: PciePassthrough) -> Result<(), VmError> {
+        self.monitor.record_activity(name);
+        self.inner().attach_pcie(name, pcie).await
+    }
+    async fn detach_pcie(&self, name: &str, pcie_id: &str) -> Result<(), VmError> {
+        self.monitor.record_activity(name);
+        self.inner().detach_pcie(name, pcie_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// VM с постоянно низкой загрузкой
+    struct MockVmManager {
+        state: parking_lot::Mutex<VmState>,
+        cpu_usage: f32,
+        /// Дополнительная VM, статус которой получить не удаётся
+        broken: bool,
+    }
+
+    #[async_trait]
+    impl VmManager for MockVmManager {
+        async fn create_vm(&self, _config: VmConfig) -> Result<(), VmError> { Ok(()) }
+        async fn start_vm(&self, _name: &str) -> Result<(), VmError> {
+            *self.state.lock() = VmState::Running;
+            Ok(())
+        }
+        async fn stop_vm(&self, _name: &str) -> Result<(), VmError> {
+            *self.state.lock() = VmState::Stopped;
+            Ok(())
+        }
+        async fn delete_vm(&self, _name: &str) -> Result<(), VmError> { Ok(()) }
+        async fn list_vms(&self) -> Result<Vec<String>, VmError> {
+            if self.broken {
+                Ok(vec!["broken-1".to_string(), "miner-1".to_string()])
+            } else {
+                Ok(vec!["miner-1".to_string()])
+            }
+        }
+        async fn get_vm_status(&self, name: &str) -> Result<VmStatus, VmError> {
+            if name == "broken-1" {
+                return Err(VmError::NotFoundError(name.to_string()));
+            }
+            Ok(VmStatus {
+                name: name.to_string(),
+                state: self.state.lock().clone(),
+                memory_usage: 0,
+                cpu_usage: self.cpu_usage,
+                attached_devices: vec![],
+                attached_usb: vec![],
+                attached_pcie: vec![],
+                last_activity: None,
+            })
+        }
+        async fn attach_device(&self, _name: &str, _device: Device) -> Result<(), VmError> { Ok(()) }
+        async fn detach_device(&self, _name: &str, _device_id: &str) -> Result<(), VmError> { Ok(()) }
+        async fn attach_usb(&self, _name: &str, _usb: UsbPassthrough) -> Result<(), VmError> { Ok(()) }
+        async fn detach_usb(&self, _name: &str, _usb_id: &str) -> Result<(), VmError> { Ok(()) }
+        async fn attach_pcie(&self, _name: &str, _pcie: PciePassthrough) -> Result<(), VmError> { Ok(()) }
+        async fn detach_pcie(&self, _name: &str, _pcie_id: &str) -> Result<(), VmError> { Ok(()) }
+    }
+
+    fn config() -> IdleShutdownConfig {
+        IdleShutdownConfig {
+            enabled: true,
+            cpu_threshold: 0.05,
+            idle_period: Duration::from_secs(600),
+            check_interval: Duration::from_secs(60),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_idle_vm_stopped_after_window() {
+        let manager = Arc::new(MockVmManager {
+            state: parking_lot::Mutex::new(VmState::Running),
+            cpu_usage: 0.01,
+            broken: false,
+        });
+        let alerts = Arc::new(AlertSystem::new());
+        let monitor = IdleMonitor::new(manager.clone(), alerts.clone(), config()).await;
+        let start = Utc::now();
+
+        assert!(monitor.check_once(start).await.unwrap().is_empty());
+        assert_eq!(monitor.last_activity("miner-1"), Some(start));
+
+        let before_window = start + chrono::Duration::seconds(599);
+        assert!(monitor.check_once(before_window).await.unwrap().is_empty());
+        assert_eq!(*manager.state.lock(), VmState::Running);
+
+        let after_window = start + chrono::Duration::seconds(600);
+        assert_eq!(monitor.check_once(after_window).await.unwrap(), vec!["miner-1".to_string()]);
+        assert_eq!(*manager.state.lock(), VmState::Stopped);
+
+        let alert = alerts.get_alert(VM_IDLE_SHUTDOWN_ALERT).await.unwrap();
+        assert_eq!(alert.stats.triggered_alerts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_busy_vm_keeps_running() {
+        let manager = Arc::new(MockVmManager {
+            state: parking_lot::Mutex::new(VmState::Running),
+            cpu_usage: 0.8,
+            broken: false,
+        });
+        let monitor = IdleMonitor::new(manager.clone(), Arc::new(AlertSystem::new()), config()).await;
+        let start = Utc::now();
+
+        monitor.check_once(start).await.unwrap();
+        let later = start + chrono::Duration::hours(2);
+        assert!(monitor.check_once(later).await.unwrap().is_empty());
+        assert_eq!(monitor.last_activity("miner-1"), Some(later));
+        assert_eq!(*manager.state.lock(), VmState::Running);
+    }
+
+    #[tokio::test]
+    async fn test_vm_error_does_not_abort_check() {
+        let manager = Arc::new(MockVmManager {
+            state: parking_lot::Mutex::new(VmState::Running),
+            cpu_usage: 0.01,
+            broken: true,
+        });
+        let monitor = IdleMonitor::new(manager.clone(), Arc::new(AlertSystem::new()), config()).await;
+        let start = Utc::now();
+
+        monitor.check_once(start).await.unwrap();
+        let after_window = start + chrono::Duration::seconds(600);
+        assert_eq!(monitor.check_once(after_window).await.unwrap(), vec!["miner-1".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_requests_through_tracked_manager_count_as_activity() {
+        let manager = Arc::new(MockVmManager {
+            state: parking_lot::Mutex::new(VmState::Running),
+            cpu_usage: 0.01,
+            broken: false,
+        });
+        let monitor = Arc::new(IdleMonitor::new(manager.clone(), Arc::new(AlertSystem::new()), config()).await);
+        let start = Utc::now() - chrono::Duration::seconds(700);
+        monitor.check_once(start).await.unwrap();
+
+        monitor.tracked().start_vm("miner-1").await.unwrap();
+        assert!(monitor.check_once(Utc::now()).await.unwrap().is_empty());
+        assert_eq!(*manager.state.lock(), VmState::Running);
+    }
+}
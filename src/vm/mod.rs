@@ -7,6 +7,7 @@ pub mod tuning;
 pub mod endorphin;
 pub mod telegram;
 pub mod error;
+pub mod idle;
 
 pub use vm::*;
 pub use gpu::*;
@@ -17,6 +18,7 @@ pub use tuning::*;
 pub use endorphin::*;
 pub use telegram::*;
 pub use error::*;
+pub use idle::*;
 
 use std::collections::HashMap;
 use async_trait::async_trait;
@@ -63,6 +65,8 @@ pub struct VmStatus {
     pub attached_devices: Vec<Device>,
     pub attached_usb: Vec<UsbDevice>,
     pub attached_pcie: Vec<PcieDevice>,
+    /// Время последней активности (CPU выше порога или активное устройство)
+    pub last_activity: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
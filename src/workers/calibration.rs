@@ -0,0 +1,170 @@
+//! Calibration - Калибровка хешрейта воркеров при подключении
+//!
+//! Воркер сообщает заявленный хешрейт, который может быть неточным.
+//! После подключения воркер в фоне выполняет короткий замер реальной
+//! производительности, и результат используется при распределении задач.
+
+use super::Worker;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Конфигурация калибровки
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CalibrationConfig {
+    /// Выполнять ли замер при подключении воркера
+    pub enabled: bool,
+    /// Длительность окна замера
+    pub window: Duration,
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Источник замера производительности воркера
+#[async_trait]
+pub trait ThroughputProbe: Send + Sync {
+    /// Возвращает измеренный хешрейт (хешей в секунду) за указанное окно
+    async fn measure(&self, worker: &Worker, window: Duration) -> Result<f64, String>;
+}
+
+/// Запас к окну замера на сетевой обмен с воркером
+const PROBE_GRACE: Duration = Duration::from_secs(5);
+
+#[derive(Serialize)]
+struct ProbeRequest {
+    window_ms: u64,
+}
+
+#[derive(Deserialize)]
+struct ProbeResponse {
+    hashrate: f64,
+}
+
+/// Замер на самом воркере: `POST {endpoint}/calibrate` с окном замера,
+/// воркер считает хеши на своём оборудовании и возвращает `{"hashrate": ..}`
+pub struct RemoteHashProbe {
+    client: reqwest::Client,
+}
+
+impl RemoteHashProbe {
+    pub fn new() -> Self {
+        Self { client: reqwest::Client::new() }
+    }
+}
+
+impl Default for RemoteHashProbe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ThroughputProbe for RemoteHashProbe {
+    async fn measure(&self, worker: &Worker, window: Duration) -> Result<f64, String> {
+        let endpoint = worker
+            .endpoint
+            .as_deref()
+            .ok_or_else(|| "Worker has no endpoint to probe".to_string())?;
+        let url = format!("{}/calibrate", endpoint.trim_end_matches('/'));
+
+        let response = self
+            .client
+            .post(&url)
+            .timeout(window + PROBE_GRACE)
+            .json(&ProbeRequest { window_ms: window.as_millis() as u64 })
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| format!("Calibration probe {} failed: {}", url, e))?;
+        let probe: ProbeResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Invalid calibration response from {}: {}", url, e))?;
+        Ok(probe.hashrate)
+    }
+}
+
+/// Выполняет калибровку воркера и сохраняет результат в `calibrated_hashrate`.
+/// При ошибке замера остаётся заявленное значение.
+pub async fn calibrate_worker(
+    worker: &mut Worker,
+    config: &CalibrationConfig,
+    probe: &dyn ThroughputProbe,
+) {
+    if !config.enabled {
+        log::debug!("Calibration skipped for worker {}", worker.id);
+        return;
+    }
+
+    match probe.measure(worker, config.window).await {
+        Ok(measured) if measured.is_finite() && measured >= 0.0 => {
+            log::info!(
+                "Worker {} calibrated: claimed {:.2} H/s, measured {:.2} H/s",
+                worker.id, worker.hashrate, measured
+            );
+            worker.calibrated_hashrate = Some(measured);
+        }
+        Ok(measured) => {
+            log::warn!("Worker {} calibration returned invalid value {}", worker.id, measured);
+        }
+        Err(e) => {
+            log::warn!("Worker {} calibration failed: {}", worker.id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::post, Json, Router};
+
+    fn worker(endpoint: Option<String>) -> Worker {
+        Worker {
+            id: "w1".to_string(),
+            name: "w1".to_string(),
+            status: super::super::WorkerStatus::Active,
+            hashrate: 100.0,
+            cpu_usage: 0.0,
+            memory_usage: 0.0,
+            gpu_usage: 0.0,
+            uptime: Duration::from_secs(0),
+            last_seen: chrono::Utc::now(),
+            capabilities: vec![],
+            calibrated_hashrate: None,
+            endpoint,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remote_probe_reports_worker_measurement() {
+        let app = Router::new().route(
+            "/calibrate",
+            post(|Json(request): Json<serde_json::Value>| async move {
+                assert_eq!(request["window_ms"], 50);
+                Json(serde_json::json!({ "hashrate": 42.5 }))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let probe = RemoteHashProbe::new();
+        let measured = probe
+            .measure(&worker(Some(format!("http://{}/", addr))), Duration::from_millis(50))
+            .await
+            .unwrap();
+        assert_eq!(measured, 42.5);
+
+        assert!(probe.measure(&worker(None), Duration::from_millis(50)).await.is_err());
+    }
+}
@@ -0,0 +1,38 @@
+//! Live Metrics - Хранение текущих метрик воркеров
+//!
+//! Метрики каждого воркера лежат под собственной короткой блокировкой:
+//! обновление и чтение снимка копируют структуру целиком, поэтому снимок
+//! всегда состоит из полей одного обновления, а блокировка реестра воркеров
+//! при этом не захватывается.
+
+use super::WorkerStatus;
+use crate::monitoring::metrics::WorkerMetrics;
+use parking_lot::RwLock;
+
+/// Текущие метрики воркера
+#[derive(Debug)]
+pub struct LiveWorkerMetrics {
+    metrics: RwLock<WorkerMetrics>,
+}
+
+impl LiveWorkerMetrics {
+    pub fn new(metrics: &WorkerMetrics) -> Self {
+        Self {
+            metrics: RwLock::new(metrics.clone()),
+        }
+    }
+
+    /// Заменяет все значения одним обновлением
+    pub fn store(&self, metrics: &WorkerMetrics) {
+        *self.metrics.write() = metrics.clone();
+    }
+
+    pub fn store_status(&self, status: &WorkerStatus) {
+        self.metrics.write().status = status.clone();
+    }
+
+    /// Возвращает согласованный снимок текущих значений
+    pub fn snapshot(&self) -> WorkerMetrics {
+        self.metrics.read().clone()
+    }
+}
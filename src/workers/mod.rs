@@ -9,20 +9,37 @@
 pub mod worker_manager;
 pub mod task_distributor;
 pub mod worker_monitor;
+pub mod live_metrics;
+pub mod calibration;
+pub mod supervisor;
 
 use crate::core::state::AppState;
-use crate::pool::pool::PoolManager;
-use crate::monitoring::metrics::WorkerMetrics;
+use crate::admin::maintenance::MaintenanceGate;
+use crate::pool::{PoolManager, RewardSystem};
+use crate::monitoring::events::{EventBus, EventKind};
+use crate::monitoring::logger::with_worker_id;
+use crate::monitoring::metrics::{WorkerMetrics, WorkerSummary};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use std::collections::HashMap;
+use live_metrics::LiveWorkerMetrics;
+use calibration::{CalibrationConfig, ThroughputProbe, calibrate_worker};
 
 /// Менеджер воркеров
 pub struct WorkerManager {
     workers: Arc<RwLock<HashMap<String, Worker>>>,
+    live_metrics: Arc<parking_lot::RwLock<HashMap<String, Arc<LiveWorkerMetrics>>>>,
     task_distributor: Arc<TaskDistributor>,
     monitor: Arc<WorkerMonitor>,
+    calibration: CalibrationConfig,
+    probe: Option<Arc<dyn ThroughputProbe>>,
+    maintenance: MaintenanceGate,
+    pool_manager: Option<Arc<PoolManager>>,
+    rewards: Option<Arc<RewardSystem>>,
+    events: Arc<EventBus>,
+    /// История метрик воркеров, которую пополняет `spawn_metric_sampler`
+    history: Arc<worker_monitor::WorkerMonitor>,
 }
 
 impl WorkerManager {
@@ -30,25 +47,179 @@ impl WorkerManager {
     pub fn new() -> Self {
         Self {
             workers: Arc::new(RwLock::new(HashMap::new())),
-            task_distributor: Arc::new(TaskDistributor::new()),
+            live_metrics: Arc::new(parking_lot::RwLock::new(HashMap::new())),
+            task_distributor: Arc::new(TaskDistributor::new(DistributionStrategy::default())),
             monitor: Arc::new(WorkerMonitor::new()),
+            calibration: CalibrationConfig { enabled: false, ..CalibrationConfig::default() },
+            probe: None,
+            maintenance: MaintenanceGate::default(),
+            pool_manager: None,
+            rewards: None,
+            events: Arc::new(EventBus::default()),
+            history: Arc::new(worker_monitor::WorkerMonitor::new(AlertThresholds::default())),
         }
     }
 
-    /// Добавляет нового воркера
+    /// Интервал и глубина истории метрик воркеров
+    pub fn with_metric_sampler(mut self, config: MetricSamplerConfig) -> Self {
+        self.history = Arc::new(
+            worker_monitor::WorkerMonitor::new(AlertThresholds::default()).with_sampler_config(config),
+        );
+        self
+    }
+
+    /// Запускает периодический сбор метрик воркеров в историю.
+    /// Задача завершается вместе с менеджером
+    pub fn spawn_metric_sampler(&self) -> tokio::task::JoinHandle<()> {
+        self.history.spawn_sampler(self.workers.clone())
+    }
+
+    /// Снимки метрик воркера за последние `window`, от старых к новым
+    pub async fn get_metric_history(&self, worker_id: &str, window: std::time::Duration) -> Vec<MetricSample> {
+        self.history.get_metric_history(worker_id, window).await
+    }
+
+    /// Добавление и удаление воркеров публикуются в шину событий
+    pub fn with_event_bus(mut self, events: Arc<EventBus>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// При удалении воркер также убирается из пулов
+    pub fn with_pool_manager(mut self, pool_manager: Arc<PoolManager>) -> Self {
+        self.pool_manager = Some(pool_manager);
+        self
+    }
+
+    /// При удалении воркера его невыплаченный баланс остаётся в системе наград
+    /// и уходит в очередную выплату; здесь он только попадает в лог. Досрочная
+    /// выплата при удалении намеренно не делается: она обошла бы порог выплат.
+    pub fn with_reward_system(mut self, rewards: Arc<RewardSystem>) -> Self {
+        self.rewards = Some(rewards);
+        self
+    }
+
+    /// Во время обслуживания задачи не распределяются
+    pub fn with_maintenance_gate(mut self, maintenance: MaintenanceGate) -> Self {
+        self.maintenance = maintenance;
+        self
+    }
+
+    /// Калибровка хешрейта при подключении воркеров
+    pub fn with_calibration(mut self, config: CalibrationConfig, probe: Arc<dyn ThroughputProbe>) -> Self {
+        self.calibration = config;
+        self.probe = Some(probe);
+        self
+    }
+
+    /// Добавляет нового воркера. Записи лога помечаются его идентификатором.
+    /// Калибровка запускается в фоне и не задерживает подключение
     pub async fn add_worker(&self, worker: Worker) -> Result<(), Box<dyn std::error::Error>> {
+        with_worker_id(worker.id.clone(), async move {
+            if self.calibration.enabled && self.probe.is_some() {
+                self.spawn_calibration(worker.clone());
+            }
+
+            let mut workers = self.workers.write().await;
+            let worker_id = worker.id.clone();
+            self.live_metrics.write().insert(
+                worker_id.clone(),
+                Arc::new(LiveWorkerMetrics::new(&WorkerMetrics::from(&worker))),
+            );
+            workers.insert(worker_id.clone(), worker);
+            drop(workers);
+            self.events.publish(EventKind::WorkerAdded, serde_json::json!({ "worker": worker_id }));
+            log::info!("Worker {} added", worker_id);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Замеряет хешрейт воркера и записывает результат, если воркер
+    /// к этому моменту ещё подключён
+    fn spawn_calibration(&self, mut worker: Worker) {
+        let Some(probe) = self.probe.clone() else { return };
+        let config = self.calibration.clone();
+        let workers = self.workers.clone();
+        tokio::spawn(with_worker_id(worker.id.clone(), async move {
+            calibrate_worker(&mut worker, &config, probe.as_ref()).await;
+            if let Some(measured) = worker.calibrated_hashrate {
+                if let Some(current) = workers.write().await.get_mut(&worker.id) {
+                    current.calibrated_hashrate = Some(measured);
+                }
+            }
+        }));
+    }
+
+    /// Удаляет воркера вместе с его членством в пулах. Невыплаченный баланс
+    /// остаётся в системе наград и уходит в очередную выплату.
+    /// Удаление отсутствующего воркера ничего не делает.
+    pub async fn remove_worker(&self, worker_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        with_worker_id(worker_id.to_string(), async {
+            if !self.workers.read().await.contains_key(worker_id) {
+                return Ok(());
+            }
+            // Сначала пулы: если их не удалось сохранить, воркер остаётся
+            let pools = match &self.pool_manager {
+                Some(pool_manager) => pool_manager.remove_worker_from_pools(worker_id).await?,
+                None => Vec::new(),
+            };
+            self.workers.write().await.remove(worker_id);
+            self.live_metrics.write().remove(worker_id);
+
+            self.events.publish(
+                EventKind::WorkerRemoved,
+                serde_json::json!({ "worker": worker_id, "pools": pools }),
+            );
+            if let Some(rewards) = &self.rewards {
+                let balance = rewards.get_worker_total(worker_id);
+                if balance > 0.0 {
+                    log::info!("Removed worker {} keeps unpaid reward {} until payout", worker_id, balance);
+                }
+            }
+
+            log::info!("Worker {} removed", worker_id);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Обновляет метрики воркера.
+    /// Читатели `get_worker_metrics` не блокируют это обновление.
+    pub async fn update_worker_metrics(
+        &self,
+        worker_id: &str,
+        metrics: WorkerMetrics,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let live = self.live_metrics.read().get(worker_id).cloned();
+        let live = live.ok_or("Worker not found")?;
+        live.store(&metrics);
+
         let mut workers = self.workers.write().await;
-        workers.insert(worker.id.clone(), worker);
-        log::info!("Worker {} added", worker.id);
+        if let Some(worker) = workers.get_mut(worker_id) {
+            worker.cpu_usage = metrics.cpu_usage;
+            worker.memory_usage = metrics.memory_usage;
+            worker.gpu_usage = metrics.gpu_usage;
+            worker.hashrate = metrics.hashrate;
+            worker.uptime = metrics.uptime;
+            worker.status = metrics.status;
+            worker.last_seen = chrono::Utc::now();
+        }
         Ok(())
     }
 
-    /// Удаляет воркера
-    pub async fn remove_worker(&self, worker_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+    /// Устанавливает статус воркера
+    pub async fn set_worker_status(
+        &self,
+        worker_id: &str,
+        status: WorkerStatus,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         let mut workers = self.workers.write().await;
-        if workers.remove(worker_id).is_some() {
-            log::info!("Worker {} removed", worker_id);
+        let worker = workers.get_mut(worker_id).ok_or("Worker not found")?;
+        if let Some(live) = self.live_metrics.read().get(worker_id) {
+            live.store_status(&status);
         }
+        worker.status = status;
         Ok(())
     }
 
@@ -58,6 +229,42 @@ impl WorkerManager {
         workers.values().cloned().collect()
     }
 
+    /// Снимок всех воркеров для последующего восстановления через `import_workers`
+    pub async fn export_workers(&self) -> Vec<Worker> {
+        self.get_workers().await
+    }
+
+    /// Восстанавливает воркеров из снимка.
+    /// `Replace` заменяет весь набор, `Merge` добавляет или обновляет воркеров по ID.
+    pub async fn import_workers(
+        &self,
+        workers: Vec<Worker>,
+        mode: ImportMode,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut seen = std::collections::HashSet::new();
+        if let Some(duplicate) = workers.iter().find(|w| !seen.insert(w.id.as_str())) {
+            return Err(format!("Duplicate worker ID in import: {}", duplicate.id).into());
+        }
+
+        let mut current = self.workers.write().await;
+        let mut live = self.live_metrics.write();
+        if mode == ImportMode::Replace {
+            current.clear();
+            live.clear();
+        }
+
+        let count = workers.len();
+        for worker in workers {
+            live.insert(
+                worker.id.clone(),
+                Arc::new(LiveWorkerMetrics::new(&WorkerMetrics::from(&worker))),
+            );
+            current.insert(worker.id.clone(), worker);
+        }
+        log::info!("Imported {} workers ({:?})", count, mode);
+        Ok(())
+    }
+
     /// Получает воркера по ID
     pub async fn get_worker(&self, worker_id: &str) -> Option<Worker> {
         let workers = self.workers.read().await;
@@ -66,12 +273,28 @@ impl WorkerManager {
 
     /// Распределяет задачу между воркерами
     pub async fn distribute_task(&self, task: Task) -> Result<String, Box<dyn std::error::Error>> {
+        self.maintenance.check()?;
         self.task_distributor.distribute_task(task, &self.workers).await
     }
 
-    /// Получает метрики воркеров
+    /// Получает снимок метрик воркеров без блокировки писателей.
+    /// Значения могут быть слегка устаревшими.
     pub async fn get_worker_metrics(&self) -> HashMap<String, WorkerMetrics> {
-        self.monitor.get_metrics(&self.workers).await
+        let live: Vec<(String, Arc<LiveWorkerMetrics>)> = self
+            .live_metrics
+            .read()
+            .iter()
+            .map(|(id, metrics)| (id.clone(), metrics.clone()))
+            .collect();
+
+        live.into_iter()
+            .map(|(id, metrics)| (id, metrics.snapshot()))
+            .collect()
+    }
+
+    /// Сводка по текущим метрикам воркеров: число по статусам и суммарные значения
+    pub async fn get_worker_summary(&self) -> WorkerSummary {
+        WorkerMetrics::summarize(&self.get_worker_metrics().await)
     }
 
     /// Получает статистику воркеров
@@ -103,10 +326,46 @@ pub struct Worker {
     pub uptime: std::time::Duration,
     pub last_seen: chrono::DateTime<chrono::Utc>,
     pub capabilities: Vec<String>,
+    /// Хешрейт, измеренный при подключении
+    #[serde(default)]
+    pub calibrated_hashrate: Option<f64>,
+    /// Адрес агента воркера, по которому выполняется калибровка
+    #[serde(default)]
+    pub endpoint: Option<String>,
+}
+
+impl Worker {
+    /// Хешрейт для планирования: калиброванный, если есть, иначе заявленный
+    pub fn effective_hashrate(&self) -> f64 {
+        self.calibrated_hashrate.unwrap_or(self.hashrate)
+    }
+}
+
+impl From<&Worker> for WorkerMetrics {
+    fn from(worker: &Worker) -> Self {
+        WorkerMetrics {
+            cpu_usage: worker.cpu_usage,
+            memory_usage: worker.memory_usage,
+            gpu_usage: worker.gpu_usage,
+            hashrate: worker.hashrate,
+            uptime: worker.uptime,
+            status: worker.status.clone(),
+            gpu_temperature: None,
+        }
+    }
+}
+
+/// Режим импорта воркеров
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImportMode {
+    /// Заменить весь набор воркеров
+    Replace,
+    /// Сохранить существующих, добавить или обновить по ID
+    Merge,
 }
 
 /// Статус воркера
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum WorkerStatus {
     Active,
     Inactive,
@@ -126,7 +385,7 @@ pub struct Task {
 }
 
 /// Приоритет задачи
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TaskPriority {
     Low,
     Normal,
@@ -152,12 +411,49 @@ pub struct WorkerStats {
     pub average_load: f64,
 }
 
+/// Стратегия выбора воркера для задачи
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DistributionStrategy {
+    /// Воркер с наибольшей свободной производительностью: хешрейт,
+    /// умноженный на долю свободного CPU
+    #[default]
+    MostCapacity,
+    /// Воркер с наименьшей загрузкой CPU
+    LeastCpu,
+    /// Воркер с наименьшим использованием памяти
+    LeastMemory,
+    /// По очереди среди подходящих воркеров
+    RoundRobin,
+    /// Пропорционально хешрейту (сглаженный взвешенный round-robin)
+    HashrateWeighted,
+    /// Среди воркеров со всеми требуемыми возможностями - с наибольшей
+    /// оценкой `score_worker`; равные по оценке выбираются по очереди
+    BestFit,
+}
+
+/// Доля совпадения возможностей в оценке `score_worker`; остальное - свободные ресурсы
+const CAPABILITY_SCORE_WEIGHT: f64 = 0.5;
+
 /// Распределитель задач
-pub struct TaskDistributor;
+pub struct TaskDistributor {
+    strategy: DistributionStrategy,
+    /// Курсор для RoundRobin и равных по оценке воркеров BestFit
+    cursor: std::sync::atomic::AtomicUsize,
+    /// Текущие веса воркеров для HashrateWeighted
+    weights: parking_lot::Mutex<HashMap<String, f64>>,
+}
 
 impl TaskDistributor {
-    pub fn new() -> Self {
-        Self
+    pub fn new(strategy: DistributionStrategy) -> Self {
+        Self {
+            strategy,
+            cursor: std::sync::atomic::AtomicUsize::new(0),
+            weights: parking_lot::Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn strategy(&self) -> DistributionStrategy {
+        self.strategy
     }
 
     pub async fn distribute_task(
@@ -166,26 +462,139 @@ impl TaskDistributor {
         workers: &Arc<RwLock<HashMap<String, Worker>>>,
     ) -> Result<String, Box<dyn std::error::Error>> {
         let workers = workers.read().await;
-        
-        // Находим подходящего воркера
-        let suitable_worker = workers.values()
+
+        // Подходящие воркеры в стабильном порядке
+        let mut candidates: Vec<&Worker> = workers.values()
             .filter(|w| w.status == WorkerStatus::Active)
             .filter(|w| self.worker_satisfies_requirements(w, &task.requirements))
-            .min_by(|a, b| a.cpu_usage.partial_cmp(&b.cpu_usage).unwrap_or(std::cmp::Ordering::Equal));
-        
-        match suitable_worker {
+            .collect();
+        candidates.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let selected = match self.strategy {
+            DistributionStrategy::MostCapacity => Self::least_by(&candidates, |w| -Self::available_capacity(w)),
+            DistributionStrategy::LeastCpu => Self::least_by(&candidates, |w| w.cpu_usage),
+            DistributionStrategy::LeastMemory => Self::least_by(&candidates, |w| w.memory_usage),
+            DistributionStrategy::RoundRobin => self.next_round_robin(&candidates),
+            DistributionStrategy::HashrateWeighted => {
+                self.next_weighted(&candidates, |id| workers.contains_key(id))
+            }
+            DistributionStrategy::BestFit => self.best_fit(&candidates, &task.requirements),
+        };
+
+        match selected {
             Some(worker) => {
-                log::info!("Task {} assigned to worker {}", task.id, worker.id);
+                log::info!("Task {} assigned to worker {} ({:?})", task.id, worker.id, self.strategy);
                 Ok(worker.id.clone())
             }
             None => Err("No suitable worker found".into()),
         }
     }
 
-    fn worker_satisfies_requirements(&self, worker: &Worker, requirements: &TaskRequirements) -> bool {
+    fn available_capacity(worker: &Worker) -> f64 {
+        worker.effective_hashrate() * (100.0 - worker.cpu_usage).max(0.0) / 100.0
+    }
+
+    fn least_by<'a>(candidates: &[&'a Worker], key: impl Fn(&Worker) -> f64) -> Option<&'a Worker> {
+        // При равенстве побеждает первый по id
+        candidates.iter().copied().fold(None, |best: Option<&Worker>, worker| match best {
+            Some(best) if key(best) <= key(worker) => Some(best),
+            _ => Some(worker),
+        })
+    }
+
+    fn next_round_robin<'a>(&self, candidates: &[&'a Worker]) -> Option<&'a Worker> {
+        if candidates.is_empty() {
+            return None;
+        }
+        let index = self.cursor.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        Some(candidates[index % candidates.len()])
+    }
+
+    /// Сглаженный взвешенный round-robin: каждый воркер получает долю задач,
+    /// пропорциональную хешрейту, без случайности
+    fn next_weighted<'a>(
+        &self,
+        candidates: &[&'a Worker],
+        is_registered: impl Fn(&str) -> bool,
+    ) -> Option<&'a Worker> {
+        let total: f64 = candidates.iter().map(|w| w.effective_hashrate().max(0.0)).sum();
+        if total <= 0.0 {
+            return self.next_round_robin(candidates);
+        }
+
+        let mut weights = self.weights.lock();
+        weights.retain(|id, _| is_registered(id));
+
+        let mut selected: Option<(&Worker, f64)> = None;
+        for worker in candidates {
+            let current = weights.entry(worker.id.clone()).or_insert(0.0);
+            *current += worker.effective_hashrate().max(0.0);
+            if selected.map_or(true, |(_, best)| *current > best) {
+                selected = Some((worker, *current));
+            }
+        }
+
+        let (worker, _) = selected?;
+        if let Some(current) = weights.get_mut(&worker.id) {
+            *current -= total;
+        }
+        Some(worker)
+    }
+
+    fn best_fit<'a>(&self, candidates: &[&'a Worker], requirements: &TaskRequirements) -> Option<&'a Worker> {
+        let scored: Vec<(&Worker, f64)> = candidates
+            .iter()
+            .copied()
+            .filter_map(|worker| Self::score_worker(worker, requirements).map(|score| (worker, score)))
+            .collect();
+        let best_score = scored.iter().map(|(_, score)| *score).fold(f64::NEG_INFINITY, f64::max);
+
+        // Равные по оценке получают задачи по очереди, а не всегда первый по id
+        let tied: Vec<&Worker> = scored
+            .into_iter()
+            .filter(|(_, score)| best_score - score < 1e-9)
+            .map(|(worker, _)| worker)
+            .collect();
+        self.next_round_robin(&tied)
+    }
+
+    /// Оценка пригодности воркера от 0 до 1. `None`, если не хватает ресурсов.
+    /// Учитывает долю требуемых возможностей, которые есть у воркера,
+    /// и свободные после задачи CPU, память и GPU.
+    pub fn score_worker(worker: &Worker, requirements: &TaskRequirements) -> Option<f64> {
+        if !Self::has_headroom(worker, requirements) {
+            return None;
+        }
+
+        let overlap = if requirements.capabilities.is_empty() {
+            1.0
+        } else {
+            let matched = requirements
+                .capabilities
+                .iter()
+                .filter(|cap| worker.capabilities.contains(cap))
+                .count();
+            matched as f64 / requirements.capabilities.len() as f64
+        };
+
+        let spare = |usage: f64, required: f64| ((100.0 - usage - required) / 100.0).clamp(0.0, 1.0);
+        let resources = (spare(worker.cpu_usage, requirements.min_cpu)
+            + spare(worker.memory_usage, requirements.min_memory)
+            + spare(worker.gpu_usage, requirements.min_gpu))
+            / 3.0;
+
+        Some(CAPABILITY_SCORE_WEIGHT * overlap + (1.0 - CAPABILITY_SCORE_WEIGHT) * resources)
+    }
+
+    /// Хватает ли воркеру свободных ресурсов под задачу
+    fn has_headroom(worker: &Worker, requirements: &TaskRequirements) -> bool {
         worker.cpu_usage + requirements.min_cpu <= 100.0 &&
         worker.memory_usage + requirements.min_memory <= 100.0 &&
-        worker.gpu_usage + requirements.min_gpu <= 100.0 &&
+        worker.gpu_usage + requirements.min_gpu <= 100.0
+    }
+
+    fn worker_satisfies_requirements(&self, worker: &Worker, requirements: &TaskRequirements) -> bool {
+        Self::has_headroom(worker, requirements) &&
         requirements.capabilities.iter().all(|cap| worker.capabilities.contains(cap))
     }
 }
@@ -213,6 +622,7 @@ impl WorkerMonitor {
                 hashrate: worker.hashrate,
                 uptime: worker.uptime,
                 status: worker.status.clone(),
+                gpu_temperature: None,
             });
         }
         
@@ -250,6 +660,408 @@ pub async fn health_check() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::admin::maintenance::MAINTENANCE_ERROR;
+    use std::time::Duration;
+
+    fn test_worker(id: &str) -> Worker {
+        Worker {
+            id: id.to_string(),
+            name: id.to_string(),
+            status: WorkerStatus::Active,
+            hashrate: 10.0,
+            cpu_usage: 10.0,
+            memory_usage: 10.0,
+            gpu_usage: 10.0,
+            uptime: Duration::from_secs(0),
+            last_seen: chrono::Utc::now(),
+            capabilities: vec![],
+            calibrated_hashrate: None,
+            endpoint: None,
+        }
+    }
+
+    struct FixedProbe(f64);
+
+    #[async_trait::async_trait]
+    impl ThroughputProbe for FixedProbe {
+        async fn measure(&self, worker: &Worker, _window: Duration) -> Result<f64, String> {
+            // Воркер "fast" на деле медленный
+            if worker.id == "fast" { Ok(self.0) } else { Ok(worker.hashrate) }
+        }
+    }
+
+    struct SlowProbe;
+
+    #[async_trait::async_trait]
+    impl ThroughputProbe for SlowProbe {
+        async fn measure(&self, _worker: &Worker, window: Duration) -> Result<f64, String> {
+            tokio::time::sleep(window).await;
+            Ok(1.0)
+        }
+    }
+
+    async fn wait_calibrated(manager: &WorkerManager, worker_id: &str) -> Worker {
+        for _ in 0..100 {
+            let worker = manager.get_worker(worker_id).await.unwrap();
+            if worker.calibrated_hashrate.is_some() {
+                return worker;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("worker {} was not calibrated", worker_id);
+    }
+
+    fn test_task() -> Task {
+        Task {
+            id: "task".to_string(),
+            name: "task".to_string(),
+            priority: TaskPriority::Normal,
+            requirements: TaskRequirements {
+                min_cpu: 1.0,
+                min_memory: 1.0,
+                min_gpu: 0.0,
+                capabilities: vec![],
+            },
+            data: serde_json::Value::Null,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_calibrated_hashrate_used_for_distribution() {
+        let config = CalibrationConfig { enabled: true, window: Duration::from_millis(10) };
+        let manager = WorkerManager::new().with_calibration(config, Arc::new(FixedProbe(5.0)));
+
+        let mut fast = test_worker("fast");
+        fast.hashrate = 100.0;
+        let mut steady = test_worker("steady");
+        steady.hashrate = 50.0;
+        manager.add_worker(fast).await.unwrap();
+        manager.add_worker(steady).await.unwrap();
+
+        let fast = wait_calibrated(&manager, "fast").await;
+        assert_eq!(fast.hashrate, 100.0);
+        assert_eq!(fast.calibrated_hashrate, Some(5.0));
+        assert_eq!(fast.effective_hashrate(), 5.0);
+
+        assert_eq!(manager.distribute_task(test_task()).await.unwrap(), "steady");
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_blocks_distribution_but_not_reads() {
+        let gate = MaintenanceGate::default();
+        let manager = WorkerManager::new().with_maintenance_gate(gate.clone());
+        manager.add_worker(test_worker("w1")).await.unwrap();
+
+        gate.set(true);
+        let err = manager.distribute_task(test_task()).await.unwrap_err();
+        assert_eq!(err.to_string(), MAINTENANCE_ERROR);
+        assert_eq!(manager.get_workers().await.len(), 1);
+        assert_eq!(manager.get_worker_summary().await.total_workers, 1);
+
+        gate.set(false);
+        assert_eq!(manager.distribute_task(test_task()).await.unwrap(), "w1");
+    }
+
+    #[tokio::test]
+    async fn test_metric_sampler_records_history() {
+        let manager = WorkerManager::new().with_metric_sampler(MetricSamplerConfig {
+            interval: Duration::from_millis(10),
+            retention: 3,
+        });
+        manager.add_worker(test_worker("w1")).await.unwrap();
+        let sampler = manager.spawn_metric_sampler();
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while manager.get_metric_history("w1", Duration::from_secs(60)).await.len() < 3 {
+            assert!(std::time::Instant::now() < deadline, "sampler did not record history");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(manager.get_metric_history("w1", Duration::from_secs(60)).await.len(), 3);
+
+        drop(manager);
+        tokio::time::timeout(Duration::from_secs(1), sampler).await.unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_metric_history_keeps_previous_limit_by_default() {
+        assert_eq!(MetricSamplerConfig::default().retention, 1000);
+    }
+
+    #[tokio::test]
+    async fn test_import_workers_merge_keeps_existing() {
+        let manager = WorkerManager::new();
+        manager.add_worker(test_worker("w1")).await.unwrap();
+        manager.add_worker(test_worker("w2")).await.unwrap();
+
+        let mut updated = test_worker("w2");
+        updated.hashrate = 42.0;
+        manager
+            .import_workers(vec![updated, test_worker("w3")], ImportMode::Merge)
+            .await
+            .unwrap();
+
+        assert_eq!(manager.export_workers().await.len(), 3);
+        assert!(manager.get_worker("w1").await.is_some());
+        assert_eq!(manager.get_worker("w2").await.unwrap().hashrate, 42.0);
+        assert_eq!(manager.get_worker_metrics().await["w2"].hashrate, 42.0);
+    }
+
+    #[tokio::test]
+    async fn test_import_workers_replace_swaps_all() {
+        let source = WorkerManager::new();
+        source.add_worker(test_worker("a")).await.unwrap();
+        source.add_worker(test_worker("b")).await.unwrap();
+        let snapshot = source.export_workers().await;
+
+        let manager = WorkerManager::new();
+        manager.add_worker(test_worker("old")).await.unwrap();
+        manager.import_workers(snapshot, ImportMode::Replace).await.unwrap();
+
+        let mut ids: Vec<String> = manager.export_workers().await.into_iter().map(|w| w.id).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["a", "b"]);
+        assert!(!manager.get_worker_metrics().await.contains_key("old"));
+    }
+
+    #[tokio::test]
+    async fn test_import_workers_rejects_duplicate_ids() {
+        let manager = WorkerManager::new();
+        manager.add_worker(test_worker("w1")).await.unwrap();
+
+        let result = manager
+            .import_workers(vec![test_worker("x"), test_worker("x")], ImportMode::Replace)
+            .await;
+        assert!(result.unwrap_err().to_string().contains("Duplicate worker ID"));
+
+        // Состояние не изменилось
+        let workers = manager.export_workers().await;
+        assert_eq!(workers.len(), 1);
+        assert_eq!(workers[0].id, "w1");
+    }
+
+    #[tokio::test]
+    async fn test_remove_worker_cascades_to_pool() {
+        let pool_manager = Arc::new(PoolManager::new());
+        pool_manager.create_pool(crate::pool::test_pool_config("gpu-pool")).await.unwrap();
+
+        let rewards = Arc::new(RewardSystem::new());
+        let manager = WorkerManager::new()
+            .with_pool_manager(pool_manager.clone())
+            .with_reward_system(rewards.clone())
+            .with_event_bus(pool_manager.events());
+        manager.add_worker(test_worker("w1")).await.unwrap();
+        pool_manager.add_pool_worker("gpu-pool", "w1", "", vec![]).await.unwrap();
+        rewards.record_reward("w1", crate::pool::ActivityType::Mining, 1.0, Duration::from_secs(3600)).await.unwrap();
+        let unpaid = rewards.get_worker_total("w1");
+        assert!(unpaid > 0.0);
+        assert_eq!(pool_manager.get_pool("gpu-pool").await.unwrap().stats.total_workers, 1);
+
+        manager.remove_worker("w1").await.unwrap();
+
+        assert!(manager.get_worker("w1").await.is_none());
+        assert!(!manager.get_worker_metrics().await.contains_key("w1"));
+        let stats = pool_manager.get_pool("gpu-pool").await.unwrap().stats;
+        assert_eq!(stats.total_workers, 0);
+        assert_eq!(stats.active_workers, 0);
+        assert!(pool_manager.get_pool_workers("gpu-pool").await.unwrap().is_empty());
+        // Заработанное до удаления не теряется и попадает в выплату
+        assert_eq!(rewards.get_worker_total("w1"), unpaid);
+
+        let events = pool_manager.events().recent(None, 10);
+        assert!(events.iter().any(|e| e.kind == EventKind::WorkerAdded && e.data["worker"] == "w1"));
+        assert!(events.iter().any(|e| e.kind == EventKind::WorkerRemoved && e.data["worker"] == "w1"));
+
+        // Повторное удаление - no-op
+        manager.remove_worker("w1").await.unwrap();
+        assert_eq!(pool_manager.events().recent(None, 10).len(), events.len());
+    }
+
+    #[tokio::test]
+    async fn test_calibration_does_not_block_add_worker() {
+        let config = CalibrationConfig { enabled: true, window: Duration::from_secs(5) };
+        let manager = WorkerManager::new().with_calibration(config, Arc::new(SlowProbe));
+
+        tokio::time::timeout(Duration::from_secs(1), manager.add_worker(test_worker("w1")))
+            .await
+            .expect("add_worker waited for calibration")
+            .unwrap();
+        let worker = manager.get_worker("w1").await.unwrap();
+        assert_eq!(worker.calibrated_hashrate, None);
+    }
+
+    #[tokio::test]
+    async fn test_calibration_can_be_skipped() {
+        let config = CalibrationConfig { enabled: false, window: Duration::from_millis(10) };
+        let manager = WorkerManager::new().with_calibration(config, Arc::new(FixedProbe(5.0)));
+
+        let mut fast = test_worker("fast");
+        fast.hashrate = 100.0;
+        manager.add_worker(fast).await.unwrap();
+        manager.add_worker(test_worker("steady")).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(manager.get_worker("fast").await.unwrap().calibrated_hashrate, None);
+        assert_eq!(manager.distribute_task(test_task()).await.unwrap(), "fast");
+    }
+
+    fn strategy_workers() -> Arc<RwLock<HashMap<String, Worker>>> {
+        let mut workers = HashMap::new();
+        let mut add = |id: &str, cpu: f64, memory: f64, hashrate: f64, status: WorkerStatus, caps: &[&str]| {
+            let mut worker = test_worker(id);
+            worker.cpu_usage = cpu;
+            worker.memory_usage = memory;
+            worker.hashrate = hashrate;
+            worker.status = status;
+            worker.capabilities = caps.iter().map(|c| c.to_string()).collect();
+            workers.insert(id.to_string(), worker);
+        };
+        add("a", 50.0, 10.0, 10.0, WorkerStatus::Active, &["cuda"]);
+        add("b", 10.0, 60.0, 30.0, WorkerStatus::Active, &["cuda"]);
+        add("c", 30.0, 30.0, 0.0, WorkerStatus::Active, &["cuda"]);
+        // Не подходят: неактивен или без нужной возможности
+        add("d", 0.0, 0.0, 100.0, WorkerStatus::Inactive, &["cuda"]);
+        add("e", 0.0, 0.0, 100.0, WorkerStatus::Active, &[]);
+        Arc::new(RwLock::new(workers))
+    }
+
+    fn cuda_task() -> Task {
+        let mut task = test_task();
+        task.requirements.capabilities = vec!["cuda".to_string()];
+        task
+    }
+
+    async fn pick(distributor: &TaskDistributor, workers: &Arc<RwLock<HashMap<String, Worker>>>, n: usize) -> Vec<String> {
+        let mut picks = Vec::new();
+        for _ in 0..n {
+            picks.push(distributor.distribute_task(cuda_task(), workers).await.unwrap());
+        }
+        picks
+    }
+
+    #[tokio::test]
+    async fn test_distribution_strategies() {
+        let workers = strategy_workers();
+
+        // По умолчанию - как до появления стратегий: наибольшая свободная
+        // производительность (a: 10 * 0.5, b: 30 * 0.9, c: 0)
+        assert_eq!(DistributionStrategy::default(), DistributionStrategy::MostCapacity);
+        let most_capacity = TaskDistributor::new(DistributionStrategy::default());
+        assert_eq!(pick(&most_capacity, &workers, 2).await, vec!["b", "b"]);
+
+        let least_cpu = TaskDistributor::new(DistributionStrategy::LeastCpu);
+        assert_eq!(pick(&least_cpu, &workers, 2).await, vec!["b", "b"]);
+
+        let least_memory = TaskDistributor::new(DistributionStrategy::LeastMemory);
+        assert_eq!(pick(&least_memory, &workers, 2).await, vec!["a", "a"]);
+
+        let round_robin = TaskDistributor::new(DistributionStrategy::RoundRobin);
+        assert_eq!(pick(&round_robin, &workers, 4).await, vec!["a", "b", "c", "a"]);
+
+        // Хешрейт 10:30:0 - задачи делятся 1:3, воркер без хешрейта простаивает
+        let weighted = TaskDistributor::new(DistributionStrategy::HashrateWeighted);
+        let picks = pick(&weighted, &workers, 8).await;
+        assert_eq!(picks.iter().filter(|id| *id == "a").count(), 2);
+        assert_eq!(picks.iter().filter(|id| *id == "b").count(), 6);
+        assert!(!picks.iter().any(|id| id == "c" || id == "d" || id == "e"));
+    }
+
+    #[test]
+    fn test_score_worker_ranks_candidates() {
+        let mut requirements = test_task().requirements;
+        requirements.min_cpu = 20.0;
+        requirements.capabilities = vec!["cuda".to_string(), "fp16".to_string()];
+
+        let mut full = test_worker("full");
+        full.capabilities = vec!["cuda".to_string(), "fp16".to_string()];
+        let mut partial = test_worker("partial");
+        partial.capabilities = vec!["cuda".to_string()];
+        let mut busy = test_worker("busy");
+        busy.capabilities = full.capabilities.clone();
+        busy.cpu_usage = 70.0;
+        let mut overloaded = test_worker("overloaded");
+        overloaded.capabilities = full.capabilities.clone();
+        overloaded.cpu_usage = 90.0;
+
+        let score = |worker: &Worker| TaskDistributor::score_worker(worker, &requirements);
+        let (full, partial, busy) = (score(&full).unwrap(), score(&partial).unwrap(), score(&busy).unwrap());
+
+        assert!((0.0..=1.0).contains(&full));
+        assert!(full > partial, "capability overlap: {} vs {}", full, partial);
+        assert!(full > busy, "spare resources: {} vs {}", full, busy);
+        // Не хватает CPU - жёсткое требование
+        assert_eq!(score(&overloaded), None);
+    }
+
+    #[tokio::test]
+    async fn test_best_fit_prefers_highest_score() {
+        let workers = strategy_workers();
+        let best_fit = TaskDistributor::new(DistributionStrategy::BestFit);
+        // "e" свободен, но без cuda; у "a" и "c" свободных ресурсов поровну
+        // и больше, чем у "b", поэтому они чередуются
+        assert_eq!(pick(&best_fit, &workers, 4).await, vec!["a", "c", "a", "c"]);
+        workers.write().await.remove("a");
+        assert_eq!(pick(&best_fit, &workers, 1).await, vec!["c"]);
+
+        // Возможности - жёсткое требование: без воркеров с cuda задача не назначается
+        workers.write().await.retain(|id, _| id == "e");
+        assert!(best_fit.distribute_task(cuda_task(), &workers).await.is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_metrics_readable_during_concurrent_updates() {
+        let manager = Arc::new(WorkerManager::new());
+        for i in 0..4 {
+            manager.add_worker(test_worker(&format!("w{}", i))).await.unwrap();
+        }
+
+        let mut handles = Vec::new();
+        for i in 0..4 {
+            let manager = manager.clone();
+            handles.push(tokio::spawn(async move {
+                let id = format!("w{}", i);
+                for step in 0..200u32 {
+                    let value = (step % 100) as f64;
+                    let mut metrics = WorkerMetrics::from(&test_worker(&id));
+                    metrics.cpu_usage = value;
+                    metrics.hashrate = value * 2.0;
+                    manager.update_worker_metrics(&id, metrics).await.unwrap();
+                }
+            }));
+        }
+
+        let reader = {
+            let manager = manager.clone();
+            tokio::spawn(async move {
+                for _ in 0..200 {
+                    let snapshot = manager.get_worker_metrics().await;
+                    assert_eq!(snapshot.len(), 4);
+                    for metrics in snapshot.values() {
+                        assert!((0.0..100.0).contains(&metrics.cpu_usage));
+                        assert!((0.0..200.0).contains(&metrics.hashrate));
+                        // Поля снимка взяты из одного обновления
+                        assert_eq!(metrics.hashrate, metrics.cpu_usage * 2.0);
+                    }
+                    tokio::task::yield_now().await;
+                }
+            })
+        };
+        handles.push(reader);
+
+        tokio::time::timeout(Duration::from_secs(10), futures::future::join_all(handles))
+            .await
+            .expect("metrics readers and writers deadlocked")
+            .into_iter()
+            .for_each(|result| result.unwrap());
+
+        let final_metrics = manager.get_worker_metrics().await;
+        assert_eq!(final_metrics["w0"].cpu_usage, 99.0);
+    }
+}
+
 pub use worker_manager::*;
 pub use task_distributor::*;
 pub use worker_monitor::*; 
\ No newline at end of file
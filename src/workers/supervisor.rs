@@ -0,0 +1,432 @@
+//! Worker Supervisor - Автоматический перезапуск воркеров
+//!
+//! Воркеры в статусе `Error` перезапускаются с экспоненциальной задержкой.
+//! После исчерпания попыток супервизор прекращает попытки и поднимает алерт.
+
+use super::{WorkerManager, Worker, WorkerStatus};
+use crate::monitoring::alert::{AlertSystem, AlertConfig};
+use crate::monitoring::logger::with_worker_id;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// ID алерта об исчерпании попыток перезапуска
+pub const RESTART_EXHAUSTED_ALERT: &str = "worker-restart-exhausted";
+
+/// Конфигурация супервизора
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SupervisorConfig {
+    pub enabled: bool,
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub check_interval: Duration,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_attempts: 5,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            check_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+impl SupervisorConfig {
+    /// Задержка перед следующей попыткой после `failures` неудачных
+    pub fn backoff(&self, failures: u32) -> Duration {
+        let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+        self.initial_backoff
+            .checked_mul(factor)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+}
+
+/// Способ перезапуска воркера
+#[async_trait]
+pub trait WorkerRestarter: Send + Sync {
+    async fn restart(&self, worker: &Worker) -> Result<(), String>;
+}
+
+/// Перезапуск через агент воркера: `POST {endpoint}/restart`
+pub struct RemoteRestarter {
+    client: reqwest::Client,
+}
+
+impl RemoteRestarter {
+    pub fn new() -> Self {
+        Self { client: reqwest::Client::new() }
+    }
+}
+
+impl Default for RemoteRestarter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl WorkerRestarter for RemoteRestarter {
+    async fn restart(&self, worker: &Worker) -> Result<(), String> {
+        let endpoint = worker
+            .endpoint
+            .as_deref()
+            .ok_or_else(|| "Worker has no endpoint to restart".to_string())?;
+        let url = format!("{}/restart", endpoint.trim_end_matches('/'));
+
+        self.client
+            .post(&url)
+            .timeout(Duration::from_secs(30))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map(|_| ())
+            .map_err(|e| format!("Restart request {} failed: {}", url, e))
+    }
+}
+
+/// Статистика перезапусков воркера
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RestartStats {
+    /// Попытки в текущем (или последнем) эпизоде восстановления
+    pub attempts: u32,
+    pub successful_restarts: u32,
+    pub gave_up: bool,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct RestartState {
+    stats: RestartStats,
+    recovered: bool,
+    next_attempt_at: Option<Instant>,
+}
+
+/// Супервизор воркеров
+pub struct WorkerSupervisor {
+    manager: Arc<WorkerManager>,
+    restarter: Arc<dyn WorkerRestarter>,
+    alert_system: Arc<AlertSystem>,
+    config: SupervisorConfig,
+    states: parking_lot::Mutex<HashMap<String, RestartState>>,
+}
+
+impl WorkerSupervisor {
+    /// Создает супервизор и регистрирует алерт об исчерпании попыток
+    pub async fn new(
+        manager: Arc<WorkerManager>,
+        restarter: Arc<dyn WorkerRestarter>,
+        alert_system: Arc<AlertSystem>,
+        config: SupervisorConfig,
+    ) -> Self {
+        let alert = AlertConfig {
+            id: RESTART_EXHAUSTED_ALERT.to_string(),
+            name: "Worker restart exhausted".to_string(),
+            description: "Worker could not be restarted after max attempts".to_string(),
+            alert_type: "worker".to_string(),
+            severity: "critical".to_string(),
+            condition: ">=".to_string(),
+            threshold: config.max_attempts as f64,
+            cooldown: Duration::from_secs(0),
+            channels: vec!["log".to_string()],
+            active: true,
+        };
+        if let Err(e) = alert_system.add_alert(alert).await {
+            log::debug!("Supervisor alert already registered: {}", e);
+        }
+
+        Self {
+            manager,
+            restarter,
+            alert_system,
+            config,
+            states: parking_lot::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Статистика перезапусков воркера
+    pub fn restart_stats(&self, worker_id: &str) -> Option<RestartStats> {
+        self.states.lock().get(worker_id).map(|state| state.stats.clone())
+    }
+
+    /// Запускает периодическую проверку воркеров
+    pub async fn run(self: Arc<Self>) {
+        if !self.config.enabled {
+            log::info!("Worker supervisor disabled");
+            return;
+        }
+
+        log::info!("Worker supervisor started");
+        let mut interval = tokio::time::interval(self.config.check_interval);
+        loop {
+            interval.tick().await;
+            self.supervise_once().await;
+        }
+    }
+
+    /// Один проход: пытается перезапустить воркеры в статусе `Error`
+    pub async fn supervise_once(&self) {
+        let workers = self.manager.get_workers().await;
+        let now = Instant::now();
+
+        for worker in workers {
+            if worker.status != WorkerStatus::Error {
+                continue;
+            }
+
+            let due = {
+                let mut states = self.states.lock();
+                let state = states.entry(worker.id.clone()).or_default();
+                if state.recovered {
+                    // Новый эпизод после успешного восстановления
+                    state.recovered = false;
+                    state.stats.attempts = 0;
+                    state.stats.gave_up = false;
+                    state.next_attempt_at = None;
+                }
+                !state.stats.gave_up && state.next_attempt_at.map_or(true, |at| now >= at)
+            };
+            if !due {
+                continue;
+            }
+
+            // Записи лога перезапуска попадают в поток логов воркера
+            with_worker_id(worker.id.clone(), async {
+                let result = self.restarter.restart(&worker).await;
+                self.record_attempt(&worker, result).await;
+            })
+            .await;
+        }
+    }
+
+    async fn record_attempt(&self, worker: &Worker, result: Result<(), String>) {
+        let exhausted = {
+            let mut states = self.states.lock();
+            let state = states.entry(worker.id.clone()).or_default();
+            state.stats.attempts += 1;
+
+            match &result {
+                Ok(()) => {
+                    state.stats.successful_restarts += 1;
+                    state.stats.last_error = None;
+                    state.recovered = true;
+                    state.next_attempt_at = None;
+                    None
+                }
+                Err(e) => {
+                    state.stats.last_error = Some(e.clone());
+                    if state.stats.attempts >= self.config.max_attempts {
+                        state.stats.gave_up = true;
+                        Some(state.stats.attempts)
+                    } else {
+                        let delay = self.config.backoff(state.stats.attempts);
+                        state.next_attempt_at = Some(Instant::now() + delay);
+                        log::warn!(
+                            "Worker {} restart attempt {} failed: {}; retrying in {:?}",
+                            worker.id, state.stats.attempts, e, delay
+                        );
+                        None
+                    }
+                }
+            }
+        };
+
+        if result.is_ok() {
+            log::info!("Worker {} restarted", worker.id);
+            if let Err(e) = self.manager.set_worker_status(&worker.id, WorkerStatus::Active).await {
+                log::error!("Failed to mark worker {} active: {}", worker.id, e);
+            }
+        }
+
+        if let Some(attempts) = exhausted {
+            log::error!("Giving up on worker {} after {} restart attempts", worker.id, attempts);
+            let mut metadata = HashMap::new();
+            metadata.insert("worker_id".to_string(), worker.id.clone());
+            if let Err(e) = self
+                .alert_system
+                .check_alert(RESTART_EXHAUSTED_ALERT, attempts as f64, metadata)
+                .await
+            {
+                log::error!("Failed to raise restart alert for worker {}: {}", worker.id, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Падает первые `failures` раз, затем перезапускает успешно
+    struct FlakyRestarter {
+        failures: u32,
+        calls: AtomicU32,
+    }
+
+    #[async_trait]
+    impl WorkerRestarter for FlakyRestarter {
+        async fn restart(&self, _worker: &Worker) -> Result<(), String> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if call <= self.failures {
+                Err(format!("restart failed on call {}", call))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn test_config() -> SupervisorConfig {
+        SupervisorConfig {
+            enabled: true,
+            max_attempts: 3,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+            check_interval: Duration::from_millis(10),
+        }
+    }
+
+    async fn errored_manager() -> Arc<WorkerManager> {
+        let manager = Arc::new(WorkerManager::new());
+        manager
+            .add_worker(Worker {
+                id: "w1".to_string(),
+                name: "w1".to_string(),
+                status: WorkerStatus::Error,
+                hashrate: 1.0,
+                cpu_usage: 0.0,
+                memory_usage: 0.0,
+                gpu_usage: 0.0,
+                uptime: Duration::from_secs(0),
+                last_seen: chrono::Utc::now(),
+                capabilities: vec![],
+                calibrated_hashrate: None,
+                endpoint: None,
+            })
+            .await
+            .unwrap();
+        manager
+    }
+
+    #[tokio::test]
+    async fn test_remote_restarter_calls_worker_agent() {
+        use axum::{routing::post, Router};
+
+        let restarts = Arc::new(AtomicU32::new(0));
+        let app = Router::new().route("/restart", post({
+            let restarts = restarts.clone();
+            move || async move {
+                restarts.fetch_add(1, Ordering::SeqCst);
+            }
+        }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let manager = errored_manager().await;
+        let mut worker = manager.get_worker("w1").await.unwrap();
+        let restarter = RemoteRestarter::new();
+        assert!(restarter.restart(&worker).await.is_err());
+
+        worker.endpoint = Some(format!("http://{}", addr));
+        restarter.restart(&worker).await.unwrap();
+        assert_eq!(restarts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_backoff_is_exponential_and_capped() {
+        let config = SupervisorConfig {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5),
+            ..SupervisorConfig::default()
+        };
+        assert_eq!(config.backoff(1), Duration::from_secs(1));
+        assert_eq!(config.backoff(2), Duration::from_secs(2));
+        assert_eq!(config.backoff(3), Duration::from_secs(4));
+        assert_eq!(config.backoff(4), Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_errored_worker_restarted_on_second_attempt() {
+        let manager = errored_manager().await;
+        let restarter = Arc::new(FlakyRestarter { failures: 1, calls: AtomicU32::new(0) });
+        let alerts = Arc::new(AlertSystem::new());
+        let supervisor =
+            WorkerSupervisor::new(manager.clone(), restarter.clone(), alerts.clone(), test_config()).await;
+
+        supervisor.supervise_once().await;
+        assert_eq!(manager.get_worker("w1").await.unwrap().status, WorkerStatus::Error);
+
+        supervisor.supervise_once().await;
+        assert_eq!(manager.get_worker("w1").await.unwrap().status, WorkerStatus::Active);
+
+        let stats = supervisor.restart_stats("w1").unwrap();
+        assert_eq!(stats.attempts, 2);
+        assert_eq!(stats.successful_restarts, 1);
+        assert!(!stats.gave_up);
+
+        // Здоровый воркер больше не перезапускается
+        supervisor.supervise_once().await;
+        assert_eq!(restarter.calls.load(Ordering::SeqCst), 2);
+
+        let alert = alerts.get_alert(RESTART_EXHAUSTED_ALERT).await.unwrap();
+        assert_eq!(alert.stats.triggered_alerts, 0);
+    }
+
+    /// Запоминает воркера, которым помечены записи лога во время перезапуска
+    #[derive(Default)]
+    struct TaggingRestarter {
+        tagged: parking_lot::Mutex<Option<String>>,
+    }
+
+    #[async_trait]
+    impl WorkerRestarter for TaggingRestarter {
+        async fn restart(&self, _worker: &Worker) -> Result<(), String> {
+            *self.tagged.lock() = crate::monitoring::logger::current_worker_id();
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restart_logs_tagged_with_worker_id() {
+        let manager = errored_manager().await;
+        let restarter = Arc::new(TaggingRestarter::default());
+        let supervisor =
+            WorkerSupervisor::new(manager, restarter.clone(), Arc::new(AlertSystem::new()), test_config()).await;
+
+        supervisor.supervise_once().await;
+
+        assert_eq!(restarter.tagged.lock().as_deref(), Some("w1"));
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_attempts_raise_alert() {
+        let manager = errored_manager().await;
+        let restarter = Arc::new(FlakyRestarter { failures: u32::MAX, calls: AtomicU32::new(0) });
+        let alerts = Arc::new(AlertSystem::new());
+        let supervisor =
+            WorkerSupervisor::new(manager.clone(), restarter.clone(), alerts.clone(), test_config()).await;
+
+        for _ in 0..5 {
+            supervisor.supervise_once().await;
+        }
+
+        assert_eq!(restarter.calls.load(Ordering::SeqCst), 3);
+        let stats = supervisor.restart_stats("w1").unwrap();
+        assert!(stats.gave_up);
+        assert_eq!(stats.attempts, 3);
+        assert_eq!(manager.get_worker("w1").await.unwrap().status, WorkerStatus::Error);
+
+        let alert = alerts.get_alert(RESTART_EXHAUSTED_ALERT).await.unwrap();
+        assert_eq!(alert.stats.triggered_alerts, 1);
+    }
+}
@@ -1,7 +1,7 @@
 //! Worker Manager - Управление воркерами
 
 use crate::core::state::AppState;
-use crate::pool::pool::PoolManager;
+use crate::pool::PoolManager;
 use crate::monitoring::metrics::WorkerMetrics;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -50,11 +50,11 @@ impl WorkerManager {
     pub async fn remove_worker(&self, worker_id: &str) -> Result<(), Box<dyn std::error::Error>> {
         let mut workers = self.workers.write().await;
         
-        if workers.remove(worker_id).is_some() {
+        if workers.contains_key(worker_id) {
+            // Убираем воркера из пулов
+            self.pool_manager.remove_worker_from_pools(worker_id).await?;
+            workers.remove(worker_id);
             info!("Worker {} removed successfully", worker_id);
-            
-            // Уведомляем пул об удалении воркера
-            self.pool_manager.remove_worker(worker_id).await?;
         } else {
             warn!("Worker {} not found", worker_id);
         }
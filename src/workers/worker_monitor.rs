@@ -1,17 +1,42 @@
 //! Worker Monitor - Мониторинг воркеров
 
-use super::worker_manager::{Worker, WorkerStatus};
+use super::{Worker, WorkerStatus};
 use crate::monitoring::metrics::WorkerMetrics;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
 use tokio::sync::RwLock;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use chrono::{DateTime, Utc};
 use log::{info, warn, error};
 
+/// Снимок метрик воркера с моментом сбора
+pub type MetricSample = (DateTime<Utc>, WorkerMetrics);
+
+/// Конфигурация периодического сбора метрик
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricSamplerConfig {
+    /// Интервал между снимками
+    pub interval: Duration,
+    /// Сколько последних снимков хранится на воркера
+    pub retention: usize,
+}
+
+impl Default for MetricSamplerConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(10),
+            retention: 1000,
+        }
+    }
+}
+
 /// Монитор воркеров
 pub struct WorkerMonitor {
-    metrics_history: Arc<RwLock<HashMap<String, Vec<WorkerMetrics>>>>,
+    metrics_history: Arc<RwLock<HashMap<String, VecDeque<MetricSample>>>>,
     alert_thresholds: AlertThresholds,
+    sampler: MetricSamplerConfig,
 }
 
 impl WorkerMonitor {
@@ -20,6 +45,79 @@ impl WorkerMonitor {
         Self {
             metrics_history: Arc::new(RwLock::new(HashMap::new())),
             alert_thresholds,
+            sampler: MetricSamplerConfig::default(),
+        }
+    }
+
+    /// Интервал и глубина истории метрик
+    pub fn with_sampler_config(mut self, sampler: MetricSamplerConfig) -> Self {
+        self.sampler = sampler;
+        self
+    }
+
+    /// Запускает фоновый сбор метрик с интервалом из конфигурации.
+    /// Задача завершается, когда монитор удалён.
+    pub fn spawn_sampler(
+        self: &Arc<Self>,
+        workers: Arc<RwLock<HashMap<String, Worker>>>,
+    ) -> tokio::task::JoinHandle<()> {
+        let monitor: Weak<Self> = Arc::downgrade(self);
+        let mut interval = tokio::time::interval(self.sampler.interval.max(Duration::from_millis(1)));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        tokio::spawn(async move {
+            loop {
+                interval.tick().await;
+                let Some(monitor) = monitor.upgrade() else {
+                    break;
+                };
+                monitor.sample(&workers).await;
+            }
+        })
+    }
+
+    /// Снимает метрики всех воркеров в историю; история удалённых воркеров отбрасывается
+    pub async fn sample(&self, workers: &Arc<RwLock<HashMap<String, Worker>>>) {
+        let workers = workers.read().await;
+        let now = Utc::now();
+        let mut history = self.metrics_history.write().await;
+
+        history.retain(|id, _| workers.contains_key(id));
+        for (id, worker) in workers.iter() {
+            Self::push_sample(&mut history, id, (now, WorkerMetrics::from(worker)), self.sampler.retention);
+        }
+    }
+
+    /// Снимки метрик воркера за последние `window`, от старых к новым
+    pub async fn get_metric_history(&self, worker_id: &str, window: Duration) -> Vec<MetricSample> {
+        let since = chrono::Duration::from_std(window)
+            .ok()
+            .and_then(|window| Utc::now().checked_sub_signed(window))
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+        let history = self.metrics_history.read().await;
+
+        history
+            .get(worker_id)
+            .map(|samples| {
+                samples
+                    .iter()
+                    .filter(|(timestamp, _)| *timestamp >= since)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn push_sample(
+        history: &mut HashMap<String, VecDeque<MetricSample>>,
+        worker_id: &str,
+        sample: MetricSample,
+        retention: usize,
+    ) {
+        let samples = history.entry(worker_id.to_string()).or_default();
+        samples.push_back(sample);
+        while samples.len() > retention.max(1) {
+            samples.pop_front();
         }
     }
 
@@ -32,19 +130,12 @@ impl WorkerMonitor {
         let mut metrics = HashMap::new();
         
         for (id, worker) in workers.iter() {
-            let worker_metrics = WorkerMetrics {
-                cpu_usage: worker.cpu_usage,
-                memory_usage: worker.memory_usage,
-                gpu_usage: worker.gpu_usage,
-                hashrate: worker.hashrate,
-                uptime: worker.uptime,
-                status: worker.status.clone(),
-            };
-            
-            metrics.insert(id.clone(), worker_metrics);
+            let worker_metrics = WorkerMetrics::from(worker);
             
             // Сохраняем в историю
             self.save_metrics_history(id, &worker_metrics).await;
+            
+            metrics.insert(id.clone(), worker_metrics);
         }
         
         metrics
@@ -53,14 +144,7 @@ impl WorkerMonitor {
     /// Сохраняет метрики в историю
     async fn save_metrics_history(&self, worker_id: &str, metrics: &WorkerMetrics) {
         let mut history = self.metrics_history.write().await;
-        
-        let worker_history = history.entry(worker_id.to_string()).or_insert_with(Vec::new);
-        worker_history.push(metrics.clone());
-        
-        // Ограничиваем размер истории
-        if worker_history.len() > 1000 {
-            worker_history.remove(0);
-        }
+        Self::push_sample(&mut history, worker_id, (Utc::now(), metrics.clone()), self.sampler.retention);
     }
 
     /// Получает среднюю нагрузку воркеров
@@ -150,13 +234,9 @@ impl WorkerMonitor {
         let history = self.metrics_history.read().await;
         
         if let Some(worker_history) = history.get(worker_id) {
-            let start = if worker_history.len() > limit {
-                worker_history.len() - limit
-            } else {
-                0
-            };
+            let start = worker_history.len().saturating_sub(limit);
             
-            worker_history[start..].to_vec()
+            worker_history.iter().skip(start).map(|(_, metrics)| metrics.clone()).collect()
         } else {
             Vec::new()
         }
@@ -265,4 +345,59 @@ pub struct PerformanceStats {
     pub average_memory: f64,
     pub average_gpu: f64,
     pub alerts_count: usize,
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn worker(id: &str, cpu_usage: f64) -> Worker {
+        Worker {
+            id: id.to_string(),
+            name: id.to_string(),
+            status: WorkerStatus::Active,
+            hashrate: 10.0,
+            cpu_usage,
+            memory_usage: 10.0,
+            gpu_usage: 10.0,
+            uptime: Duration::from_secs(0),
+            last_seen: Utc::now(),
+            capabilities: vec![],
+            calibrated_hashrate: None,
+            endpoint: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sampler_accumulates_bounded_history() {
+        let workers = Arc::new(RwLock::new(HashMap::new()));
+        workers.write().await.insert("w1".to_string(), worker("w1", 10.0));
+        let monitor = Arc::new(
+            WorkerMonitor::new(AlertThresholds::default()).with_sampler_config(MetricSamplerConfig {
+                interval: Duration::from_millis(5),
+                retention: 4,
+            }),
+        );
+
+        let sampler = monitor.spawn_sampler(workers.clone());
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while monitor.get_metric_history("w1", Duration::from_secs(60)).await.len() < 2 {
+            assert!(tokio::time::Instant::now() < deadline, "sampler did not record history");
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        // Новые значения попадают в историю, старые вытесняются
+        workers.write().await.get_mut("w1").unwrap().cpu_usage = 55.0;
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        sampler.abort();
+
+        let history = monitor.get_metric_history("w1", Duration::from_secs(60)).await;
+        assert_eq!(history.len(), 4);
+        assert!(history.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        assert_eq!(history.last().unwrap().1.cpu_usage, 55.0);
+        assert!(history.iter().all(|(_, metrics)| metrics.cpu_usage == 55.0));
+
+        assert!(monitor.get_metric_history("w1", Duration::ZERO).await.len() <= 1);
+        assert!(monitor.get_metric_history("missing", Duration::from_secs(60)).await.is_empty());
+    }
+}